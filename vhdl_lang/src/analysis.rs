@@ -8,11 +8,19 @@
 mod analyze;
 mod assignment;
 mod association;
+mod call_hierarchy;
 mod concurrent;
 mod declarative;
+mod dependency_graph;
 mod design_unit;
 mod expression;
+mod folding;
 mod formal_region;
+mod highlight;
+mod hover;
+mod inlay_hints;
+mod instance_hierarchy;
+mod instantiation;
 mod literals;
 mod lock;
 mod named_entity;
@@ -22,13 +30,18 @@ mod package_instance;
 mod range;
 mod region;
 mod root;
+mod selection_range;
 mod semantic;
 mod sequential;
+mod signature_help;
 mod standard;
 mod static_expression;
 mod target;
+mod type_hierarchy;
+mod use_clause_fix;
 mod visibility;
 
+mod compile_order;
 mod completion;
 
 #[cfg(test)]
@@ -36,8 +49,18 @@ pub(crate) mod tests;
 pub(crate) use root::{Library, LockedUnit};
 
 pub use self::root::{DesignRoot, EntHierarchy};
+pub use call_hierarchy::CallHierarchyCall;
 pub use completion::CompletionItem;
+pub use dependency_graph::{describe_unit, DependencyGraph};
+pub use folding::{FoldingRange, FoldingRangeKind};
+pub use highlight::{Highlight, HighlightKind};
+pub use hover::HoverInfo;
+pub use inlay_hints::{InlayHint, InlayHintKind};
+pub use instance_hierarchy::InstanceHierarchy;
+pub use instantiation::{Instantiation, InstantiationKind};
 pub use named_entity::{
-    AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, HasEntityId, Object, Overloaded,
-    Related, Sequential, Type,
+    AnyEnt, AnyEntKind, Concurrent, Design, EntRef, EntityId, HasEntityId, Object, ObjectInterface,
+    Overloaded, Related, Sequential, Type,
 };
+pub use signature_help::{SignatureHelp, SignatureLabel};
+pub use use_clause_fix::UseClauseFix;
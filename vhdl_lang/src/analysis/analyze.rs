@@ -7,6 +7,7 @@
 use super::named_entity::*;
 use super::region::*;
 use super::root::*;
+use super::standard::is_added_in_vhdl2008;
 use crate::ast::*;
 use crate::data::*;
 use crate::syntax::TokenAccess;
@@ -22,7 +23,7 @@ pub enum AnalysisError {
 
 impl AnalysisError {
     pub fn not_fatal_error(pos: impl AsRef<SrcPos>, msg: impl Into<String>) -> AnalysisError {
-        AnalysisError::NotFatal(Diagnostic::error(pos, msg))
+        AnalysisError::NotFatal(Diagnostic::mismatched_kinds(pos, msg))
     }
 }
 
@@ -41,7 +42,11 @@ impl CircularDependencyError {
 
     pub fn push_into(self, diagnostics: &mut dyn DiagnosticHandler) {
         if let Some(pos) = self.reference {
-            diagnostics.push(Diagnostic::error(pos, "Found circular dependency"));
+            diagnostics.push(Diagnostic::new(
+                pos,
+                "Found circular dependency",
+                ErrorCode::CircularDependency,
+            ));
         }
     }
 }
@@ -135,8 +140,11 @@ pub(super) struct AnalyzeContext<'a> {
 
     pub work_sym: Symbol,
     std_sym: Symbol,
+    pub(super) guard_sym: Symbol,
     standard_sym: Symbol,
     pub(super) is_std_logic_1164: bool,
+    // The VHDL revision of the library of the current unit
+    pub(super) standard: VHDLStandard,
 
     // Record dependencies and sensitivies when
     // analyzing design units
@@ -169,12 +177,14 @@ impl<'a> AnalyzeContext<'a> {
         AnalyzeContext {
             work_sym: root.symbol_utf8("work"),
             std_sym: root.symbol_utf8("std"),
+            guard_sym: root.symbol_utf8("guard"),
             standard_sym: root.symbol_utf8("standard"),
             is_std_logic_1164: current_unit
                 == &UnitId::package(
                     &root.symbol_utf8("ieee"),
                     &root.symbol_utf8("std_logic_1164"),
                 ),
+            standard: root.standard_of(current_unit.library_name()),
             root,
             current_unit: current_unit.clone(),
             arena,
@@ -286,7 +296,13 @@ impl<'a> AnalyzeContext<'a> {
             let standard_region = self
                 .standard_package_region()
                 .expect("Expected standard package");
-            scope.make_all_potentially_visible(None, standard_region);
+            if self.standard < VHDLStandard::VHDL2008 {
+                // The standard package of the installed libraries is the VHDL-2008 revision,
+                // hide the predefined declarations that were added in it
+                scope.make_potentially_visible_except(None, standard_region, is_added_in_vhdl2008);
+            } else {
+                scope.make_all_potentially_visible(None, standard_region);
+            }
         }
 
         Ok(())
@@ -368,7 +384,7 @@ impl<'a> AnalyzeContext<'a> {
                     let ent = self.arena.get(id);
                     let design = DesignEnt::from_any(ent).ok_or_else(|| {
                         // Almost impossible but better not fail silently
-                        Diagnostic::error(
+                        Diagnostic::mismatched_kinds(
                             pos,
                             format!(
                                 "Found non-design {} unit within library {}",
@@ -382,11 +398,12 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
-        Err(AnalysisError::NotFatal(Diagnostic::error(
+        Err(AnalysisError::NotFatal(Diagnostic::new(
             pos,
             format!(
                 "No architecture '{architecture_name}' for entity '{library_name}.{entity_name}'"
             ),
+            ErrorCode::Unresolved,
         )))
     }
 
@@ -404,7 +421,7 @@ impl<'a> AnalyzeContext<'a> {
                         let ent = self.arena.get(id);
                         let design = DesignEnt::from_any(ent).ok_or_else(|| {
                             // Almost impossible but better not fail silently
-                            Diagnostic::error(
+                            Diagnostic::mismatched_kinds(
                                 pos,
                                 format!(
                                     "Found non-design {} unit within library {}",
@@ -419,9 +436,10 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
-        Err(AnalysisError::NotFatal(Diagnostic::error(
+        Err(AnalysisError::NotFatal(Diagnostic::new(
             pos,
             format!("No primary unit '{primary_name}' within library '{library_name}'"),
+            ErrorCode::Unresolved,
         )))
     }
 
@@ -93,14 +93,22 @@ impl<'a> AnalyzeContext<'a> {
                     if let Some(resolved_formal) = resolved_prefix.select(elem.type_mark()) {
                         Ok(resolved_formal)
                     } else {
-                        Err(Diagnostic::error(name_pos, "Invalid formal").into())
+                        Err(
+                            Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal)
+                                .into(),
+                        )
                     }
                 } else {
-                    Err(Diagnostic::error(name_pos, "Invalid formal").into())
+                    Err(
+                        Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal)
+                            .into(),
+                    )
                 }
             }
 
-            Name::SelectedAll(_) => Err(Diagnostic::error(name_pos, "Invalid formal").into()),
+            Name::SelectedAll(_) => {
+                Err(Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal).into())
+            }
             Name::Designator(designator) => {
                 let (idx, ent) = formal_region.lookup(name_pos, designator.designator())?;
                 designator.set_unique_reference(ent.inner());
@@ -117,18 +125,30 @@ impl<'a> AnalyzeContext<'a> {
 
                 if let ResolvedFormal::Converted(..) = resolved_prefix {
                     // Converted formals may not be further selected
-                    return Err(Diagnostic::error(name_pos, "Invalid formal").into());
+                    return Err(Diagnostic::new(
+                        name_pos,
+                        "Invalid formal",
+                        ErrorCode::InvalidFormal,
+                    )
+                    .into());
                 }
 
                 self.drange_unknown_type(scope, drange.as_mut(), diagnostics)?;
                 Ok(resolved_prefix)
             }
-            Name::Attribute(..) => Err(Diagnostic::error(name_pos, "Invalid formal").into()),
+            Name::Attribute(..) => {
+                Err(Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal).into())
+            }
             Name::CallOrIndexed(ref mut fcall) => {
                 let prefix = if let Some(prefix) = fcall.name.item.prefix() {
                     prefix
                 } else {
-                    return Err(Diagnostic::error(name_pos, "Invalid formal").into());
+                    return Err(Diagnostic::new(
+                        name_pos,
+                        "Invalid formal",
+                        ErrorCode::InvalidFormal,
+                    )
+                    .into());
                 };
 
                 if formal_region.lookup(name_pos, prefix.designator()).is_err() {
@@ -142,7 +162,12 @@ impl<'a> AnalyzeContext<'a> {
                         designator.set_unique_reference(ent.inner());
                         (idx, pos, ent)
                     } else {
-                        return Err(Diagnostic::error(name_pos, "Invalid formal conversion").into());
+                        return Err(Diagnostic::new(
+                            name_pos,
+                            "Invalid formal conversion",
+                            ErrorCode::InvalidFormalConversion,
+                        )
+                        .into());
                     };
 
                     let converted_typ = match as_fatal(self.name_resolve(
@@ -154,13 +179,14 @@ impl<'a> AnalyzeContext<'a> {
                         Some(ResolvedName::Type(typ)) => {
                             let ctyp = formal_ent.type_mark().base();
                             if !typ.base().is_closely_related(ctyp) {
-                                return Err(Diagnostic::error(
+                                return Err(Diagnostic::new(
                                     pos,
                                     format!(
                                         "{} cannot be converted to {}",
                                         ctyp.describe(),
                                         typ.describe()
                                     ),
+                                    ErrorCode::TypeMismatch,
                                 )
                                 .into());
                             }
@@ -181,9 +207,10 @@ impl<'a> AnalyzeContext<'a> {
 
                             if candidates.len() > 1 {
                                 // Ambiguous call
-                                let mut diagnostic = Diagnostic::error(
+                                let mut diagnostic = Diagnostic::new(
                                     &fcall.name.pos,
                                     format!("Ambiguous call to function '{des}'"),
+                                    ErrorCode::AmbiguousCall,
                                 );
 
                                 diagnostic.add_subprogram_candidates("might be", candidates);
@@ -194,21 +221,25 @@ impl<'a> AnalyzeContext<'a> {
                                 ent.return_type().unwrap()
                             } else {
                                 // No match
-                                return Err(Diagnostic::error(
+                                return Err(Diagnostic::new(
                                     &fcall.name.pos,
                                     format!(
                                         "No function '{}' accepting {}",
                                         fcall.name,
                                         formal_ent.type_mark().describe()
                                     ),
+                                    ErrorCode::Unresolved,
                                 )
                                 .into());
                             }
                         }
                         _ => {
-                            return Err(
-                                Diagnostic::error(name_pos, "Invalid formal conversion").into()
-                            );
+                            return Err(Diagnostic::new(
+                                name_pos,
+                                "Invalid formal conversion",
+                                ErrorCode::InvalidFormalConversion,
+                            )
+                            .into());
                         }
                     };
 
@@ -234,13 +265,21 @@ impl<'a> AnalyzeContext<'a> {
                     if let Some(resolved_formal) = resolved_prefix.select(new_typ) {
                         Ok(resolved_formal)
                     } else {
-                        Err(Diagnostic::error(name_pos, "Invalid formal").into())
+                        Err(
+                            Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal)
+                                .into(),
+                        )
                     }
                 } else {
-                    Err(Diagnostic::error(name_pos, "Invalid formal").into())
+                    Err(
+                        Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal)
+                            .into(),
+                    )
                 }
             }
-            Name::External(..) => Err(Diagnostic::error(name_pos, "Invalid formal").into()),
+            Name::External(..) => {
+                Err(Diagnostic::new(name_pos, "Invalid formal", ErrorCode::InvalidFormal).into())
+            }
         }
     }
 
@@ -303,9 +342,10 @@ impl<'a> AnalyzeContext<'a> {
             // Only complain if nothing else is wrong
             for idx in not_associated {
                 if let Some(formal) = formal_region.nth(idx) {
-                    let mut diagnostic = Diagnostic::error(
+                    let mut diagnostic = Diagnostic::new(
                         error_pos,
                         format!("No association of {}", formal.describe()),
+                        ErrorCode::Unassociated,
                     );
 
                     if let Some(decl_pos) = formal.decl_pos() {
@@ -316,7 +356,11 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             for pos in extra_associations.into_iter() {
-                diagnostics.error(pos, "Unexpected extra argument")
+                diagnostics.add(
+                    pos,
+                    "Unexpected extra argument",
+                    ErrorCode::TooManyArguments,
+                )
             }
             Err(EvalError::Unknown)
         }
@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Calls between subprograms, processes and design units
+//! and instantiations between entities
+
+use super::root::DesignRoot;
+use super::HasEntityId;
+use crate::ast::search::{FoundDeclaration, NotFinished, Search, SearchState, Searcher};
+use crate::ast::visitor::{walk, Visitor, VisitorResult};
+use crate::ast::{
+    ArchitectureBody, ConcurrentStatement, InstantiatedUnit, InstantiationStatement,
+    LabeledConcurrentStatement, Reference,
+};
+use crate::data::*;
+use crate::syntax::TokenAccess;
+use crate::{AnyEntKind, Design, EntRef, EntityId, Overloaded};
+
+/// A named entity together with the positions where it calls or is called
+/// or where it instantiates or is instantiated
+#[derive(Debug, Clone)]
+pub struct CallHierarchyCall<'a> {
+    pub ent: EntRef<'a>,
+    pub ranges: Vec<SrcPos>,
+}
+
+impl DesignRoot {
+    /// The subprograms, processes and design units calling a subprogram
+    /// together with the positions of the calls
+    pub fn incoming_calls<'a>(&'a self, callee: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        let callee = callee.declaration().id();
+        self.group_calls(
+            self.find_all_calls()
+                .into_iter()
+                .filter(|call| call.callee == callee)
+                .map(|call| (call.caller, call.pos)),
+        )
+    }
+
+    /// The subprograms called by a subprogram, process or design unit
+    /// together with the positions of the calls
+    pub fn outgoing_calls<'a>(&'a self, caller: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        let caller = caller.declaration().id();
+        self.group_calls(
+            self.find_all_calls()
+                .into_iter()
+                .filter(|call| call.caller == caller)
+                .map(|call| (call.callee, call.pos)),
+        )
+    }
+
+    /// The entities whose architectures instantiate an entity
+    /// together with the positions of the instantiations
+    pub fn instantiated_by<'a>(&'a self, entity: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        let entity = entity.id();
+        self.group_calls(
+            self.find_all_instantiations()
+                .into_iter()
+                .filter(|call| call.callee == entity)
+                .map(|call| (call.caller, call.pos)),
+        )
+    }
+
+    /// The entities instantiated by the architectures of an entity
+    /// together with the positions of the instantiations
+    pub fn instantiates<'a>(&'a self, entity: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        let entity = entity.id();
+        self.group_calls(
+            self.find_all_instantiations()
+                .into_iter()
+                .filter(|call| call.caller == entity)
+                .map(|call| (call.callee, call.pos)),
+        )
+    }
+
+    fn find_all_calls(&self) -> Vec<Call> {
+        let mut searcher = FindAllCalls {
+            root: self,
+            calls: Vec::new(),
+        };
+        let _ = self.search(&mut searcher);
+        searcher.calls
+    }
+
+    fn find_all_instantiations(&self) -> Vec<Call> {
+        let mut visitor = FindAllInstantiations {
+            root: self,
+            entity: None,
+            calls: Vec::new(),
+        };
+        for unit in self.libraries().flat_map(|library| library.units()) {
+            if let Some(data) = unit.unit.get() {
+                walk(data.data(), &mut visitor, &unit.tokens);
+            }
+        }
+        visitor.calls
+    }
+
+    fn group_calls(
+        &self,
+        calls: impl Iterator<Item = (EntityId, SrcPos)>,
+    ) -> Vec<CallHierarchyCall<'_>> {
+        let mut result: Vec<CallHierarchyCall> = Vec::new();
+        for (id, pos) in calls {
+            if let Some(call) = result.iter_mut().find(|call| call.ent.id() == id) {
+                if !call.ranges.contains(&pos) {
+                    call.ranges.push(pos);
+                }
+            } else {
+                result.push(CallHierarchyCall {
+                    ent: self.get_ent(id),
+                    ranges: vec![pos],
+                });
+            }
+        }
+        for call in result.iter_mut() {
+            call.ranges.sort_by_key(|pos| pos.start());
+        }
+        result.sort_by(|a, b| {
+            let key = |call: &CallHierarchyCall| {
+                call.ent
+                    .decl_pos()
+                    .map(|pos| (pos.file_name().to_owned(), pos.start()))
+            };
+            key(a).cmp(&key(b))
+        });
+        result
+    }
+}
+
+/// A call of a subprogram, the caller and callee are declarations and not bodies
+struct Call {
+    caller: EntityId,
+    callee: EntityId,
+    pos: SrcPos,
+}
+
+/// Finds the calls within the statements of subprogram bodies and architectures.
+/// Calls within a labeled process are made by the process.
+struct FindAllCalls<'a> {
+    root: &'a DesignRoot,
+    calls: Vec<Call>,
+}
+
+impl<'a> FindAllCalls<'a> {
+    fn search_calls(&mut self, ctx: &dyn TokenAccess, caller: EntityId, node: &mut impl Search) {
+        let mut searcher = FindCalls {
+            root: self.root,
+            caller: self.root.get_ent(caller).declaration().id(),
+            calls: &mut self.calls,
+        };
+        let _ = node.search(ctx, &mut searcher);
+    }
+
+    fn search_concurrent_statements(
+        &mut self,
+        ctx: &dyn TokenAccess,
+        caller: EntityId,
+        statements: &mut [LabeledConcurrentStatement],
+    ) {
+        for statement in statements.iter_mut() {
+            match statement.statement.item {
+                ConcurrentStatement::Process(ref mut process) => {
+                    // Declarations of the process are searched separately
+                    let caller = statement.label.decl.unwrap_or(caller);
+                    self.search_calls(ctx, caller, &mut process.statements);
+                }
+                ConcurrentStatement::Block(ref mut block) => {
+                    self.search_concurrent_statements(ctx, caller, &mut block.statements);
+                }
+                ConcurrentStatement::ForGenerate(ref mut gen) => {
+                    self.search_concurrent_statements(ctx, caller, &mut gen.body.statements);
+                }
+                ConcurrentStatement::IfGenerate(ref mut gen) => {
+                    for conditional in gen.conds.conditionals.iter_mut() {
+                        self.search_concurrent_statements(
+                            ctx,
+                            caller,
+                            &mut conditional.item.statements,
+                        );
+                    }
+                    if let Some(ref mut else_item) = gen.conds.else_item {
+                        self.search_concurrent_statements(ctx, caller, &mut else_item.statements);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref mut gen) => {
+                    for alternative in gen.sels.alternatives.iter_mut() {
+                        self.search_concurrent_statements(
+                            ctx,
+                            caller,
+                            &mut alternative.item.statements,
+                        );
+                    }
+                }
+                _ => self.search_calls(ctx, caller, statement),
+            }
+        }
+    }
+}
+
+impl<'a> Searcher for FindAllCalls<'a> {
+    fn search_decl(&mut self, ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        match decl {
+            FoundDeclaration::Subprogram(body) => {
+                if let Some(caller) = body.specification.ent_id() {
+                    self.search_calls(ctx, caller, &mut body.statements);
+                }
+            }
+            FoundDeclaration::Architecture(architecture) => {
+                if let Some(caller) = architecture.ident.decl {
+                    self.search_concurrent_statements(ctx, caller, &mut architecture.statements);
+                }
+            }
+            _ => {}
+        }
+        NotFinished
+    }
+}
+
+/// Collects the references to subprograms
+struct FindCalls<'a, 'b> {
+    root: &'a DesignRoot,
+    caller: EntityId,
+    calls: &'b mut Vec<Call>,
+}
+
+impl<'a, 'b> Searcher for FindCalls<'a, 'b> {
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &mut Reference,
+    ) -> SearchState {
+        if let Some(id) = reference {
+            let ent = self.root.get_ent(*id).declaration();
+            if matches!(
+                ent.kind(),
+                AnyEntKind::Overloaded(Overloaded::Subprogram(_) | Overloaded::SubprogramDecl(_))
+            ) && !ent.is_implicit()
+            {
+                self.calls.push(Call {
+                    caller: self.caller,
+                    callee: ent.id(),
+                    pos: pos.clone(),
+                });
+            }
+        }
+        NotFinished
+    }
+}
+
+/// Finds the instantiations of entities within architectures, the caller is the entity
+/// of the architecture and the callee is the instantiated entity
+struct FindAllInstantiations<'a> {
+    root: &'a DesignRoot,
+    entity: Option<EntityId>,
+    calls: Vec<Call>,
+}
+
+impl<'a> Visitor for FindAllInstantiations<'a> {
+    fn visit_architecture_body(
+        &mut self,
+        node: &ArchitectureBody,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.entity = node
+            .ident
+            .decl
+            .and_then(|id| match self.root.get_ent(id).kind() {
+                AnyEntKind::Design(Design::Architecture(entity)) => Some(entity.id()),
+                _ => None,
+            });
+        VisitorResult::Continue
+    }
+
+    fn visit_instantiation_statement(
+        &mut self,
+        node: &InstantiationStatement,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        let Some(caller) = self.entity else {
+            return VisitorResult::Continue;
+        };
+        let pos = match &node.unit {
+            InstantiatedUnit::Entity(name, _)
+            | InstantiatedUnit::Component(name)
+            | InstantiatedUnit::Configuration(name) => &name.pos,
+        };
+        if let Some(entity) = self.root.instantiated_entity(&node.unit) {
+            self.calls.push(Call {
+                caller,
+                callee: entity.id(),
+                pos: pos.clone(),
+            });
+        }
+        VisitorResult::Continue
+    }
+}
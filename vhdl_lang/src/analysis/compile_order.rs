@@ -0,0 +1,150 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Dependency sorted order of files for external tools such as simulators
+
+use super::root::DesignRoot;
+use crate::ast::*;
+use crate::data::*;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::path::PathBuf;
+
+impl DesignRoot {
+    /// The units directly used by each unit during the last analysis
+    pub(crate) fn unit_dependencies(&self) -> FnvHashMap<UnitId, FnvHashSet<UnitId>> {
+        let mut dependencies: FnvHashMap<UnitId, FnvHashSet<UnitId>> = FnvHashMap::default();
+        for (unit_id, users) in self.users_of.read().iter() {
+            for user in users.iter() {
+                dependencies
+                    .entry(user.clone())
+                    .or_default()
+                    .insert(unit_id.clone());
+            }
+        }
+        dependencies
+    }
+
+    /// Units that are needed to elaborate a unit without being analysis dependencies.
+    /// These are the secondary units of a primary unit and
+    /// the entities bound to component instances by default binding.
+    fn elaboration_needs(&self, unit_id: &UnitId) -> Vec<UnitId> {
+        let mut needs = Vec::new();
+        let library_name = unit_id.library_name();
+        let Some(units) = self.get_library_units(library_name) else {
+            return needs;
+        };
+
+        match unit_id.key() {
+            UnitKey::Primary(primary_name) => {
+                for (key, unit) in units.iter() {
+                    if matches!(key, UnitKey::Secondary(name, _) if name == primary_name) {
+                        needs.push(unit.unit_id().clone());
+                    }
+                }
+            }
+            UnitKey::Secondary(..) => {
+                needs.extend(self.bound_entities(unit_id));
+            }
+        }
+        needs
+    }
+
+    /// Returns the library and file name of each design unit in an order where
+    /// every file comes after the files it depends on.
+    ///
+    /// When a top level unit is given only the files required to elaborate it are included,
+    /// otherwise all files are included. Requires that the design has been analyzed.
+    pub fn compile_order(&self, top: Option<&UnitId>) -> Vec<(Symbol, PathBuf)> {
+        let dependencies = self.unit_dependencies();
+
+        let required: FnvHashSet<UnitId> = if let Some(top) = top {
+            let mut required = FnvHashSet::default();
+            let mut pending = vec![top.clone()];
+            while let Some(unit_id) = pending.pop() {
+                if !required.insert(unit_id.clone()) {
+                    continue;
+                }
+                if let Some(deps) = dependencies.get(&unit_id) {
+                    pending.extend(deps.iter().cloned());
+                }
+                pending.extend(self.elaboration_needs(&unit_id));
+            }
+            required
+        } else {
+            self.libraries()
+                .flat_map(|library| library.units())
+                .map(|unit| unit.unit_id().clone())
+                .collect()
+        };
+
+        // Files are identified by library and source since a file may belong to several libraries
+        let file_of = |unit_id: &UnitId| -> Option<(Symbol, PathBuf)> {
+            let unit = self.get_unit(unit_id)?;
+            Some((
+                unit_id.library_name().clone(),
+                unit.source().file_name().to_owned(),
+            ))
+        };
+
+        let mut file_dependencies: FnvHashMap<(Symbol, PathBuf), FnvHashSet<(Symbol, PathBuf)>> =
+            FnvHashMap::default();
+        for unit_id in required.iter() {
+            let Some(file) = file_of(unit_id) else {
+                continue;
+            };
+            let deps = file_dependencies.entry(file.clone()).or_default();
+            for dep in dependencies.get(unit_id).into_iter().flatten() {
+                if let Some(dep_file) = file_of(dep).filter(|dep_file| dep_file != &file) {
+                    if required.contains(dep) {
+                        deps.insert(dep_file);
+                    }
+                }
+            }
+        }
+
+        let sort_key = |(library_name, file_name): &(Symbol, PathBuf)| {
+            (library_name.name_utf8(), file_name.clone())
+        };
+
+        let mut files: Vec<_> = file_dependencies.keys().cloned().collect();
+        files.sort_by_key(sort_key);
+
+        let mut order = Vec::with_capacity(files.len());
+        let mut visited = FnvHashSet::default();
+        for file in files {
+            visit_file(
+                file,
+                &file_dependencies,
+                &sort_key,
+                &mut visited,
+                &mut order,
+            );
+        }
+        order
+    }
+}
+
+/// Depth first topological sort where circular dependencies are broken arbitrarily
+fn visit_file<K: Ord>(
+    file: (Symbol, PathBuf),
+    file_dependencies: &FnvHashMap<(Symbol, PathBuf), FnvHashSet<(Symbol, PathBuf)>>,
+    sort_key: &impl Fn(&(Symbol, PathBuf)) -> K,
+    visited: &mut FnvHashSet<(Symbol, PathBuf)>,
+    order: &mut Vec<(Symbol, PathBuf)>,
+) {
+    if !visited.insert(file.clone()) {
+        return;
+    }
+
+    if let Some(deps) = file_dependencies.get(&file) {
+        let mut deps: Vec<_> = deps.iter().cloned().collect();
+        deps.sort_by_key(sort_key);
+        for dep in deps {
+            visit_file(dep, file_dependencies, sort_key, visited, order);
+        }
+    }
+    order.push(file);
+}
@@ -5,7 +5,7 @@ use crate::ast::{
     AnyDesignUnit, AnyPrimaryUnit, AnySecondaryUnit, ComponentDeclaration, Designator,
     EntityDeclaration, InstantiationStatement, InterfaceDeclaration, MapAspect, PackageDeclaration,
 };
-use crate::data::{ContentReader, Symbol};
+use crate::data::{ContentReader, Symbol, VHDLStandard};
 use crate::syntax::Kind::*;
 use crate::syntax::{Kind, Symbols, Token, TokenAccess, Tokenizer, Value};
 use crate::AnyEntKind::Design;
@@ -45,24 +45,35 @@ macro_rules! ident {
     };
 }
 
-#[derive(Eq, PartialEq, Debug)]
-enum MapAspectKind {
+#[derive(Eq, PartialEq, Hash, Debug, Clone, Copy)]
+pub(super) enum MapAspectKind {
     Port,
     Generic,
 }
 
-/// Extracts the name of ports or generics from an AST for an entity with a certain ID.
+/// Extracts the declarations of ports or generics from an AST for an entity with a certain ID.
 /// The entity can be an `Entity`, `Component` or `Package`.
 /// After walking the AST, the ports or generics are written to the `items` vector.
 /// The `kind` member chooses whether to select ports or generics.
 struct PortsOrGenericsExtractor {
     id: EntityId,
-    items: Vec<EntityId>,
+    items: Vec<InterfaceDeclaration>,
     kind: MapAspectKind,
 }
 
 impl DesignRoot {
     fn extract_port_or_generic_names(&self, id: EntityId, kind: MapAspectKind) -> Vec<EntityId> {
+        self.extract_port_or_generic_declarations(id, kind)
+            .iter()
+            .filter_map(|decl| decl.ent_id())
+            .collect()
+    }
+
+    pub(super) fn extract_port_or_generic_declarations(
+        &self,
+        id: EntityId,
+        kind: MapAspectKind,
+    ) -> Vec<InterfaceDeclaration> {
         let mut searcher = PortsOrGenericsExtractor::new(id, kind);
         self.walk(&mut searcher);
         searcher.items
@@ -78,12 +89,8 @@ impl PortsOrGenericsExtractor {
         }
     }
 
-    fn add_map_aspect_items(&mut self, map_aspect: &Vec<InterfaceDeclaration>) {
-        for decl in map_aspect {
-            if let Some(id) = decl.ent_id() {
-                self.items.push(id)
-            }
-        }
+    fn add_map_aspect_items(&mut self, map_aspect: &[InterfaceDeclaration]) {
+        self.items.extend(map_aspect.iter().cloned());
     }
 
     fn add_optional_map_aspect_items(&mut self, map_aspect: &Option<Vec<InterfaceDeclaration>>) {
@@ -259,9 +266,14 @@ impl<'a> Visitor for AutocompletionVisitor<'a> {
 /// `tokenize_input(input)` -> {USE, ieee, DOT, std_logic_1164, DOT, a}
 ///
 /// On error, or if the source is empty, returns an empty vector.
-fn tokenize_input(symbols: &Symbols, source: &Source, cursor: Position) -> Vec<Token> {
+pub(super) fn tokenize_input(
+    symbols: &Symbols,
+    source: &Source,
+    cursor: Position,
+    standard: VHDLStandard,
+) -> Vec<Token> {
     let contents = source.contents();
-    let mut tokenizer = Tokenizer::new(symbols, source, ContentReader::new(&contents));
+    let mut tokenizer = Tokenizer::new(symbols, source, ContentReader::new(&contents), standard);
     let mut tokens = Vec::new();
     loop {
         match tokenizer.pop() {
@@ -345,12 +357,14 @@ impl DesignRoot {
 
     /// Main entry point for completion. Given a source-file and a cursor position,
     /// lists available completion options at the cursor position.
+    /// The source is tokenized according to the VHDL revision of its library.
     pub fn list_completion_options(
         &self,
         source: &Source,
         cursor: Position,
+        standard: VHDLStandard,
     ) -> Vec<CompletionItem> {
-        let tokens = tokenize_input(&self.symbols, source, cursor);
+        let tokens = tokenize_input(&self.symbols, source, cursor, standard);
         match &tokens[..] {
             [.., kind!(Library)] | [.., kind!(Use)] | [.., kind!(Use), kind!(Identifier)] => {
                 self.list_all_libraries()
@@ -383,7 +397,12 @@ mod test {
     #[test]
     fn tokenizing_an_empty_input() {
         let input = Code::new("");
-        let tokens = tokenize_input(&input.symbols, input.source(), Position::new(0, 0));
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            Position::new(0, 0),
+            VHDLStandard::default(),
+        );
         assert_eq!(tokens.len(), 0);
     }
 
@@ -391,19 +410,34 @@ mod test {
     fn tokenizing_stops_at_the_cursors_position() {
         let input = Code::new("use ieee.std_logic_1164.all");
         let mut cursor = input.s1("std_logic_11").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [kind!(Use), kind!(Identifier), kind!(Dot), kind!(Identifier)]
         );
         cursor = input.s1("std_logic_1164").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [kind!(Use), kind!(Identifier), kind!(Dot), kind!(Identifier)]
         );
         cursor = input.s1("std_logic_1164.").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [
@@ -415,7 +449,12 @@ mod test {
             ]
         );
         cursor = input.s1("std_logic_1164.all").pos().end();
-        let tokens = tokenize_input(&input.symbols, input.source(), cursor);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::default(),
+        );
         assert_matches!(
             tokens[..],
             [
@@ -429,13 +468,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn tokenizing_uses_the_keywords_of_the_standard() {
+        let input = Code::new("view");
+        let cursor = input.s1("view").pos().end();
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::VHDL2008,
+        );
+        assert_matches!(tokens[..], [kind!(Identifier)]);
+        let tokens = tokenize_input(
+            &input.symbols,
+            input.source(),
+            cursor,
+            VHDLStandard::VHDL2019,
+        );
+        assert_matches!(tokens[..], [kind!(View)]);
+    }
+
     #[test]
     pub fn completing_libraries() {
         let input = LibraryBuilder::new();
         let code = Code::new("library ");
         let (root, _) = input.get_analyzed_root();
         let cursor = code.s1("library ").pos().end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         assert_eq!(options, root.list_all_libraries())
     }
 
@@ -444,7 +503,7 @@ mod test {
         let (root, _) = LibraryBuilder::new().get_analyzed_root();
         let code = Code::new("use std.");
         let cursor = code.pos().end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         assert!(options.contains(&CompletionItem::Simple(root.find_textio_pkg())));
         assert!(options.contains(&CompletionItem::Simple(root.find_standard_pkg())));
         assert!(options.contains(&CompletionItem::Simple(root.find_env_pkg())));
@@ -452,7 +511,7 @@ mod test {
 
         let code = Code::new("use std.t");
         let cursor = code.pos().end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         // Note that the filtering only happens at client side
         assert!(options.contains(&CompletionItem::Simple(root.find_textio_pkg())));
         assert!(options.contains(&CompletionItem::Simple(root.find_standard_pkg())));
@@ -466,7 +525,7 @@ mod test {
         let code = Code::new("use std.env.");
         let (root, _) = input.get_analyzed_root();
         let cursor = code.pos().end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         println!("{:?}", options);
 
         assert!(options.contains(&CompletionItem::Overloaded(
@@ -519,7 +578,7 @@ mod test {
         );
         let (root, _) = input.get_analyzed_root();
         let cursor = code.s1("generic map (").pos().end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         let ent = root
             .search_reference(code.source(), code.s1("B").start())
             .unwrap();
@@ -534,7 +593,7 @@ mod test {
             .unwrap();
 
         let cursor = code.s1("port map (").pos().end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         assert!(options.contains(&CompletionItem::Formal(rst)));
         assert!(options.contains(&CompletionItem::Formal(dout)));
         assert_eq!(options.len(), 2);
@@ -543,14 +602,14 @@ mod test {
             clk =>")
             .pos()
             .end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         assert_eq!(options.len(), 0);
         let cursor = code
             .s1("port map (
             clk => c")
             .pos()
             .end();
-        let options = root.list_completion_options(code.source(), cursor);
+        let options = root.list_completion_options(code.source(), cursor, VHDLStandard::default());
         assert_eq!(options.len(), 0);
     }
 }
@@ -82,6 +82,22 @@ impl<'a> AnalyzeContext<'a> {
                     self.boolean_expr(scope, guard_condition, diagnostics)?;
                 }
                 let nested = scope.nested();
+                if let Some(ref guard_condition) = block.guard_condition {
+                    // LRM 11.2 A guarded block implicitly declares the signal GUARD
+                    let guard = self.arena.implicit(
+                        parent,
+                        self.guard_sym.clone(),
+                        AnyEntKind::Object(Object {
+                            class: ObjectClass::Signal,
+                            iface: None,
+                            subtype: Subtype::new(self.boolean()),
+                            has_default: false,
+                            signal_kind: None,
+                        }),
+                        Some(&guard_condition.pos),
+                    );
+                    nested.add(guard, diagnostics);
+                }
                 if let Some(ref mut list) = block.header.generic_clause {
                     self.analyze_interface_list(&nested, parent, list, diagnostics)?;
                 }
@@ -172,7 +188,15 @@ impl<'a> AnalyzeContext<'a> {
             }
             ConcurrentStatement::Assignment(ref mut assign) => {
                 // @TODO more delaymechanism
-                let ConcurrentSignalAssignment { target, rhs, .. } = assign;
+                let ConcurrentSignalAssignment {
+                    guarded,
+                    target,
+                    rhs,
+                    ..
+                } = assign;
+                if *guarded {
+                    self.check_guard_signal(scope, &target.pos, diagnostics);
+                }
                 self.analyze_waveform_assignment(
                     scope,
                     target,
@@ -402,6 +426,36 @@ impl<'a> AnalyzeContext<'a> {
         self.analyze_assoc_elems(scope, aspect.list.items.as_mut_slice(), diagnostics)
     }
 
+    /// LRM 11.6 A guarded assignment is controlled by the boolean signal GUARD
+    /// which is implicitly declared by a guarded block
+    fn check_guard_signal(
+        &self,
+        scope: &Scope<'a>,
+        pos: &SrcPos,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match scope.lookup(pos, &Designator::Identifier(self.guard_sym.clone())) {
+            Ok(NamedEntities::Single(ent)) => {
+                let is_boolean_signal = matches!(
+                    ent.actual_kind(),
+                    AnyEntKind::Object(object)
+                        if object.class == ObjectClass::Signal
+                            && object.subtype.base_type() == self.boolean()
+                );
+                if !is_boolean_signal {
+                    diagnostics.push(ent.kind_error(pos, "boolean signal 'GUARD'"));
+                }
+            }
+            Ok(NamedEntities::Overloaded(_)) | Err(_) => {
+                diagnostics.add(
+                    pos,
+                    "Guarded signal assignment must be within a guarded block",
+                    ErrorCode::Unresolved,
+                );
+            }
+        }
+    }
+
     pub fn sensitivity_list_check(
         &self,
         scope: &Scope<'a>,
@@ -414,24 +468,27 @@ impl<'a> AnalyzeContext<'a> {
                 &name.pos,
                 &mut name.item,
                 "is not a signal and cannot be in a sensitivity list",
+                ErrorCode::DisallowedInSensitivityList,
                 diagnostics,
             ))? {
                 if object_name.base.class() != ObjectClass::Signal {
-                    diagnostics.error(
+                    diagnostics.add(
                         &name.pos,
                         format!(
                             "{} is not a signal and cannot be in a sensitivity list",
                             object_name.base.describe_class()
                         ),
+                        ErrorCode::DisallowedInSensitivityList,
                     )
                 } else if object_name.base.mode() == Some(Mode::Out) && !object_name.base.is_port()
                 {
-                    diagnostics.error(
+                    diagnostics.add(
                         &name.pos,
                         format!(
                             "{} cannot be in a sensitivity list",
                             object_name.base.describe_class()
                         ),
+                        ErrorCode::DisallowedInSensitivityList,
                     )
                 }
             }
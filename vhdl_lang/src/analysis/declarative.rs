@@ -27,6 +27,8 @@ impl<'a> AnalyzeContext<'a> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         let mut incomplete_types: FnvHashMap<Symbol, (EntRef<'a>, SrcPos)> = FnvHashMap::default();
+        // The types with a disconnection specification of others or all
+        let mut disconnected_types: FnvHashMap<EntityId, SrcPos> = FnvHashMap::default();
 
         for i in 0..declarations.len() {
             // Handle incomplete types
@@ -44,12 +46,13 @@ impl<'a> AnalyzeContext<'a> {
                                 let decl_pos = match full_definiton {
                                     Some(full_decl) => full_decl.ident.pos(),
                                     None => {
-                                        let mut error = Diagnostic::error(
+                                        let mut error = Diagnostic::new(
                                             type_decl.ident.pos(),
                                             format!(
                                             "Missing full type declaration of incomplete type '{}'",
                                             type_decl.ident.name()
                                         ),
+                                            ErrorCode::MissingFullTypeDeclaration,
                                         );
                                         error.add_related(type_decl.ident.pos(), "The full type declaration shall occur immediately within the same declarative part");
                                         diagnostics.push(error);
@@ -104,6 +107,14 @@ impl<'a> AnalyzeContext<'a> {
                         }
                     }
                 },
+                Declaration::Disconnection(disconnection) => {
+                    self.analyze_disconnection_specification(
+                        scope,
+                        disconnection,
+                        &mut disconnected_types,
+                        diagnostics,
+                    )?;
+                }
                 _ => {
                     self.analyze_declaration(scope, parent, &mut declarations[i], diagnostics)?;
                 }
@@ -166,9 +177,10 @@ impl<'a> AnalyzeContext<'a> {
                     if let Some(ref signature) = signature {
                         diagnostics.push(Diagnostic::should_not_have_signature("Alias", signature));
                     }
-                    diagnostics.error(
+                    diagnostics.add(
                         &name.pos,
                         format!("{} cannot be aliased", resolved_name.describe_type()),
+                        ErrorCode::MismatchedKinds,
                     );
                     return Err(EvalError::Unknown);
                 }
@@ -284,6 +296,7 @@ impl<'a> AnalyzeContext<'a> {
                                 iface: None,
                                 has_default: object_decl.expression.is_some(),
                                 subtype,
+                                signal_kind: object_decl.signal_kind,
                             })
                         };
 
@@ -411,16 +424,18 @@ impl<'a> AnalyzeContext<'a> {
                                     diagnostics,
                                 )?;
                             } else {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &ident.item.pos,
                                     format!("{} is not an attribute", ent.describe()),
+                                    ErrorCode::IllegalAttribute,
                                 );
                             }
                         }
                         Ok(NamedEntities::Overloaded(_)) => {
-                            diagnostics.error(
+                            diagnostics.add(
                                 &ident.item.pos,
                                 format!("Overloaded name '{}' is not an attribute", ident.item),
+                                ErrorCode::IllegalAttribute,
                             );
                         }
                         Err(err) => {
@@ -555,13 +570,409 @@ impl<'a> AnalyzeContext<'a> {
                     scope.add(ent, diagnostics);
                 }
             }
+            Declaration::GroupTemplate(ref mut template) => {
+                let ent = self.arena.define(
+                    &mut template.ident,
+                    parent,
+                    AnyEntKind::GroupTemplate(template.entity_class_entries.clone()),
+                );
+                scope.add(ent, diagnostics);
+            }
+            Declaration::Group(ref mut group) => {
+                self.analyze_group_declaration(scope, parent, group, diagnostics)?;
+            }
+            Declaration::View(ref mut view) => {
+                self.analyze_mode_view_declaration(scope, parent, view, diagnostics)?;
+            }
             Declaration::Configuration(..) => {}
-            Declaration::Type(..) => unreachable!("Handled elsewhere"),
+            Declaration::Type(..) | Declaration::Disconnection(..) => {
+                unreachable!("Handled elsewhere")
+            }
+        };
+
+        Ok(())
+    }
+
+    fn analyze_group_declaration(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        group: &mut GroupDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        fn is_group_template(kind: &AnyEntKind) -> bool {
+            matches!(kind, AnyEntKind::GroupTemplate(_))
+        }
+
+        let template = match self
+            .resolve_selected_name(scope, &mut group.group_template)
+            .and_then(|entities| {
+                self.resolve_non_overloaded_with_kind(
+                    entities,
+                    group.group_template.suffix_pos(),
+                    &is_group_template,
+                    "group template",
+                )
+            }) {
+            Ok(ent) => Some(ent),
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                None
+            }
         };
 
+        let mut constituents = Vec::with_capacity(group.constituents.len());
+        for constituent in group.constituents.iter_mut() {
+            constituents.push(as_fatal(self.resolve_group_constituent(
+                scope,
+                constituent,
+                diagnostics,
+            ))?);
+        }
+
+        if let Some(AnyEntKind::GroupTemplate(entries)) = template.map(|ent| ent.actual_kind()) {
+            // LRM 6.10 A box on the last entry allows any number of constituents of its class
+            let has_box = entries.last().is_some_and(|entry| entry.is_box);
+            let count_ok = if has_box {
+                constituents.len() + 1 >= entries.len()
+            } else {
+                constituents.len() == entries.len()
+            };
+
+            if !count_ok {
+                diagnostics.add(
+                    &group.group_template.pos,
+                    format!(
+                        "Group template '{}' expects {}{} constituents, got {}",
+                        group.group_template.item,
+                        if has_box { "at least " } else { "" },
+                        if has_box {
+                            entries.len() - 1
+                        } else {
+                            entries.len()
+                        },
+                        constituents.len()
+                    ),
+                    ErrorCode::MismatchedKinds,
+                );
+            } else {
+                for (i, (constituent, ent)) in
+                    group.constituents.iter().zip(constituents).enumerate()
+                {
+                    let Some(ent) = ent else {
+                        continue;
+                    };
+                    let entry = entries.get(i).or(entries.last()).unwrap();
+                    if let Some(class) = entity_class_of(ent) {
+                        if class != entry.entity_class {
+                            diagnostics.push(
+                                ent.kind_error(&constituent.pos, &entry.entity_class.to_string()),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        scope.add(
+            self.arena
+                .define(&mut group.ident, parent, AnyEntKind::Group),
+            diagnostics,
+        );
         Ok(())
     }
 
+    /// Resolve the named entity of a constituent of a group declaration
+    fn resolve_group_constituent(
+        &self,
+        scope: &Scope<'a>,
+        name: &mut WithPos<Name>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<EntRef<'a>> {
+        let resolved = self.name_resolve(scope, &name.pos, &mut name.item, diagnostics)?;
+        match resolved {
+            ResolvedName::Design(ent) => Ok(ent.into()),
+            ResolvedName::Type(typ) => Ok(typ.into()),
+            ResolvedName::ObjectName(oname) => match oname.base {
+                ObjectBase::Object(obj) => Ok(obj.ent),
+                ObjectBase::ObjectAlias(_, ent) | ObjectBase::DeferredConstant(ent) => Ok(ent),
+                ObjectBase::ExternalName(_) => Err(EvalError::Unknown),
+            },
+            ResolvedName::Overloaded(des, overloaded) => {
+                if let Some(ent) = overloaded.as_unique() {
+                    if let Some(reference) = name.item.suffix_reference_mut() {
+                        reference.set_unique_reference(ent);
+                    }
+                    Ok(ent)
+                } else {
+                    diagnostics.add(
+                        &des.pos,
+                        format!("Ambiguous group constituent '{}'", des.item),
+                        ErrorCode::AmbiguousCall,
+                    );
+                    Err(EvalError::Unknown)
+                }
+            }
+            ResolvedName::Final(ent) => Ok(ent),
+            ResolvedName::Library(_) | ResolvedName::Expression(_) => {
+                diagnostics.add(
+                    &name.pos,
+                    format!("{} cannot be a group constituent", resolved.describe_type()),
+                    ErrorCode::MismatchedKinds,
+                );
+                Err(EvalError::Unknown)
+            }
+        }
+    }
+
+    /// LRM 7.4 Disconnection specification
+    fn analyze_disconnection_specification(
+        &self,
+        scope: &Scope<'a>,
+        disconnection: &mut DisconnectionSpecification,
+        disconnected_types: &mut FnvHashMap<EntityId, SrcPos>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let DisconnectionSpecification {
+            signal_list,
+            type_mark,
+            after,
+        } = disconnection;
+
+        let typ = match self.resolve_type_mark(scope, type_mark) {
+            Ok(typ) => Some(typ),
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                None
+            }
+        };
+
+        // A disconnection specification of others or all must be the last one of the type
+        if let Some(typ) = typ {
+            if let Some(prev_pos) = disconnected_types.get(&typ.base_type().id()) {
+                let mut error = Diagnostic::new(
+                    &type_mark.pos,
+                    format!(
+                        "Disconnection specification of {} after the disconnection specification of others or all",
+                        typ.describe()
+                    ),
+                    ErrorCode::Duplicate,
+                );
+                error.add_related(prev_pos, "Previously defined here");
+                diagnostics.push(error);
+            } else if matches!(
+                signal_list,
+                GuardedSignalList::Others | GuardedSignalList::All
+            ) {
+                disconnected_types.insert(typ.base_type().id(), type_mark.pos.clone());
+            }
+        }
+
+        if let GuardedSignalList::Names(names) = signal_list {
+            for name in names.iter_mut() {
+                let Some(object_name) = as_fatal(self.resolve_object_name(
+                    scope,
+                    &name.pos,
+                    &mut name.item,
+                    "is not a signal",
+                    ErrorCode::MismatchedKinds,
+                    diagnostics,
+                ))?
+                else {
+                    continue;
+                };
+                if object_name.base.class() != ObjectClass::Signal {
+                    diagnostics.add(
+                        &name.pos,
+                        format!("{} is not a signal", object_name.base.describe_class()),
+                        ErrorCode::MismatchedKinds,
+                    );
+                } else if matches!(
+                    object_name.base,
+                    ObjectBase::Object(object) | ObjectBase::ObjectAlias(object, _)
+                        if object.object().signal_kind.is_none()
+                ) {
+                    diagnostics.add(
+                        &name.pos,
+                        format!(
+                            "{} is not a guarded signal",
+                            object_name.base.describe_class()
+                        ),
+                        ErrorCode::MismatchedKinds,
+                    );
+                } else if let Some(typ) = typ {
+                    // The type mark must denote the type of the signals
+                    if object_name.type_mark().base_type() != typ.base_type() {
+                        diagnostics.add(
+                            &name.pos,
+                            format!(
+                                "{} does not match {}",
+                                object_name.describe_type(),
+                                typ.describe()
+                            ),
+                            ErrorCode::TypeMismatch,
+                        );
+                    }
+                }
+            }
+        }
+
+        self.expr_pos_with_ttyp(scope, self.time(), &after.pos, &mut after.item, diagnostics)
+    }
+
+    /// VHDL-2019 LRM 6.5.2.2 Mode view declarations
+    fn analyze_mode_view_declaration(
+        &self,
+        scope: &Scope<'a>,
+        parent: EntRef<'a>,
+        view: &mut ModeViewDeclaration,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> FatalResult {
+        let subtype = match self.resolve_subtype_indication(scope, &mut view.typ, diagnostics) {
+            Ok(subtype) => Some(subtype),
+            Err(err) => {
+                err.add_to(diagnostics)?;
+                None
+            }
+        };
+
+        let record_type = subtype.and_then(|subtype| {
+            let typ = subtype.type_mark();
+            if let Type::Record(region) = typ.base_type().kind() {
+                Some((typ, region))
+            } else {
+                diagnostics.add(
+                    &view.typ.type_mark.pos,
+                    format!(
+                        "The subtype of a mode view must be a record type, got {}",
+                        typ.describe()
+                    ),
+                    ErrorCode::TypeMismatch,
+                );
+                None
+            }
+        });
+
+        let mut element_modes: FnvHashMap<Symbol, SrcPos> = FnvHashMap::default();
+        for element in view.elements.iter_mut() {
+            let element_view = match element.mode {
+                ElementMode::Simple(_) => None,
+                ElementMode::Record(ref mut name) | ElementMode::Array(ref mut name) => {
+                    as_fatal(self.resolve_view_name(scope, name, diagnostics))?
+                        .map(|subtype| (subtype, name.pos.clone()))
+                }
+            };
+
+            for name in element.names.iter_mut() {
+                let ident = &name.item;
+                match element_modes.entry(ident.item.clone()) {
+                    Entry::Occupied(entry) => {
+                        diagnostics.push(duplicate_error(
+                            &ident.item,
+                            &ident.pos,
+                            Some(entry.get()),
+                        ));
+                        continue;
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(ident.pos.clone());
+                    }
+                }
+
+                let Some((record_type, region)) = record_type else {
+                    continue;
+                };
+                let des = Designator::Identifier(ident.item.clone());
+                let Some(elem) = region.lookup(&des) else {
+                    diagnostics.push(Diagnostic::no_declaration_within(
+                        &record_type,
+                        &ident.pos,
+                        &des,
+                    ));
+                    continue;
+                };
+                name.reference.set_unique_reference(&elem);
+
+                if let Some((view_subtype, ref view_pos)) = element_view {
+                    let view_type = view_subtype.base_type();
+                    let elem_type = elem.type_mark();
+                    let matches = match element.mode {
+                        ElementMode::Array(_) => elem_type
+                            .array_type()
+                            .is_some_and(|(elem_type, _)| elem_type.base_type() == view_type),
+                        _ => elem_type.base_type() == view_type,
+                    };
+                    if !matches {
+                        diagnostics.add(
+                            view_pos,
+                            format!(
+                                "Mode view of {} cannot be used for record element '{}' of {}",
+                                view_subtype.type_mark().describe(),
+                                elem.designator(),
+                                elem_type.describe()
+                            ),
+                            ErrorCode::TypeMismatch,
+                        );
+                    }
+                }
+            }
+        }
+
+        if let Some((_, region)) = record_type {
+            for elem in region.iter() {
+                let has_mode = match elem.designator() {
+                    Designator::Identifier(sym) => element_modes.contains_key(sym),
+                    _ => true,
+                };
+                if !has_mode {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            &view.ident.tree.pos,
+                            format!("Missing mode for record element '{}'", elem.designator()),
+                            ErrorCode::Unassociated,
+                        )
+                        .opt_related(
+                            elem.decl_pos(),
+                            format!("Record element '{}' defined here", elem.designator()),
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(subtype) = subtype {
+            scope.add(
+                self.arena
+                    .define(&mut view.ident, parent, AnyEntKind::View(subtype)),
+                diagnostics,
+            );
+        }
+        Ok(())
+    }
+
+    /// Resolve the name of a mode view, such as `v` or `v'converse`, to the subtype of the view
+    fn resolve_view_name(
+        &self,
+        scope: &Scope<'a>,
+        name: &mut WithPos<Name>,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Subtype<'a>> {
+        let resolved = self.name_resolve(scope, &name.pos, &mut name.item, diagnostics)?;
+        if let ResolvedName::Final(ent) = resolved {
+            if let AnyEntKind::View(subtype) = ent.actual_kind() {
+                return Ok(*subtype);
+            }
+            diagnostics.push(ent.kind_error(&name.pos, "view"));
+        } else {
+            diagnostics.add(
+                &name.pos,
+                format!("Expected view, got {}", resolved.describe()),
+                ErrorCode::MismatchedKinds,
+            );
+        }
+        Err(EvalError::Unknown)
+    }
+
     fn find_subpgm_declaration(
         &self,
         scope: &Scope<'a>,
@@ -700,16 +1111,17 @@ impl<'a> AnalyzeContext<'a> {
                         };
 
                         if !is_ok {
-                            diagnostics.push(Diagnostic::error(
+                            diagnostics.push(Diagnostic::mismatched_kinds(
                                 type_decl.ident.pos(),
                                 format!("'{}' is not a protected type", &type_decl.ident),
                             ));
                         }
                     }
                     None => {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::new(
                             type_decl.ident.pos(),
                             format!("No declaration of protected type '{}'", &type_decl.ident),
+                            ErrorCode::Unresolved,
                         ));
                     }
                 };
@@ -929,13 +1341,14 @@ impl<'a> AnalyzeContext<'a> {
                     match self.resolve_physical_unit(scope, &mut value.unit) {
                         Ok(secondary_unit_type) => {
                             if secondary_unit_type.base_type() != phys_type {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &value.unit.item.pos,
                                     format!(
                                         "Physical unit of type '{}' does not match {}",
                                         secondary_unit_type.designator(),
                                         phys_type.describe()
                                     ),
+                                    ErrorCode::TypeMismatch,
                                 )
                             }
                         }
@@ -975,7 +1388,11 @@ impl<'a> AnalyzeContext<'a> {
                     } else if range_typ.is_any_real() {
                         UniversalType::Real
                     } else {
-                        diagnostics.error(&range.pos(), "Expected real or integer range");
+                        diagnostics.add(
+                            &range.pos(),
+                            "Expected real or integer range",
+                            ErrorCode::TypeMismatch,
+                        );
                         return Ok(());
                     }
                 } else {
@@ -1101,12 +1518,15 @@ impl<'a> AnalyzeContext<'a> {
         parent: EntRef<'a>,
         decl: &mut InterfaceDeclaration,
         diagnostics: &mut dyn DiagnosticHandler,
-    ) -> AnalysisResult<EntRef<'a>> {
+    ) -> EvalResult<EntRef<'a>> {
         let ent = match decl {
             InterfaceDeclaration::File(ref mut file_decl) => {
-                let file_type = self.resolve_subtype_indication(
-                    scope,
-                    &mut file_decl.subtype_indication,
+                let file_type = catch_analysis_err(
+                    self.resolve_subtype_indication(
+                        scope,
+                        &mut file_decl.subtype_indication,
+                        diagnostics,
+                    ),
                     diagnostics,
                 )?;
                 self.arena.define(
@@ -1136,7 +1556,7 @@ impl<'a> AnalyzeContext<'a> {
                     }
                 }
 
-                let subtype = subtype?;
+                let subtype = catch_analysis_err(subtype, diagnostics)?;
                 self.arena.define(
                     &mut object_decl.ident,
                     parent,
@@ -1148,6 +1568,7 @@ impl<'a> AnalyzeContext<'a> {
                         )),
                         subtype,
                         has_default: object_decl.expression.is_some(),
+                        signal_kind: object_decl.signal_kind,
                     }),
                 )
             }
@@ -1175,18 +1596,23 @@ impl<'a> AnalyzeContext<'a> {
                 typ.into()
             }
             InterfaceDeclaration::Subprogram(ref mut subpgm, ..) => {
-                let (_, ent) = self.subprogram_declaration(
-                    scope,
-                    parent,
-                    subpgm,
-                    Overloaded::InterfaceSubprogram,
+                let (_, ent) = catch_analysis_err(
+                    self.subprogram_declaration(
+                        scope,
+                        parent,
+                        subpgm,
+                        Overloaded::InterfaceSubprogram,
+                        diagnostics,
+                    ),
                     diagnostics,
                 )?;
                 ent.into()
             }
             InterfaceDeclaration::Package(ref mut instance) => {
-                let package_region =
-                    self.analyze_package_instance_name(scope, &mut instance.package_name)?;
+                let package_region = catch_analysis_err(
+                    self.analyze_package_instance_name(scope, &mut instance.package_name),
+                    diagnostics,
+                )?;
 
                 self.arena.define(
                     &mut instance.ident,
@@ -1194,10 +1620,66 @@ impl<'a> AnalyzeContext<'a> {
                     AnyEntKind::Design(Design::PackageInstance(package_region.clone())),
                 )
             }
+            InterfaceDeclaration::View(ref mut view_decl) => {
+                let subtype =
+                    self.analyze_mode_view_indication(scope, &mut view_decl.view, diagnostics)?;
+                self.arena.define(
+                    &mut view_decl.ident,
+                    parent,
+                    AnyEntKind::Object(Object {
+                        class: ObjectClass::Signal,
+                        // The mode of each element is given by the mode view
+                        iface: Some(ObjectInterface::new(view_decl.list_type, Mode::InOut)),
+                        subtype,
+                        has_default: false,
+                        signal_kind: None,
+                    }),
+                )
+            }
         };
         Ok(ent)
     }
 
+    /// VHDL-2019 LRM 6.5.2 Returns the subtype of an interface signal with a mode view indication
+    fn analyze_mode_view_indication(
+        &self,
+        scope: &Scope<'a>,
+        view: &mut ModeViewIndication,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> EvalResult<Subtype<'a>> {
+        let view_subtype = as_fatal(self.resolve_view_name(scope, &mut view.name, diagnostics))?;
+        let Some(ref mut subtype_indication) = view.subtype_indication else {
+            return view_subtype.ok_or(EvalError::Unknown);
+        };
+        let subtype = catch_analysis_err(
+            self.resolve_subtype_indication(scope, subtype_indication, diagnostics),
+            diagnostics,
+        )?;
+
+        if let Some(view_subtype) = view_subtype {
+            let view_type = view_subtype.base_type();
+            let matches = match view.kind {
+                ModeViewIndicationKind::Record => subtype.base_type() == view_type,
+                ModeViewIndicationKind::Array => subtype
+                    .type_mark()
+                    .array_type()
+                    .is_some_and(|(elem_type, _)| elem_type.base_type() == view_type),
+            };
+            if !matches {
+                diagnostics.add(
+                    &subtype_indication.type_mark.pos,
+                    format!(
+                        "{} does not match mode view of {}",
+                        subtype.type_mark().describe(),
+                        view_subtype.type_mark().describe()
+                    ),
+                    ErrorCode::TypeMismatch,
+                );
+            }
+        }
+        Ok(subtype)
+    }
+
     pub fn analyze_interface_list(
         &self,
         scope: &Scope<'a>,
@@ -1206,13 +1688,10 @@ impl<'a> AnalyzeContext<'a> {
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> FatalResult {
         for decl in declarations.iter_mut() {
-            match self.analyze_interface_declaration(scope, parent, decl, diagnostics) {
-                Ok(ent) => {
-                    scope.add(ent, diagnostics);
-                }
-                Err(err) => {
-                    err.add_to(diagnostics)?;
-                }
+            if let Some(ent) =
+                as_fatal(self.analyze_interface_declaration(scope, parent, decl, diagnostics))?
+            {
+                scope.add(ent, diagnostics);
             }
         }
         Ok(())
@@ -1228,14 +1707,11 @@ impl<'a> AnalyzeContext<'a> {
         let mut params = FormalRegion::new(InterfaceType::Parameter);
 
         for decl in declarations.iter_mut() {
-            match self.analyze_interface_declaration(scope, parent, decl, diagnostics) {
-                Ok(ent) => {
-                    scope.add(ent, diagnostics);
-                    params.add(ent);
-                }
-                Err(err) => {
-                    err.add_to(diagnostics)?;
-                }
+            if let Some(ent) =
+                as_fatal(self.analyze_interface_declaration(scope, parent, decl, diagnostics))?
+            {
+                scope.add(ent, diagnostics);
+                params.add(ent);
             }
         }
         Ok(params)
@@ -1285,16 +1761,17 @@ impl<'a> AnalyzeContext<'a> {
                                 self.drange_unknown_type(scope, drange, diagnostics)?;
                             }
                         } else {
-                            diagnostics.error(
+                            diagnostics.add(
                                 drange.pos(),
                                 format!("Got extra index constraint for {}", base_type.describe()),
+                                ErrorCode::DimensionMismatch,
                             );
                         }
                     }
 
                     // empty dranges means (open)
                     if dranges.len() < indexes.len() && !dranges.is_empty() {
-                        diagnostics.error(
+                        diagnostics.add(
                             pos,
                             format!(
                                 "Too few index constraints for {}. Got {} but expected {}",
@@ -1302,6 +1779,7 @@ impl<'a> AnalyzeContext<'a> {
                                 dranges.len(),
                                 indexes.len()
                             ),
+                            ErrorCode::DimensionMismatch,
                         );
                     }
 
@@ -1315,12 +1793,13 @@ impl<'a> AnalyzeContext<'a> {
                         )?;
                     }
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         pos,
                         format!(
                             "Array constraint cannot be used for {}",
                             base_type.describe()
                         ),
+                        ErrorCode::IllegalConstraint,
                     );
                 }
             }
@@ -1328,12 +1807,13 @@ impl<'a> AnalyzeContext<'a> {
                 if base_type.is_scalar() {
                     self.range_with_ttyp(scope, base_type.into(), range, diagnostics)?;
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         pos,
                         format!(
                             "Scalar constraint cannot be used for {}",
                             base_type.describe()
                         ),
+                        ErrorCode::IllegalConstraint,
                     );
                 }
             }
@@ -1357,12 +1837,13 @@ impl<'a> AnalyzeContext<'a> {
                         }
                     }
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         pos,
                         format!(
                             "Record constraint cannot be used for {}",
                             base_type.describe()
                         ),
+                        ErrorCode::IllegalConstraint,
                     );
                 }
             }
@@ -1516,28 +1997,69 @@ impl Diagnostic {
         des: &Designator,
         overloaded: &OverloadedName,
     ) -> Diagnostic {
-        let mut diagnostic = Diagnostic::error(
+        let mut diagnostic = Diagnostic::new(
             pos,
             format!(
                 "Could not find declaration of {} with given signature",
                 des.describe()
             ),
+            ErrorCode::NoOverloadedWithSignature,
         );
         diagnostic.add_subprogram_candidates("Found", overloaded.entities());
         diagnostic
     }
 
     fn should_not_have_signature(prefix: &str, pos: impl AsRef<SrcPos>) -> Diagnostic {
-        Diagnostic::error(
+        Diagnostic::new(
             pos,
             format!("{prefix} should only have a signature for subprograms and enum literals"),
+            ErrorCode::IllegalSignature,
         )
     }
 
     fn signature_required(pos: impl AsRef<SrcPos>) -> Diagnostic {
-        Diagnostic::error(
+        Diagnostic::new(
             pos,
             "Signature required for alias of subprogram and enum literals",
+            ErrorCode::SignatureRequired,
         )
     }
 }
+
+/// The entity class of a named entity that can be a constituent of a group
+fn entity_class_of(ent: EntRef) -> Option<EntityClass> {
+    let class = match ent.actual_kind() {
+        AnyEntKind::Object(object) => match object.class {
+            ObjectClass::Signal => EntityClass::Signal,
+            ObjectClass::Constant => EntityClass::Constant,
+            ObjectClass::Variable | ObjectClass::SharedVariable => EntityClass::Variable,
+        },
+        AnyEntKind::DeferredConstant(_) => EntityClass::Constant,
+        AnyEntKind::Type(Type::Subtype(_)) => EntityClass::Subtype,
+        AnyEntKind::Type(_) => EntityClass::Type,
+        AnyEntKind::File(_) | AnyEntKind::InterfaceFile(_) => EntityClass::File,
+        AnyEntKind::Component(_) => EntityClass::Component,
+        AnyEntKind::Design(Design::Entity(..)) => EntityClass::Entity,
+        AnyEntKind::Design(Design::Architecture(_)) => EntityClass::Architecture,
+        AnyEntKind::Design(Design::Configuration) => EntityClass::Configuration,
+        AnyEntKind::Design(
+            Design::Package(..) | Design::UninstPackage(..) | Design::PackageInstance(_),
+        ) => EntityClass::Package,
+        AnyEntKind::Overloaded(
+            Overloaded::Subprogram(signature)
+            | Overloaded::SubprogramDecl(signature)
+            | Overloaded::InterfaceSubprogram(signature),
+        ) => {
+            if signature.return_type.is_some() {
+                EntityClass::Function
+            } else {
+                EntityClass::Procedure
+            }
+        }
+        AnyEntKind::PhysicalLiteral(_) => EntityClass::Units,
+        AnyEntKind::Group => EntityClass::Group,
+        AnyEntKind::Concurrent(_) | AnyEntKind::Sequential(_) => EntityClass::Label,
+        _ => return None,
+    };
+    Some(class)
+}
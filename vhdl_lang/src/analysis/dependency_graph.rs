@@ -0,0 +1,219 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Dependencies between design units
+
+use super::instance_hierarchy::escape_dot;
+use super::named_entity::*;
+use super::root::DesignRoot;
+use crate::ast::visitor::{walk, Visitor, VisitorResult};
+use crate::ast::*;
+use crate::data::*;
+use crate::syntax::TokenAccess;
+use fnv::{FnvHashMap, FnvHashSet};
+use std::fmt::Write;
+use std::ops::Deref;
+
+/// Collects the components instantiated within a design unit
+#[derive(Default)]
+struct InstantiatedComponents {
+    components: Vec<EntityId>,
+}
+
+impl Visitor for InstantiatedComponents {
+    fn visit_instantiated_unit(
+        &mut self,
+        node: &InstantiatedUnit,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        if let InstantiatedUnit::Component(ref name) = node {
+            if let Some(id) = name.item.reference() {
+                self.components.push(id);
+            }
+        }
+        VisitorResult::Continue
+    }
+}
+
+/// The dependencies between design units found during the last analysis
+///
+/// A unit depends on the units it references through context clauses, names,
+/// instantiations and its primary unit. Instantiated components depend on
+/// the entity they are bound to by default binding.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct DependencyGraph {
+    // unit => units used by it
+    dependencies: FnvHashMap<UnitId, FnvHashSet<UnitId>>,
+    // unit => units using it
+    users: FnvHashMap<UnitId, FnvHashSet<UnitId>>,
+}
+
+impl DependencyGraph {
+    fn add_unit(&mut self, unit_id: &UnitId) {
+        self.dependencies.entry(unit_id.clone()).or_default();
+        self.users.entry(unit_id.clone()).or_default();
+    }
+
+    fn add_dependency(&mut self, user: &UnitId, used: &UnitId) {
+        self.add_unit(user);
+        self.add_unit(used);
+        self.dependencies
+            .get_mut(user)
+            .unwrap()
+            .insert(used.clone());
+        self.users.get_mut(used).unwrap().insert(user.clone());
+    }
+
+    /// All units of the graph sorted by library and name
+    pub fn units(&self) -> Vec<&UnitId> {
+        sorted(self.dependencies.keys())
+    }
+
+    /// The units directly used by a unit
+    pub fn dependencies_of(&self, unit_id: &UnitId) -> Vec<&UnitId> {
+        sorted(self.dependencies.get(unit_id).into_iter().flatten())
+    }
+
+    /// The units directly using a unit
+    pub fn users_of(&self, unit_id: &UnitId) -> Vec<&UnitId> {
+        sorted(self.users.get(unit_id).into_iter().flatten())
+    }
+
+    /// All units that a unit needs directly or indirectly
+    pub fn transitive_dependencies_of(&self, unit_id: &UnitId) -> Vec<&UnitId> {
+        sorted(transitive_closure(&self.dependencies, unit_id).into_iter())
+    }
+
+    /// All units that are affected by a change of a unit
+    pub fn transitive_users_of(&self, unit_id: &UnitId) -> Vec<&UnitId> {
+        sorted(transitive_closure(&self.users, unit_id).into_iter())
+    }
+
+    /// The graph restricted to a set of units
+    pub fn subgraph<'a>(&self, units: impl IntoIterator<Item = &'a UnitId>) -> DependencyGraph {
+        let units: FnvHashSet<&UnitId> = units.into_iter().collect();
+        let mut graph = DependencyGraph::default();
+        for unit_id in units.iter() {
+            graph.add_unit(unit_id);
+            for used in self.dependencies.get(*unit_id).into_iter().flatten() {
+                if units.contains(used) {
+                    graph.add_dependency(unit_id, used);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Graphviz DOT digraph with an edge from each unit to the units it uses
+    pub fn to_dot(&self) -> String {
+        let mut result = String::from("digraph dependencies {\n  node [shape=box];\n");
+        for unit_id in self.units() {
+            writeln!(result, "  \"{}\";", escape_dot(&describe_unit(unit_id))).unwrap();
+        }
+        for unit_id in self.units() {
+            for used in self.dependencies_of(unit_id) {
+                writeln!(
+                    result,
+                    "  \"{}\" -> \"{}\";",
+                    escape_dot(&describe_unit(unit_id)),
+                    escape_dot(&describe_unit(used))
+                )
+                .unwrap();
+            }
+        }
+        result.push_str("}\n");
+        result
+    }
+}
+
+/// Describe a unit as `library.primary` or `library.primary(secondary)`
+pub fn describe_unit(unit_id: &UnitId) -> String {
+    match unit_id.secondary_name() {
+        Some(name) if unit_id.kind() == AnyKind::Secondary(SecondaryKind::Architecture) => {
+            format!(
+                "{}.{}({})",
+                unit_id.library_name(),
+                unit_id.primary_name(),
+                name
+            )
+        }
+        Some(_) => format!("{}.{} body", unit_id.library_name(), unit_id.primary_name()),
+        None => format!("{}.{}", unit_id.library_name(), unit_id.primary_name()),
+    }
+}
+
+fn sorted<'a>(units: impl Iterator<Item = &'a UnitId>) -> Vec<&'a UnitId> {
+    let mut units: Vec<_> = units.collect();
+    units.sort_by_cached_key(|unit_id| describe_unit(unit_id));
+    units
+}
+
+fn transitive_closure<'a>(
+    edges: &'a FnvHashMap<UnitId, FnvHashSet<UnitId>>,
+    unit_id: &UnitId,
+) -> FnvHashSet<&'a UnitId> {
+    let mut result = FnvHashSet::default();
+    let mut pending: Vec<&UnitId> = edges.get(unit_id).into_iter().flatten().collect();
+    while let Some(unit_id) = pending.pop() {
+        if result.insert(unit_id) {
+            pending.extend(edges.get(unit_id).into_iter().flatten());
+        }
+    }
+    result
+}
+
+impl DesignRoot {
+    /// Returns the unit id of a design unit if it exists
+    pub fn unit_id(&self, library_name: &Symbol, key: &UnitKey) -> Option<UnitId> {
+        self.get_library_units(library_name)?
+            .get(key)
+            .map(|unit| unit.unit_id().clone())
+    }
+
+    /// The entities bound to instantiated components of a unit by default binding
+    pub(super) fn bound_entities(&self, unit_id: &UnitId) -> Vec<UnitId> {
+        let mut entities = Vec::new();
+        let Some(unit) = self.get_unit(unit_id) else {
+            return entities;
+        };
+
+        let data = self.get_analysis(unit);
+        let mut visitor = InstantiatedComponents::default();
+        walk(data.deref(), &mut visitor, &unit.tokens);
+
+        for id in visitor.components {
+            for entity in self.find_implementation(self.get_ent(id)) {
+                if let (Some(library_name), Designator::Identifier(name)) =
+                    (entity.library_name(), entity.designator())
+                {
+                    entities.extend(self.unit_id(library_name, &UnitKey::Primary(name.clone())));
+                }
+            }
+        }
+        entities
+    }
+
+    /// The dependencies between all design units. Requires that the design has been analyzed.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        let mut graph = DependencyGraph::default();
+
+        for library in self.libraries() {
+            for unit in library.units() {
+                graph.add_unit(unit.unit_id());
+                for entity in self.bound_entities(unit.unit_id()) {
+                    graph.add_dependency(unit.unit_id(), &entity);
+                }
+            }
+        }
+
+        for (user, dependencies) in self.unit_dependencies() {
+            for used in dependencies.iter() {
+                graph.add_dependency(&user, used);
+            }
+        }
+        graph
+    }
+}
@@ -103,13 +103,14 @@ impl<'a> AnalyzeContext<'a> {
                     if primary_pos.source == secondary_pos.source
                         && primary_pos.start() > secondary_pos.start()
                     {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::new(
                             secondary_pos,
                             capitalize(&format!(
                                 "{} declared before {}",
                                 self.current_unit_id().describe(),
                                 named_entity.describe()
                             )),
+                            ErrorCode::DeclaredBefore,
                         ));
                     }
                 }
@@ -249,7 +250,7 @@ impl<'a> AnalyzeContext<'a> {
             if let Design::Entity(ref visibility, ref region) = primary.kind() {
                 (visibility, region)
             } else {
-                let mut diagnostic = Diagnostic::error(unit.pos(), "Expected an entity");
+                let mut diagnostic = Diagnostic::mismatched_kinds(unit.pos(), "Expected an entity");
 
                 if let Some(pos) = primary.decl_pos() {
                     diagnostic.add_related(pos, format!("Found {}", primary.describe()))
@@ -299,7 +300,7 @@ impl<'a> AnalyzeContext<'a> {
             Design::Package(ref visibility, ref region)
             | Design::UninstPackage(ref visibility, ref region) => (visibility, region),
             _ => {
-                let mut diagnostic = Diagnostic::error(unit.pos(), "Expected a package");
+                let mut diagnostic = Diagnostic::mismatched_kinds(unit.pos(), "Expected a package");
 
                 if let Some(pos) = primary.decl_pos() {
                     diagnostic.add_related(pos, format!("Found {}", primary.describe()))
@@ -342,13 +343,14 @@ impl<'a> AnalyzeContext<'a> {
             if primary_pos.source == secondary_pos.source
                 && primary_pos.start() > secondary_pos.start()
             {
-                diagnostics.push(Diagnostic::error(
+                diagnostics.push(Diagnostic::new(
                     secondary_pos,
                     format!(
                         "{} declared before {}",
                         capitalize(&self.current_unit_id().describe()),
                         primary.describe(),
                     ),
+                    ErrorCode::DeclaredBefore,
                 ));
             }
         }
@@ -482,17 +484,19 @@ impl<'a> AnalyzeContext<'a> {
                     for library_name in name_list.items.iter_mut() {
                         if self.work_sym == library_name.item.item {
                             library_name.set_unique_reference(self.work_library());
-                            diagnostics.push(Diagnostic::hint(
+                            diagnostics.push(Diagnostic::new(
                                 &library_name.item,
                                 "Library clause not necessary for current working library",
+                                ErrorCode::UnnecessaryWorkLibrary,
                             ))
                         } else if let Some(library) = self.get_library(&library_name.item.item) {
                             library_name.set_unique_reference(library);
                             scope.make_potentially_visible(Some(&library_name.item.pos), library);
                         } else {
-                            diagnostics.push(Diagnostic::error(
+                            diagnostics.push(Diagnostic::new(
                                 &library_name.item,
                                 format!("No such library '{}'", library_name.item),
+                                ErrorCode::Unresolved,
                             ));
                         }
                     }
@@ -507,7 +511,7 @@ impl<'a> AnalyzeContext<'a> {
                         match name.item {
                             Name::Selected(..) => {}
                             _ => {
-                                diagnostics.push(Diagnostic::error(
+                                diagnostics.push(Diagnostic::mismatched_kinds(
                                     &name.pos,
                                     "Context reference must be a selected name",
                                 ));
@@ -528,7 +532,7 @@ impl<'a> AnalyzeContext<'a> {
                                     }
                                     _ => {
                                         if let Name::Selected(_, ref suffix) = name.item {
-                                            diagnostics.push(Diagnostic::error(
+                                            diagnostics.push(Diagnostic::mismatched_kinds(
                                                 suffix,
                                                 format!(
                                                     "{} does not denote a context declaration",
@@ -565,7 +569,7 @@ impl<'a> AnalyzeContext<'a> {
                 Name::Selected(..) => {}
                 Name::SelectedAll(..) => {}
                 _ => {
-                    diagnostics.push(Diagnostic::error(
+                    diagnostics.push(Diagnostic::mismatched_kinds(
                         &name.pos,
                         "Use clause must be a selected name",
                     ));
@@ -595,13 +599,20 @@ impl<'a> AnalyzeContext<'a> {
                                 scope.make_all_potentially_visible(Some(&name.pos), primary_region);
                             }
                             _ => {
-                                diagnostics
-                                    .error(visibility_pos, "Invalid prefix for selected name");
+                                diagnostics.add(
+                                    visibility_pos,
+                                    "Invalid prefix for selected name",
+                                    ErrorCode::MismatchedKinds,
+                                );
                             }
                         },
 
                         _ => {
-                            diagnostics.error(visibility_pos, "Invalid prefix for selected name");
+                            diagnostics.add(
+                                visibility_pos,
+                                "Invalid prefix for selected name",
+                                ErrorCode::MismatchedKinds,
+                            );
                         }
                     }
                 }
@@ -232,9 +232,9 @@ impl<'a> AnalyzeContext<'a> {
             | ExpressionType::String
             | ExpressionType::Null
             | ExpressionType::Aggregate => {
-                diagnostics.error(
+                diagnostics.add(
                 &expr.pos,
-                "Ambiguous expression. You can use a qualified expression type'(expr) to disambiguate.",
+                "Ambiguous expression. You can use a qualified expression type'(expr) to disambiguate.", ErrorCode::AmbiguousExpression
             );
                 Err(EvalError::Unknown)
             }
@@ -252,7 +252,7 @@ impl<'a> AnalyzeContext<'a> {
         match scope.lookup(op_pos, &designator)? {
             NamedEntities::Single(ent) => {
                 // Should never happen but better know if it does
-                Err(Diagnostic::error(
+                Err(Diagnostic::mismatched_kinds(
                     op_pos,
                     format!(
                         "Operator symbol cannot denote non-overloaded symbol {}",
@@ -269,9 +269,10 @@ impl<'a> AnalyzeContext<'a> {
                     .collect();
 
                 if op_candidates.is_empty() {
-                    Err(Diagnostic::error(
+                    Err(Diagnostic::new(
                         op_pos,
                         format!("Found no match for {}", designator.describe()),
+                        ErrorCode::InvalidCall,
                     )
                     .into())
                 } else {
@@ -397,9 +398,10 @@ impl<'a> AnalyzeContext<'a> {
         }
 
         if candidates.is_empty() {
-            diagnostics.error(
+            diagnostics.add(
                 &op.pos,
                 format!("Found no match for {}", designator.describe()),
+                ErrorCode::InvalidCall,
             );
 
             Err(EvalError::Unknown)
@@ -510,12 +512,13 @@ impl<'a> AnalyzeContext<'a> {
                     match scope.lookup(expr_pos, &Designator::Character(*chr)) {
                         Ok(NamedEntities::Single(ent)) => {
                             // Should never happen but better know if it does
-                            diagnostics.error(
+                            diagnostics.add(
                                 expr_pos,
                                 format!(
                                     "Character literal cannot denote non-overloaded symbol {}",
                                     ent.describe(),
                                 ),
+                                ErrorCode::MismatchedKinds,
                             );
                             Err(EvalError::Unknown)
                         }
@@ -525,12 +528,13 @@ impl<'a> AnalyzeContext<'a> {
                                 if let Some(return_type) = ent.return_type() {
                                     Ok(ExpressionType::Unambiguous(return_type))
                                 } else {
-                                    diagnostics.error(
+                                    diagnostics.add(
                                         expr_pos,
                                         format!(
                                             "Character literal cannot denote procedure symbol {}",
                                             ent.describe(),
                                         ),
+                                        ErrorCode::MismatchedKinds,
                                     );
                                     Err(EvalError::Unknown)
                                 }
@@ -651,22 +655,24 @@ impl<'a> AnalyzeContext<'a> {
             match types {
                 ExpressionType::Unambiguous(typ) => {
                     if !typ.base().is_any_integer() {
-                        diagnostics.error(
+                        diagnostics.add(
                             &expr.pos,
                             format!("Expected integer type, got {}", typ.describe()),
+                            ErrorCode::TypeMismatch,
                         )
                     }
                 }
                 ExpressionType::Ambiguous(types) => {
                     // @TODO does not check if type is ambiguous
                     if types.iter().any(|typ| !typ.is_any_integer()) {
-                        diagnostics.error(&expr.pos, "Expected integer type")
+                        diagnostics.add(&expr.pos, "Expected integer type", ErrorCode::TypeMismatch)
                     }
                 }
                 ExpressionType::String | ExpressionType::Null | ExpressionType::Aggregate => {
-                    diagnostics.error(
+                    diagnostics.add(
                         &expr.pos,
                         format!("Expected integer type, got {}", types.describe()),
+                        ErrorCode::TypeMismatch,
                     )
                 }
             }
@@ -687,13 +693,13 @@ impl<'a> AnalyzeContext<'a> {
                     if typ.base() != self.boolean().base() {
                         let implicit_bools = self.implicit_bool_types(scope, &expr.pos);
                         if !implicit_bools.contains(&typ.base()) {
-                            diagnostics.error(
+                            diagnostics.add(
                                 &expr.pos,
                                 format!(
                                     "{} cannot be implictly converted to {}. Operator ?? is not defined for this type.",
                                     typ.describe(),
                                     self.boolean().describe()
-                                ),
+                                ), ErrorCode::NoImplicitConversion
                             );
                         }
                     }
@@ -714,21 +720,23 @@ impl<'a> AnalyzeContext<'a> {
                                 self.expr_with_ttyp(scope, typ, expr, diagnostics)?;
                             }
                             std::cmp::Ordering::Greater => {
-                                let mut diag = Diagnostic::error(
+                                let mut diag = Diagnostic::new(
                                     &expr.pos,
                                     "Ambiguous use of implicit boolean conversion ??",
+                                    ErrorCode::AmbiguousExpression,
                                 );
                                 diag.add_type_candididates("Could be", implicit_bool_types);
                                 diagnostics.push(diag);
                             }
 
                             std::cmp::Ordering::Less => {
-                                let mut diag = Diagnostic::error(
+                                let mut diag = Diagnostic::new(
                                     &expr.pos,
                                     format!(
                                         "Cannot disambiguate expression to {}",
                                         self.boolean().describe()
                                     ),
+                                    ErrorCode::AmbiguousExpression,
                                 );
                                 diag.add_type_candididates(
                                     "Implicit boolean conversion operator ?? is not defined for",
@@ -891,9 +899,10 @@ impl<'a> AnalyzeContext<'a> {
                 _ => {
                     self.analyze_aggregate(scope, assocs, diagnostics)?;
 
-                    diagnostics.error(
+                    diagnostics.add(
                         expr_pos,
                         format!("composite does not match {}", target_type.describe()),
+                        ErrorCode::TypeMismatch,
                     );
                 }
             },
@@ -972,18 +981,20 @@ impl<'a> AnalyzeContext<'a> {
                                     }
                                 } else {
                                     is_ok_so_far = false;
-                                    diagnostics.error(
+                                    diagnostics.add(
                                         &choice.pos,
                                         "Record aggregate choice must be a simple name",
+                                        ErrorCode::MismatchedKinds,
                                     );
                                     None
                                 }
                             }
                             Choice::DiscreteRange(_) => {
                                 is_ok_so_far = false;
-                                diagnostics.error(
+                                diagnostics.add(
                                     &choice.pos,
                                     "Record aggregate choice must be a simple name",
+                                    ErrorCode::MismatchedKinds,
                                 );
                                 None
                             }
@@ -1001,7 +1012,7 @@ impl<'a> AnalyzeContext<'a> {
                                     .collect();
 
                                 if remaining_types.len() > 1 {
-                                    let mut diag = Diagnostic::error(&choice.pos, format!("Other elements of record '{}' are not of the same type", record_type.designator()));
+                                    let mut diag = Diagnostic::new(&choice.pos, format!("Other elements of record '{}' are not of the same type", record_type.designator()), ErrorCode::TypeMismatch);
                                     for elem in elems.iter() {
                                         if !associated.is_associated(&elem) {
                                             if let Some(decl_pos) = elem.decl_pos() {
@@ -1019,12 +1030,13 @@ impl<'a> AnalyzeContext<'a> {
                                     diagnostics.push(diag);
                                 } else if remaining_types.is_empty() {
                                     diagnostics.push(
-                                        Diagnostic::error(
+                                        Diagnostic::new(
                                             &choice.pos,
                                             format!(
                                             "All elements of record '{}' are already associated",
                                             record_type.designator()
                                         ),
+                                            ErrorCode::AlreadyAssociated,
                                         )
                                         .opt_related(
                                             record_type.decl_pos(),
@@ -1053,8 +1065,11 @@ impl<'a> AnalyzeContext<'a> {
                         if let (Some(first), Some(last)) = (choices.first(), choices.last()) {
                             is_ok_so_far = false;
                             let pos = first.pos.combine(&last.pos);
-                            diagnostics
-                                .error(&pos, "Record aggregate choice must be a simple name");
+                            diagnostics.add(
+                                &pos,
+                                "Record aggregate choice must be a simple name",
+                                ErrorCode::MismatchedKinds,
+                            );
                         }
                         None
                     };
@@ -1079,7 +1094,7 @@ impl<'a> AnalyzeContext<'a> {
                         self.expr_unknown_ttyp(scope, expr, diagnostics)?;
 
                         diagnostics.push(
-                            Diagnostic::error(
+                            Diagnostic::mismatched_kinds(
                                 &expr.pos,
                                 format!(
                                     "Unexpected positional assoctiation for record '{}'",
@@ -1101,12 +1116,13 @@ impl<'a> AnalyzeContext<'a> {
             for elem in elems.iter() {
                 if !associated.is_associated(&elem) {
                     diagnostics.push(
-                        Diagnostic::error(
+                        Diagnostic::new(
                             full_pos,
                             format!(
                                 "Missing association of record element '{}'",
                                 elem.designator()
                             ),
+                            ErrorCode::Unassociated,
                         )
                         .opt_related(
                             elem.decl_pos(),
@@ -1210,12 +1226,13 @@ impl<'a> AnalyzeContext<'a> {
                     ))?;
                 }
             } else {
-                diagnostics.error(
+                diagnostics.add(
                     &expr.pos,
                     format!(
                         "Expected sub-aggregate for target {}",
                         array_type.describe()
                     ),
+                    ErrorCode::MismatchedKinds,
                 );
             }
         } else if can_be_array {
@@ -1244,12 +1261,13 @@ impl Diagnostic {
         op: Operator,
         candidates: impl IntoIterator<Item = OverloadedEnt<'a>>,
     ) -> Diagnostic {
-        let mut diag = Diagnostic::error(
+        let mut diag = Diagnostic::new(
             pos,
             format!(
                 "ambiguous use of {}",
                 Designator::OperatorSymbol(op).describe()
             ),
+            ErrorCode::AmbiguousCall,
         );
         diag.add_subprogram_candidates("might be", candidates);
         diag
@@ -1268,12 +1286,13 @@ impl<'a> RecordAssociations<'a> {
     ) {
         if let Some(prev_pos) = self.0.insert(elem.id(), pos) {
             diagnostics.push(
-                Diagnostic::error(
+                Diagnostic::new(
                     pos,
                     format!(
                         "Record element '{}' has already been associated",
                         elem.designator()
                     ),
+                    ErrorCode::AlreadyAssociated,
                 )
                 .related(prev_pos, "Previously associated here"),
             );
@@ -1446,9 +1465,10 @@ mod test {
 
         check_diagnostics(
             without_releated(&diagnostics),
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("and"),
                 "Found no match for operator \"and\"",
+                ErrorCode::InvalidCall,
             )],
         );
     }
@@ -1464,7 +1484,7 @@ mod test {
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 code.s1("x'subtype"),
                 "integer type 'INTEGER' cannot be used in an expression",
             )],
@@ -1482,10 +1502,12 @@ mod test {
 
         check_diagnostics(
             without_releated(&diagnostics),
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("missing"),
                 "No declaration of 'missing'",
-            )],
+                ErrorCode::Unresolved,
+            )
+            .with_data("missing")],
         );
     }
 
@@ -1551,17 +1573,19 @@ function \"-\"(arg : string) return integer;
         test.expr_with_ttyp(&code, test.lookup_type("INTEGER"), &mut diagnostics);
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(code.s1("-"), "ambiguous use of operator \"-\"")
-                    .related(
-                        decls.s("\"-\"", 1),
-                        "might be operator \"-\"[BIT_VECTOR return INTEGER]",
-                    )
-                    .related(
-                        decls.s("\"-\"", 2),
-                        "might be operator \"-\"[STRING return INTEGER]",
-                    ),
-            ],
+            vec![Diagnostic::new(
+                code.s1("-"),
+                "ambiguous use of operator \"-\"",
+                ErrorCode::AmbiguousCall,
+            )
+            .related(
+                decls.s("\"-\"", 1),
+                "might be operator \"-\"[BIT_VECTOR return INTEGER]",
+            )
+            .related(
+                decls.s("\"-\"", 2),
+                "might be operator \"-\"[STRING return INTEGER]",
+            )],
         );
     }
 
@@ -0,0 +1,293 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Regions of a source file that can be folded
+
+use super::root::DesignRoot;
+use super::use_clause_fix::ContextClauseTokens;
+use crate::ast::visitor::{walk, Visitor, VisitorResult};
+use crate::ast::{
+    ArchitectureBody, ConcurrentStatement, LabeledConcurrentStatement, LabeledSequentialStatement,
+    SequentialStatement, SubprogramBody, SubprogramDeclaration,
+};
+use crate::data::*;
+use crate::syntax::{Kind, Token, TokenAccess};
+
+/// A range of lines that can be folded
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct FoldingRange {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub kind: FoldingRangeKind,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum FoldingRangeKind {
+    /// A design unit, a statement or a declarative part
+    Code,
+    /// A block of comments on consecutive lines
+    Comment,
+    /// A context clause
+    Imports,
+}
+
+impl DesignRoot {
+    /// The regions of a source file that can be folded such as design units, processes,
+    /// compound statements, declarative parts and comment blocks
+    pub fn folding_ranges(&self, source: &Source) -> Vec<FoldingRange> {
+        let mut ranges = Vec::new();
+        let units = self
+            .libraries()
+            .flat_map(|library| library.units())
+            .filter(|unit| {
+                unit.tokens
+                    .first()
+                    .is_some_and(|token| &token.pos.source == source)
+            });
+        for unit in units {
+            let tokens = &unit.tokens;
+            let mut visitor = FoldingRangeVisitor {
+                tokens,
+                ranges: &mut ranges,
+            };
+            visitor.add_design_unit();
+            visitor.add_comments();
+            if let Some(data) = unit.unit.get() {
+                walk(data.data(), &mut visitor, tokens);
+            }
+        }
+        // A file may be part of several libraries
+        ranges.sort();
+        ranges.dedup();
+        ranges
+    }
+}
+
+struct FoldingRangeVisitor<'a> {
+    tokens: &'a [Token],
+    ranges: &'a mut Vec<FoldingRange>,
+}
+
+impl<'a> FoldingRangeVisitor<'a> {
+    fn add(&mut self, start_line: u32, end_line: u32, kind: FoldingRangeKind) {
+        if end_line > start_line {
+            self.ranges.push(FoldingRange {
+                start_line,
+                end_line,
+                kind,
+            });
+        }
+    }
+
+    fn add_design_unit(&mut self) {
+        let (Some(first), Some(last)) = (self.tokens.first(), self.tokens.last()) else {
+            return;
+        };
+        let context_clause = ContextClauseTokens::new(self.tokens);
+        let start_line = if let Some(end_line) = context_clause.end_line {
+            self.add(first.pos.start().line, end_line, FoldingRangeKind::Imports);
+            self.tokens
+                .iter()
+                .map(|token| token.pos.start().line)
+                .find(|line| *line > end_line)
+                .unwrap_or(end_line)
+        } else {
+            first.pos.start().line
+        };
+        self.add(start_line, last.pos.end().line, FoldingRangeKind::Code);
+    }
+
+    /// Comments on consecutive lines before a token
+    fn add_comments(&mut self) {
+        let tokens = self.tokens;
+        for comments in tokens.iter().filter_map(|token| token.comments.as_ref()) {
+            let mut block: Option<(u32, u32)> = None;
+            for comment in &comments.leading {
+                let range = comment.range;
+                block = match block {
+                    Some((start, end)) if range.start.line == end + 1 => {
+                        Some((start, range.end.line))
+                    }
+                    _ => {
+                        if let Some((start, end)) = block {
+                            self.add(start, end, FoldingRangeKind::Comment);
+                        }
+                        Some((range.start.line, range.end.line))
+                    }
+                };
+            }
+            if let Some((start, end)) = block {
+                self.add(start, end, FoldingRangeKind::Comment);
+            }
+        }
+    }
+
+    fn add_statement(&mut self, label: Option<&SrcPos>, pos: &SrcPos) {
+        let start = label.unwrap_or(pos).start();
+        self.add(start.line, pos.end().line, FoldingRangeKind::Code);
+    }
+
+    /// Fold the declarations between the line of the header and the line of `begin`
+    fn add_declarative_part(&mut self, header_line: u32, begin: Option<&Token>) {
+        if let Some(begin) = begin {
+            self.add(
+                header_line + 1,
+                begin.pos.start().line.saturating_sub(1),
+                FoldingRangeKind::Code,
+            );
+        }
+    }
+
+    /// The tokens from `start` until but not including `end`
+    fn tokens_between(&self, start: Position, end: Position) -> &'a [Token] {
+        let tokens = self.tokens;
+        let first = tokens.partition_point(|token| token.pos.start() < start);
+        let last = tokens.partition_point(|token| token.pos.start() < end);
+        &tokens[first..last.max(first)]
+    }
+
+    /// The first `is` after `start` that is not within parenthesis
+    fn find_is(&self, start: Position) -> Option<&'a Token> {
+        let mut depth = 0;
+        for token in self.tokens_between(start, Position::new(u32::MAX, 0)) {
+            match token.kind {
+                Kind::LeftPar => depth += 1,
+                Kind::RightPar => depth -= 1,
+                Kind::Is if depth == 0 => return Some(token),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// The `begin` of a construct is the last one before its first statement
+    /// since any `begin` of nested subprogram bodies comes before it
+    fn find_begin(&self, start: Position, end: Position) -> Option<&'a Token> {
+        self.tokens_between(start, end)
+            .iter()
+            .rev()
+            .find(|token| token.kind == Kind::Begin)
+    }
+}
+
+fn concurrent_statement_start(statement: &LabeledConcurrentStatement) -> Position {
+    statement
+        .label
+        .tree
+        .as_ref()
+        .map_or(statement.statement.pos.start(), |label| label.pos.start())
+}
+
+fn sequential_statement_start(statement: &LabeledSequentialStatement) -> Position {
+    statement
+        .label
+        .tree
+        .as_ref()
+        .map_or(statement.statement.pos.start(), |label| label.pos.start())
+}
+
+impl<'a> Visitor for FoldingRangeVisitor<'a> {
+    fn visit_architecture_body(
+        &mut self,
+        node: &ArchitectureBody,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        if !node.decl.is_empty() {
+            let start = node.ident.tree.pos.start();
+            let end = node
+                .statements
+                .first()
+                .map(concurrent_statement_start)
+                .or_else(|| self.tokens.last().map(|token| token.pos.end()));
+            if let (Some(is), Some(end)) = (self.find_is(start), end) {
+                let begin = self.find_begin(start, end);
+                self.add_declarative_part(is.pos.start().line, begin);
+            }
+        }
+        VisitorResult::Continue
+    }
+
+    fn visit_subprogram_body(
+        &mut self,
+        node: &SubprogramBody,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        let designator = match &node.specification {
+            SubprogramDeclaration::Procedure(procedure) => &procedure.designator.tree.pos,
+            SubprogramDeclaration::Function(function) => &function.designator.tree.pos,
+        };
+        // The end is only known from the statements
+        let (Some(first), Some(last)) = (node.statements.first(), node.statements.last()) else {
+            return VisitorResult::Continue;
+        };
+        let start = designator.start();
+        let end_line = self
+            .tokens_between(last.statement.pos.end(), Position::new(u32::MAX, 0))
+            .iter()
+            .find(|token| token.kind == Kind::End)
+            .map_or(last.statement.pos.end().line, |token| {
+                token.pos.start().line
+            });
+        self.add(start.line, end_line, FoldingRangeKind::Code);
+        if !node.declarations.is_empty() {
+            if let Some(is) = self.find_is(start) {
+                let begin = self.find_begin(start, sequential_statement_start(first));
+                self.add_declarative_part(is.pos.start().line, begin);
+            }
+        }
+        VisitorResult::Continue
+    }
+
+    fn visit_labeled_concurrent_statement(
+        &mut self,
+        node: &LabeledConcurrentStatement,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        let pos = &node.statement.pos;
+        let (decl_is_empty, first_statement) = match &node.statement.item {
+            ConcurrentStatement::Process(process) => (
+                process.decl.is_empty(),
+                process.statements.first().map(sequential_statement_start),
+            ),
+            ConcurrentStatement::Block(block) => (
+                block.decl.is_empty(),
+                block.statements.first().map(concurrent_statement_start),
+            ),
+            ConcurrentStatement::ForGenerate(_)
+            | ConcurrentStatement::IfGenerate(_)
+            | ConcurrentStatement::CaseGenerate(_) => {
+                self.add_statement(node.label.tree.as_ref().map(|label| &label.pos), pos);
+                return VisitorResult::Continue;
+            }
+            _ => return VisitorResult::Continue,
+        };
+        self.add_statement(node.label.tree.as_ref().map(|label| &label.pos), pos);
+        if !decl_is_empty {
+            let begin = self.find_begin(pos.start(), first_statement.unwrap_or(pos.end()));
+            self.add_declarative_part(pos.start().line, begin);
+        }
+        VisitorResult::Continue
+    }
+
+    fn visit_labeled_sequential_statement(
+        &mut self,
+        node: &LabeledSequentialStatement,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        if matches!(
+            node.statement.item,
+            SequentialStatement::If(_)
+                | SequentialStatement::Case(_)
+                | SequentialStatement::Loop(_)
+        ) {
+            self.add_statement(
+                node.label.tree.as_ref().map(|label| &label.pos),
+                &node.statement.pos,
+            );
+        }
+        VisitorResult::Continue
+    }
+}
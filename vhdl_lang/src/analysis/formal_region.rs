@@ -6,6 +6,7 @@
 
 use crate::{
     ast::{Designator, InterfaceType, Mode, ObjectClass},
+    data::ErrorCode,
     Diagnostic, SrcPos,
 };
 
@@ -117,9 +118,10 @@ impl<'a> FormalRegion<'a> {
                 return Ok((idx, *ent));
             }
         }
-        Err(Diagnostic::error(
+        Err(Diagnostic::new(
             pos,
             format!("No declaration of '{designator}'"),
+            ErrorCode::Unresolved,
         ))
     }
 
@@ -293,9 +295,10 @@ impl<'a> GpkgRegion<'a> {
                 return Ok((idx, *ent));
             }
         }
-        Err(Diagnostic::error(
+        Err(Diagnostic::new(
             pos,
             format!("No declaration of '{designator}'"),
+            ErrorCode::Unresolved,
         ))
     }
 
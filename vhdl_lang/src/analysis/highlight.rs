@@ -0,0 +1,175 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Occurrences of a named entity within a source file
+
+use super::root::DesignRoot;
+use crate::ast::search::{FindAllReferences, Search};
+use crate::ast::visitor::{walk, Visitor, VisitorResult};
+use crate::ast::{
+    ConcurrentSignalAssignment, ElementAssociation, Expression, Name, SignalAssignment,
+    SignalForceAssignment, SignalReleaseAssignment, Target, VariableAssignment,
+};
+use crate::data::*;
+use crate::syntax::TokenAccess;
+use crate::EntRef;
+use fnv::FnvHashSet;
+
+/// An occurrence of a named entity within a source file
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Highlight {
+    pub pos: SrcPos,
+    pub kind: HighlightKind,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum HighlightKind {
+    /// The declaration
+    Declaration,
+    /// Any other reference such as where the value is read
+    Read,
+    /// The target of an assignment
+    Write,
+}
+
+impl DesignRoot {
+    /// Find all declarations of and references to a named entity within a source file
+    pub fn find_highlights(&self, source: &Source, ent: EntRef) -> Vec<Highlight> {
+        let units: Vec<_> = self
+            .libraries()
+            .flat_map(|library| library.units())
+            .filter(|unit| {
+                unit.tokens
+                    .first()
+                    .is_some_and(|token| &token.pos.source == source)
+            })
+            .collect();
+
+        // Only the units of the source can contain highlights
+        let mut searcher = FindAllReferences::new(self, ent);
+        let mut targets = AssignmentTargets::default();
+        for unit in units.iter() {
+            let _ = unit.unit.write().search(&unit.tokens, &mut searcher);
+            if let Some(data) = unit.unit.get() {
+                walk(data.data(), &mut targets, &unit.tokens);
+            }
+        }
+        let declarations: FnvHashSet<_> = searcher
+            .declarations
+            .iter()
+            .filter(|pos| pos.source() == source)
+            .map(|pos| pos.range())
+            .collect();
+
+        let mut highlights: Vec<_> = searcher
+            .references
+            .into_iter()
+            .filter(|pos| pos.source() == source)
+            .map(|pos| {
+                let kind = if declarations.contains(&pos.range()) {
+                    HighlightKind::Declaration
+                } else if targets.positions.contains(&pos.range()) {
+                    HighlightKind::Write
+                } else {
+                    HighlightKind::Read
+                };
+                Highlight { pos, kind }
+            })
+            .collect();
+        // A file may be part of several libraries
+        highlights.sort_by_key(|highlight| (highlight.pos.start(), highlight.pos.end()));
+        highlights.dedup_by(|a, b| a.pos.range() == b.pos.range());
+        highlights
+    }
+}
+
+/// Collects the positions of the names that are assigned
+#[derive(Default)]
+struct AssignmentTargets {
+    positions: FnvHashSet<Range>,
+}
+
+impl AssignmentTargets {
+    fn add_target(&mut self, target: &WithPos<Target>) {
+        match &target.item {
+            Target::Name(name) => self.add_name(&target.pos, name),
+            Target::Aggregate(elements) => {
+                for element in elements {
+                    let expression = match element {
+                        ElementAssociation::Positional(expression) => expression,
+                        ElementAssociation::Named(_, expression) => expression,
+                    };
+                    if let Expression::Name(name) = &expression.item {
+                        self.add_name(&expression.pos, name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// The prefix of an indexed or sliced name is assigned but not the index or range
+    fn add_name(&mut self, pos: &SrcPos, name: &Name) {
+        match name {
+            Name::Designator(_) => {
+                self.positions.insert(pos.range());
+            }
+            Name::Selected(prefix, suffix) => {
+                self.positions.insert(suffix.pos.range());
+                self.add_name(&prefix.pos, &prefix.item);
+            }
+            Name::Slice(prefix, _) => self.add_name(&prefix.pos, &prefix.item),
+            Name::CallOrIndexed(call) => self.add_name(&call.name.pos, &call.name.item),
+            _ => {}
+        }
+    }
+}
+
+impl Visitor for AssignmentTargets {
+    fn visit_signal_assignment(
+        &mut self,
+        node: &SignalAssignment,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.add_target(&node.target);
+        VisitorResult::Continue
+    }
+
+    fn visit_signal_force_assignment(
+        &mut self,
+        node: &SignalForceAssignment,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.add_target(&node.target);
+        VisitorResult::Continue
+    }
+
+    fn visit_signal_release_assignment(
+        &mut self,
+        node: &SignalReleaseAssignment,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.add_target(&node.target);
+        VisitorResult::Continue
+    }
+
+    fn visit_variable_assignment(
+        &mut self,
+        node: &VariableAssignment,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.add_target(&node.target);
+        VisitorResult::Continue
+    }
+
+    fn visit_concurrent_signal_assignment(
+        &mut self,
+        node: &ConcurrentSignalAssignment,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.add_target(&node.target);
+        VisitorResult::Continue
+    }
+}
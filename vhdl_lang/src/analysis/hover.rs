@@ -0,0 +1,353 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Information shown when hovering over a named entity
+
+use super::named_entity::{ObjectEnt, OverloadedEnt, TypeEnt};
+use super::root::DesignRoot;
+use crate::ast::search::{FoundDeclaration, SearchResult, SearchState, Searcher};
+use crate::ast::{
+    AbstractLiteral, ElementAssociation, Expression, Literal, Name, ObjectClass, Operator,
+};
+use crate::data::*;
+use crate::syntax::{Comment, Token, TokenAccess};
+use crate::{AnyEntKind, Design, EntRef, EntityId, Object, ObjectInterface, Overloaded, Type};
+
+/// Constants referring to other constants are only followed this deep
+const MAX_VALUE_DEPTH: usize = 16;
+
+/// Information about a named entity to show when hovering over it
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct HoverInfo {
+    /// The declaration as VHDL code
+    pub declaration: Option<String>,
+    /// The comments directly above the declaration and the comment trailing it
+    pub documentation: Option<String>,
+    /// Resolved information such as the base type of a subtype or the value of a constant
+    pub details: Vec<String>,
+    /// The design unit or library containing the declaration such as `package lib.pkg`
+    pub location: Option<String>,
+}
+
+impl DesignRoot {
+    /// Collect the information about a named entity to show when hovering over it
+    pub fn hover_info(&self, ent: EntRef) -> HoverInfo {
+        HoverInfo {
+            declaration: self.format_declaration(ent),
+            documentation: self.documentation(ent),
+            details: self.details(ent),
+            location: location(ent),
+        }
+    }
+
+    /// The comments of the tokens on the line of the declaration of a named entity
+    fn documentation(&self, ent: EntRef) -> Option<String> {
+        let decl_pos = ent.decl_pos()?;
+        let unit = self.unit_at(decl_pos.source(), decl_pos.start())?;
+        let tokens = &unit.tokens;
+        let idx = tokens
+            .iter()
+            .position(|token| token.pos.range() == decl_pos.range())?;
+        let line = decl_pos.start().line;
+
+        let on_line = |token: &Token| token.pos.start().line == line;
+        let first = tokens[..idx]
+            .iter()
+            .rposition(|token| !on_line(token))
+            .map_or(0, |idx| idx + 1);
+        let last = tokens[idx..]
+            .iter()
+            .position(|token| !on_line(token))
+            .map_or(tokens.len() - 1, |offset| idx + offset - 1);
+
+        let mut lines = Vec::new();
+        if let Some(comments) = tokens[first].comments.as_ref() {
+            // Only the block of comments ending on the line above the declaration
+            let mut next_line = line;
+            let block = comments
+                .leading
+                .iter()
+                .rev()
+                .take_while(|comment| {
+                    let is_adjacent = comment.range.end.line + 1 == next_line;
+                    next_line = comment.range.start.line;
+                    is_adjacent
+                })
+                .count();
+            for comment in &comments.leading[comments.leading.len() - block..] {
+                lines.extend(comment_lines(comment));
+            }
+        }
+        if let Some(comment) = tokens[last]
+            .comments
+            .as_ref()
+            .and_then(|comments| comments.trailing.as_ref())
+        {
+            if !lines.is_empty() {
+                // Separate paragraph in markdown
+                lines.push(String::new());
+            }
+            lines.extend(comment_lines(comment));
+        }
+
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines.join("\n"))
+        }
+    }
+
+    fn details(&self, ent: EntRef) -> Vec<String> {
+        let mut details = Vec::new();
+        match ent.actual_kind() {
+            AnyEntKind::Type(Type::Subtype(_) | Type::Alias(_)) => {
+                if let Some(typ) = TypeEnt::from_any(ent) {
+                    details.push(format!("Base type: `{}`", typ.base_type().designator()));
+                }
+            }
+            AnyEntKind::Object(object) => {
+                details.push(describe_object_class(object));
+                details.push(describe_type(object.subtype.type_mark()));
+                if let Some(value) = self.constant_value(ent) {
+                    details.push(format!("Value: `{value}`"));
+                }
+            }
+            AnyEntKind::DeferredConstant(subtype) => {
+                details.push("Deferred constant".to_owned());
+                details.push(describe_type(subtype.type_mark()));
+                if let Some(value) = self.constant_value(ent) {
+                    details.push(format!("Value: `{value}`"));
+                }
+            }
+            AnyEntKind::Overloaded(_) => {
+                if let Some(overloaded) = OverloadedEnt::from_any(ent) {
+                    details.push(format!(
+                        "Signature: `{}`",
+                        overloaded.signature().describe()
+                    ));
+                }
+            }
+            _ => {}
+        }
+        details
+    }
+
+    /// The value of a constant when it is statically known and not a literal already
+    /// visible in the declaration
+    fn constant_value(&self, ent: EntRef) -> Option<String> {
+        if !ent.kind().is_deferred_constant() {
+            let object = ObjectEnt::from_any(ent)?;
+            if object.class() != ObjectClass::Constant || object.object().iface.is_some() {
+                return None;
+            }
+        }
+        let definition = self.find_definition_of(ent)?;
+        let expression = self.constant_expression(definition)?;
+        if matches!(expression, Expression::Literal(_)) && definition.id() == ent.id() {
+            return None;
+        }
+        Some(self.evaluate(&expression, 0)?.to_string())
+    }
+
+    /// The initial value of a constant declaration
+    fn constant_expression(&self, ent: EntRef) -> Option<Expression> {
+        let mut searcher = ConstantExpression {
+            id: ent.id(),
+            result: None,
+        };
+        let _ = self.search_declaring_unit(ent, &mut searcher);
+        searcher.result
+    }
+
+    fn evaluate(&self, expression: &Expression, depth: usize) -> Option<StaticValue> {
+        if depth > MAX_VALUE_DEPTH {
+            return None;
+        }
+        match expression {
+            Expression::Literal(Literal::AbstractLiteral(AbstractLiteral::Integer(value))) => {
+                Some(StaticValue::Integer(i64::try_from(*value).ok()?))
+            }
+            Expression::Literal(literal) => Some(StaticValue::Text(literal.to_string())),
+            Expression::Name(name) => {
+                let Name::Designator(designator) = name.as_ref() else {
+                    return None;
+                };
+                let ent = self.get_ent(designator.reference?);
+                match ent.actual_kind() {
+                    AnyEntKind::Object(Object {
+                        class: ObjectClass::Constant,
+                        iface: None,
+                        ..
+                    }) => {
+                        let definition = self.find_definition_of(ent)?;
+                        let expression = self.constant_expression(definition)?;
+                        self.evaluate(&expression, depth + 1)
+                    }
+                    AnyEntKind::Overloaded(Overloaded::EnumLiteral(_)) => {
+                        Some(StaticValue::Text(ent.designator().to_string()))
+                    }
+                    _ => None,
+                }
+            }
+            Expression::Unary(op, operand) => {
+                let StaticValue::Integer(value) = self.evaluate(&operand.item, depth)? else {
+                    return None;
+                };
+                match op.item.item {
+                    Operator::Plus => Some(StaticValue::Integer(value)),
+                    Operator::Minus => Some(StaticValue::Integer(value.checked_neg()?)),
+                    Operator::Abs => Some(StaticValue::Integer(value.checked_abs()?)),
+                    _ => None,
+                }
+            }
+            Expression::Binary(op, left, right) => {
+                let (StaticValue::Integer(left), StaticValue::Integer(right)) = (
+                    self.evaluate(&left.item, depth)?,
+                    self.evaluate(&right.item, depth)?,
+                ) else {
+                    return None;
+                };
+                let value = match op.item.item {
+                    Operator::Plus => left.checked_add(right),
+                    Operator::Minus => left.checked_sub(right),
+                    Operator::Times => left.checked_mul(right),
+                    Operator::Div => left.checked_div(right),
+                    Operator::Mod => left.checked_rem_euclid(right).map(|value| {
+                        // The result of mod has the sign of the right operand
+                        if right < 0 && value != 0 {
+                            value + right
+                        } else {
+                            value
+                        }
+                    }),
+                    Operator::Rem => left.checked_rem(right),
+                    Operator::Pow => u32::try_from(right)
+                        .ok()
+                        .and_then(|right| left.checked_pow(right)),
+                    _ => None,
+                }?;
+                Some(StaticValue::Integer(value))
+            }
+            // A parenthesized expression
+            Expression::Aggregate(elements) => match elements.as_slice() {
+                [ElementAssociation::Positional(expression)] => {
+                    self.evaluate(&expression.item, depth)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+enum StaticValue {
+    Integer(i64),
+    Text(String),
+}
+
+impl std::fmt::Display for StaticValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaticValue::Integer(value) => write!(f, "{value}"),
+            StaticValue::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+struct ConstantExpression {
+    id: EntityId,
+    result: Option<Expression>,
+}
+
+impl Searcher for ConstantExpression {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let FoundDeclaration::Object(object) = decl {
+            if object.ident.decl == Some(self.id) {
+                self.result = object
+                    .expression
+                    .as_ref()
+                    .map(|expression| expression.item.clone());
+                return SearchState::Finished(SearchResult::Found);
+            }
+        }
+        SearchState::NotFinished
+    }
+}
+
+fn describe_object_class(object: &Object) -> String {
+    let class = capitalize(object.class.describe());
+    match object.iface {
+        None => class,
+        Some(ObjectInterface::Generic) => format!("{class} generic"),
+        Some(ObjectInterface::Port(mode)) => format!("{class} port of mode `{mode}`"),
+        Some(ObjectInterface::Parameter(mode)) => {
+            format!("{class} parameter of mode `{mode}`")
+        }
+    }
+}
+
+fn describe_type(type_mark: TypeEnt) -> String {
+    let base_type = type_mark.base_type();
+    if base_type.id() == type_mark.id() {
+        format!("Type: `{}`", type_mark.designator())
+    } else {
+        format!(
+            "Type: `{}` with base type `{}`",
+            type_mark.designator(),
+            base_type.designator()
+        )
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The lines of a comment without separator lines such as `-------`
+fn comment_lines(comment: &Comment) -> Vec<String> {
+    comment
+        .value
+        .lines()
+        .map(|line| {
+            let line = line.trim_end();
+            if comment.multi_line {
+                line.trim_start().trim_start_matches('*').trim_start()
+            } else {
+                line.strip_prefix(' ').unwrap_or(line)
+            }
+        })
+        .filter(|line| line.is_empty() || !line.chars().all(|chr| chr == '-' || chr == '*'))
+        .map(|line| line.to_owned())
+        .collect()
+}
+
+/// The design unit or library containing the declaration of a named entity
+fn location(ent: EntRef) -> Option<String> {
+    let library_name = ent.library_name()?;
+    let mut parent = ent.parent;
+    while let Some(unit) = parent {
+        if let AnyEntKind::Design(design) = unit.kind() {
+            return Some(match design {
+                Design::Architecture(entity) => format!(
+                    "architecture {} of {library_name}.{}",
+                    unit.designator(),
+                    entity.designator()
+                ),
+                _ => format!("{} {library_name}.{}", design.describe(), unit.designator()),
+            });
+        }
+        parent = unit.parent;
+    }
+    if matches!(ent.kind(), AnyEntKind::Library) {
+        None
+    } else {
+        Some(format!("library {library_name}"))
+    }
+}
@@ -0,0 +1,230 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Implicit information shown inline with the code of a source file
+
+use super::completion::MapAspectKind;
+use super::named_entity::{OverloadedEnt, Type, UniversalType};
+use super::root::DesignRoot;
+use super::HasEntityId;
+use crate::ast::visitor::{walk, Visitor, VisitorResult};
+use crate::ast::{
+    ActualPart, AssociationElement, CallOrIndexed, ConcurrentStatement, Designator, Expression,
+    ForGenerateStatement, Ident, InstantiationStatement, IterationScheme,
+    LabeledConcurrentStatement, MapAspect, Name, WithDecl,
+};
+use crate::data::*;
+use crate::syntax::TokenAccess;
+use crate::{AnyEntKind, EntityId};
+use fnv::FnvHashMap;
+
+/// Processes and blocks spanning at least this many lines get a hint with their label
+/// at the end when the end label is missing
+const END_LABEL_MIN_LINES: u32 = 10;
+
+/// A piece of implicit information shown at a position in the code
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct InlayHint {
+    pub pos: Position,
+    pub label: String,
+    pub kind: InlayHintKind,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum InlayHintKind {
+    /// The formal name of a positional association such as `arg =>`
+    Parameter,
+    /// The resolved type of a loop parameter such as `: INTEGER`
+    Type,
+    /// The label of a process or block without an end label
+    EndLabel,
+}
+
+impl DesignRoot {
+    /// Collect the inlay hints of a source file,
+    /// only hints within `range` are returned when given
+    pub fn inlay_hints(&self, source: &Source, range: Option<Range>) -> Vec<InlayHint> {
+        let mut visitor = InlayHintVisitor {
+            root: self,
+            ports: FnvHashMap::default(),
+            hints: Vec::new(),
+        };
+        let units = self
+            .libraries()
+            .flat_map(|library| library.units())
+            .filter(|unit| {
+                unit.tokens
+                    .first()
+                    .is_some_and(|token| &token.pos.source == source)
+            });
+        for unit in units {
+            if let Some(data) = unit.unit.get() {
+                walk(data.data(), &mut visitor, &unit.tokens);
+            }
+        }
+
+        let mut hints = visitor.hints;
+        if let Some(range) = range {
+            hints.retain(|hint| range.start <= hint.pos && hint.pos <= range.end);
+        }
+        hints.sort_by_key(|hint| hint.pos);
+        hints
+    }
+}
+
+struct InlayHintVisitor<'a> {
+    root: &'a DesignRoot,
+    /// The generics or ports of instantiated units by the id of the unit
+    ports: FnvHashMap<(EntityId, MapAspectKind), Vec<Option<EntityId>>>,
+    hints: Vec<InlayHint>,
+}
+
+impl<'a> InlayHintVisitor<'a> {
+    fn add_parameter_hints(
+        &mut self,
+        elements: &[AssociationElement],
+        formals: impl Iterator<Item = Designator>,
+    ) {
+        for (element, formal) in elements.iter().zip(formals) {
+            if element.formal.is_some() {
+                // Positional associations can only precede named ones
+                break;
+            }
+            if let ActualPart::Expression(Expression::Name(name)) = &element.actual.item {
+                if let Name::Designator(designator) = name.as_ref() {
+                    if designator.item == formal {
+                        continue;
+                    }
+                }
+            }
+            self.hints.push(InlayHint {
+                pos: element.actual.pos.start(),
+                label: format!("{formal} =>"),
+                kind: InlayHintKind::Parameter,
+            });
+        }
+    }
+
+    fn add_map_aspect_hints(&mut self, id: EntityId, map: &MapAspect, kind: MapAspectKind) {
+        let root = self.root;
+        let formals = self.ports.entry((id, kind)).or_insert_with(|| {
+            root.extract_port_or_generic_declarations(id, kind)
+                .iter()
+                .map(|decl| decl.ent_id())
+                .collect()
+        });
+        let formals: Vec<Designator> = formals
+            .iter()
+            .map_while(|id| id.map(|id| root.get_ent(id).designator().clone()))
+            .collect();
+        self.add_parameter_hints(&map.list.items, formals.into_iter());
+    }
+
+    fn add_type_hint(&mut self, index: &WithDecl<Ident>) {
+        let Some(id) = index.decl else {
+            return;
+        };
+        if let AnyEntKind::LoopParameter(Some(typ)) = self.root.get_ent(id).kind() {
+            let label = if matches!(typ.kind(), Type::Universal(UniversalType::Integer)) {
+                // LRM 10.10 A universal_integer range is implicitly converted to INTEGER
+                ": INTEGER".to_owned()
+            } else {
+                format!(": {}", typ.designator())
+            };
+            self.hints.push(InlayHint {
+                pos: index.tree.pos.end(),
+                label,
+                kind: InlayHintKind::Type,
+            });
+        }
+    }
+}
+
+impl<'a> Visitor for InlayHintVisitor<'a> {
+    fn visit_call_or_indexed(
+        &mut self,
+        node: &CallOrIndexed,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        let reference = match &node.name.item {
+            Name::Designator(designator) => designator.reference,
+            Name::Selected(_, suffix) => suffix.item.reference,
+            _ => None,
+        };
+        if let Some(overloaded) =
+            reference.and_then(|id| OverloadedEnt::from_any(self.root.get_ent(id)))
+        {
+            let formals = overloaded.signature().formals.iter();
+            self.add_parameter_hints(
+                &node.parameters,
+                formals.map(|formal| formal.designator().clone()),
+            );
+        }
+        VisitorResult::Continue
+    }
+
+    fn visit_instantiation_statement(
+        &mut self,
+        node: &InstantiationStatement,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        if let Some(id) = node.entity_reference() {
+            if let Some(map) = &node.generic_map {
+                self.add_map_aspect_hints(id, map, MapAspectKind::Generic);
+            }
+            if let Some(map) = &node.port_map {
+                self.add_map_aspect_hints(id, map, MapAspectKind::Port);
+            }
+        }
+        VisitorResult::Continue
+    }
+
+    fn visit_iteration_scheme(
+        &mut self,
+        node: &IterationScheme,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        if let IterationScheme::For(index, _) = node {
+            self.add_type_hint(index);
+        }
+        VisitorResult::Continue
+    }
+
+    fn visit_for_generate_statement(
+        &mut self,
+        node: &ForGenerateStatement,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        self.add_type_hint(&node.index_name);
+        VisitorResult::Continue
+    }
+
+    fn visit_labeled_concurrent_statement(
+        &mut self,
+        node: &LabeledConcurrentStatement,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        let end_label_pos = match &node.statement.item {
+            ConcurrentStatement::Process(process) => &process.end_label_pos,
+            ConcurrentStatement::Block(block) => &block.end_label_pos,
+            _ => return VisitorResult::Continue,
+        };
+        let Some(label) = &node.label.tree else {
+            return VisitorResult::Continue;
+        };
+        let pos = &node.statement.pos;
+        if end_label_pos.is_none() && pos.end().line - pos.start().line + 1 >= END_LABEL_MIN_LINES {
+            // Just before the final semicolon
+            let end = pos.end();
+            self.hints.push(InlayHint {
+                pos: Position::new(end.line, end.character.saturating_sub(1)),
+                label: label.item.to_string(),
+                kind: InlayHintKind::EndLabel,
+            });
+        }
+        VisitorResult::Continue
+    }
+}
@@ -0,0 +1,372 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Elaboration of the instance hierarchy below a top level entity
+
+use super::named_entity::*;
+use super::root::{DesignRoot, LockedUnit};
+use crate::ast::*;
+use crate::data::*;
+use serde_json::{json, Value};
+use std::fmt::Write;
+use std::ops::Deref;
+
+/// An instance within the elaborated design hierarchy
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InstanceHierarchy {
+    /// The instance label. Labels of enclosing block and generate statements
+    /// are prepended separated by a dot. The top level uses the entity name.
+    pub label: String,
+    /// The library of the entity or None if the instantiated unit could not be resolved
+    pub library: Option<String>,
+    pub entity: String,
+    /// The selected architecture or None if the entity has no architecture
+    pub architecture: Option<String>,
+    pub children: Vec<InstanceHierarchy>,
+}
+
+impl InstanceHierarchy {
+    fn unresolved(label: String, name: &SelectedName) -> InstanceHierarchy {
+        InstanceHierarchy {
+            label,
+            library: None,
+            entity: suffix_of(name).to_string(),
+            architecture: None,
+            children: Vec::new(),
+        }
+    }
+
+    /// The instantiated unit as `library.entity(architecture)`
+    pub fn describe(&self) -> String {
+        let mut result = String::new();
+        if let Some(ref library) = self.library {
+            write!(result, "{library}.").unwrap();
+        }
+        result.push_str(&self.entity);
+        if let Some(ref architecture) = self.architecture {
+            write!(result, "({architecture})").unwrap();
+        }
+        if self.library.is_none() {
+            result.push_str(" (unresolved)");
+        }
+        result
+    }
+
+    /// Indented text with one instance per line
+    pub fn to_text(&self) -> String {
+        let mut result = String::new();
+        self.write_text(0, &mut result);
+        result
+    }
+
+    fn write_text(&self, depth: usize, result: &mut String) {
+        writeln!(
+            result,
+            "{}{}: {}",
+            "  ".repeat(depth),
+            self.label,
+            self.describe()
+        )
+        .unwrap();
+        for child in self.children.iter() {
+            child.write_text(depth + 1, result);
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        // Serializing a Value cannot fail since all keys are strings
+        serde_json::to_string_pretty(&self.json_value()).unwrap()
+    }
+
+    fn json_value(&self) -> Value {
+        let children: Vec<Value> = self.children.iter().map(|c| c.json_value()).collect();
+        json!({
+            "label": self.label,
+            "library": self.library,
+            "entity": self.entity,
+            "architecture": self.architecture,
+            "children": children,
+        })
+    }
+
+    /// Graphviz DOT digraph where each node is identified by its hierarchical path
+    pub fn to_dot(&self) -> String {
+        let mut result = String::from("digraph hierarchy {\n  node [shape=box];\n");
+        self.write_dot(&self.label, &mut result);
+        result.push_str("}\n");
+        result
+    }
+
+    fn write_dot(&self, path: &str, result: &mut String) {
+        writeln!(
+            result,
+            "  \"{}\" [label=\"{}\\n{}\"];",
+            escape_dot(path),
+            escape_dot(&self.label),
+            escape_dot(&self.describe())
+        )
+        .unwrap();
+
+        for child in self.children.iter() {
+            let child_path = format!("{path}.{}", child.label);
+            writeln!(
+                result,
+                "  \"{}\" -> \"{}\";",
+                escape_dot(path),
+                escape_dot(&child_path)
+            )
+            .unwrap();
+            child.write_dot(&child_path, result);
+        }
+    }
+}
+
+pub(super) fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn suffix_of(name: &SelectedName) -> &Designator {
+    match name {
+        SelectedName::Designator(designator) => &designator.item,
+        SelectedName::Selected(_, designator) => &designator.item.item,
+    }
+}
+
+impl DesignRoot {
+    /// Elaborate the instance hierarchy below an entity
+    ///
+    /// Unless an architecture is given by the instantiation or a configuration,
+    /// the architecture that appears last in the sources is selected.
+    pub fn instance_hierarchy(
+        &self,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+    ) -> Option<InstanceHierarchy> {
+        let entity: EntRef = self.get_design_entity(library_name, entity_name)?.into();
+        Some(self.elaborate(
+            entity.designator().to_string(),
+            entity,
+            None,
+            &mut Vec::new(),
+        ))
+    }
+
+    fn elaborate(
+        &self,
+        label: String,
+        entity: EntRef,
+        architecture: Option<&Symbol>,
+        parents: &mut Vec<EntityId>,
+    ) -> InstanceHierarchy {
+        let library_name = entity.library_name();
+        let architecture = if let (Some(library_name), Designator::Identifier(entity_name)) =
+            (library_name, entity.designator())
+        {
+            self.select_architecture(library_name, entity_name, architecture)
+        } else {
+            None
+        };
+
+        let mut children = Vec::new();
+        if let Some((_, unit)) = architecture {
+            // Do not expand recursive instantiations
+            if !parents.contains(&entity.id()) {
+                parents.push(entity.id());
+                let data = self.get_analysis(unit);
+                if let AnyDesignUnit::Secondary(AnySecondaryUnit::Architecture(arch)) = data.deref()
+                {
+                    self.elaborate_statements("", &arch.statements, parents, &mut children);
+                }
+                parents.pop();
+            }
+        }
+
+        InstanceHierarchy {
+            label,
+            library: library_name.map(|name| name.name_utf8()),
+            entity: entity.designator().to_string(),
+            architecture: architecture.map(|(name, _)| name.name_utf8()),
+            children,
+        }
+    }
+
+    fn select_architecture<'a>(
+        &'a self,
+        library_name: &Symbol,
+        entity_name: &Symbol,
+        architecture: Option<&Symbol>,
+    ) -> Option<(&'a Symbol, &'a LockedUnit)> {
+        let units = self.get_library_units(library_name)?;
+
+        units
+            .iter()
+            .filter_map(|(key, unit)| match key {
+                UnitKey::Secondary(primary_name, name)
+                    if primary_name == entity_name
+                        && architecture.is_none_or(|architecture| architecture == name)
+                        && unit.kind() == AnyKind::Secondary(SecondaryKind::Architecture) =>
+                {
+                    Some((name, unit))
+                }
+                _ => None,
+            })
+            .max_by_key(|(_, unit)| (unit.source().file_name().to_owned(), unit.pos().start()))
+    }
+
+    fn elaborate_statements(
+        &self,
+        prefix: &str,
+        statements: &[LabeledConcurrentStatement],
+        parents: &mut Vec<EntityId>,
+        children: &mut Vec<InstanceHierarchy>,
+    ) {
+        for statement in statements.iter() {
+            let label = if let Some(ref label) = statement.label.tree {
+                format!("{prefix}{}", label.item.name_utf8())
+            } else {
+                prefix.to_owned()
+            };
+
+            let mut elaborate_body = |body: &GenerateBody, children: &mut Vec<_>| {
+                let prefix = if let Some(ref alternative) = body.alternative_label {
+                    format!("{label}.{}.", alternative.tree.item.name_utf8())
+                } else {
+                    format!("{label}.")
+                };
+                self.elaborate_statements(&prefix, &body.statements, parents, children);
+            };
+
+            match statement.statement.item {
+                ConcurrentStatement::Instance(ref instance) => {
+                    children.push(self.elaborate_instance(label, instance, parents));
+                }
+                ConcurrentStatement::Block(ref block) => {
+                    self.elaborate_statements(
+                        &format!("{label}."),
+                        &block.statements,
+                        parents,
+                        children,
+                    );
+                }
+                ConcurrentStatement::ForGenerate(ref gen) => {
+                    elaborate_body(&gen.body, children);
+                }
+                ConcurrentStatement::IfGenerate(ref gen) => {
+                    for conditional in gen.conds.conditionals.iter() {
+                        elaborate_body(&conditional.item, children);
+                    }
+                    if let Some(ref body) = gen.conds.else_item {
+                        elaborate_body(body, children);
+                    }
+                }
+                ConcurrentStatement::CaseGenerate(ref gen) => {
+                    for alternative in gen.sels.alternatives.iter() {
+                        elaborate_body(&alternative.item, children);
+                    }
+                }
+                ConcurrentStatement::ProcedureCall(..)
+                | ConcurrentStatement::Process(..)
+                | ConcurrentStatement::Assert(..)
+                | ConcurrentStatement::Assignment(..) => {}
+            }
+        }
+    }
+
+    fn elaborate_instance(
+        &self,
+        label: String,
+        instance: &InstantiationStatement,
+        parents: &mut Vec<EntityId>,
+    ) -> InstanceHierarchy {
+        match instance.unit {
+            InstantiatedUnit::Entity(ref name, ref architecture) => {
+                if let Some(entity) = self.entity_of(name.item.reference()) {
+                    let architecture = architecture.as_ref().map(|arch| &arch.item.item);
+                    return self.elaborate(label, entity, architecture, parents);
+                }
+                InstanceHierarchy::unresolved(label, &name.item)
+            }
+            InstantiatedUnit::Component(ref name) => {
+                if let Some(entity) = self.instantiated_entity(&instance.unit) {
+                    return self.elaborate(label, entity, None, parents);
+                }
+                InstanceHierarchy::unresolved(label, &name.item)
+            }
+            InstantiatedUnit::Configuration(ref name) => {
+                if let Some(hierarchy) =
+                    self.elaborate_configuration(label.clone(), name.item.reference(), parents)
+                {
+                    return hierarchy;
+                }
+                InstanceHierarchy::unresolved(label, &name.item)
+            }
+        }
+    }
+
+    fn elaborate_configuration(
+        &self,
+        label: String,
+        reference: Reference,
+        parents: &mut Vec<EntityId>,
+    ) -> Option<InstanceHierarchy> {
+        let (entity, architecture) = self.configured_entity(reference)?;
+        Some(self.elaborate(label, entity, architecture.as_ref(), parents))
+    }
+
+    /// The entity and architecture selected by a configuration
+    fn configured_entity(&self, reference: Reference) -> Option<(EntRef<'_>, Option<Symbol>)> {
+        let configuration = self.get_ent(reference?);
+        let library_name = configuration.library_name()?;
+        let Designator::Identifier(name) = configuration.designator() else {
+            return None;
+        };
+
+        let unit = self
+            .get_library_units(library_name)?
+            .get(&UnitKey::Primary(name.clone()))?;
+        let data = self.get_analysis(unit);
+        let AnyDesignUnit::Primary(AnyPrimaryUnit::Configuration(ref configuration)) = data.deref()
+        else {
+            return None;
+        };
+
+        let entity = self.entity_of(configuration.entity_name.item.reference())?;
+        let architecture = match configuration.block_config.block_spec.item {
+            Name::Designator(ref designator) => match designator.item {
+                Designator::Identifier(ref architecture) => Some(architecture.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+        Some((entity, architecture))
+    }
+
+    /// The entity instantiated directly, by a component with the same name
+    /// or by a configuration
+    pub(super) fn instantiated_entity(&self, unit: &InstantiatedUnit) -> Option<EntRef<'_>> {
+        match unit {
+            InstantiatedUnit::Entity(ref name, _) => self.entity_of(name.item.reference()),
+            InstantiatedUnit::Component(ref name) => name
+                .item
+                .reference()
+                .map(|id| self.get_ent(id))
+                .and_then(|component| self.find_implementation(component).into_iter().next())
+                .and_then(|entity| self.entity_of(Some(entity.id()))),
+            InstantiatedUnit::Configuration(ref name) => self
+                .configured_entity(name.item.reference())
+                .map(|(entity, _)| entity),
+        }
+    }
+
+    fn entity_of(&self, reference: Reference) -> Option<EntRef<'_>> {
+        let ent = self.get_ent(reference?);
+        if matches!(ent.kind(), AnyEntKind::Design(Design::Entity(..))) {
+            Some(ent)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,189 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Generate instantiations of design entities with all generics and ports associated
+
+use super::completion::MapAspectKind;
+use super::root::DesignRoot;
+use super::use_clause_fix::ContextClauseTokens;
+use super::HasEntityId;
+use crate::ast::{HasUnitId, InterfaceDeclaration};
+use crate::data::*;
+use crate::syntax::Kind;
+use crate::{AnyEntKind, Design, EntRef};
+
+/// The kind of instantiation statement to generate
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InstantiationKind {
+    /// A direct entity instantiation such as `ent_inst: entity work.ent`
+    Entity,
+    /// A component instantiation together with the component declaration
+    Component,
+}
+
+/// The text of an instantiation in an architecture
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Instantiation {
+    /// The instantiation statement which is inserted at the start of the line of the cursor
+    pub statement: String,
+    /// Other text needed by the statement such as a library clause or a component declaration
+    pub inserts: Vec<(Position, String)>,
+}
+
+impl DesignRoot {
+    /// The entities named `name` in any library
+    pub fn find_design_entities<'a>(&'a self, name: &str) -> Vec<EntRef<'a>> {
+        let name = self.symbol_utf8(name);
+        let mut entities: Vec<_> = self
+            .available_libraries()
+            .filter_map(|library_name| self.get_design_entity(library_name, &name))
+            .map(EntRef::from)
+            .collect();
+        entities.sort_by_key(|ent| ent.library_name().map(|name| name.name_utf8()));
+        entities
+    }
+
+    /// Generate an instantiation of an entity in the architecture at the cursor.
+    /// The statement is indented as the line of the cursor where nested lines are
+    /// indented further by `indent_unit`.
+    pub fn generate_instantiation(
+        &self,
+        source: &Source,
+        cursor: Position,
+        entity: EntRef,
+        kind: InstantiationKind,
+        indent_unit: &str,
+    ) -> Option<Instantiation> {
+        if !matches!(entity.kind(), AnyEntKind::Design(Design::Entity(..))) {
+            return None;
+        }
+        let entity_library = entity.library_name()?;
+        let entity_name = entity.designator();
+
+        let unit = self.unit_at(source, cursor)?;
+        if !matches!(
+            unit.tokens.iter().find(|token| token.kind != Kind::Library
+                && token.kind != Kind::Use),
+            Some(token) if token.kind == Kind::Architecture
+        ) {
+            return None;
+        }
+        let unit_library = unit.unit_id().library_name();
+
+        let indent: String = source
+            .contents()
+            .get_line(cursor.line as usize)
+            .unwrap_or_default()
+            .chars()
+            .take_while(|chr| *chr == ' ' || *chr == '\t')
+            .collect();
+
+        let generics =
+            self.extract_port_or_generic_declarations(entity.id(), MapAspectKind::Generic);
+        let ports = self.extract_port_or_generic_declarations(entity.id(), MapAspectKind::Port);
+
+        let mut inserts = Vec::new();
+        let mut statement = format!("{indent}{entity_name}_inst: ");
+        match kind {
+            InstantiationKind::Entity => {
+                let context = ContextClauseTokens::new(&unit.tokens);
+                let prefix = if entity_library == unit_library {
+                    "work".to_owned()
+                } else {
+                    if let Some(mut clause) =
+                        context.library_clause(self, entity_library, unit_library)
+                    {
+                        if !context.has_items() {
+                            clause.push('\n');
+                        }
+                        inserts.push((context.insert_pos, clause));
+                    }
+                    entity_library.to_string()
+                };
+                statement.push_str(&format!("entity {prefix}.{entity_name}"));
+            }
+            InstantiationKind::Component => {
+                // Declared first in the declarative part of the architecture
+                let is_token = unit.tokens.iter().find(|token| token.kind == Kind::Is)?;
+                let mut declaration = format!("{indent}component {entity_name} is\n");
+                for (keyword, decls) in [("generic", &generics), ("port", &ports)] {
+                    if decls.is_empty() {
+                        continue;
+                    }
+                    declaration.push_str(&format!("{indent}{indent_unit}{keyword} (\n"));
+                    for (idx, decl) in decls.iter().enumerate() {
+                        let separator = if idx + 1 < decls.len() { ";" } else { "" };
+                        declaration.push_str(&format!(
+                            "{indent}{indent_unit}{indent_unit}{decl}{separator}\n"
+                        ));
+                    }
+                    declaration.push_str(&format!("{indent}{indent_unit});\n"));
+                }
+                declaration.push_str(&format!("{indent}end component;\n\n"));
+                inserts.push((Position::new(is_token.pos.end().line + 1, 0), declaration));
+                statement.push_str(&format!("component {entity_name}"));
+            }
+        }
+
+        let aspects: Vec<_> = [("generic map", &generics), ("port map", &ports)]
+            .into_iter()
+            .filter(|(_, decls)| !decls.is_empty())
+            .collect();
+        if aspects.is_empty() {
+            statement.push(';');
+        }
+        statement.push('\n');
+
+        for (aspect_idx, (keyword, decls)) in aspects.iter().enumerate() {
+            statement.push_str(&format!("{indent}{indent_unit}{keyword} (\n"));
+            let formals: Vec<_> = decls
+                .iter()
+                .filter_map(|decl| {
+                    let formal = self.get_ent(decl.ent_id()?).designator().to_string();
+                    Some((formal, decl))
+                })
+                .collect();
+            let width = formals
+                .iter()
+                .map(|(formal, _)| formal.len())
+                .max()
+                .unwrap_or(0);
+
+            for (idx, (formal, decl)) in formals.iter().enumerate() {
+                let separator = if idx + 1 < formals.len() { "," } else { "" };
+                statement.push_str(&format!(
+                    "{indent}{indent_unit}{indent_unit}{formal:width$} => {formal}{separator}"
+                ));
+                if let Some(default) = default_value(decl) {
+                    statement.push_str(&format!(" -- default: {default}"));
+                }
+                statement.push('\n');
+            }
+
+            let end = if aspect_idx + 1 < aspects.len() {
+                ")"
+            } else {
+                ");"
+            };
+            statement.push_str(&format!("{indent}{indent_unit}{end}\n"));
+        }
+
+        Some(Instantiation { statement, inserts })
+    }
+}
+
+/// The default value of a generic or port as VHDL code
+fn default_value(decl: &InterfaceDeclaration) -> Option<String> {
+    match decl {
+        InterfaceDeclaration::Object(object) => {
+            object.expression.as_ref().map(|expr| expr.item.to_string())
+        }
+        InterfaceDeclaration::Subprogram(_, default) => {
+            default.as_ref().map(|default| default.to_string())
+        }
+        _ => None,
+    }
+}
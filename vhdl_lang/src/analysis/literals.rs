@@ -26,17 +26,19 @@ impl<'a> AnalyzeContext<'a> {
             for chr in string_lit.chars() {
                 let chr = Designator::Character(*chr);
                 if !literals.contains(&chr) {
-                    diagnostics.push(Diagnostic::error(
+                    diagnostics.push(Diagnostic::new(
                         pos,
                         format!("{} does not define character {}", elem_type.describe(), chr),
+                        ErrorCode::Unresolved,
                     ));
                     break;
                 }
             }
         } else {
-            diagnostics.push(Diagnostic::error(
+            diagnostics.push(Diagnostic::new(
                 pos,
                 format!("string literal does not match {}", target_type.describe()),
+                ErrorCode::TypeMismatch,
             ));
         }
     }
@@ -58,17 +60,19 @@ impl<'a> AnalyzeContext<'a> {
                 AbstractLiteral::Integer(_) => {
                     if !self.can_be_target_type(self.universal_integer().into(), target_type.base())
                     {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::new(
                             pos,
                             format!("integer literal does not match {}", target_type.describe()),
+                            ErrorCode::TypeMismatch,
                         ));
                     }
                 }
                 AbstractLiteral::Real(_) => {
                     if !self.can_be_target_type(self.universal_real().into(), target_type.base()) {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::new(
                             pos,
                             format!("real literal does not match {}", target_type.describe()),
+                            ErrorCode::TypeMismatch,
                         ));
                     }
                 }
@@ -76,22 +80,24 @@ impl<'a> AnalyzeContext<'a> {
             Literal::Character(char) => match target_base.kind() {
                 Type::Enum(literals) => {
                     if !literals.contains(&Designator::Character(*char)) {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::new(
                             pos,
                             format!(
                                 "character literal does not match {}",
                                 target_type.describe()
                             ),
+                            ErrorCode::TypeMismatch,
                         ));
                     }
                 }
                 _ => {
-                    diagnostics.push(Diagnostic::error(
+                    diagnostics.push(Diagnostic::new(
                         pos,
                         format!(
                             "character literal does not match {}",
                             target_type.describe()
                         ),
+                        ErrorCode::TypeMismatch,
                     ));
                 }
             },
@@ -116,25 +122,31 @@ impl<'a> AnalyzeContext<'a> {
                     Err(err) => {
                         match err {
                             BitStringConversionError::IllegalDecimalCharacter(rel_pos) => {
-                                diagnostics.error(
+                                diagnostics.add(
                                     pos,
                                     format!(
                                         "Illegal digit '{}' for base 10",
                                         bit_string.value.bytes[rel_pos] as char,
                                     ),
+                                    ErrorCode::InvalidLiteral,
                                 )
                             }
                             BitStringConversionError::IllegalTruncate(_, _) => {
-                                diagnostics.error(
+                                diagnostics.add(
                                     pos,
                                     format!(
                                         "Truncating vector to length {} would lose information",
                                         bit_string.length.unwrap() // Safe as this error can only happen when there is a length
                                     ),
+                                    ErrorCode::InvalidLiteral,
                                 );
                             }
                             BitStringConversionError::EmptySignedExpansion => {
-                                diagnostics.error(pos, "Cannot expand an empty signed bit string");
+                                diagnostics.add(
+                                    pos,
+                                    "Cannot expand an empty signed bit string",
+                                    ErrorCode::InvalidLiteral,
+                                );
                             }
                         }
                     }
@@ -158,9 +170,10 @@ impl<'a> AnalyzeContext<'a> {
             }
             Literal::Null => {
                 if !matches!(target_base.kind(), Type::Access(_)) {
-                    diagnostics.push(Diagnostic::error(
+                    diagnostics.push(Diagnostic::new(
                         pos,
                         format!("null literal does not match {}", target_base.describe()),
+                        ErrorCode::TypeMismatch,
                     ));
                 }
             }
@@ -182,13 +195,13 @@ impl<'a> AnalyzeContext<'a> {
                 if let AnyEntKind::PhysicalLiteral(physical_ent) = unit_ent.actual_kind() {
                     Ok(*physical_ent)
                 } else {
-                    Err(Diagnostic::error(
+                    Err(Diagnostic::mismatched_kinds(
                         &unit.item.pos,
                         format!("{} is not a physical unit", unit_ent.describe()),
                     ))
                 }
             }
-            NamedEntities::Overloaded(_) => Err(Diagnostic::error(
+            NamedEntities::Overloaded(_) => Err(Diagnostic::mismatched_kinds(
                 &unit.item.pos,
                 "Overloaded name may not be physical unit",
             )),
@@ -13,7 +13,9 @@ use crate::ast::{
     ObjectClass, ObjectDeclaration, PackageInstantiation, SubprogramBody, SubprogramDeclaration,
     SubprogramInstantiation, TypeDeclaration, WithDecl,
 };
-use crate::ast::{ExternalObjectClass, InterfaceDeclaration, InterfaceObjectDeclaration};
+use crate::ast::{
+    EntityClassEntry, ExternalObjectClass, InterfaceDeclaration, InterfaceObjectDeclaration,
+};
 use crate::data::*;
 
 mod types;
@@ -53,6 +55,9 @@ pub enum AnyEntKind<'a> {
     LoopParameter(Option<BaseType<'a>>),
     PhysicalLiteral(TypeEnt<'a>),
     DeferredConstant(Subtype<'a>),
+    GroupTemplate(Vec<EntityClassEntry>),
+    Group,
+    View(Subtype<'a>),
     Library,
     Design(Design<'a>),
 }
@@ -114,6 +119,9 @@ impl<'a> AnyEntKind<'a> {
             Object(object) => object.class.describe(),
             PhysicalLiteral(..) => "physical literal",
             DeferredConstant(..) => "deferred constant",
+            GroupTemplate(..) => "group template",
+            Group => "group",
+            View(..) => "view",
             Library => "library",
             Design(design) => design.describe(),
             Type(typ) => typ.describe(),
@@ -344,9 +352,14 @@ impl<'a> AnyEnt<'a> {
         &self.kind
     }
 
-    pub fn error(&self, diagnostics: &mut dyn DiagnosticHandler, message: impl Into<String>) {
+    pub fn error(
+        &self,
+        diagnostics: &mut dyn DiagnosticHandler,
+        message: impl Into<String>,
+        code: ErrorCode,
+    ) {
         if let Some(ref pos) = self.decl_pos {
-            diagnostics.push(Diagnostic::error(pos, message));
+            diagnostics.push(Diagnostic::new(pos, message, code));
         }
     }
 
@@ -485,6 +498,7 @@ impl HasEntityId for InterfaceDeclaration {
             InterfaceDeclaration::Type(typ) => typ.decl,
             InterfaceDeclaration::Subprogram(decl, _) => decl.ent_id(),
             InterfaceDeclaration::Package(pkg) => pkg.ent_id(),
+            InterfaceDeclaration::View(view) => view.ident.decl,
         }
     }
 }
@@ -530,7 +544,10 @@ impl HasEntityId for Declaration {
             Declaration::SubprogramInstantiation(decl) => decl.ent_id(),
             Declaration::Package(pkg) => pkg.ent_id(),
             Declaration::Use(_) => None,
-            Declaration::Configuration(_) => None,
+            Declaration::Configuration(_) | Declaration::Disconnection(_) => None,
+            Declaration::GroupTemplate(template) => template.ident.decl,
+            Declaration::Group(group) => group.ident.decl,
+            Declaration::View(view) => view.ident.decl,
         }
     }
 }
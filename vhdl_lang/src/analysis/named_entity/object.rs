@@ -10,6 +10,7 @@ use crate::analysis::AnyEntKind;
 use crate::ast::InterfaceType;
 use crate::ast::Mode;
 use crate::ast::ObjectClass;
+use crate::ast::SignalKind;
 
 use super::AnyEnt;
 use super::EntRef;
@@ -124,6 +125,8 @@ pub struct Object<'a> {
     pub iface: Option<ObjectInterface>,
     pub subtype: Subtype<'a>,
     pub has_default: bool,
+    /// Set for guarded signals declared with 'bus' or 'register'
+    pub signal_kind: Option<SignalKind>,
 }
 
 impl<'a> Object<'a> {
@@ -133,6 +136,7 @@ impl<'a> Object<'a> {
             iface: Some(ObjectInterface::Parameter(Mode::In)),
             subtype,
             has_default: false,
+            signal_kind: None,
         }
     }
 
@@ -203,7 +203,7 @@ impl<'a> TypeEnt<'a> {
             Type::Protected(region, _) => {
                 if let Some(decl) = region.lookup_immediate(suffix.designator()) {
                     match decl {
-                        NamedEntities::Single(ent) => Err(Diagnostic::error(
+                        NamedEntities::Single(ent) => Err(Diagnostic::mismatched_kinds(
                             &suffix.pos,
                             format!(
                                 "Protected type selection must be a method, got {}",
@@ -222,7 +222,7 @@ impl<'a> TypeEnt<'a> {
                     ))
                 }
             }
-            Type::Incomplete => Err(Diagnostic::error(
+            Type::Incomplete => Err(Diagnostic::mismatched_kinds(
                 prefix_pos,
                 "Cannot select incomplete type before full type definition",
             )),
@@ -164,6 +164,9 @@ impl<'a> ResolvedName<'a> {
             AnyEntKind::File(_)
             | AnyEntKind::InterfaceFile(_)
             | AnyEntKind::Component(_)
+            | AnyEntKind::GroupTemplate(_)
+            | AnyEntKind::Group
+            | AnyEntKind::View(_)
             | AnyEntKind::PhysicalLiteral(_) => ResolvedName::Final(ent),
             AnyEntKind::Design(_)
             | AnyEntKind::Library
@@ -223,6 +226,9 @@ impl<'a> ResolvedName<'a> {
             | AnyEntKind::Concurrent(_)
             | AnyEntKind::Sequential(_)
             | AnyEntKind::LoopParameter(_)
+            | AnyEntKind::GroupTemplate(_)
+            | AnyEntKind::Group
+            | AnyEntKind::View(_)
             | AnyEntKind::PhysicalLiteral(_) => ResolvedName::Final(ent),
             AnyEntKind::Attribute(_) | AnyEntKind::ElementDeclaration(_) => {
                 return Err(format!(
@@ -306,13 +312,14 @@ impl<'a> ResolvedName<'a> {
             }
         }
 
-        diagnostics.error(
+        diagnostics.add(
             prefix_pos,
             format!(
                 "Expected signal prefix for '{} attribute, got {}",
                 attr.attr,
                 self.describe()
             ),
+            ErrorCode::IllegalAttribute,
         );
         Err(EvalError::Unknown)
     }
@@ -421,7 +428,7 @@ impl<'a> AnalyzeContext<'a> {
     ) -> Result<Option<DisambiguatedType<'a>>, Diagnostic> {
         match name {
             ResolvedName::Library(_) | ResolvedName::Design(_) | ResolvedName::Type(_) => {
-                Err(Diagnostic::error(
+                Err(Diagnostic::mismatched_kinds(
                     pos,
                     format!("{} cannot be used in an expression", name.describe_type()),
                 ))
@@ -435,7 +442,7 @@ impl<'a> AnalyzeContext<'a> {
                     Ok(Some(DisambiguatedType::Unambiguous(subtype.type_mark())))
                 }
                 AnyEntKind::InterfaceFile(typ) => Ok(Some(DisambiguatedType::Unambiguous(*typ))),
-                _ => Err(Diagnostic::error(
+                _ => Err(Diagnostic::mismatched_kinds(
                     pos,
                     format!("{} cannot be used in an expression", name.describe_type()),
                 )),
@@ -469,7 +476,7 @@ impl<'a> AnalyzeContext<'a> {
     ) -> Result<Option<TypeEnt<'a>>, Diagnostic> {
         match name {
             ResolvedName::Library(_) | ResolvedName::Design(_) | ResolvedName::Type(_) => {
-                Err(Diagnostic::error(
+                Err(Diagnostic::mismatched_kinds(
                     pos,
                     format!("{} cannot be used in an expression", name.describe_type()),
                 ))
@@ -479,7 +486,7 @@ impl<'a> AnalyzeContext<'a> {
                 AnyEntKind::PhysicalLiteral(typ) => Ok(Some(*typ)),
                 AnyEntKind::File(subtype) => Ok(Some(subtype.type_mark())),
                 AnyEntKind::InterfaceFile(typ) => Ok(Some(*typ)),
-                _ => Err(Diagnostic::error(
+                _ => Err(Diagnostic::mismatched_kinds(
                     pos,
                     format!("{} cannot be used in an expression", name.describe_type()),
                 )),
@@ -569,7 +576,7 @@ impl<'a> AnalyzeContext<'a> {
                 return if matches!(typ.base_type().kind(), Type::Enum { .. } | Type::Integer) {
                     Ok(Some(typ))
                 } else {
-                    Err(Diagnostic::error(
+                    Err(Diagnostic::mismatched_kinds(
                         expr_pos,
                         format!("{} cannot be used as a discrete range", typ.describe()),
                     )
@@ -615,13 +622,14 @@ impl<'a> AnalyzeContext<'a> {
                             self.drange_unknown_type(scope, drange, diagnostics)?;
                         }
                     } else {
-                        diagnostics.error(
+                        diagnostics.add(
                             name_pos,
                             format!(
                                 "Cannot slice {}-dimensional {}",
                                 indexes.len(),
                                 typ.describe()
                             ),
+                            ErrorCode::DimensionMismatch,
                         )
                     }
                 }
@@ -704,7 +712,11 @@ impl<'a> AnalyzeContext<'a> {
             {
                 idx as usize
             } else {
-                diagnostics.error(&expr.pos, "Expected an integer literal");
+                diagnostics.add(
+                    &expr.pos,
+                    "Expected an integer literal",
+                    ErrorCode::InvalidLiteral,
+                );
                 return Err(EvalError::Unknown);
             }
         } else {
@@ -722,7 +734,7 @@ impl<'a> AnalyzeContext<'a> {
             if let Some(expr) = expr {
                 let ndims = indexes.len();
                 let dimensions = plural("dimension", "dimensions", ndims);
-                diagnostics.error(&expr.pos, format!("Index {idx} out of range for array with {ndims} {dimensions}, expected 1 to {ndims}"));
+                diagnostics.add(&expr.pos, format!("Index {idx} out of range for array with {ndims} {dimensions}, expected 1 to {ndims}"), ErrorCode::DimensionMismatch);
             }
             Err(EvalError::Unknown)
         }
@@ -874,6 +886,13 @@ impl<'a> AnalyzeContext<'a> {
                 check_no_attr_argument(attr, diagnostics);
                 Ok(AttrResolveResult::Value(self.string().base()))
             }
+            AttributeDesignator::Converse => {
+                // Mode views are handled before attribute_suffix is called
+                diagnostics.push(Diagnostic::cannot_be_prefix_of_attribute(
+                    name_pos, prefix, attr,
+                ));
+                Err(EvalError::Unknown)
+            }
 
             AttributeDesignator::Signal(sattr) => {
                 let typ = prefix.as_type_of_signal_attr_prefix(prefix_pos, attr, diagnostics)?;
@@ -927,14 +946,19 @@ impl<'a> AnalyzeContext<'a> {
             }
 
             AttributeDesignator::Ident(_) => {
-                diagnostics.error(
+                diagnostics.add(
                     &attr.attr.pos,
                     format!("Unknown attribute '{}", attr.attr.item),
+                    ErrorCode::Unresolved,
                 );
                 Err(EvalError::Unknown)
             }
             AttributeDesignator::Range(_) => {
-                diagnostics.error(name_pos, "Range cannot be used as an expression");
+                diagnostics.add(
+                    name_pos,
+                    "Range cannot be used as an expression",
+                    ErrorCode::MismatchedKinds,
+                );
                 Err(EvalError::Unknown)
             }
             AttributeDesignator::Type(attr) => self
@@ -960,13 +984,14 @@ impl<'a> AnalyzeContext<'a> {
     ) -> EvalResult<TypeEnt<'a>> {
         // all type attribute suffixes require that the prefix be an object type
         let Some(obj) = prefix.as_object_name() else {
-            diagnostics.error(
+            diagnostics.add(
                 pos,
                 format!(
                     "The {} attribute can only be used on objects, not {}",
                     suffix,
                     prefix.describe()
                 ),
+                ErrorCode::IllegalAttribute,
             );
             return Err(EvalError::Unknown);
         };
@@ -976,9 +1001,10 @@ impl<'a> AnalyzeContext<'a> {
                 if let Some((elem_type, _)) = obj.type_mark().array_type() {
                     Ok(elem_type)
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         pos,
                         "The element attribute can only be used for array types",
+                        ErrorCode::IllegalAttribute,
                     );
                     Err(EvalError::Unknown)
                 }
@@ -1017,7 +1043,7 @@ impl<'a> AnalyzeContext<'a> {
 
                         catch_diagnostic(
                             ResolvedName::from_scope_not_overloaded(ent)
-                                .map_err(|e| Diagnostic::error(name_pos, e)),
+                                .map_err(|e| Diagnostic::mismatched_kinds(name_pos, e)),
                             diagnostics,
                         )?
                     }
@@ -1028,6 +1054,13 @@ impl<'a> AnalyzeContext<'a> {
                 });
             }
             SplitName::External(ename) => {
+                if self.standard < VHDLStandard::VHDL2008 {
+                    diagnostics.push(Diagnostic::not_in_standard(
+                        name_pos,
+                        "External name",
+                        self.standard,
+                    ));
+                }
                 let ExternalName { subtype, class, .. } = ename;
                 let subtype = catch_analysis_err(
                     self.resolve_subtype_indication(scope, subtype, diagnostics),
@@ -1078,9 +1111,10 @@ impl<'a> AnalyzeContext<'a> {
                                 resolved =
                                     ResolvedName::Expression(DisambiguatedType::Ambiguous(types));
                             } else {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &prefix.pos,
                                     "Procedure calls are not valid in names and expressions",
+                                    ErrorCode::MismatchedKinds,
                                 );
                                 return Err(EvalError::Unknown);
                             }
@@ -1090,9 +1124,10 @@ impl<'a> AnalyzeContext<'a> {
                                 resolved =
                                     ResolvedName::Expression(DisambiguatedType::Unambiguous(typ));
                             } else {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &prefix.pos,
                                     "Procedure calls are not valid in names and expressions",
+                                    ErrorCode::MismatchedKinds,
                                 );
                                 return Err(EvalError::Unknown);
                             }
@@ -1103,6 +1138,16 @@ impl<'a> AnalyzeContext<'a> {
         }
 
         if let Suffix::Attribute(ref mut attr) = suffix {
+            // VHDL-2019 LRM 6.5.2.2 The converse of a mode view is itself a mode view
+            if let (AttributeDesignator::Converse, ResolvedName::Final(ent)) =
+                (&attr.attr.item, &resolved)
+            {
+                if matches!(ent.kind(), AnyEntKind::View(_)) {
+                    check_no_attr_argument(attr, diagnostics);
+                    return Ok(ResolvedName::Final(ent));
+                }
+            }
+
             let typ =
                 self.attribute_suffix(name_pos, &prefix.pos, scope, &resolved, attr, diagnostics)?;
             return match typ {
@@ -1150,9 +1195,10 @@ impl<'a> AnalyzeContext<'a> {
                                 resolved =
                                     ResolvedName::Expression(DisambiguatedType::Ambiguous(types));
                             } else {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &prefix.pos,
                                     "Procedure calls are not valid in names and expressions",
+                                    ErrorCode::MismatchedKinds,
                                 );
                                 return Err(EvalError::Unknown);
                             }
@@ -1164,9 +1210,10 @@ impl<'a> AnalyzeContext<'a> {
                                     DisambiguatedType::Unambiguous(return_type),
                                 );
                             } else {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &prefix.pos,
                                     "Procedure calls are not valid in names and expressions",
+                                    ErrorCode::MismatchedKinds,
                                 );
                                 return Err(EvalError::Unknown);
                             }
@@ -1281,7 +1328,7 @@ impl<'a> AnalyzeContext<'a> {
 
                             catch_diagnostic(
                                 ResolvedName::from_design_not_overloaded(named_entity)
-                                    .map_err(|e| Diagnostic::error(&designator.pos, e)),
+                                    .map_err(|e| Diagnostic::mismatched_kinds(&designator.pos, e)),
                                 diagnostics,
                             )?
                         }
@@ -1329,6 +1376,7 @@ impl<'a> AnalyzeContext<'a> {
         name_pos: &SrcPos,
         name: &mut Name,
         err_msg: &'static str,
+        code: ErrorCode,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> EvalResult<ObjectName<'a>> {
         let resolved = self.name_resolve(scope, name_pos, name, diagnostics)?;
@@ -1340,7 +1388,11 @@ impl<'a> AnalyzeContext<'a> {
             | ResolvedName::Overloaded { .. }
             | ResolvedName::Expression(_)
             | ResolvedName::Final(_) => {
-                diagnostics.error(name_pos, format!("{} {}", resolved.describe(), err_msg));
+                diagnostics.add(
+                    name_pos,
+                    format!("{} {}", resolved.describe(), err_msg),
+                    code,
+                );
                 Err(EvalError::Unknown)
             }
         }
@@ -1362,9 +1414,10 @@ impl<'a> AnalyzeContext<'a> {
             | ResolvedName::Overloaded { .. }
             | ResolvedName::Expression(_)
             | ResolvedName::Final(_) => {
-                diagnostics.error(
+                diagnostics.add(
                     name_pos,
                     format!("Expected type name, got {}", resolved.describe()),
+                    ErrorCode::MismatchedKinds,
                 );
                 Err(EvalError::Unknown)
             }
@@ -1383,25 +1436,27 @@ impl<'a> AnalyzeContext<'a> {
             match types {
                 ExpressionType::Unambiguous(ctyp) => {
                     if !typ.base().is_closely_related(ctyp.base()) {
-                        diagnostics.error(
+                        diagnostics.add(
                             pos,
                             format!(
                                 "{} cannot be converted to {}",
                                 ctyp.describe(),
                                 typ.describe()
                             ),
+                            ErrorCode::TypeMismatch,
                         )
                     }
                 }
                 ExpressionType::String
                 | ExpressionType::Ambiguous(_)
                 | ExpressionType::Null
-                | ExpressionType::Aggregate => diagnostics.error(
+                | ExpressionType::Aggregate => diagnostics.add(
                     pos,
                     format!(
                         "{} cannot be the argument of type conversion",
                         types.describe()
                     ),
+                    ErrorCode::MismatchedKinds,
                 ),
             }
         }
@@ -1510,7 +1565,7 @@ impl<'a> AnalyzeContext<'a> {
 
             Ok(*elem_type)
         } else {
-            Err(Diagnostic::error(
+            Err(Diagnostic::mismatched_kinds(
                 suffix_pos,
                 format!("{} cannot be indexed", type_mark.describe()),
             )
@@ -1551,13 +1606,14 @@ impl<'a> AnalyzeContext<'a> {
             }
             AnyEntKind::Design(_) => {
                 let design = DesignEnt::from_any(prefix).ok_or_else(|| {
-                    Diagnostic::error(
+                    Diagnostic::internal(
                         &suffix.pos,
                         format!(
                             "Internal error when expecting design unit, got {}",
                             prefix.describe()
                         ),
                     )
+                    .with_severity(Severity::Error)
                 })?;
 
                 let named = design.selected(prefix_pos, suffix)?;
@@ -1584,7 +1640,7 @@ impl<'a> AnalyzeContext<'a> {
                     return Ok(visible);
                 };
 
-                Err(AnalysisError::NotFatal(Diagnostic::error(
+                Err(AnalysisError::NotFatal(Diagnostic::mismatched_kinds(
                     &prefix.pos,
                     "Invalid prefix for selected name",
                 )))
@@ -1630,7 +1686,7 @@ impl Diagnostic {
             resolved.describe_type()
         };
 
-        Diagnostic::error(prefix_pos, format!("{name_desc} cannot be {suffix_desc}"))
+        Diagnostic::mismatched_kinds(prefix_pos, format!("{name_desc} cannot be {suffix_desc}"))
     }
 
     fn cannot_be_prefix_of_attribute(
@@ -1638,13 +1694,14 @@ impl Diagnostic {
         resolved: &ResolvedName,
         attr: &AttributeSuffix,
     ) -> Diagnostic {
-        Diagnostic::error(
+        Diagnostic::new(
             prefix_pos,
             format!(
                 "{} cannot be the the prefix of '{} attribute",
                 resolved.describe_type(),
                 attr.attr
             ),
+            ErrorCode::IllegalAttribute,
         )
     }
 
@@ -1654,7 +1711,11 @@ impl Diagnostic {
         got: usize,
         expected: usize,
     ) -> Diagnostic {
-        let mut diag = Diagnostic::error(pos, "Number of indexes does not match array dimension");
+        let mut diag = Diagnostic::new(
+            pos,
+            "Number of indexes does not match array dimension",
+            ErrorCode::DimensionMismatch,
+        );
 
         if let Some(decl_pos) = base_type.decl_pos() {
             diag.add_related(
@@ -1675,16 +1736,17 @@ impl Diagnostic {
 
     /// An internal logic error that we want to show to the user to get bug reports
     fn unreachable(pos: &SrcPos, expected: &str) -> Diagnostic {
-        Diagnostic::warning(pos, format!("Internal error, unreachable code {expected}"))
+        Diagnostic::internal(pos, format!("Internal error, unreachable code {expected}"))
     }
 
     pub fn ambiguous_call<'a>(
         call_name: &WithPos<Designator>,
         candidates: impl IntoIterator<Item = OverloadedEnt<'a>>,
     ) -> Diagnostic {
-        let mut diag = Diagnostic::error(
+        let mut diag = Diagnostic::new(
             &call_name.pos,
             format!("Ambiguous call to {}", call_name.item.describe()),
+            ErrorCode::AmbiguousCall,
         );
         diag.add_subprogram_candidates("Might be", candidates);
         diag
@@ -1693,9 +1755,10 @@ impl Diagnostic {
 
 fn check_no_attr_argument(suffix: &AttributeSuffix, diagnostics: &mut dyn DiagnosticHandler) {
     if let Some(ref expr) = suffix.expr {
-        diagnostics.error(
+        diagnostics.add(
             &expr.pos,
             format!("'{} attribute does not take an argument", suffix.attr),
+            ErrorCode::IllegalAttribute,
         )
     }
 }
@@ -1706,9 +1769,10 @@ fn check_no_sattr_argument(
     diagnostics: &mut dyn DiagnosticHandler,
 ) {
     if let Some(ref expr) = expr {
-        diagnostics.error(
+        diagnostics.add(
             &expr.pos,
             format!("'{attr} attribute does not take an argument"),
+            ErrorCode::IllegalAttribute,
         )
     }
 }
@@ -1721,9 +1785,10 @@ fn check_single_argument<'a>(
     if let Some(ref mut expr) = suffix.expr {
         Some(expr)
     } else {
-        diagnostics.error(
+        diagnostics.add(
             pos,
             format!("'{} attribute requires a single argument", suffix.attr),
+            ErrorCode::IllegalAttribute,
         );
         None
     }
@@ -1837,9 +1902,10 @@ variable thevar : integer;
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("thevar'element"),
                 "The element attribute can only be used for array types",
+                ErrorCode::IllegalAttribute,
             )],
         )
     }
@@ -1860,9 +1926,10 @@ type my_type is array(natural range<>) of integer;
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("my_type'subtype"),
                 "The subtype attribute can only be used on objects, not array type 'my_type'",
+                ErrorCode::IllegalAttribute,
             )],
         )
     }
@@ -1883,9 +1950,10 @@ variable x: integer;
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("x'subtype'subtype"),
                 "The subtype attribute can only be used on objects, not integer type 'INTEGER'",
+                ErrorCode::IllegalAttribute,
             )],
         )
     }
@@ -1955,9 +2023,10 @@ variable c0 : integer_vector(0 to 1);
         assert_matches!(resolved, Ok(ResolvedName::ObjectName(oname)) if oname.type_mark() == test.lookup_type("integer"));
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("'a'"),
                 "character literal does not match integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
             )],
         )
     }
@@ -1979,7 +2048,7 @@ variable c0 : integer_vector(0 to 1);
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 &code.s1("c0"),
                 "variable 'c0' cannot be called as a function",
             )],
@@ -2062,7 +2131,7 @@ procedure proc(arg: natural);
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 code.s1("proc"),
                 "Procedure calls are not valid in names and expressions",
             )],
@@ -2227,7 +2296,7 @@ variable c0 : integer_vector(0 to 6);
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 code.s1("real"),
                 "real type 'REAL' cannot be used as a discrete range",
             )],
@@ -2248,9 +2317,10 @@ variable c0 : arr_t;
         let _ = test.name_resolve(&code, None, &mut diagnostics);
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("c0(0 to 1)"),
                 "Cannot slice 2-dimensional array type 'arr_t'",
+                ErrorCode::DimensionMismatch,
             )],
         )
     }
@@ -2337,9 +2407,10 @@ type arr_t is array (integer range 0 to 3, character range 'a' to 'c') of intege
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("3"),
                 "Index 3 out of range for array with 2 dimensions, expected 1 to 2",
+                ErrorCode::DimensionMismatch,
             )],
         );
 
@@ -2351,9 +2422,10 @@ type arr_t is array (integer range 0 to 3, character range 'a' to 'c') of intege
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("1+1"),
                 "Expected an integer literal",
+                ErrorCode::InvalidLiteral,
             )],
         )
     }
@@ -2447,9 +2519,10 @@ constant c0 : arr_t := (others => 0);
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.pos(),
                 "'image attribute requires a single argument",
+                ErrorCode::IllegalAttribute,
             )],
         )
     }
@@ -2468,9 +2541,10 @@ constant c0 : arr_t := (others => 0);
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("0"),
                 "'low attribute does not take an argument",
+                ErrorCode::IllegalAttribute,
             )],
         )
     }
@@ -2518,9 +2592,10 @@ constant c0 : arr_t := (others => 0);
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("'a'"),
                 "Expected integer type, got type 'CHARACTER'",
+                ErrorCode::TypeMismatch,
             )],
         );
 
@@ -2573,9 +2648,10 @@ variable thevar : integer;
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("thevar"),
                 "Expected signal prefix for 'delayed attribute, got variable 'thevar'",
+                ErrorCode::IllegalAttribute,
             )],
         )
     }
@@ -2702,9 +2778,10 @@ variable thevar : integer;
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("missing"),
                 "Unknown attribute 'missing",
+                ErrorCode::Unresolved,
             )],
         )
     }
@@ -2725,7 +2802,7 @@ variable thevar : integer_vector(0 to 1);
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 code,
                 "Range cannot be used as an expression",
             )],
@@ -2797,9 +2874,10 @@ signal thesig : integer;
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("'a'"),
                 "type 'CHARACTER' cannot be converted to integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
             )],
         );
 
@@ -2814,9 +2892,10 @@ signal thesig : integer;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("false"),
                 "type 'BOOLEAN' cannot be converted to real type 'REAL'",
+                ErrorCode::TypeMismatch,
             )],
         );
     }
@@ -2858,9 +2937,10 @@ type character_vector_2d is array (natural range 0 to 1, natural range 0 to 2) o
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("string'(\"01\")"),
                 "array type 'STRING' cannot be converted to array type 'character_vector_2d'",
+                ErrorCode::TypeMismatch,
             )],
         );
 
@@ -2875,9 +2955,10 @@ type character_vector_2d is array (natural range 0 to 1, natural range 0 to 2) o
         );
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("string'(\"01\")"),
                 "array type 'STRING' cannot be converted to array type 'INTEGER_VECTOR'",
+                ErrorCode::TypeMismatch,
             )],
         );
     }
@@ -2962,17 +3043,19 @@ type enum_t is (alpha, beta);
         );
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(code.s1("myfun"), "Ambiguous call to 'myfun'")
-                    .related(
-                        decl.s("myfun", 1),
-                        "Might be function myfun[INTEGER return INTEGER]",
-                    )
-                    .related(
-                        decl.s("myfun", 2),
-                        "Might be function myfun[CHARACTER return INTEGER]",
-                    ),
-            ],
+            vec![Diagnostic::new(
+                code.s1("myfun"),
+                "Ambiguous call to 'myfun'",
+                ErrorCode::AmbiguousCall,
+            )
+            .related(
+                decl.s("myfun", 1),
+                "Might be function myfun[INTEGER return INTEGER]",
+            )
+            .related(
+                decl.s("myfun", 2),
+                "Might be function myfun[CHARACTER return INTEGER]",
+            )],
         )
     }
 
@@ -3001,17 +3084,19 @@ type enum_t is (alpha, beta);
         );
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(code.s1("myfun"), "Ambiguous call to 'myfun'")
-                    .related(
-                        decl.s("myfun", 1),
-                        "Might be function myfun[INTEGER return rec1_t]",
-                    )
-                    .related(
-                        decl.s("myfun", 2),
-                        "Might be function myfun[INTEGER return rec2_t]",
-                    ),
-            ],
+            vec![Diagnostic::new(
+                code.s1("myfun"),
+                "Ambiguous call to 'myfun'",
+                ErrorCode::AmbiguousCall,
+            )
+            .related(
+                decl.s("myfun", 1),
+                "Might be function myfun[INTEGER return rec1_t]",
+            )
+            .related(
+                decl.s("myfun", 2),
+                "Might be function myfun[INTEGER return rec2_t]",
+            )],
         )
     }
 }
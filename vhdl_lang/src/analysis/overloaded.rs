@@ -98,9 +98,10 @@ impl<'a> Candidates<'a> {
                     {
                         // Special case to get better error for single rejected enumeration literal
                         // For example when assigning true to an integer.
-                        return Err(Diagnostic::error(
+                        return Err(Diagnostic::new(
                             name,
                             format!("'{}' does not match {}", name, ttyp.describe()),
+                            ErrorCode::TypeMismatch,
                         ));
                     }
                 }
@@ -113,7 +114,11 @@ impl<'a> Candidates<'a> {
                 "Could not resolve"
             };
 
-            let mut diag = Diagnostic::error(name, format!("{err_prefix} '{name}'"));
+            let mut diag = Diagnostic::new(
+                name,
+                format!("{err_prefix} '{name}'"),
+                ErrorCode::InvalidCall,
+            );
 
             rejected.sort_by(|x, y| x.ent.decl_pos().cmp(&y.ent.decl_pos()));
 
@@ -403,9 +408,10 @@ impl Diagnostic {
         name: &WithPos<Designator>,
         rejected: impl IntoIterator<Item = OverloadedEnt<'a>>,
     ) -> Self {
-        let mut diag = Diagnostic::error(
+        let mut diag = Diagnostic::new(
             &name.pos,
             format!("Could not resolve call to '{}'", name.designator()),
+            ErrorCode::InvalidCall,
         );
         diag.add_subprogram_candidates("Does not match", rejected);
         diag
@@ -494,9 +500,10 @@ function myfun(arg : integer) return integer;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 call.s1("'c'"),
                 "character literal does not match integer type 'INTEGER'",
+                ErrorCode::TypeMismatch,
             )],
         );
     }
@@ -539,9 +546,17 @@ function myfun(arg1 : integer) return integer;
         check_diagnostics(
             diagnostics,
             vec![
-                Diagnostic::error(fcall.s1("missing"), "No declaration of 'missing'"),
-                Diagnostic::error(fcall, "No association of parameter 'arg1'")
-                    .related(decl.s1("arg1"), "Defined here"),
+                Diagnostic::new(
+                    fcall.s1("missing"),
+                    "No declaration of 'missing'",
+                    ErrorCode::Unresolved,
+                ),
+                Diagnostic::new(
+                    fcall,
+                    "No association of parameter 'arg1'",
+                    ErrorCode::Unassociated,
+                )
+                .related(decl.s1("arg1"), "Defined here"),
             ],
         );
     }
@@ -561,17 +576,19 @@ function myfun(arg2 : integer) return character;
         assert_eq!(test.disambiguate(&fcall, None, &mut diagnostics), None);
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(fcall.s1("myfun"), "Could not resolve call to 'myfun'")
-                    .related(
-                        decl.s1("myfun"),
-                        "Does not match function myfun[INTEGER return INTEGER]",
-                    )
-                    .related(
-                        decl.s("myfun", 2),
-                        "Does not match function myfun[INTEGER return CHARACTER]",
-                    ),
-            ],
+            vec![Diagnostic::new(
+                fcall.s1("myfun"),
+                "Could not resolve call to 'myfun'",
+                ErrorCode::InvalidCall,
+            )
+            .related(
+                decl.s1("myfun"),
+                "Does not match function myfun[INTEGER return INTEGER]",
+            )
+            .related(
+                decl.s("myfun", 2),
+                "Does not match function myfun[INTEGER return CHARACTER]",
+            )],
         );
     }
 
@@ -634,17 +651,19 @@ function myfun(arg1 : character) return character;
         assert_eq!(test.disambiguate(&fcall, None, &mut diagnostics), None);
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(fcall.s1("myfun"), "Could not resolve call to 'myfun'")
-                    .related(
-                        decl.s1("myfun"),
-                        "Does not match function myfun[CHARACTER return INTEGER]",
-                    )
-                    .related(
-                        decl.s("myfun", 2),
-                        "Does not match function myfun[CHARACTER return CHARACTER]",
-                    ),
-            ],
+            vec![Diagnostic::new(
+                fcall.s1("myfun"),
+                "Could not resolve call to 'myfun'",
+                ErrorCode::InvalidCall,
+            )
+            .related(
+                decl.s1("myfun"),
+                "Does not match function myfun[CHARACTER return INTEGER]",
+            )
+            .related(
+                decl.s("myfun", 2),
+                "Does not match function myfun[CHARACTER return CHARACTER]",
+            )],
         );
     }
 
@@ -689,17 +708,19 @@ function myfun(arg1 : integer) return character;
 
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(fcall.s1("myfun"), "Could not resolve call to 'myfun'")
-                    .related(
-                        decl.s1("myfun"),
-                        "Does not match function myfun[INTEGER return INTEGER]",
-                    )
-                    .related(
-                        decl.s("myfun", 2),
-                        "Does not match function myfun[INTEGER return CHARACTER]",
-                    ),
-            ],
+            vec![Diagnostic::new(
+                fcall.s1("myfun"),
+                "Could not resolve call to 'myfun'",
+                ErrorCode::InvalidCall,
+            )
+            .related(
+                decl.s1("myfun"),
+                "Does not match function myfun[INTEGER return INTEGER]",
+            )
+            .related(
+                decl.s("myfun", 2),
+                "Does not match function myfun[INTEGER return CHARACTER]",
+            )],
         )
     }
 
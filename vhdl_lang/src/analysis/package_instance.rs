@@ -36,8 +36,10 @@ use crate::ast::Name;
 use crate::ast::Operator;
 use crate::ast::PackageInstantiation;
 use crate::data::DiagnosticHandler;
+use crate::data::ErrorCode;
 use crate::Diagnostic;
 use crate::NullDiagnostics;
+use crate::Severity;
 
 impl<'a> AnalyzeContext<'a> {
     fn package_generic_map(
@@ -64,16 +66,21 @@ impl<'a> AnalyzeContext<'a> {
                         }
                     }
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         &formal.pos,
                         "Expected simple name for package generic formal",
+                        ErrorCode::InvalidFormal,
                     );
                     continue;
                 }
             } else if let Some(ent) = generics.nth(idx) {
                 ent
             } else {
-                diagnostics.error(&assoc.actual.pos, "Extra actual for generic map");
+                diagnostics.add(
+                    &assoc.actual.pos,
+                    "Extra actual for generic map",
+                    ErrorCode::TooManyArguments,
+                );
                 continue;
             };
 
@@ -101,12 +108,13 @@ impl<'a> AnalyzeContext<'a> {
                                             )?;
                                         }
                                     } else {
-                                        diagnostics.error(
+                                        diagnostics.add(
                                             &assoc.actual.pos,
                                             format!(
                                                 "Array constraint cannot be used for {}",
                                                 typ.describe()
                                             ),
+                                            ErrorCode::IllegalConstraint,
                                         );
                                     }
                                     typ
@@ -123,8 +131,11 @@ impl<'a> AnalyzeContext<'a> {
                                 _ => self.type_name(scope, &assoc.actual.pos, name, diagnostics)?,
                             }
                         } else {
-                            diagnostics
-                                .error(&assoc.actual.pos, "Cannot map expression to type generic");
+                            diagnostics.add(
+                                &assoc.actual.pos,
+                                "Cannot map expression to type generic",
+                                ErrorCode::MismatchedKinds,
+                            );
                             continue;
                         };
 
@@ -151,7 +162,7 @@ impl<'a> AnalyzeContext<'a> {
                                 if let Some(ent) = overloaded.get(&signature) {
                                     name.set_unique_reference(&ent);
                                 } else {
-                                    let mut diag = Diagnostic::error(
+                                    let mut diag = Diagnostic::mismatched_kinds(
                                         &assoc.actual.pos,
                                         format!(
                                             "Cannot map '{}' to subprogram generic {}{}",
@@ -169,32 +180,39 @@ impl<'a> AnalyzeContext<'a> {
                                     diagnostics.push(diag)
                                 }
                             } else {
-                                diagnostics.error(
+                                diagnostics.add(
                                     &assoc.actual.pos,
                                     format!(
                                         "Cannot map {} to subprogram generic",
                                         resolved.describe()
                                     ),
+                                    ErrorCode::MismatchedKinds,
                                 )
                             }
                         }
                         Expression::Literal(Literal::String(string)) => {
                             if Operator::from_latin1(string.clone()).is_none() {
-                                diagnostics.error(&assoc.actual.pos, "Invalid operator symbol");
+                                diagnostics.add(
+                                    &assoc.actual.pos,
+                                    "Invalid operator symbol",
+                                    ErrorCode::MismatchedKinds,
+                                );
                             }
                         }
-                        _ => diagnostics.error(
+                        _ => diagnostics.add(
                             &assoc.actual.pos,
                             "Cannot map expression to subprogram generic",
+                            ErrorCode::MismatchedKinds,
                         ),
                     },
                     GpkgInterfaceEnt::Package(_) => match expr {
                         Expression::Name(name) => {
                             self.name_resolve(scope, &assoc.actual.pos, name, diagnostics)?;
                         }
-                        _ => diagnostics.error(
+                        _ => diagnostics.add(
                             &assoc.actual.pos,
                             "Cannot map expression to package generic",
+                            ErrorCode::MismatchedKinds,
                         ),
                     },
                 },
@@ -243,7 +261,8 @@ impl<'a> AnalyzeContext<'a> {
                             nested.add(inst, &mut NullDiagnostics);
                         }
                         Err(err) => {
-                            let mut diag = Diagnostic::error(&unit.ident.tree.pos, err);
+                            let mut diag = Diagnostic::internal(&unit.ident.tree.pos, err)
+                                .with_severity(Severity::Error);
                             if let Some(pos) = uninst.decl_pos() {
                                 diag.add_related(pos, "When instantiating this declaration");
                             }
@@ -348,6 +367,9 @@ impl<'a> AnalyzeContext<'a> {
             AnyEntKind::DeferredConstant(subtype) => {
                 AnyEntKind::DeferredConstant(self.map_subtype(mapping, *subtype)?)
             }
+            AnyEntKind::GroupTemplate(entries) => AnyEntKind::GroupTemplate(entries.clone()),
+            AnyEntKind::Group => AnyEntKind::Group,
+            AnyEntKind::View(subtype) => AnyEntKind::View(self.map_subtype(mapping, *subtype)?),
             AnyEntKind::Library => AnyEntKind::Library,
             AnyEntKind::Design(design) => match design {
                 Design::PackageInstance(region) => AnyEntKind::Design(Design::PackageInstance(
@@ -529,6 +551,7 @@ impl<'a> AnalyzeContext<'a> {
             iface,
             subtype,
             has_default,
+            signal_kind,
         } = obj;
 
         Ok(Object {
@@ -536,6 +559,7 @@ impl<'a> AnalyzeContext<'a> {
             iface: *iface,
             subtype: self.map_subtype(mapping, *subtype)?,
             has_default: *has_default,
+            signal_kind: *signal_kind,
         })
     }
 
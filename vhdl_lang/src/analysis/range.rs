@@ -48,9 +48,10 @@ impl<'a> AnalyzeContext<'a> {
                 if typ.base().is_scalar() {
                     Ok(DisambiguatedType::Unambiguous(typ))
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         &expr.pos,
                         format!("Non-scalar {} cannot be used in a range", typ.describe()),
+                        ErrorCode::MismatchedKinds,
                     );
                     Err(EvalError::Unknown)
                 }
@@ -59,7 +60,11 @@ impl<'a> AnalyzeContext<'a> {
                 types.into_iter().filter(|typ| typ.is_scalar()).collect(),
             )),
             ExpressionType::String | ExpressionType::Null | ExpressionType::Aggregate => {
-                diagnostics.error(&expr.pos, "Non-scalar expression cannot be used in a range");
+                diagnostics.add(
+                    &expr.pos,
+                    "Non-scalar expression cannot be used in a range",
+                    ErrorCode::MismatchedKinds,
+                );
                 Err(EvalError::Unknown)
             }
         }
@@ -87,12 +92,12 @@ impl<'a> AnalyzeContext<'a> {
                         attr.name.set_unique_reference(ent);
                         ent.return_type().unwrap()
                     } else {
-                        diagnostics.error(
+                        diagnostics.add(
                         &attr.name.pos,
                         format!(
                             "{} cannot be prefix of range attribute, array type or object is required",
                             resolved.describe()
-                        ),
+                        ), ErrorCode::IllegalAttribute
                     );
                         return Err(EvalError::Unknown);
                     }
@@ -105,12 +110,13 @@ impl<'a> AnalyzeContext<'a> {
             | ResolvedName::Final(_)
             | ResolvedName::Library(_)
             | ResolvedName::Design(_) => {
-                diagnostics.error(
+                diagnostics.add(
                     &attr.name.pos,
                     format!(
                         "{} cannot be prefix of range attribute, array type or object is required",
                         resolved.describe()
                     ),
+                    ErrorCode::IllegalAttribute,
                 );
                 return Err(EvalError::Unknown);
             }
@@ -124,12 +130,13 @@ impl<'a> AnalyzeContext<'a> {
                 Err(EvalError::Unknown)
             }
         } else {
-            diagnostics.error(
+            diagnostics.add(
                 &attr.name.pos,
                 format!(
                     "{} cannot be prefix of range attribute, array type or object is required",
                     resolved.describe()
                 ),
+                ErrorCode::IllegalAttribute,
             );
             Err(EvalError::Unknown)
         }
@@ -156,13 +163,14 @@ impl<'a> AnalyzeContext<'a> {
                         if let Some(typ) = self.common_type(l.base(), r.base()) {
                             return Ok(typ);
                         } else {
-                            diagnostics.error(
+                            diagnostics.add(
                                 constraint.pos(),
                                 format!(
                                     "Range type mismatch, left is {}, right is {}",
                                     l.base().describe(),
                                     r.base().describe()
                                 ),
+                                ErrorCode::TypeMismatch,
                             );
                             return Err(EvalError::Unknown);
                         }
@@ -175,7 +183,11 @@ impl<'a> AnalyzeContext<'a> {
                         self.common_types(l, r.base())
                     }
                     (DisambiguatedType::Ambiguous(_), DisambiguatedType::Ambiguous(_)) => {
-                        diagnostics.error(constraint.pos(), "Range is ambiguous");
+                        diagnostics.add(
+                            constraint.pos(),
+                            "Range is ambiguous",
+                            ErrorCode::AmbiguousExpression,
+                        );
                         return Err(EvalError::Unknown);
                     }
                 };
@@ -203,13 +215,18 @@ impl<'a> AnalyzeContext<'a> {
 
                     Ok(typ)
                 } else if types.is_empty() {
-                    diagnostics.error(
+                    diagnostics.add(
                         constraint.pos(),
                         "Range type of left and right side does not match",
+                        ErrorCode::TypeMismatch,
                     );
                     Err(EvalError::Unknown)
                 } else {
-                    diagnostics.error(constraint.pos(), "Range is ambiguous");
+                    diagnostics.add(
+                        constraint.pos(),
+                        "Range is ambiguous",
+                        ErrorCode::AmbiguousExpression,
+                    );
                     Err(EvalError::Unknown)
                 }
             }
@@ -244,12 +261,13 @@ impl<'a> AnalyzeContext<'a> {
         if typ.is_discrete() {
             Ok(typ)
         } else {
-            diagnostics.error(
+            diagnostics.add(
                 &drange.pos(),
                 format!(
                     "Non-discrete {} cannot be used in discrete range",
                     typ.describe()
                 ),
+                ErrorCode::MismatchedKinds,
             );
             Err(EvalError::Unknown)
         }
@@ -303,9 +321,10 @@ impl<'a> AnalyzeContext<'a> {
                 )?;
 
                 if let Some(ref mut signature) = signature {
-                    diagnostics.error(
+                    diagnostics.add(
                         &signature.pos,
                         format!("Did not expect signature for '{attr} attribute"),
+                        ErrorCode::IllegalSignature,
                     );
                 }
 
@@ -366,6 +385,7 @@ mod tests {
     use crate::ast::search::check_no_unresolved;
     use crate::ast::Range;
     use crate::data::DiagnosticHandler;
+    use crate::data::ErrorCode;
     use crate::data::NoDiagnostics;
     use crate::syntax::test::check_diagnostics;
     use crate::syntax::test::Code;
@@ -436,7 +456,7 @@ mod tests {
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 code.s1("0.0 to 1.0"),
                 "Non-discrete type universal_real cannot be used in discrete range",
             )],
@@ -455,7 +475,7 @@ mod tests {
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::mismatched_kinds(
                 code.s1("(0, 0)"),
                 "Non-scalar expression cannot be used in a range",
             )],
@@ -503,9 +523,10 @@ function f1 return integer;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("0 to false"),
                 "Range type mismatch, left is type universal_integer, right is type 'BOOLEAN'",
+                ErrorCode::TypeMismatch,
             )],
         );
     }
@@ -530,9 +551,10 @@ function f1 return integer;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("f1 to false"),
                 "Range type of left and right side does not match",
+                ErrorCode::TypeMismatch,
             )],
         );
     }
@@ -557,7 +579,11 @@ function f1 return integer;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(code.s1("f1 to f1"), "Range is ambiguous")],
+            vec![Diagnostic::new(
+                code.s1("f1 to f1"),
+                "Range is ambiguous",
+                ErrorCode::AmbiguousExpression,
+            )],
         );
     }
 
@@ -595,9 +621,9 @@ function myfun return arr_t;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s1("character"),
-                "type 'CHARACTER' cannot be prefix of range attribute, array type or object is required",
+                "type 'CHARACTER' cannot be prefix of range attribute, array type or object is required", ErrorCode::IllegalAttribute
             )],
         );
     }
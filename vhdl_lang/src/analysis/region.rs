@@ -91,13 +91,14 @@ impl<'a> OverloadedName<'a> {
                 // @TODO only libraries have decl_pos=None
                 let pos = ent.decl_pos().unwrap();
 
-                let mut diagnostic = Diagnostic::error(
+                let mut diagnostic = Diagnostic::new(
                     pos,
                     format!(
                         "Duplicate declaration of '{}' with signature {}",
                         ent.designator(),
                         ent.signature().describe()
                     ),
+                    ErrorCode::Duplicate,
                 );
                 if let Some(old_pos) = old_ent.decl_pos() {
                     diagnostic.add_related(old_pos, "Previously defined here");
@@ -159,7 +160,7 @@ impl<'a> NamedEntities<'a> {
         match self {
             Self::Single(ent) => Ok(ent),
             Self::Overloaded(overloaded) => {
-                let mut error = Diagnostic::error(pos, message());
+                let mut error = Diagnostic::mismatched_kinds(pos, message());
                 for ent in overloaded.entities() {
                     if let Some(decl_pos) = ent.decl_pos() {
                         error.add_related(decl_pos, "Defined here");
@@ -266,6 +267,18 @@ impl<'a> ScopeInner<'a> {
             .make_all_potentially_visible(visible_pos, region);
     }
 
+    pub fn make_potentially_visible_except(
+        &mut self,
+        visible_pos: Option<&SrcPos>,
+        region: &'a Region<'a>,
+        exclude: impl Fn(EntRef<'a>) -> bool,
+    ) {
+        self.cache.clear();
+        self.region
+            .visibility
+            .make_potentially_visible_except(visible_pos, region, exclude);
+    }
+
     /// Used when using context clauses
     pub fn add_context_visibility(&mut self, visible_pos: Option<&SrcPos>, region: &Region<'a>) {
         self.cache.clear();
@@ -359,21 +372,27 @@ impl<'a> ScopeInner<'a> {
 
         match result {
             Some(visible) => Ok(visible),
-            None => Err(Diagnostic::error(
-                pos,
-                match designator {
-                    Designator::Identifier(ident) => {
-                        format!("No declaration of '{ident}'")
-                    }
-                    Designator::OperatorSymbol(operator) => {
-                        format!("No declaration of operator '{operator}'")
-                    }
-                    Designator::Character(chr) => {
-                        format!("No declaration of '{chr}'")
-                    }
-                    Designator::Anonymous(_) => "No declaration of <anonymous>".to_owned(),
-                },
-            )),
+            None => Err(match designator {
+                Designator::Identifier(ident) => Diagnostic::new(
+                    pos,
+                    format!("No declaration of '{ident}'"),
+                    ErrorCode::Unresolved,
+                )
+                .with_data(ident.to_string()),
+                Designator::OperatorSymbol(operator) => Diagnostic::new(
+                    pos,
+                    format!("No declaration of operator '{operator}'"),
+                    ErrorCode::Unresolved,
+                ),
+                Designator::Character(chr) => Diagnostic::new(
+                    pos,
+                    format!("No declaration of '{chr}'"),
+                    ErrorCode::Unresolved,
+                ),
+                Designator::Anonymous(_) => {
+                    Diagnostic::new(pos, "No declaration of <anonymous>", ErrorCode::Unresolved)
+                }
+            }),
         }
     }
 
@@ -488,6 +507,19 @@ impl<'a> Scope<'a> {
             .make_all_potentially_visible(visible_pos, region);
     }
 
+    /// Make the declarations of a region potentially visible except for the excluded ones
+    pub fn make_potentially_visible_except(
+        &self,
+        visible_pos: Option<&SrcPos>,
+        region: &'a Region<'a>,
+        exclude: impl Fn(EntRef<'a>) -> bool,
+    ) {
+        self.0
+            .as_ref()
+            .borrow_mut()
+            .make_potentially_visible_except(visible_pos, region, exclude);
+    }
+
     pub fn close(&self, diagnostics: &mut dyn DiagnosticHandler) {
         self.0.as_ref().borrow().close(diagnostics)
     }
@@ -632,7 +664,7 @@ impl<'a> Region<'a> {
             RegionKind::PackageDeclaration | RegionKind::PackageBody => {
                 for ent in self.entities.values() {
                     if let AnyEntKind::DeferredConstant(..) = ent.first_kind() {
-                        ent.first().error(diagnostics, format!("Deferred constant '{}' lacks corresponding full constant declaration in package body", ent.designator()));
+                        ent.first().error(diagnostics, format!("Deferred constant '{}' lacks corresponding full constant declaration in package body", ent.designator()), ErrorCode::MissingDeferredDeclaration);
                     }
                 }
             }
@@ -647,6 +679,7 @@ impl<'a> Region<'a> {
                     ent.first().error(
                         diagnostics,
                         format!("Missing body for protected type '{}'", ent.designator()),
+                        ErrorCode::MissingProtectedBody,
                     );
                 }
             }
@@ -663,6 +696,7 @@ impl<'a> Region<'a> {
             ent.error(
                 diagnostics,
                 "Deferred constants are only allowed in package declarations (not body)",
+                ErrorCode::IllegalDeferredConstant,
             );
             return;
         };
@@ -682,7 +716,7 @@ impl<'a> Region<'a> {
                             {
                                 ent.error(
                                     diagnostics,
-                                    "Full declaration of deferred constant is only allowed in a package body",
+                                    "Full declaration of deferred constant is only allowed in a package body", ErrorCode::IllegalDeferredConstant
                                 );
                             } else {
                                 *prev_ent = ent;
@@ -804,7 +838,11 @@ pub(super) fn duplicate_error(
     pos: &SrcPos,
     prev_pos: Option<&SrcPos>,
 ) -> Diagnostic {
-    let mut diagnostic = Diagnostic::error(pos, format!("Duplicate declaration of '{name}'"));
+    let mut diagnostic = Diagnostic::new(
+        pos,
+        format!("Duplicate declaration of '{name}'"),
+        ErrorCode::Duplicate,
+    );
 
     if let Some(prev_pos) = prev_pos {
         diagnostic.add_related(prev_pos, "Previously defined here");
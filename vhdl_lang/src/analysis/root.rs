@@ -24,6 +24,7 @@ use parking_lot::RwLock;
 use std::collections::hash_map::Entry;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// A design unit with design unit data
@@ -172,21 +173,24 @@ impl Library {
     fn append_duplicate_diagnostics(&self, diagnostics: &mut dyn DiagnosticHandler) {
         for (prev_pos, unit) in self.duplicates.iter() {
             let diagnostic = match unit.key() {
-                UnitKey::Primary(ref primary_name) => Diagnostic::error(
+                UnitKey::Primary(ref primary_name) => Diagnostic::new(
                     unit.pos(),
                     format!(
                         "A primary unit has already been declared with name '{}' in library '{}'",
                         primary_name, &self.name
                     ),
+                    ErrorCode::Duplicate,
                 ),
                 UnitKey::Secondary(ref primary_name, ref name) => match unit.kind() {
-                    AnyKind::Secondary(SecondaryKind::Architecture) => Diagnostic::error(
+                    AnyKind::Secondary(SecondaryKind::Architecture) => Diagnostic::new(
                         unit.ident(),
                         format!("Duplicate architecture '{name}' of entity '{primary_name}'",),
+                        ErrorCode::Duplicate,
                     ),
-                    AnyKind::Secondary(SecondaryKind::PackageBody) => Diagnostic::error(
+                    AnyKind::Secondary(SecondaryKind::PackageBody) => Diagnostic::new(
                         unit.pos(),
                         format!("Duplicate package body of package '{primary_name}'"),
+                        ErrorCode::Duplicate,
                     ),
                     AnyKind::Primary(_) => {
                         unreachable!();
@@ -258,6 +262,10 @@ impl Library {
         self.id
     }
 
+    pub(crate) fn units(&self) -> impl Iterator<Item = &LockedUnit> {
+        self.units.values()
+    }
+
     pub(crate) fn primary_units(&self) -> impl Iterator<Item = &LockedUnit> {
         self.units.iter().filter_map(|(key, value)| match key {
             UnitKey::Primary(_) => Some(value),
@@ -298,7 +306,7 @@ pub struct DesignRoot {
 
     // Dependency tracking for incremental analysis.
     // user  =>  set(users)
-    users_of: RwLock<FnvHashMap<UnitId, FnvHashSet<UnitId>>>,
+    pub(super) users_of: RwLock<FnvHashMap<UnitId, FnvHashSet<UnitId>>>,
 
     // missing unit name  =>  set(affected)
     #[allow(clippy::type_complexity)]
@@ -307,6 +315,13 @@ pub struct DesignRoot {
     // Tracks which units have a "use library.all;" clause.
     // library name  =>  set(affected)
     users_of_library_all: RwLock<FnvHashMap<Symbol, FnvHashSet<UnitId>>>,
+
+    // The VHDL revision of each library, libraries without one use the default standard
+    standards: FnvHashMap<Symbol, VHDLStandard>,
+    default_standard: VHDLStandard,
+
+    // Units analyzed by a cancelled analysis, reported as analyzed by the next analysis
+    unreported_units: Vec<UnitId>,
 }
 
 impl DesignRoot {
@@ -323,9 +338,30 @@ impl DesignRoot {
             users_of: RwLock::new(FnvHashMap::default()),
             missing_unit: RwLock::new(FnvHashMap::default()),
             users_of_library_all: RwLock::new(FnvHashMap::default()),
+            standards: FnvHashMap::default(),
+            default_standard: VHDLStandard::default(),
+            unreported_units: Vec::new(),
         }
     }
 
+    /// Set the VHDL revision of libraries that do not have a revision of their own
+    pub fn set_default_standard(&mut self, standard: VHDLStandard) {
+        self.default_standard = standard;
+    }
+
+    /// Set the VHDL revision that the design units of a library are analyzed with
+    pub fn set_library_standard(&mut self, library_name: Symbol, standard: VHDLStandard) {
+        self.standards.insert(library_name, standard);
+    }
+
+    /// Returns the VHDL revision of a library
+    pub fn standard_of(&self, library_name: &Symbol) -> VHDLStandard {
+        self.standards
+            .get(library_name)
+            .copied()
+            .unwrap_or(self.default_standard)
+    }
+
     /// Create library if it does not exist or return existing
     fn get_or_create_library(&mut self, name: Symbol) -> &mut Library {
         match self.libraries.entry(name) {
@@ -368,6 +404,16 @@ impl DesignRoot {
         &'a self,
         library_name: &Symbol,
         ident: &Symbol,
+    ) -> Option<DesignEnt<'a>> {
+        self.get_primary_unit_ent(library_name, ident)
+            .filter(|design| matches!(design.kind(), Design::Entity(..)))
+    }
+
+    /// Get the named entity of any kind of primary unit such as a package or an entity
+    pub(crate) fn get_primary_unit_ent<'a>(
+        &'a self,
+        library_name: &Symbol,
+        ident: &Symbol,
     ) -> Option<DesignEnt<'a>> {
         let units = self.get_library_units(library_name)?;
         let unit = units.get(&UnitKey::Primary(ident.clone()))?;
@@ -375,10 +421,7 @@ impl DesignRoot {
 
         if let AnyDesignUnit::Primary(primary) = data.deref() {
             if let Some(id) = primary.ent_id() {
-                let design = DesignEnt::from_any(self.arenas.get(id))?;
-                if matches!(design.kind(), Design::Entity(..)) {
-                    return Some(design);
-                }
+                return DesignEnt::from_any(self.arenas.get(id));
             }
         }
         None
@@ -434,7 +477,8 @@ impl DesignRoot {
             || decl.kind().is_deferred_constant()
         {
             let mut searcher = FindEnt::new(self, |ent| ent.is_declared_by(decl));
-            let _ = self.search(&mut searcher);
+            // The definition is in a body depending on the declaring unit
+            let _ = self.search_users_of(decl, &mut searcher);
 
             Some(searcher.result.unwrap_or(decl))
         } else {
@@ -495,6 +539,18 @@ impl DesignRoot {
         }
     }
 
+    /// Find all declarations of and references to named entities within a source file
+    /// sorted by position. Declarations are marked by the boolean.
+    pub fn find_all_in_source(&self, source: &Source) -> Vec<(SrcPos, EntRef<'_>, bool)> {
+        let mut searcher = FindAllInSource::new(self, source);
+        let _ = self.search(&mut searcher);
+        let mut result = searcher.result;
+        // A file may be part of several libraries
+        result.sort_by_key(|(pos, ..)| (pos.start(), pos.end()));
+        result.dedup_by(|(pos_a, ..), (pos_b, ..)| pos_a.range() == pos_b.range());
+        result
+    }
+
     /// Search for all references to the declaration at decl_pos
     pub fn find_all_references(&self, ent: EntRef) -> Vec<SrcPos> {
         let mut searcher = FindAllReferences::new(self, ent);
@@ -681,6 +737,62 @@ impl DesignRoot {
         NotFound
     }
 
+    /// Search the design unit declaring `ent` and all units depending on it.
+    /// Only those units can refer to `ent`, when the declaring unit is unknown the
+    /// whole design is searched.
+    pub(super) fn search_users_of(
+        &self,
+        ent: EntRef,
+        searcher: &mut impl Searcher,
+    ) -> SearchResult {
+        let Some(unit_id) = self.declaring_unit(ent) else {
+            return self.search(searcher);
+        };
+
+        let mut affected = FnvHashSet::default();
+        affected.insert(unit_id);
+        let unit_ids = get_all_affected(&self.users_of.read(), affected);
+
+        for unit_id in unit_ids.iter() {
+            if let Some(unit) = self.get_unit(unit_id) {
+                return_if_found!(unit.unit.write().search(&unit.tokens, searcher));
+            }
+        }
+        NotFound
+    }
+
+    /// Search only the design unit declaring `ent`,
+    /// when the declaring unit is unknown the whole design is searched.
+    pub(super) fn search_declaring_unit(
+        &self,
+        ent: EntRef,
+        searcher: &mut impl Searcher,
+    ) -> SearchResult {
+        match self
+            .declaring_unit(ent)
+            .and_then(|unit_id| self.get_unit(&unit_id))
+        {
+            Some(unit) => unit.unit.write().search(&unit.tokens, searcher),
+            None => self.search(searcher),
+        }
+    }
+
+    /// The design unit an entity is declared in
+    fn declaring_unit(&self, ent: EntRef) -> Option<UnitId> {
+        let mut ent = ent;
+        while let Some(parent) = ent.parent {
+            if matches!(parent.kind(), AnyEntKind::Library) {
+                break;
+            }
+            ent = parent;
+        }
+        let decl_pos = ent.decl_pos()?;
+        self.get_lib(ent.library_name()?)?
+            .units()
+            .find(|unit| unit.ident().pos() == decl_pos)
+            .map(|unit| unit.unit_id().clone())
+    }
+
     pub fn symbol_utf8(&self, name: &str) -> Symbol {
         self.symbols.symtab().insert_utf8(name)
     }
@@ -1078,6 +1190,18 @@ impl DesignRoot {
 
     // Returns the units that where re-analyzed
     pub fn analyze(&mut self, diagnostics: &mut dyn DiagnosticHandler) -> Vec<UnitId> {
+        self.analyze_cancellable(diagnostics, &AtomicBool::new(false))
+            .unwrap()
+    }
+
+    /// Analyze the design like [`Self::analyze`] but stop before analyzing the next unit
+    /// once `cancelled` is set. Returns `None` without any diagnostics when cancelled,
+    /// the units that were not analyzed yet are analyzed by the next call.
+    pub fn analyze_cancellable(
+        &mut self,
+        diagnostics: &mut dyn DiagnosticHandler,
+        cancelled: &AtomicBool,
+    ) -> Option<Vec<UnitId>> {
         self.reset();
 
         let mut units = Vec::default();
@@ -1089,6 +1213,16 @@ impl DesignRoot {
             }
         }
 
+        // Units analyzed by a cancelled analysis have not been reported yet
+        let unreported: FnvHashSet<_> = std::mem::take(&mut self.unreported_units)
+            .into_iter()
+            .filter(|unit_id| {
+                self.get_unit(unit_id)
+                    .is_some_and(|unit| unit.unit.is_analyzed())
+            })
+            .collect();
+        units.extend(unreported);
+
         for library in self.libraries.values_mut() {
             library.refresh(diagnostics);
         }
@@ -1110,9 +1244,16 @@ impl DesignRoot {
         use rayon::prelude::*;
 
         units.par_iter().for_each(|id| {
-            self.get_analysis(self.get_unit(id).unwrap());
+            if !cancelled.load(Ordering::Relaxed) {
+                self.get_analysis(self.get_unit(id).unwrap());
+            }
         });
 
+        if cancelled.load(Ordering::Relaxed) {
+            self.unreported_units = units;
+            return None;
+        }
+
         for library in self.libraries.values() {
             self.arenas.link(&library.arena);
             for unit in library.units.values() {
@@ -1130,7 +1271,7 @@ impl DesignRoot {
             }
         }
 
-        units
+        Some(units)
     }
 
     /// Get the named entity
@@ -1249,10 +1390,12 @@ end package body;
 
         check_diagnostics(
             diagnostics,
-            vec![
-                Diagnostic::error(code.s("pkg", 3), "Duplicate package body of package 'pkg'")
-                    .related(code.s("pkg", 2), "Previously defined here"),
-            ],
+            vec![Diagnostic::new(
+                code.s("pkg", 3),
+                "Duplicate package body of package 'pkg'",
+                ErrorCode::Duplicate,
+            )
+            .related(code.s("pkg", 2), "Previously defined here")],
         );
     }
 
@@ -1287,21 +1430,21 @@ package pkg is new gpkg generic map (const => foo);
         check_diagnostics(
             diagnostics,
             vec![
-                Diagnostic::error(
+                Diagnostic::new(
                     code.s("pkg", 2),
-                    "A primary unit has already been declared with name 'pkg' in library 'libname'",
+                    "A primary unit has already been declared with name 'pkg' in library 'libname'", ErrorCode::Duplicate
                 ).related(code.s("pkg", 1), "Previously defined here"),
-                Diagnostic::error(
+                Diagnostic::new(
                     code.s("entname", 2),
-                    "A primary unit has already been declared with name 'entname' in library 'libname'",
+                    "A primary unit has already been declared with name 'entname' in library 'libname'", ErrorCode::Duplicate
                 ).related(code.s("entname", 1), "Previously defined here"),
-                Diagnostic::error(
+                Diagnostic::new(
                     code.s("pkg", 3),
-                    "A primary unit has already been declared with name 'pkg' in library 'libname'",
+                    "A primary unit has already been declared with name 'pkg' in library 'libname'", ErrorCode::Duplicate
                 ).related(code.s("pkg", 1), "Previously defined here"),
-                Diagnostic::error(
+                Diagnostic::new(
                     code.s("pkg", 4),
-                    "A primary unit has already been declared with name 'pkg' in library 'libname'",
+                    "A primary unit has already been declared with name 'pkg' in library 'libname'", ErrorCode::Duplicate
                 ).related(code.s("pkg", 1), "Previously defined here"),
             ],
         );
@@ -1329,9 +1472,10 @@ end architecture;
         assert_eq!(library.duplicates.len(), 1);
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s("rtl", 2),
                 "Duplicate architecture 'rtl' of entity 'ent'",
+                ErrorCode::Duplicate,
             )
             .related(code.s("rtl", 1), "Previously defined here")],
         );
@@ -1359,9 +1503,10 @@ end configuration;
 
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::new(
                 code.s("cfg", 2),
                 "A primary unit has already been declared with name 'cfg' in library 'libname'",
+                ErrorCode::Duplicate,
             )
             .related(code.s1("cfg"), "Previously defined here")],
         );
@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Ranges of the syntax tree surrounding a cursor used to expand a selection
+
+use super::root::DesignRoot;
+use crate::ast::search::{FoundDeclaration, NotFound, SearchState, Searcher};
+use crate::ast::Reference;
+use crate::data::*;
+use crate::syntax::TokenAccess;
+
+impl DesignRoot {
+    /// The ranges of the nodes of the syntax tree that contain the cursor,
+    /// from the innermost to the design unit
+    pub fn selection_ranges(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        let mut searcher = EnclosingPositions {
+            source: source.clone(),
+            cursor,
+            positions: Vec::new(),
+        };
+        let _ = self.search(&mut searcher);
+        let mut positions = searcher.positions;
+        if let Some(unit) = self.unit_at(source, cursor) {
+            if let (Some(first), Some(last)) = (unit.tokens.first(), unit.tokens.last()) {
+                positions.push(first.pos.combine(&last.pos));
+            }
+        }
+
+        // The innermost range is the one starting last and ending first
+        positions.sort_by(|a, b| {
+            b.start()
+                .cmp(&a.start())
+                .then_with(|| a.end().cmp(&b.end()))
+        });
+        let mut ranges: Vec<SrcPos> = Vec::with_capacity(positions.len());
+        for pos in positions {
+            let contains_previous = ranges.last().is_none_or(|previous| {
+                pos.start() <= previous.start()
+                    && previous.end() <= pos.end()
+                    && pos.range() != previous.range()
+            });
+            if contains_previous {
+                ranges.push(pos);
+            }
+        }
+        ranges
+    }
+}
+
+/// Collects the positions of all nodes containing the cursor
+struct EnclosingPositions {
+    source: Source,
+    cursor: Position,
+    positions: Vec<SrcPos>,
+}
+
+impl EnclosingPositions {
+    fn add(&mut self, pos: &SrcPos) -> bool {
+        let is_inside = pos.contains(self.cursor);
+        if is_inside {
+            self.positions.push(pos.clone());
+        }
+        is_inside
+    }
+}
+
+impl Searcher for EnclosingPositions {
+    fn search_with_pos(&mut self, _ctx: &dyn TokenAccess, pos: &SrcPos) -> SearchState {
+        if self.add(pos) {
+            SearchState::NotFinished
+        } else {
+            // Nothing within the node can contain the cursor
+            SearchState::Finished(NotFound)
+        }
+    }
+
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        self.add(decl.pos());
+        if let Some(pos) = decl.end_ident_pos() {
+            self.add(pos);
+        }
+        SearchState::NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        _ref: &mut Reference,
+    ) -> SearchState {
+        self.add(pos);
+        SearchState::NotFinished
+    }
+
+    fn search_source(&mut self, _ctx: &dyn TokenAccess, source: &Source) -> SearchState {
+        if source == &self.source {
+            SearchState::NotFinished
+        } else {
+            SearchState::Finished(NotFound)
+        }
+    }
+}
@@ -89,9 +89,10 @@ impl<'a> AnalyzeContext<'a> {
                     if let Some((elem_type, _)) = typ.array_type() {
                         Ok(elem_type)
                     } else {
-                        Err(Diagnostic::error(
+                        Err(Diagnostic::new(
                             pos,
                             format!("array type expected for '{attr} attribute",),
+                            ErrorCode::IllegalAttribute,
                         )
                         .into())
                     }
@@ -185,7 +186,7 @@ impl<'a> AnalyzeContext<'a> {
 
                         if !ent.is_procedure() {
                             let mut diagnostic =
-                                Diagnostic::error(&name.pos, "Invalid procedure call");
+                                Diagnostic::mismatched_kinds(&name.pos, "Invalid procedure call");
                             for ent in names.sorted_entities() {
                                 if let Some(decl_pos) = ent.decl_pos() {
                                     diagnostic.add_related(
@@ -219,7 +220,7 @@ impl<'a> AnalyzeContext<'a> {
                         diagnostics,
                     )?;
                 } else {
-                    diagnostics.push(Diagnostic::error(
+                    diagnostics.push(Diagnostic::mismatched_kinds(
                         &name.pos,
                         format!("{} is not a procedure", resolved.describe_type()),
                     ));
@@ -227,7 +228,7 @@ impl<'a> AnalyzeContext<'a> {
                 }
             }
             resolved => {
-                diagnostics.push(Diagnostic::error(
+                diagnostics.push(Diagnostic::mismatched_kinds(
                     &name.pos,
                     format!("{} is not a procedure", resolved.describe_type()),
                 ));
@@ -273,7 +274,7 @@ impl Diagnostic {
 
 impl<'a> AnyEnt<'a> {
     pub(super) fn kind_error(&self, pos: &SrcPos, expected: &str) -> Diagnostic {
-        let mut error = Diagnostic::error(
+        let mut error = Diagnostic::mismatched_kinds(
             pos,
             format!("Expected {}, got {}", expected, self.describe()),
         );
@@ -286,9 +287,10 @@ impl<'a> AnyEnt<'a> {
 
 impl Diagnostic {
     pub(super) fn type_mismatch(pos: &SrcPos, desc: &str, expected_type: TypeEnt) -> Diagnostic {
-        Diagnostic::error(
+        Diagnostic::new(
             pos,
             format!("{} does not match {}", desc, expected_type.describe()),
+            ErrorCode::TypeMismatch,
         )
     }
 
@@ -296,7 +298,7 @@ impl Diagnostic {
         named_entity: &AnyEnt,
         prefix: &SrcPos,
     ) -> Diagnostic {
-        Diagnostic::error(
+        Diagnostic::mismatched_kinds(
             prefix,
             capitalize(&format!(
                 "{} may not be the prefix of a selected name",
@@ -310,13 +312,14 @@ impl Diagnostic {
         pos: &SrcPos,
         suffix: &Designator,
     ) -> Diagnostic {
-        Diagnostic::error(
+        Diagnostic::new(
             pos,
             format!(
                 "No declaration of '{}' within {}",
                 suffix,
                 named_entity.describe(),
             ),
+            ErrorCode::Unresolved,
         )
     }
 }
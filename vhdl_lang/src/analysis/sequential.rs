@@ -117,22 +117,28 @@ impl<'a> AnalyzeContext<'a> {
                         if let Some(ref mut expression) = expression {
                             self.expr_with_ttyp(scope, ttyp, expression, diagnostics)?;
                         } else {
-                            diagnostics.error(
+                            diagnostics.add(
                                 &statement.statement.pos,
                                 "Functions cannot return without a value",
+                                ErrorCode::IllegalReturn,
                             );
                         }
                     }
                     SequentialRoot::Procedure => {
                         if expression.is_some() {
-                            diagnostics.error(
+                            diagnostics.add(
                                 &statement.statement.pos,
                                 "Procedures cannot return a value",
+                                ErrorCode::IllegalReturn,
                             );
                         }
                     }
                     SequentialRoot::Process => {
-                        diagnostics.error(&statement.statement.pos, "Cannot return from a process");
+                        diagnostics.add(
+                            &statement.statement.pos,
+                            "Cannot return from a process",
+                            ErrorCode::IllegalReturn,
+                        );
                     }
                 }
             }
@@ -180,9 +186,10 @@ impl<'a> AnalyzeContext<'a> {
                 if let Some(loop_label) = loop_label {
                     self.check_loop_label(scope, parent, loop_label, diagnostics);
                 } else if !find_outer_loop(parent, None) {
-                    diagnostics.error(
+                    diagnostics.add(
                         &statement.statement.pos,
                         "Exit can only be used inside a loop",
+                        ErrorCode::IllegalLoopControl,
                     )
                 }
 
@@ -199,9 +206,10 @@ impl<'a> AnalyzeContext<'a> {
                 if let Some(loop_label) = loop_label {
                     self.check_loop_label(scope, parent, loop_label, diagnostics);
                 } else if !find_outer_loop(parent, None) {
-                    diagnostics.error(
+                    diagnostics.add(
                         &statement.statement.pos,
                         "Next can only be used inside a loop",
+                        ErrorCode::IllegalLoopControl,
                     )
                 }
 
@@ -330,24 +338,27 @@ impl<'a> AnalyzeContext<'a> {
                 label.set_unique_reference(ent);
                 if matches!(ent.kind(), AnyEntKind::Sequential(Some(Sequential::Loop))) {
                     if !find_outer_loop(parent, Some(label.item.name())) {
-                        diagnostics.error(
+                        diagnostics.add(
                             &label.item.pos,
                             format!("Cannot be used outside of loop '{}'", ent.designator()),
+                            ErrorCode::IllegalLoopControl,
                         );
                     }
                 } else {
-                    diagnostics.error(
+                    diagnostics.add(
                         &label.item.pos,
                         format!("Expected loop label, got {}", ent.describe()),
+                        ErrorCode::MismatchedKinds,
                     );
                 }
             }
-            Ok(NamedEntities::Overloaded(_)) => diagnostics.error(
+            Ok(NamedEntities::Overloaded(_)) => diagnostics.add(
                 &label.item.pos,
                 format!(
                     "Expected loop label, got overloaded name {}",
                     &label.item.item
                 ),
+                ErrorCode::MismatchedKinds,
             ),
             Err(diag) => {
                 diagnostics.push(diag);
@@ -0,0 +1,508 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Signatures of the subprogram call or the map aspect surrounding the cursor
+
+use super::completion::{tokenize_input, MapAspectKind};
+use super::named_entity::{OverloadedEnt, Signature};
+use super::region::NamedEntities;
+use super::root::{DesignRoot, LockedUnit};
+use super::visibility::Visible;
+use super::HasEntityId;
+use crate::ast::search::FindAllEnt;
+use crate::ast::{Designator, HasUnitId, ObjectClass};
+use crate::data::*;
+use crate::syntax::Kind::*;
+use crate::syntax::{Token, Value};
+use crate::{AnyEntKind, Design, EntRef, Overloaded};
+use std::ops::Range;
+
+/// The signatures that apply to the call or map aspect at the cursor
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SignatureHelp {
+    pub signatures: Vec<SignatureLabel>,
+    /// The index of the first signature that has the parameter at the cursor
+    pub active_signature: usize,
+}
+
+/// A subprogram signature or the generic or port list of an instantiated unit
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SignatureLabel {
+    /// Such as `function f(arg : in natural) return natural`
+    pub label: String,
+    /// The byte range of every parameter within the label
+    pub parameters: Vec<Range<usize>>,
+    /// The parameter at the cursor, either by position or by named association
+    pub active_parameter: Option<usize>,
+}
+
+/// A signature together with the names of its parameters
+struct Candidate {
+    signature: SignatureLabel,
+    names: Vec<Option<Designator>>,
+}
+
+impl Candidate {
+    fn new(prefix: String, parameters: Vec<(Option<Designator>, String)>, suffix: &str) -> Self {
+        let mut label = prefix;
+        label.push('(');
+        let mut ranges = Vec::with_capacity(parameters.len());
+        let mut names = Vec::with_capacity(parameters.len());
+        for (idx, (name, parameter)) in parameters.into_iter().enumerate() {
+            if idx > 0 {
+                label.push_str("; ");
+            }
+            ranges.push(label.len()..label.len() + parameter.len());
+            label.push_str(&parameter);
+            names.push(name);
+        }
+        label.push(')');
+        label.push_str(suffix);
+        Candidate {
+            signature: SignatureLabel {
+                label,
+                parameters: ranges,
+                active_parameter: None,
+            },
+            names,
+        }
+    }
+}
+
+/// The parameter being typed
+enum ActiveParameter {
+    Position(usize),
+    Named(Designator),
+}
+
+impl DesignRoot {
+    /// The signatures of the subprogram call or the generic or port map aspect that
+    /// surrounds the cursor. For a subprogram call all overloads visible in the design
+    /// unit are listed. The source is tokenized according to the VHDL revision of its library.
+    pub fn signature_help(
+        &self,
+        source: &Source,
+        cursor: Position,
+        standard: VHDLStandard,
+    ) -> Option<SignatureHelp> {
+        let tokens = tokenize_input(&self.symbols, source, cursor, standard);
+        let open_idx = unclosed_parenthesis(&tokens)?;
+        let active = active_parameter(&tokens[open_idx + 1..]);
+        let unit = self.unit_at(source, cursor)?;
+        let header = &tokens[..open_idx];
+
+        let candidates = match header {
+            [.., Token {
+                kind: kind @ (Generic | Port),
+                ..
+            }, Token { kind: Map, .. }] => {
+                let kind = if *kind == Generic {
+                    MapAspectKind::Generic
+                } else {
+                    MapAspectKind::Port
+                };
+                vec![self.map_aspect_signature(unit, &header[..header.len() - 2], kind)?]
+            }
+            [.., Token {
+                kind: Identifier,
+                value: Value::Identifier(name),
+                ..
+            }] => self.subprogram_signatures(unit, header, name),
+            _ => return None,
+        };
+        if candidates.is_empty() {
+            return None;
+        }
+
+        let mut active_signature = None;
+        let mut signatures = Vec::with_capacity(candidates.len());
+        for (idx, candidate) in candidates.into_iter().enumerate() {
+            let mut signature = candidate.signature;
+            signature.active_parameter = match active {
+                ActiveParameter::Position(position) => {
+                    Some(position).filter(|position| *position < candidate.names.len())
+                }
+                ActiveParameter::Named(ref name) => candidate
+                    .names
+                    .iter()
+                    .position(|formal| formal.as_ref() == Some(name)),
+            };
+            if active_signature.is_none() && signature.active_parameter.is_some() {
+                active_signature = Some(idx);
+            }
+            signatures.push(signature);
+        }
+
+        Some(SignatureHelp {
+            signatures,
+            active_signature: active_signature.unwrap_or(0),
+        })
+    }
+
+    /// The generics or ports of the entity, component or package instantiated
+    /// by the statement ending with `header`
+    fn map_aspect_signature(
+        &self,
+        unit: &LockedUnit,
+        header: &[Token],
+        kind: MapAspectKind,
+    ) -> Option<Candidate> {
+        let mut end = header.len();
+        // The port map follows the generic map
+        if kind == MapAspectKind::Port && header.last()?.kind == RightPar {
+            let open = matching_parenthesis(header)?;
+            if matches!(
+                header[..open],
+                [.., Token { kind: Generic, .. }, Token { kind: Map, .. }]
+            ) {
+                end = open - 2;
+            }
+        }
+        // The architecture of an entity instantiation
+        if header[..end].last()?.kind == RightPar {
+            end = matching_parenthesis(&header[..end])?;
+        }
+
+        let mut names = Vec::new();
+        let mut start = end;
+        while let Some(Token {
+            kind: Identifier,
+            value: Value::Identifier(name),
+            ..
+        }) = start.checked_sub(1).map(|idx| &header[idx])
+        {
+            names.insert(0, name);
+            start -= 1;
+            if start > 0 && header[start - 1].kind == Dot {
+                start -= 1;
+            } else {
+                break;
+            }
+        }
+
+        let unit_library = unit.unit_id().library_name();
+        let (keyword, ent) = match (start.checked_sub(1).map(|idx| &header[idx]), &names[..]) {
+            (Some(Token { kind: Entity, .. }), [library_name, name]) => (
+                "entity",
+                self.primary_unit_ent(library_name, name, unit_library)?,
+            ),
+            (Some(Token { kind: New, .. }), [library_name, name]) => (
+                "package",
+                self.primary_unit_ent(library_name, name, unit_library)?,
+            ),
+            (
+                Some(Token {
+                    kind: Component | Colon,
+                    ..
+                }),
+                [.., name],
+            ) => ("component", self.find_component(unit, name)?),
+            _ => return None,
+        };
+
+        let parameters = self
+            .extract_port_or_generic_declarations(ent.id(), kind)
+            .iter()
+            .map(|decl| {
+                let name = decl
+                    .ent_id()
+                    .map(|id| self.get_ent(id).designator().clone());
+                (name, decl.to_string())
+            })
+            .collect();
+        let list = match kind {
+            MapAspectKind::Generic => "generic",
+            MapAspectKind::Port => "port",
+        };
+        let name = match keyword {
+            "component" => ent.designator().to_string(),
+            _ => format!("{}.{}", ent.library_name()?, ent.designator()),
+        };
+        Some(Candidate::new(
+            format!("{keyword} {name} {list} "),
+            parameters,
+            "",
+        ))
+    }
+
+    /// The primary unit `library_name.name` where `work` is the library of the design unit
+    fn primary_unit_ent(
+        &self,
+        library_name: &Symbol,
+        name: &Symbol,
+        unit_library: &Symbol,
+    ) -> Option<EntRef<'_>> {
+        let library_name = if library_name == &self.symbol_utf8("work") {
+            unit_library
+        } else {
+            library_name
+        };
+        self.get_primary_unit_ent(library_name, name)
+            .map(EntRef::from)
+    }
+
+    /// A component declaration named `name` preferring one in the source of the design unit
+    fn find_component(&self, unit: &LockedUnit, name: &Symbol) -> Option<EntRef<'_>> {
+        let source = unit.tokens.first()?.pos.source();
+        let mut searcher = FindAllEnt::new(self, |ent| {
+            matches!(ent.kind(), AnyEntKind::Component(_))
+                && matches!(ent.designator(), Designator::Identifier(ident) if ident == name)
+        });
+        let _ = self.search(&mut searcher);
+        let mut components = searcher.result;
+        components.sort_by_key(|ent| ent.decl_pos().map(|pos| pos.source()) != Some(source));
+        components.first().copied()
+    }
+
+    /// The signatures of the subprograms named `name` that are declared in the design unit,
+    /// in its primary unit or made visible by use clauses
+    fn subprogram_signatures(
+        &self,
+        unit: &LockedUnit,
+        header: &[Token],
+        name: &Symbol,
+    ) -> Vec<Candidate> {
+        let designator = Designator::Identifier(name.clone());
+        let unit_library = unit.unit_id().library_name();
+        let mut candidates: Vec<EntRef<'_>> = Vec::new();
+
+        match header {
+            // A selected name such as `lib.pkg.name` or `pkg.name`
+            [.., prefix @ Token {
+                kind: Identifier, ..
+            }, Token { kind: Dot, .. }, _] => {
+                let Value::Identifier(ref package_name) = prefix.value else {
+                    return Vec::new();
+                };
+                let library_name = match header {
+                    [.., Token {
+                        kind: Identifier,
+                        value: Value::Identifier(library_name),
+                        ..
+                    }, Token { kind: Dot, .. }, _, _, _] => Some(library_name),
+                    _ => None,
+                };
+                let package = match library_name {
+                    Some(library_name) => {
+                        self.primary_unit_ent(library_name, package_name, unit_library)
+                    }
+                    None => std::iter::once(unit_library)
+                        .chain(self.available_libraries())
+                        .find_map(|library_name| {
+                            self.get_primary_unit_ent(library_name, package_name)
+                        })
+                        .map(EntRef::from),
+                };
+                if let Some(package) = package {
+                    add_declared_in(package, &designator, &mut candidates);
+                }
+            }
+            _ => {
+                // Declared within the design unit itself
+                let (Some(first), Some(last)) = (unit.tokens.first(), unit.tokens.last()) else {
+                    return Vec::new();
+                };
+                let unit_pos = first.pos.combine(&last.pos);
+                let mut searcher = FindAllEnt::new(self, |ent| {
+                    ent.designator() == &designator
+                        && ent.decl_pos().is_some_and(|pos| {
+                            pos.source() == unit_pos.source() && unit_pos.contains(pos.start())
+                        })
+                });
+                let _ = self.search(&mut searcher);
+                candidates.extend(searcher.result);
+
+                // Declared in or made visible by the primary unit
+                if let Some(primary) =
+                    self.get_primary_unit_ent(unit_library, unit.unit_id().primary_name())
+                {
+                    let primary = EntRef::from(primary);
+                    add_declared_in(primary, &designator, &mut candidates);
+                    if let AnyEntKind::Design(
+                        Design::Entity(visibility, _)
+                        | Design::Package(visibility, _)
+                        | Design::UninstPackage(visibility, _),
+                    ) = primary.kind()
+                    {
+                        let mut visible = Visible::default();
+                        visibility.lookup_into(&designator, &mut visible);
+                        candidates.extend(visible.entities());
+                    }
+                }
+
+                // Made visible by the use clauses of the design unit
+                for (library_name, package_name) in use_clauses(&unit.tokens, name) {
+                    if let Some(package) =
+                        self.primary_unit_ent(library_name, package_name, unit_library)
+                    {
+                        add_declared_in(package, &designator, &mut candidates);
+                    }
+                }
+            }
+        }
+
+        let mut subprograms: Vec<OverloadedEnt<'_>> = candidates
+            .into_iter()
+            .map(|ent| ent.declaration())
+            .filter_map(OverloadedEnt::from_any)
+            .filter(|ent| !matches!(ent.kind(), Overloaded::EnumLiteral(_)))
+            .collect();
+        subprograms.sort_by(|a, b| a.decl_pos().cmp(&b.decl_pos()));
+        subprograms.dedup_by_key(|ent| ent.id());
+
+        subprograms
+            .into_iter()
+            .map(|ent| subprogram_candidate(ent.designator(), ent.signature()))
+            .collect()
+    }
+}
+
+/// Add the named entities declared in the region of a package, entity or package instance
+fn add_declared_in<'a>(
+    unit: EntRef<'a>,
+    designator: &Designator,
+    candidates: &mut Vec<EntRef<'a>>,
+) {
+    let region = match unit.kind() {
+        AnyEntKind::Design(
+            Design::Entity(_, region)
+            | Design::Package(_, region)
+            | Design::UninstPackage(_, region)
+            | Design::PackageInstance(region),
+        ) => region,
+        _ => return,
+    };
+    match region.lookup_immediate(designator) {
+        Some(NamedEntities::Single(ent)) => candidates.push(ent),
+        Some(NamedEntities::Overloaded(overloaded)) => {
+            candidates.extend(overloaded.entities().map(EntRef::from))
+        }
+        None => {}
+    }
+}
+
+fn subprogram_candidate(designator: &Designator, signature: &Signature) -> Candidate {
+    let parameters = signature
+        .formals
+        .iter()
+        .map(|formal| {
+            let mut parameter = String::new();
+            match formal.kind() {
+                AnyEntKind::Object(object) => {
+                    if object.class != ObjectClass::Constant {
+                        parameter.push_str(&format!("{} ", object.class));
+                    }
+                    parameter.push_str(&format!("{} : ", formal.designator()));
+                    if let Some(mode) = object.mode() {
+                        parameter.push_str(&format!("{mode} "));
+                    }
+                }
+                _ => parameter.push_str(&format!("file {} : ", formal.designator())),
+            }
+            parameter.push_str(&formal.type_mark().designator().to_string());
+            (Some(formal.designator().clone()), parameter)
+        })
+        .collect();
+
+    match signature.return_type() {
+        Some(return_type) => Candidate::new(
+            format!("function {designator}"),
+            parameters,
+            &format!(" return {}", return_type.designator()),
+        ),
+        None => Candidate::new(format!("procedure {designator}"), parameters, ""),
+    }
+}
+
+/// The packages of the use clauses `use lib.pkg.all` and `use lib.pkg.name`
+fn use_clauses<'t>(tokens: &'t [Token], name: &Symbol) -> Vec<(&'t Symbol, &'t Symbol)> {
+    tokens
+        .windows(7)
+        .filter_map(|window| match window {
+            [Token { kind: Use, .. }, Token {
+                kind: Identifier,
+                value: Value::Identifier(library_name),
+                ..
+            }, Token { kind: Dot, .. }, Token {
+                kind: Identifier,
+                value: Value::Identifier(package_name),
+                ..
+            }, Token { kind: Dot, .. }, suffix, Token {
+                kind: SemiColon | Comma,
+                ..
+            }] => match (&suffix.kind, &suffix.value) {
+                (All, _) => Some((library_name, package_name)),
+                (Identifier, Value::Identifier(suffix)) if suffix == name => {
+                    Some((library_name, package_name))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// The index of the left parenthesis that is not closed before the cursor
+fn unclosed_parenthesis(tokens: &[Token]) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().rev() {
+        match token.kind {
+            RightPar => depth += 1,
+            LeftPar if depth == 0 => return Some(idx),
+            LeftPar => depth -= 1,
+            // A statement or declaration ended before the cursor
+            SemiColon => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The index of the left parenthesis matching the right parenthesis ending `tokens`
+fn matching_parenthesis(tokens: &[Token]) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().rev() {
+        match token.kind {
+            RightPar => depth += 1,
+            LeftPar => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The parameter being typed given the tokens after the left parenthesis
+fn active_parameter(tokens: &[Token]) -> ActiveParameter {
+    let mut depth = 0;
+    let mut position = 0;
+    let mut start = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        match token.kind {
+            LeftPar => depth += 1,
+            RightPar => depth -= 1,
+            Comma if depth == 0 => {
+                position += 1;
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    match tokens[start..] {
+        [Token {
+            kind: Identifier,
+            value: Value::Identifier(ref name),
+            ..
+        }, Token {
+            kind: RightArrow, ..
+        }, ..] => ActiveParameter::Named(Designator::Identifier(name.clone())),
+        _ => ActiveParameter::Position(position),
+    }
+}
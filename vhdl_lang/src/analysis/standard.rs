@@ -10,6 +10,7 @@ use crate::ast::Mode;
 use crate::ast::ObjectClass;
 use crate::ast::Operator;
 use crate::data::DiagnosticHandler;
+use crate::data::VHDLStandard;
 use crate::syntax::Symbols;
 
 use super::analyze::AnalyzeContext;
@@ -453,6 +454,7 @@ impl<'a> AnalyzeContext<'a> {
                             iface: Some(ObjectInterface::Parameter(Mode::Out)),
                             subtype: Subtype::new(type_mark),
                             has_default: false,
+                            signal_kind: None,
                         }),
                     ),
                 ],
@@ -537,6 +539,7 @@ impl<'a> AnalyzeContext<'a> {
                     iface: Some(ObjectInterface::Parameter(Mode::InOut)),
                     subtype: Subtype::new(type_ent.to_owned()),
                     has_default: false,
+                    signal_kind: None,
                 }),
             )],
             None,
@@ -544,6 +547,15 @@ impl<'a> AnalyzeContext<'a> {
         .into()
     }
 
+    /// Remove the implicit declarations that do not exist in the revision of the current unit
+    fn in_standard(
+        &self,
+        implicits: impl Iterator<Item = EntRef<'a>>,
+    ) -> impl Iterator<Item = EntRef<'a>> {
+        let is_vhdl2008 = self.standard >= VHDLStandard::VHDL2008;
+        implicits.filter(move |ent| is_vhdl2008 || !is_added_in_vhdl2008(ent))
+    }
+
     pub fn comparison(&self, op: Operator, typ: TypeEnt<'a>) -> EntRef<'a> {
         self.binary(op, typ, typ, typ, self.boolean())
     }
@@ -567,38 +579,40 @@ impl<'a> AnalyzeContext<'a> {
     ) -> impl Iterator<Item = EntRef<'a>> {
         let integer = self.integer();
 
-        [
-            self.minimum(typ),
-            self.maximum(typ),
-            self.create_to_string(typ),
-            self.symmetric_unary(Operator::Minus, typ),
-            self.symmetric_unary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Minus, typ),
-            // 9.2.7 Multiplying operators
-            self.symmetric_binary(Operator::Times, typ),
-            self.symmetric_binary(Operator::Div, typ),
-            // 9.2.8 Miscellaneous operators
-            self.symmetric_unary(Operator::Abs, typ),
-            self.binary(Operator::Pow, typ, typ, integer, typ),
-        ]
-        .into_iter()
-        .chain(
-            if kind == UniversalType::Integer {
-                Some(
-                    [
-                        self.symmetric_binary(Operator::Mod, typ),
-                        self.symmetric_binary(Operator::Rem, typ),
-                    ]
-                    .into_iter(),
-                )
-            } else {
-                None
-            }
+        self.in_standard(
+            [
+                self.minimum(typ),
+                self.maximum(typ),
+                self.create_to_string(typ),
+                self.symmetric_unary(Operator::Minus, typ),
+                self.symmetric_unary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Minus, typ),
+                // 9.2.7 Multiplying operators
+                self.symmetric_binary(Operator::Times, typ),
+                self.symmetric_binary(Operator::Div, typ),
+                // 9.2.8 Miscellaneous operators
+                self.symmetric_unary(Operator::Abs, typ),
+                self.binary(Operator::Pow, typ, typ, integer, typ),
+            ]
             .into_iter()
-            .flatten(),
+            .chain(
+                if kind == UniversalType::Integer {
+                    Some(
+                        [
+                            self.symmetric_binary(Operator::Mod, typ),
+                            self.symmetric_binary(Operator::Rem, typ),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                }
+                .into_iter()
+                .flatten(),
+            )
+            .chain(self.comparators(typ)),
         )
-        .chain(self.comparators(typ))
     }
 
     pub fn universal_implicits(
@@ -606,77 +620,81 @@ impl<'a> AnalyzeContext<'a> {
         kind: UniversalType,
         typ: TypeEnt<'a>,
     ) -> impl Iterator<Item = EntRef<'a>> {
-        [
-            self.minimum(typ),
-            self.maximum(typ),
-            self.create_to_string(typ),
-            self.symmetric_unary(Operator::Minus, typ),
-            self.symmetric_unary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Minus, typ),
-            // 9.2.7 Multiplying operators
-            self.symmetric_binary(Operator::Times, typ),
-            self.symmetric_binary(Operator::Div, typ),
-            // 9.2.8 Miscellaneous operators
-            self.symmetric_unary(Operator::Abs, typ),
-            self.binary(
-                Operator::Pow,
-                typ,
-                typ,
-                self.universal_integer().into(),
-                typ,
-            ),
-        ]
-        .into_iter()
-        .chain(
-            if kind == UniversalType::Integer {
-                Some(
-                    [
-                        self.symmetric_binary(Operator::Mod, typ),
-                        self.symmetric_binary(Operator::Rem, typ),
-                    ]
-                    .into_iter(),
-                )
-            } else {
-                None
-            }
+        self.in_standard(
+            [
+                self.minimum(typ),
+                self.maximum(typ),
+                self.create_to_string(typ),
+                self.symmetric_unary(Operator::Minus, typ),
+                self.symmetric_unary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Minus, typ),
+                // 9.2.7 Multiplying operators
+                self.symmetric_binary(Operator::Times, typ),
+                self.symmetric_binary(Operator::Div, typ),
+                // 9.2.8 Miscellaneous operators
+                self.symmetric_unary(Operator::Abs, typ),
+                self.binary(
+                    Operator::Pow,
+                    typ,
+                    typ,
+                    self.universal_integer().into(),
+                    typ,
+                ),
+            ]
             .into_iter()
-            .flatten(),
+            .chain(
+                if kind == UniversalType::Integer {
+                    Some(
+                        [
+                            self.symmetric_binary(Operator::Mod, typ),
+                            self.symmetric_binary(Operator::Rem, typ),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                }
+                .into_iter()
+                .flatten(),
+            )
+            .chain(self.comparators(typ)),
         )
-        .chain(self.comparators(typ))
     }
 
     pub fn physical_implicits(&self, typ: TypeEnt<'a>) -> impl Iterator<Item = EntRef<'a>> {
         let integer = self.integer();
         let real = self.real();
 
-        [
-            self.minimum(typ),
-            self.maximum(typ),
-            self.symmetric_unary(Operator::Minus, typ),
-            self.symmetric_unary(Operator::Plus, typ),
-            self.symmetric_unary(Operator::Abs, typ),
-            self.symmetric_binary(Operator::Plus, typ),
-            self.symmetric_binary(Operator::Minus, typ),
-            // 9.2.7 Multiplying operators
-            self.binary(Operator::Times, typ, typ, integer, typ),
-            self.binary(Operator::Times, typ, typ, real, typ),
-            self.binary(Operator::Times, typ, integer, typ, typ),
-            self.binary(Operator::Times, typ, real, typ, typ),
-            self.binary(Operator::Div, typ, typ, integer, typ),
-            self.binary(Operator::Div, typ, typ, real, typ),
-            self.binary(
-                Operator::Div,
-                typ,
-                typ,
-                typ,
-                self.universal_integer().into(),
-            ),
-            self.symmetric_binary(Operator::Mod, typ),
-            self.symmetric_binary(Operator::Rem, typ),
-        ]
-        .into_iter()
-        .chain(self.comparators(typ))
+        self.in_standard(
+            [
+                self.minimum(typ),
+                self.maximum(typ),
+                self.symmetric_unary(Operator::Minus, typ),
+                self.symmetric_unary(Operator::Plus, typ),
+                self.symmetric_unary(Operator::Abs, typ),
+                self.symmetric_binary(Operator::Plus, typ),
+                self.symmetric_binary(Operator::Minus, typ),
+                // 9.2.7 Multiplying operators
+                self.binary(Operator::Times, typ, typ, integer, typ),
+                self.binary(Operator::Times, typ, typ, real, typ),
+                self.binary(Operator::Times, typ, integer, typ, typ),
+                self.binary(Operator::Times, typ, real, typ, typ),
+                self.binary(Operator::Div, typ, typ, integer, typ),
+                self.binary(Operator::Div, typ, typ, real, typ),
+                self.binary(
+                    Operator::Div,
+                    typ,
+                    typ,
+                    typ,
+                    self.universal_integer().into(),
+                ),
+                self.symmetric_binary(Operator::Mod, typ),
+                self.symmetric_binary(Operator::Rem, typ),
+            ]
+            .into_iter()
+            .chain(self.comparators(typ)),
+        )
     }
 
     pub fn enum_implicits(
@@ -684,31 +702,33 @@ impl<'a> AnalyzeContext<'a> {
         typ: TypeEnt<'a>,
         matching_op: bool,
     ) -> impl Iterator<Item = EntRef<'a>> {
-        [
-            self.create_to_string(typ),
-            self.minimum(typ),
-            self.maximum(typ),
-        ]
-        .into_iter()
-        .chain(self.comparators(typ))
-        .chain(
-            if matching_op {
-                Some(
-                    [
-                        self.symmetric_binary(Operator::QueEQ, typ),
-                        self.symmetric_binary(Operator::QueNE, typ),
-                        self.symmetric_binary(Operator::QueGT, typ),
-                        self.symmetric_binary(Operator::QueGTE, typ),
-                        self.symmetric_binary(Operator::QueLT, typ),
-                        self.symmetric_binary(Operator::QueLTE, typ),
-                    ]
-                    .into_iter(),
-                )
-            } else {
-                None
-            }
+        self.in_standard(
+            [
+                self.create_to_string(typ),
+                self.minimum(typ),
+                self.maximum(typ),
+            ]
             .into_iter()
-            .flatten(),
+            .chain(self.comparators(typ))
+            .chain(
+                if matching_op {
+                    Some(
+                        [
+                            self.symmetric_binary(Operator::QueEQ, typ),
+                            self.symmetric_binary(Operator::QueNE, typ),
+                            self.symmetric_binary(Operator::QueGT, typ),
+                            self.symmetric_binary(Operator::QueGTE, typ),
+                            self.symmetric_binary(Operator::QueLT, typ),
+                            self.symmetric_binary(Operator::QueLTE, typ),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                }
+                .into_iter()
+                .flatten(),
+            ),
         )
     }
 
@@ -772,63 +792,65 @@ impl<'a> AnalyzeContext<'a> {
         let is_one_dimensional = indexes.len() == 1;
         let is_character_elem = matches!(elem_type.base().kind(), Type::Enum(designators) if designators.iter().all(|des| matches!(des, Designator::Character(_))));
 
-        [
-            self.comparison(Operator::EQ, typ),
-            self.comparison(Operator::NE, typ),
-        ]
-        .into_iter()
-        .chain(if is_one_dimensional && is_character_elem {
-            // To string is only defined for 1d array types with character elements
-            Some(self.create_to_string(typ)).into_iter()
-        } else {
-            None.into_iter()
-        })
-        .chain(
-            (if is_one_dimensional {
-                Some(self.concatenations(typ, *elem_type))
-            } else {
-                None
-            })
+        self.in_standard(
+            [
+                self.comparison(Operator::EQ, typ),
+                self.comparison(Operator::NE, typ),
+            ]
             .into_iter()
-            .flatten(),
-        )
-        .chain(
-            (if is_scalar {
-                Some(
-                    [
-                        self.comparison(Operator::GT, typ),
-                        self.comparison(Operator::GTE, typ),
-                        self.comparison(Operator::LT, typ),
-                        self.comparison(Operator::LTE, typ),
-                        self.elementwise_min_or_maximum("MINIMUM", typ, *elem_type),
-                        self.elementwise_min_or_maximum("MAXIMUM", typ, *elem_type),
-                    ]
-                    .into_iter(),
-                )
+            .chain(if is_one_dimensional && is_character_elem {
+                // To string is only defined for 1d array types with character elements
+                Some(self.create_to_string(typ)).into_iter()
             } else {
-                None
+                None.into_iter()
             })
-            .into_iter()
-            .flatten(),
-        )
-        .chain(
-            if matching_op {
-                Some(
-                    [
-                        self.binary(Operator::QueEQ, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueNE, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueGT, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueGTE, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueLT, typ, typ, typ, *elem_type),
-                        self.binary(Operator::QueLTE, typ, typ, typ, *elem_type),
-                    ]
-                    .into_iter(),
-                )
-            } else {
-                None
-            }
-            .into_iter()
-            .flatten(),
+            .chain(
+                (if is_one_dimensional {
+                    Some(self.concatenations(typ, *elem_type))
+                } else {
+                    None
+                })
+                .into_iter()
+                .flatten(),
+            )
+            .chain(
+                (if is_scalar {
+                    Some(
+                        [
+                            self.comparison(Operator::GT, typ),
+                            self.comparison(Operator::GTE, typ),
+                            self.comparison(Operator::LT, typ),
+                            self.comparison(Operator::LTE, typ),
+                            self.elementwise_min_or_maximum("MINIMUM", typ, *elem_type),
+                            self.elementwise_min_or_maximum("MAXIMUM", typ, *elem_type),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                })
+                .into_iter()
+                .flatten(),
+            )
+            .chain(
+                if matching_op {
+                    Some(
+                        [
+                            self.binary(Operator::QueEQ, typ, typ, typ, *elem_type),
+                            self.binary(Operator::QueNE, typ, typ, typ, *elem_type),
+                            self.binary(Operator::QueGT, typ, typ, typ, *elem_type),
+                            self.binary(Operator::QueGTE, typ, typ, typ, *elem_type),
+                            self.binary(Operator::QueLT, typ, typ, typ, *elem_type),
+                            self.binary(Operator::QueLTE, typ, typ, typ, *elem_type),
+                        ]
+                        .into_iter(),
+                    )
+                } else {
+                    None
+                }
+                .into_iter()
+                .flatten(),
+            ),
         )
     }
 
@@ -847,7 +869,9 @@ impl<'a> AnalyzeContext<'a> {
         region: &mut Region<'a>,
         diagnostics: &mut dyn DiagnosticHandler,
     ) {
-        {
+        let is_vhdl2008 = self.standard >= VHDLStandard::VHDL2008;
+
+        if is_vhdl2008 {
             let time = self.time();
             let to_string = self.create_to_string(time);
 
@@ -909,6 +933,14 @@ impl<'a> AnalyzeContext<'a> {
                     self.binary(op, atyp, styp, atyp, atyp),
                 ]
                 .into_iter()
+                // The reduction and the array-scalar operations were added in VHDL-2008
+                .take(if is_vhdl2008 {
+                    4
+                } else if op == Operator::Not {
+                    2
+                } else {
+                    1
+                })
             });
 
             for ent in implicits {
@@ -920,6 +952,10 @@ impl<'a> AnalyzeContext<'a> {
             }
         }
 
+        if !is_vhdl2008 {
+            return;
+        }
+
         // Predefined overloaded TO_STRING operations
         // function TO_STRING (VALUE: REAL; DIGITS: NATURAL) return STRING;
         {
@@ -1090,3 +1126,23 @@ impl<'a> AnalyzeContext<'a> {
         }
     }
 }
+
+/// The implicit declarations that were added in VHDL-2008
+pub(super) fn is_added_in_vhdl2008(ent: EntRef<'_>) -> bool {
+    match ent.designator() {
+        Designator::Identifier(name) => ["TO_STRING", "MINIMUM", "MAXIMUM"]
+            .iter()
+            .any(|added| name.name_utf8().eq_ignore_ascii_case(added)),
+        Designator::OperatorSymbol(op) => matches!(
+            op,
+            Operator::QueQue
+                | Operator::QueEQ
+                | Operator::QueNE
+                | Operator::QueLT
+                | Operator::QueLTE
+                | Operator::QueGT
+                | Operator::QueGTE
+        ),
+        _ => false,
+    }
+}
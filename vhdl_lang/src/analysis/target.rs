@@ -43,24 +43,27 @@ impl<'a> AnalyzeContext<'a> {
             target_pos,
             target,
             "may not be the target of an assignment",
+            ErrorCode::IllegalTarget,
             diagnostics,
         )?;
         if !is_valid_assignment_target(&object_name.base) {
-            diagnostics.push(Diagnostic::error(
+            diagnostics.push(Diagnostic::new(
                 target_pos,
                 format!(
                     "{} may not be the target of an assignment",
                     object_name.base.describe_class()
                 ),
+                ErrorCode::IllegalTarget,
             ));
         } else if !is_valid_assignment_type(&object_name.base, assignment_type) {
-            diagnostics.push(Diagnostic::error(
+            diagnostics.push(Diagnostic::new(
                 target_pos,
                 format!(
                     "{} may not be the target of a {} assignment",
                     object_name.base.describe_class(),
                     assignment_type.to_str()
                 ),
+                ErrorCode::IllegalTarget,
             ));
         }
         Ok(object_name.type_mark())
@@ -0,0 +1,185 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::SrcPos;
+use crate::CallHierarchyCall;
+use pretty_assertions::assert_eq;
+
+fn describe(calls: Vec<CallHierarchyCall>) -> Vec<(String, Vec<SrcPos>)> {
+    calls
+        .into_iter()
+        .map(|call| (call.ent.designator().to_string(), call.ranges))
+        .collect()
+}
+
+#[test]
+fn incoming_and_outgoing_calls_of_subprograms() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function inc(arg : natural) return natural;
+  procedure prc;
+end package;
+
+package body pkg is
+  function inc(arg : natural) return natural is
+  begin
+    return arg + 1;
+  end function;
+
+  procedure prc is
+    variable v : natural;
+  begin
+    v := inc(0) + inc(1);
+  end procedure;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  s <= inc(2);
+
+  main : process
+  begin
+    prc;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let inc = root
+        .search_reference(code.source(), code.s1("inc").start())
+        .unwrap();
+    let prc = root
+        .search_reference(code.source(), code.s1("prc").start())
+        .unwrap();
+    let main = root
+        .search_reference(code.source(), code.s1("main").start())
+        .unwrap();
+
+    assert_eq!(
+        describe(root.incoming_calls(inc)),
+        vec![
+            (
+                "prc".to_owned(),
+                vec![code.s("inc", 3).pos(), code.s("inc", 4).pos()]
+            ),
+            ("a".to_owned(), vec![code.s("inc", 5).pos()]),
+        ]
+    );
+    assert_eq!(
+        describe(root.incoming_calls(prc)),
+        vec![("main".to_owned(), vec![code.s("prc", 3).pos()])]
+    );
+    // The body of a subprogram is equivalent to its declaration
+    let prc_body = root
+        .search_reference(code.source(), code.s("prc", 2).start())
+        .unwrap();
+    assert_eq!(
+        describe(root.outgoing_calls(prc_body)),
+        vec![(
+            "inc".to_owned(),
+            vec![code.s("inc", 3).pos(), code.s("inc", 4).pos()]
+        )]
+    );
+    assert_eq!(
+        describe(root.outgoing_calls(main)),
+        vec![("prc".to_owned(), vec![code.s("prc", 3).pos()])]
+    );
+}
+
+#[test]
+fn entities_instantiated_by_and_instantiating_entities() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity leaf is
+end entity;
+
+architecture a of leaf is
+begin
+end architecture;
+
+entity mid is
+end entity;
+
+architecture a of mid is
+begin
+  inst : entity work.leaf;
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+  component mid is
+  end component;
+begin
+  inst_mid : component mid;
+  inst_leaf : entity work.leaf(a);
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let leaf = root
+        .search_reference(code.source(), code.s1("leaf").start())
+        .unwrap();
+    let mid = root
+        .search_reference(code.source(), code.s1("mid").start())
+        .unwrap();
+    let top = root
+        .search_reference(code.source(), code.s1("top").start())
+        .unwrap();
+
+    assert_eq!(
+        describe(root.instantiated_by(leaf)),
+        vec![
+            (
+                "mid".to_owned(),
+                vec![code.s1("work.leaf;").s1("work.leaf").pos()]
+            ),
+            (
+                "top".to_owned(),
+                vec![code.s1("work.leaf(a)").s1("work.leaf").pos()]
+            ),
+        ]
+    );
+    assert_eq!(
+        describe(root.instantiates(top)),
+        vec![
+            (
+                "leaf".to_owned(),
+                vec![code.s1("work.leaf(a)").s1("work.leaf").pos()]
+            ),
+            (
+                "mid".to_owned(),
+                vec![code.s1("component mid;").s1("mid").pos()]
+            ),
+        ]
+    );
+    assert_eq!(
+        describe(root.instantiated_by(mid)),
+        vec![(
+            "top".to_owned(),
+            vec![code.s1("component mid;").s1("mid").pos()]
+        )]
+    );
+    assert!(root.instantiates(leaf).is_empty());
+}
@@ -0,0 +1,138 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::ast::{UnitId, UnitKey};
+use crate::{describe_unit, DependencyGraph};
+use pretty_assertions::assert_eq;
+
+fn get_graph(builder: &LibraryBuilder) -> (DesignRoot, DependencyGraph) {
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let graph = root.dependency_graph();
+    (root, graph)
+}
+
+fn unit_id(root: &DesignRoot, library: &str, key: UnitKey) -> UnitId {
+    root.unit_id(&root.symbol_utf8(library), &key).unwrap()
+}
+
+fn describe(units: Vec<&UnitId>) -> Vec<String> {
+    units.into_iter().map(describe_unit).collect()
+}
+
+fn code() -> LibraryBuilder {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "common",
+        "
+package pkg is
+  constant c : natural := 0;
+end package;
+
+package body pkg is
+end package body;
+",
+    );
+    builder.code(
+        "lib",
+        "
+library common;
+use common.pkg.all;
+
+entity leaf is
+end entity;
+
+architecture a of leaf is
+begin
+end architecture;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component leaf is
+  end component;
+begin
+  comp_inst: component leaf;
+end architecture;
+
+entity other is
+end entity;
+
+architecture a of other is
+begin
+  inst: entity work.top;
+end architecture;
+",
+    );
+    builder
+}
+
+#[test]
+fn direct_dependencies_and_users() {
+    let (root, graph) = get_graph(&code());
+    let leaf_a = unit_id(
+        &root,
+        "lib",
+        UnitKey::Secondary(root.symbol_utf8("leaf"), root.symbol_utf8("a")),
+    );
+    let pkg = unit_id(&root, "common", UnitKey::Primary(root.symbol_utf8("pkg")));
+
+    assert_eq!(describe(graph.dependencies_of(&leaf_a)), vec!["lib.leaf"]);
+    assert_eq!(
+        describe(graph.users_of(&pkg)),
+        vec!["common.pkg body", "lib.leaf"]
+    );
+}
+
+#[test]
+fn transitive_dependencies_include_component_bindings() {
+    let (root, graph) = get_graph(&code());
+    let top_rtl = unit_id(
+        &root,
+        "lib",
+        UnitKey::Secondary(root.symbol_utf8("top"), root.symbol_utf8("rtl")),
+    );
+
+    assert_eq!(
+        describe(graph.transitive_dependencies_of(&top_rtl)),
+        vec!["common.pkg", "lib.leaf", "lib.top", "std.standard"]
+    );
+}
+
+#[test]
+fn transitive_users_of_package() {
+    let (root, graph) = get_graph(&code());
+    let pkg = unit_id(&root, "common", UnitKey::Primary(root.symbol_utf8("pkg")));
+
+    assert_eq!(
+        describe(graph.transitive_users_of(&pkg)),
+        vec!["common.pkg body", "lib.leaf", "lib.leaf(a)", "lib.top(rtl)"]
+    );
+}
+
+#[test]
+fn dot_output_of_subgraph() {
+    let (root, graph) = get_graph(&code());
+    let pkg = unit_id(&root, "common", UnitKey::Primary(root.symbol_utf8("pkg")));
+    let body = unit_id(
+        &root,
+        "common",
+        UnitKey::Secondary(root.symbol_utf8("pkg"), root.symbol_utf8("pkg")),
+    );
+
+    assert_eq!(
+        graph.subgraph([&pkg, &body]).to_dot(),
+        "digraph dependencies {
+  node [shape=box];
+  \"common.pkg\";
+  \"common.pkg body\";
+  \"common.pkg body\" -> \"common.pkg\";
+}
+"
+    );
+}
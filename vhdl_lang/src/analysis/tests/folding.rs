@@ -0,0 +1,116 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::{FoldingRange, FoldingRangeKind};
+use pretty_assertions::assert_eq;
+
+fn folding_ranges(builder: &LibraryBuilder, code: &Code) -> Vec<(u32, u32, FoldingRangeKind)> {
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    root.folding_ranges(code.source())
+        .into_iter()
+        .map(
+            |FoldingRange {
+                 start_line,
+                 end_line,
+                 kind,
+             }| (start_line, end_line, kind),
+        )
+        .collect()
+}
+
+#[test]
+fn folds_units_statements_and_declarative_parts() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+library std;
+use std.standard.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+  signal t : natural;
+begin
+  proc: process
+    variable v : natural;
+    variable w : natural;
+  begin
+    for i in 0 to 1 loop
+      v := i;
+    end loop;
+    if v = 0 then
+      w := 1;
+    else
+      w := 2;
+    end if;
+    wait;
+  end process;
+
+  gen: for i in 0 to 1 generate
+  end generate;
+end architecture;
+",
+    );
+
+    use FoldingRangeKind::*;
+    assert_eq!(
+        folding_ranges(&builder, &code),
+        vec![
+            (0, 1, Imports),
+            (3, 4, Code),
+            (6, 27, Code),
+            (7, 8, Code),
+            (10, 23, Code),
+            (11, 12, Code),
+            (14, 16, Code),
+            (17, 21, Code),
+            (25, 26, Code),
+        ]
+    );
+}
+
+#[test]
+fn folds_subprogram_bodies_and_comment_blocks() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "\
+package pkg is
+end package;
+
+package body pkg is
+  -- A comment block
+  -- spanning two lines
+  procedure proc(
+    value : natural
+  ) is
+    variable v : natural;
+    variable w : natural;
+  begin
+    v := value;
+    w := v;
+  end procedure;
+end package body;
+",
+    );
+
+    use FoldingRangeKind::*;
+    assert_eq!(
+        folding_ranges(&builder, &code),
+        vec![
+            (0, 1, Code),
+            (3, 15, Code),
+            (4, 5, Comment),
+            (6, 14, Code),
+            (9, 10, Code),
+        ]
+    );
+}
@@ -0,0 +1,91 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn group_declarations_resolve_template_and_constituents() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  group pin2pin is (signal, signal);
+  group path is (label <>);
+  signal sig_a, sig_b : bit;
+  group timing : pin2pin (sig_a, sig_b);
+  group paths : path (lbl);
+  attribute max_delay : time;
+  attribute max_delay of timing : group is 10 ns;
+begin
+  lbl: sig_b <= sig_a;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("pin2pin", 2).start()),
+        Some(code.s1("pin2pin").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("sig_b", 2).start()),
+        Some(code.s1("sig_b").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("(lbl)").s1("lbl").start()),
+        Some(code.s1("lbl:").s1("lbl").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s("timing", 2).start()),
+        Some(code.s1("timing").pos())
+    );
+}
+
+#[test]
+fn group_constituents_must_match_the_entity_classes_of_the_template() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  group pair is (signal, constant);
+  group many is (constant <>);
+  signal sig : bit;
+  constant const : bit := '0';
+  group g1 : pair (const, const);
+  group g2 : pair (sig);
+  group g4 : many (const, const, const);
+  group g5 : sig (const);
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::mismatched_kinds(
+                code.s1("(const, const)").s1("const"),
+                "Expected signal, got constant 'const'",
+            )
+            .related(code.s1("const :").s1("const"), "Defined here"),
+            Diagnostic::mismatched_kinds(
+                code.s1("pair (sig)").s1("pair"),
+                "Group template 'pair' expects 2 constituents, got 1",
+            ),
+            Diagnostic::mismatched_kinds(
+                code.s1("g5 : sig").s1("sig"),
+                "Expected group template, got signal 'sig'",
+            )
+            .related(code.s1("sig :").s1("sig"), "Defined here"),
+        ],
+    );
+}
@@ -0,0 +1,191 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn guarded_block_declares_guard_signal() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (
+    clk : in bit;
+    q : out bit bus);
+end entity;
+
+architecture a of ent is
+  signal d : bit register;
+  signal enabled : boolean;
+  disconnect d : bit after 2 ns;
+begin
+  blk: block (clk = '1' and not clk'stable) is
+    disconnect all : bit after 1 ns;
+  begin
+    d <= guarded not d;
+    q <= guarded d when guard else '0';
+    enabled <= guard;
+  end block;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("when guard").s1("guard").start()),
+        Some(code.s1("clk = '1' and not clk'stable").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("disconnect d").s("d", 2).end()),
+        Some(code.s1("signal d").s1("d").pos())
+    );
+}
+
+#[test]
+fn guarded_assignment_outside_guarded_block() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal d : bit;
+begin
+  blk: block is
+  begin
+    d <= guarded '1';
+  end block;
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![Diagnostic::new(
+            code.s1("d <=").s1("d"),
+            "Guarded signal assignment must be within a guarded block",
+            ErrorCode::Unresolved,
+        )],
+    );
+}
+
+#[test]
+fn disconnection_specification_requires_signals_of_the_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  signal sig : bit bus;
+  constant const : bit := '0';
+  disconnect sig : boolean after 1 ns;
+  disconnect const : bit after 1 ns;
+  disconnect others : bit after 1;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("disconnect sig").s1("sig"),
+                "signal 'sig' of type 'BIT' does not match type 'BOOLEAN'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("disconnect const").s1("const"),
+                "constant 'const' is not a signal",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("after 1;").s1("1"),
+                "integer literal does not match physical type 'TIME'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn disconnection_specification_requires_guarded_signals() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (p : in bit);
+end entity;
+
+architecture a of ent is
+  signal guarded_sig : bit register;
+  signal sig : bit;
+  disconnect guarded_sig : bit after 1 ns;
+  disconnect sig : bit after 1 ns;
+  disconnect p : bit after 1 ns;
+begin
+end architecture;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("disconnect sig").s1("sig"),
+                "signal 'sig' is not a guarded signal",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("disconnect p").s1("p"),
+                "interface signal 'p' of mode in is not a guarded signal",
+                ErrorCode::MismatchedKinds,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn disconnection_specification_of_others_or_all_is_the_last_of_its_type() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  signal a, b : bit bus;
+  signal c : boolean bus;
+  disconnect a : bit after 1 ns;
+  disconnect others : bit after 2 ns;
+  disconnect b : bit after 3 ns;
+  disconnect all : bit after 4 ns;
+  disconnect all : boolean after 1 ns;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("disconnect b : bit").s1("bit"),
+                "Disconnection specification of type 'BIT' after the disconnection specification of others or all",
+                ErrorCode::Duplicate,
+            )
+            .related(code.s1("others : bit").s1("bit"), "Previously defined here"),
+            Diagnostic::new(
+                code.s1("all : bit").s1("bit"),
+                "Disconnection specification of type 'BIT' after the disconnection specification of others or all",
+                ErrorCode::Duplicate,
+            )
+            .related(code.s1("others : bit").s1("bit"), "Previously defined here"),
+        ],
+    );
+}
@@ -0,0 +1,111 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::{Highlight, HighlightKind};
+use pretty_assertions::assert_eq;
+
+fn highlights(builder: &LibraryBuilder, code: &Code, name: &str) -> Vec<Highlight> {
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let ent = root
+        .search_reference(code.source(), code.s1(name).start())
+        .unwrap();
+    root.find_highlights(code.source(), ent)
+}
+
+fn highlight(code: Code, kind: HighlightKind) -> Highlight {
+    Highlight {
+        pos: code.pos(),
+        kind,
+    }
+}
+
+#[test]
+fn distinguishes_assignment_targets_from_reads() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type rec_t is record
+    fld : bit;
+  end record;
+  signal arr : bit_vector(0 to 1);
+  signal reg : rec_t;
+begin
+  arr(0) <= arr(1);
+  reg.fld <= arr(0);
+
+  process
+    variable cnt : natural;
+  begin
+    cnt := cnt + 1;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    assert_eq!(
+        highlights(&builder, &code, "arr"),
+        vec![
+            highlight(code.s1("arr"), HighlightKind::Declaration),
+            highlight(code.s("arr", 2), HighlightKind::Write),
+            highlight(code.s("arr", 3), HighlightKind::Read),
+            highlight(code.s("arr", 4), HighlightKind::Read),
+        ]
+    );
+    assert_eq!(
+        highlights(&builder, &code, "fld"),
+        vec![
+            highlight(code.s1("fld"), HighlightKind::Declaration),
+            highlight(code.s("fld", 2), HighlightKind::Write),
+        ]
+    );
+    assert_eq!(
+        highlights(&builder, &code, "cnt"),
+        vec![
+            highlight(code.s1("cnt"), HighlightKind::Declaration),
+            highlight(code.s("cnt", 2), HighlightKind::Write),
+            highlight(code.s("cnt", 3), HighlightKind::Read),
+        ]
+    );
+}
+
+#[test]
+fn only_highlights_within_source() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant c : natural := 0;
+end package;
+",
+    );
+    let code = builder.code(
+        "libname",
+        "
+use work.pkg.c;
+
+entity ent is
+  generic (g : natural := c);
+end entity;
+",
+    );
+
+    assert_eq!(
+        highlights(&builder, &code, "c;"),
+        vec![
+            highlight(code.s1("c;").s1("c"), HighlightKind::Read),
+            highlight(code.s1(":= c").s1("c"), HighlightKind::Read),
+        ]
+    );
+}
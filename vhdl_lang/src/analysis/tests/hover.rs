@@ -0,0 +1,180 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::HoverInfo;
+use pretty_assertions::assert_eq;
+
+fn hover(builder: LibraryBuilder, code: &Code, name: &str) -> HoverInfo {
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let pos = code.s1(name);
+    root.hover_info(root.search_reference(pos.source(), pos.start()).unwrap())
+}
+
+#[test]
+fn documentation_from_comments_above_and_trailing() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+  -- Not part of the documentation
+
+  ------------------------
+  -- The number of bytes
+  -- in a word
+  ------------------------
+  constant word_size : natural := 4; -- Never zero
+end package;
+",
+    );
+
+    assert_eq!(
+        hover(builder, &code, "word_size"),
+        HoverInfo {
+            declaration: Some("constant word_size : natural := 4;".to_owned()),
+            documentation: Some("The number of bytes\nin a word\n\nNever zero".to_owned()),
+            details: vec![
+                "Constant".to_owned(),
+                "Type: `NATURAL` with base type `INTEGER`".to_owned(),
+            ],
+            location: Some("package lib.pkg".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn port_class_and_mode() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+entity ent is
+  port (
+    clk : in bit; -- The clock
+    data : out bit_vector(7 downto 0)
+  );
+end entity;
+
+architecture a of ent is
+begin
+  data <= (others => clk);
+end architecture;
+",
+    );
+
+    let info = hover(builder, &code, "data <=");
+    assert_eq!(info.documentation, None);
+    assert_eq!(
+        info.details,
+        vec![
+            "Signal port of mode `out`".to_owned(),
+            "Type: `BIT_VECTOR`".to_owned(),
+        ]
+    );
+    assert_eq!(info.location, Some("entity lib.ent".to_owned()));
+}
+
+#[test]
+fn trailing_comment_of_port() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+entity ent is
+  port (
+    clk : in bit; -- The clock
+    data : out bit
+  );
+end entity;
+",
+    );
+
+    let info = hover(builder, &code, "clk");
+    assert_eq!(info.documentation, Some("The clock".to_owned()));
+}
+
+#[test]
+fn base_type_of_subtype() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+  type word_t is range 0 to 15;
+  subtype small_t is word_t range 0 to 3;
+end package;
+",
+    );
+
+    let info = hover(builder, &code, "small_t");
+    assert_eq!(info.details, vec!["Base type: `word_t`".to_owned()]);
+}
+
+#[test]
+fn signature_of_chosen_overload() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+  function f(arg : natural) return natural;
+  -- Converts a boolean
+  function f(arg : boolean) return natural;
+  constant c : natural := f(true);
+end package;
+",
+    );
+
+    let info = hover(builder, &code, "f(true)");
+    assert_eq!(
+        info.declaration,
+        Some("function f(\n  constant arg : in boolean\n) return natural;".to_owned())
+    );
+    assert_eq!(info.documentation, Some("Converts a boolean".to_owned()));
+    assert_eq!(
+        info.details,
+        vec!["Signature: `[BOOLEAN return NATURAL]`".to_owned()]
+    );
+}
+
+#[test]
+fn value_of_constants() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+  constant width : natural := 8;
+  constant depth : natural := (width + 2) * 4 - 1;
+  constant deferred : natural;
+  constant name : string := \"abc\";
+  constant other : string := name;
+end package;
+
+package body pkg is
+  constant deferred : natural := depth / 3;
+end package body;
+",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let value = |name: &str| {
+        let pos = code.s1(name);
+        let ent = root.search_reference(pos.source(), pos.start()).unwrap();
+        root.hover_info(ent)
+            .details
+            .into_iter()
+            .find_map(|detail| detail.strip_prefix("Value: ").map(|value| value.to_owned()))
+    };
+
+    assert_eq!(value("width"), None);
+    assert_eq!(value("depth"), Some("`39`".to_owned()));
+    assert_eq!(value("deferred"), Some("`13`".to_owned()));
+    assert_eq!(value("other"), Some("`\"abc\"`".to_owned()));
+}
@@ -0,0 +1,255 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{Position, Range};
+use crate::{InlayHint, InlayHintKind};
+use pretty_assertions::assert_eq;
+
+fn hints(builder: &LibraryBuilder, code: &Code, range: Option<Range>) -> Vec<InlayHint> {
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    root.inlay_hints(code.source(), range)
+}
+
+fn hint(pos: Position, label: &str, kind: InlayHintKind) -> InlayHint {
+    InlayHint {
+        pos,
+        label: label.to_owned(),
+        kind,
+    }
+}
+
+#[test]
+fn formal_names_of_positional_subprogram_arguments() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  function f(arg : natural; other : boolean) return natural;
+  procedure p(value : natural);
+end package;
+
+package body pkg is
+  function f(arg : natural; other : boolean) return natural is
+  begin
+    return arg;
+  end function;
+
+  procedure p(value : natural) is
+    variable arg : natural;
+  begin
+    arg := f(arg, true);
+    arg := f(0, other => false);
+    p(f(1, false));
+  end procedure;
+end package body;
+",
+    );
+
+    assert_eq!(
+        hints(&builder, &code, None),
+        vec![
+            hint(
+                code.s1("f(arg, true)").s1("true").start(),
+                "other =>",
+                InlayHintKind::Parameter
+            ),
+            hint(
+                code.s1("f(0, other").s1("0").start(),
+                "arg =>",
+                InlayHintKind::Parameter
+            ),
+            hint(
+                code.s1("p(f(1, false))").s1("f").start(),
+                "value =>",
+                InlayHintKind::Parameter
+            ),
+            hint(
+                code.s1("f(1, false)").s1("1").start(),
+                "arg =>",
+                InlayHintKind::Parameter
+            ),
+            hint(
+                code.s1("f(1, false)").s1("false").start(),
+                "other =>",
+                InlayHintKind::Parameter
+            ),
+        ]
+    );
+}
+
+#[test]
+fn formal_names_of_positional_generic_and_port_map_elements() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity child is
+  generic (width : natural);
+  port (clk : in bit; data : out bit);
+end entity;
+
+architecture a of child is
+begin
+end architecture;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, s : bit;
+begin
+  inst: entity work.child
+    generic map (8)
+    port map (clk, s);
+end architecture;
+",
+    );
+
+    assert_eq!(
+        hints(&builder, &code, None),
+        vec![
+            hint(
+                code.s1("(8)").s1("8").start(),
+                "width =>",
+                InlayHintKind::Parameter
+            ),
+            hint(
+                code.s1("clk, s)").s1("s").start(),
+                "data =>",
+                InlayHintKind::Parameter
+            ),
+        ]
+    );
+}
+
+#[test]
+fn types_of_loop_parameters_and_generate_indices() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  type state_t is (idle, busy);
+begin
+  gen: for i in 0 to 3 generate
+  end generate;
+
+  process
+  begin
+    for state in state_t loop
+    end loop;
+    wait;
+  end process;
+end architecture;
+",
+    );
+
+    assert_eq!(
+        hints(&builder, &code, None),
+        vec![
+            hint(
+                code.s1("for i").s1("i").end(),
+                ": INTEGER",
+                InlayHintKind::Type
+            ),
+            hint(
+                code.s1("for state").s1("state").end(),
+                ": state_t",
+                InlayHintKind::Type
+            ),
+        ]
+    );
+}
+
+#[test]
+fn labels_of_long_processes_without_end_label() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  short: process
+  begin
+    wait;
+  end process;
+
+  long: process
+    variable v : natural;
+  begin
+    v := 1;
+    v := 2;
+    v := 3;
+    v := 4;
+    v := 5;
+    v := 6;
+    wait;
+  end process;
+
+  labeled: process
+    variable v : natural;
+  begin
+    v := 1;
+    v := 2;
+    v := 3;
+    v := 4;
+    v := 5;
+    v := 6;
+    wait;
+  end process labeled;
+end architecture;
+",
+    );
+
+    assert_eq!(
+        hints(&builder, &code, None),
+        vec![hint(
+            code.s("end process;", 2).s1(";").start(),
+            "long",
+            InlayHintKind::EndLabel
+        )]
+    );
+}
+
+#[test]
+fn only_hints_within_range() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  gen0: for i in 0 to 3 generate
+  end generate;
+
+  gen1: for j in 0 to 3 generate
+  end generate;
+end architecture;
+",
+    );
+
+    let range = code.s1("gen1: for j in 0 to 3 generate").pos().range();
+    assert_eq!(
+        hints(&builder, &code, Some(range)),
+        vec![hint(
+            code.s1("for j").s1("j").end(),
+            ": INTEGER",
+            InlayHintKind::Type
+        )]
+    );
+}
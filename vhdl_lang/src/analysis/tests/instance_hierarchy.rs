@@ -0,0 +1,245 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::InstanceHierarchy;
+use pretty_assertions::assert_eq;
+
+fn instance(
+    label: &str,
+    library: &str,
+    entity: &str,
+    architecture: &str,
+    children: Vec<InstanceHierarchy>,
+) -> InstanceHierarchy {
+    InstanceHierarchy {
+        label: label.to_owned(),
+        library: Some(library.to_owned()),
+        entity: entity.to_owned(),
+        architecture: Some(architecture.to_owned()),
+        children,
+    }
+}
+
+fn get_hierarchy(builder: &LibraryBuilder, library: &str, entity: &str) -> InstanceHierarchy {
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    root.instance_hierarchy(&root.symbol_utf8(library), &root.symbol_utf8(entity))
+        .unwrap()
+}
+
+#[test]
+fn entity_component_and_configuration_instances() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib",
+        "
+entity leaf is
+end entity;
+
+architecture first of leaf is
+begin
+end architecture;
+
+architecture second of leaf is
+begin
+end architecture;
+
+configuration cfg of leaf is
+  for first
+  end for;
+end configuration;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component leaf is
+  end component;
+begin
+  ent_inst: entity work.leaf(first);
+  comp_inst: component leaf;
+  cfg_inst: configuration work.cfg;
+end architecture;
+",
+    );
+
+    assert_eq!(
+        get_hierarchy(&builder, "lib", "top"),
+        instance(
+            "top",
+            "lib",
+            "top",
+            "rtl",
+            vec![
+                instance("ent_inst", "lib", "leaf", "first", vec![]),
+                instance("comp_inst", "lib", "leaf", "second", vec![]),
+                instance("cfg_inst", "lib", "leaf", "first", vec![]),
+            ]
+        )
+    );
+}
+
+#[test]
+fn instances_within_blocks_and_generates_are_prefixed() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib",
+        "
+entity leaf is
+end entity;
+
+architecture a of leaf is
+begin
+end architecture;
+
+entity mid is
+end entity;
+
+architecture a of mid is
+begin
+  blk: block
+  begin
+    inst: entity work.leaf;
+  end block;
+end architecture;
+
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  gen: for i in 0 to 1 generate
+    inst: entity work.mid;
+  end generate;
+
+  if_gen: if alt1: true generate
+    inst: entity work.leaf;
+  end generate;
+end architecture;
+",
+    );
+
+    assert_eq!(
+        get_hierarchy(&builder, "lib", "top"),
+        instance(
+            "top",
+            "lib",
+            "top",
+            "a",
+            vec![
+                instance(
+                    "gen.inst",
+                    "lib",
+                    "mid",
+                    "a",
+                    vec![instance("blk.inst", "lib", "leaf", "a", vec![])]
+                ),
+                instance("if_gen.alt1.inst", "lib", "leaf", "a", vec![]),
+            ]
+        )
+    );
+}
+
+#[test]
+fn unresolved_component_and_recursion() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib",
+        "
+entity top is
+end entity;
+
+architecture a of top is
+  component missing is
+  end component;
+begin
+  missing_inst: component missing;
+  self_inst: entity work.top;
+end architecture;
+",
+    );
+
+    assert_eq!(
+        get_hierarchy(&builder, "lib", "top"),
+        instance(
+            "top",
+            "lib",
+            "top",
+            "a",
+            vec![
+                InstanceHierarchy {
+                    label: "missing_inst".to_owned(),
+                    library: None,
+                    entity: "missing".to_owned(),
+                    architecture: None,
+                    children: vec![],
+                },
+                instance("self_inst", "lib", "top", "a", vec![]),
+            ]
+        )
+    );
+}
+
+#[test]
+fn missing_top_level() {
+    let builder = LibraryBuilder::new();
+    let (root, _) = builder.get_analyzed_root();
+    assert_eq!(
+        root.instance_hierarchy(&root.symbol_utf8("lib"), &root.symbol_utf8("top")),
+        None
+    );
+}
+
+#[test]
+fn text_json_and_dot_output() {
+    let hierarchy = instance(
+        "top",
+        "lib",
+        "top",
+        "rtl",
+        vec![InstanceHierarchy {
+            label: "u1".to_owned(),
+            library: None,
+            entity: "missing".to_owned(),
+            architecture: None,
+            children: vec![],
+        }],
+    );
+
+    assert_eq!(
+        hierarchy.to_text(),
+        "top: lib.top(rtl)\n  u1: missing (unresolved)\n"
+    );
+
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&hierarchy.to_json()).unwrap(),
+        serde_json::json!({
+            "label": "top",
+            "library": "lib",
+            "entity": "top",
+            "architecture": "rtl",
+            "children": [{
+                "label": "u1",
+                "library": null,
+                "entity": "missing",
+                "architecture": null,
+                "children": [],
+            }],
+        })
+    );
+
+    assert_eq!(
+        hierarchy.to_dot(),
+        "digraph hierarchy {
+  node [shape=box];
+  \"top\" [label=\"top\\nlib.top(rtl)\"];
+  \"top\" -> \"top.u1\";
+  \"top.u1\" [label=\"u1\\nmissing (unresolved)\"];
+}
+"
+    );
+}
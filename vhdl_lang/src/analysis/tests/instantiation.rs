@@ -0,0 +1,172 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::Position;
+use crate::{Instantiation, InstantiationKind};
+use pretty_assertions::assert_eq;
+
+fn add_ent(builder: &mut LibraryBuilder, library_name: &str) {
+    builder.code(
+        library_name,
+        "
+entity ent is
+  generic (
+    width : natural := 8;
+    depth : natural
+  );
+  port (
+    clk : in bit;
+    data : out bit_vector(7 downto 0) := (others => '0')
+  );
+end entity;
+",
+    );
+}
+
+fn top(builder: &mut LibraryBuilder, library_name: &str) -> Code {
+    builder.code(
+        library_name,
+        "
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  ent;
+end architecture;
+",
+    )
+}
+
+fn generate(
+    builder: &LibraryBuilder,
+    code: &Code,
+    kind: InstantiationKind,
+) -> Option<Instantiation> {
+    let (root, _) = builder.get_analyzed_root();
+    let entities = root.find_design_entities("ent");
+    assert_eq!(entities.len(), 1);
+    root.generate_instantiation(
+        code.source(),
+        code.s1("ent;").start(),
+        entities[0],
+        kind,
+        "  ",
+    )
+}
+
+#[test]
+fn entity_instantiation_in_other_library() {
+    let mut builder = LibraryBuilder::new();
+    add_ent(&mut builder, "common");
+    let code = top(&mut builder, "lib");
+
+    assert_eq!(
+        generate(&builder, &code, InstantiationKind::Entity),
+        Some(Instantiation {
+            statement: "  ent_inst: entity common.ent
+    generic map (
+      width => width, -- default: 8
+      depth => depth
+    )
+    port map (
+      clk  => clk,
+      data => data -- default: (others => '0')
+    );
+"
+            .to_owned(),
+            inserts: vec![(Position::new(4, 0), "library common;\n\n".to_owned())],
+        })
+    );
+}
+
+#[test]
+fn entity_instantiation_in_same_library() {
+    let mut builder = LibraryBuilder::new();
+    add_ent(&mut builder, "lib");
+    let code = top(&mut builder, "lib");
+
+    let instantiation = generate(&builder, &code, InstantiationKind::Entity).unwrap();
+    assert!(instantiation
+        .statement
+        .starts_with("  ent_inst: entity work.ent\n"));
+    assert_eq!(instantiation.inserts, vec![]);
+}
+
+#[test]
+fn component_instantiation_declares_component() {
+    let mut builder = LibraryBuilder::new();
+    add_ent(&mut builder, "lib");
+    let code = top(&mut builder, "lib");
+
+    let instantiation = generate(&builder, &code, InstantiationKind::Component).unwrap();
+    assert!(instantiation
+        .statement
+        .starts_with("  ent_inst: component ent\n    generic map (\n"));
+    assert_eq!(
+        instantiation.inserts,
+        vec![(
+            Position::new(5, 0),
+            "  component ent is
+    generic (
+      width : natural := 8;
+      depth : natural
+    );
+    port (
+      clk : in bit;
+      data : out bit_vector(7 downto 0) := (others => '0')
+    );
+  end component;
+
+"
+            .to_owned()
+        )]
+    );
+}
+
+#[test]
+fn entity_without_generics_or_ports() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "lib",
+        "
+entity ent is
+end entity;
+",
+    );
+    let code = top(&mut builder, "lib");
+
+    let instantiation = generate(&builder, &code, InstantiationKind::Entity).unwrap();
+    assert_eq!(instantiation.statement, "  ent_inst: entity work.ent;\n");
+}
+
+#[test]
+fn only_instantiates_in_architectures() {
+    let mut builder = LibraryBuilder::new();
+    add_ent(&mut builder, "lib");
+    let code = builder.code(
+        "lib",
+        "
+package pkg is
+  constant ent : natural := 0;
+end package;
+",
+    );
+
+    let (root, _) = builder.get_analyzed_root();
+    let entities = root.find_design_entities("ent");
+    assert_eq!(
+        root.generate_instantiation(
+            code.source(),
+            code.s1("ent :").start(),
+            entities[0],
+            InstantiationKind::Entity,
+            "  "
+        ),
+        None
+    );
+}
@@ -6,23 +6,39 @@
 
 mod assignment_typecheck;
 mod association_formal;
+mod call_hierarchy;
 mod circular_dependencies;
 mod context_clause;
 mod deferred_constant;
+mod dependency_graph;
+mod folding;
+mod group_declarations;
+mod guarded_signals;
 mod hierarchy;
+mod highlight;
 mod homographs;
+mod hover;
 mod implicit;
 mod incomplete_type;
 mod incremental_analysis;
+mod inlay_hints;
+mod instance_hierarchy;
+mod instantiation;
+mod mode_views;
 mod package_instance;
 mod protected_type;
 mod resolves_design_units;
 mod resolves_names;
 mod resolves_type_mark;
+mod selection_range;
 mod sensitivity_list;
+mod signature_help;
+mod standard_revisions;
 mod subprogram_arguments;
 mod tool_directive;
+mod type_hierarchy;
 mod typecheck_expression;
+mod use_clause_fix;
 mod util;
 mod visibility;
 
@@ -31,8 +47,8 @@ use std::cell::RefCell;
 pub use self::util::*;
 use crate::ast::Designator;
 use crate::ast::UnitId;
-pub use crate::data::Diagnostic;
 use crate::data::NoDiagnostics;
+pub use crate::data::{Diagnostic, ErrorCode};
 pub use crate::syntax::test::*;
 use crate::syntax::Token;
 
@@ -0,0 +1,209 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::VHDLStandard;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn mode_view_ports() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard("libname", VHDLStandard::VHDL2019);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type stream_t is record
+    data : bit;
+    valid : bit;
+    ready : bit;
+  end record;
+  type stream_vec_t is array (natural range <>) of stream_t;
+
+  type bus_t is record
+    req : stream_t;
+    rsp : stream_vec_t(0 to 1);
+  end record;
+
+  view source_v of stream_t is
+    data, valid : out;
+    ready : in;
+  end view;
+
+  view master_v of bus_t is
+    req : view source_v;
+    rsp : view (source_v'converse);
+  end view master_v;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+  port (
+    src : view source_v;
+    snk : view source_v'converse of stream_t;
+    srcs : view (source_v) of stream_vec_t(0 to 3);
+    m : view master_v);
+end entity;
+
+architecture a of ent is
+begin
+  src.data <= snk.data;
+  snk.ready <= src.ready;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    assert_eq!(
+        root.search_reference_pos(
+            code.source(),
+            code.s1("view source_v;").s1("source_v").start()
+        ),
+        Some(code.s1("source_v").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(
+            code.source(),
+            code.s1("view source_v'converse").s1("source_v").start()
+        ),
+        Some(code.s1("source_v").pos())
+    );
+    assert_eq!(
+        root.search_reference_pos(code.source(), code.s1("ready : in").s1("ready").start()),
+        Some(code.s1("ready : bit").s1("ready").pos())
+    );
+}
+
+#[test]
+fn mode_view_element_errors() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard("libname", VHDLStandard::VHDL2019);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type stream_t is record
+    data : bit;
+    valid : bit;
+  end record;
+
+  type other_t is record
+    data : bit;
+  end record;
+
+  view other_v of other_t is
+    data : in;
+  end view;
+
+  view bad_v of stream_t is
+    data : out;
+    data : in;
+    missing : in;
+  end view;
+
+  view nested_v of stream_t is
+    data : view other_v;
+    valid : in;
+  end view;
+
+  view scalar_v of bit is
+  end view;
+end package;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s("data : in", 2).s1("data"),
+                "Duplicate declaration of 'data'",
+                ErrorCode::Duplicate,
+            )
+            .related(code.s1("data : out").s1("data"), "Previously defined here"),
+            Diagnostic::new(
+                code.s1("missing"),
+                "No declaration of 'missing' within record type 'stream_t'",
+                ErrorCode::Unresolved,
+            ),
+            Diagnostic::new(
+                code.s1("bad_v"),
+                "Missing mode for record element 'valid'",
+                ErrorCode::Unassociated,
+            )
+            .related(
+                code.s1("valid : bit").s1("valid"),
+                "Record element 'valid' defined here",
+            ),
+            Diagnostic::new(
+                code.s1("view other_v;").s1("other_v"),
+                "Mode view of record type 'other_t' cannot be used for record element 'data' of type 'BIT'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("of bit").s1("bit"),
+                "The subtype of a mode view must be a record type, got type 'BIT'",
+                ErrorCode::TypeMismatch,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn mode_view_indication_errors() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard("libname", VHDLStandard::VHDL2019);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type stream_t is record
+    data : bit;
+  end record;
+
+  type other_t is record
+    data : bit;
+  end record;
+
+  view source_v of stream_t is
+    data : out;
+  end view;
+end package;
+
+use work.pkg.all;
+
+entity ent is
+  port (
+    a : view stream_t;
+    b : view source_v of other_t;
+    c : view stream_t'converse of stream_t);
+end entity;",
+    );
+
+    let diagnostics = builder.analyze();
+    check_diagnostics(
+        diagnostics,
+        vec![
+            Diagnostic::new(
+                code.s1("view stream_t").s1("stream_t"),
+                "Expected view, got record type 'stream_t'",
+                ErrorCode::MismatchedKinds,
+            ),
+            Diagnostic::new(
+                code.s1("of other_t").s1("other_t"),
+                "record type 'other_t' does not match mode view of record type 'stream_t'",
+                ErrorCode::TypeMismatch,
+            ),
+            Diagnostic::new(
+                code.s1("stream_t'converse"),
+                "record type 'stream_t' cannot be the the prefix of 'converse attribute",
+                ErrorCode::IllegalAttribute,
+            ),
+        ],
+    );
+}
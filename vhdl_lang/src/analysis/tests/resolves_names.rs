@@ -2033,3 +2033,52 @@ end architecture;
         );
     }
 }
+
+#[test]
+fn find_all_declarations_and_references_in_source() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+  port (inp : in bit);
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  sig <= inp;
+end architecture;",
+    );
+
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let names: Vec<_> = root
+        .find_all_in_source(code.source())
+        .into_iter()
+        .map(|(pos, ent, is_declaration)| {
+            let start = pos.start();
+            (
+                start.line,
+                start.character,
+                ent.designator().to_string(),
+                is_declaration,
+            )
+        })
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            (1, 7, "ent".to_owned(), true),
+            (2, 8, "inp".to_owned(), true),
+            (2, 17, "BIT".to_owned(), false),
+            (5, 13, "a".to_owned(), true),
+            (5, 18, "ent".to_owned(), false),
+            (6, 9, "sig".to_owned(), true),
+            (6, 15, "BIT".to_owned(), false),
+            (8, 2, "sig".to_owned(), false),
+            (8, 9, "inp".to_owned(), false),
+        ]
+    );
+}
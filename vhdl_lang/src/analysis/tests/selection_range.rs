@@ -0,0 +1,71 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn expands_from_name_to_design_unit() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal s : natural;
+begin
+  process
+  begin
+    s <= s + 1;
+    wait;
+  end process;
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let cursor = code.s1("s + 1").start();
+    let ranges: Vec<_> = root
+        .selection_ranges(code.source(), cursor)
+        .into_iter()
+        .map(|pos| pos.range())
+        .collect();
+    assert_eq!(
+        ranges,
+        vec![
+            code.s1("s + 1").s1("s").pos().range(),
+            code.s1("s + 1").pos().range(),
+            code.s1("s <= s + 1;").pos().range(),
+            code.s1("process")
+                .pos()
+                .combine(&code.s1("end process;").pos())
+                .range(),
+            code.s1("architecture")
+                .pos()
+                .combine(&code.s1("end architecture;").pos())
+                .range(),
+        ]
+    );
+}
+
+#[test]
+fn no_ranges_outside_of_design_units() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+entity ent is
+end entity;
+
+",
+    );
+    let (root, _) = builder.get_analyzed_root();
+
+    assert_eq!(root.selection_ranges(code.source(), code.end()), Vec::new());
+}
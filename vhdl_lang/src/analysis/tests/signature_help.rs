@@ -0,0 +1,240 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::{Position, VHDLStandard};
+use crate::SignatureHelp;
+use pretty_assertions::assert_eq;
+
+fn add_pkg(builder: &mut LibraryBuilder) {
+    builder.code(
+        "common",
+        "
+package pkg is
+  function f(arg : natural) return natural;
+  function f(arg : boolean; other : bit) return natural;
+  procedure p(signal s : out bit; value : bit);
+end package;
+",
+    );
+}
+
+fn help(builder: &LibraryBuilder, code: &Code, cursor: Position) -> Option<SignatureHelp> {
+    let (root, _) = builder.get_analyzed_root();
+    root.signature_help(code.source(), cursor, VHDLStandard::default())
+}
+
+fn labels(help: &SignatureHelp) -> Vec<(&str, Option<usize>)> {
+    help.signatures
+        .iter()
+        .map(|signature| (signature.label.as_str(), signature.active_parameter))
+        .collect()
+}
+
+#[test]
+fn lists_overloads_made_visible_by_use_clause() {
+    let mut builder = LibraryBuilder::new();
+    add_pkg(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+library common;
+use common.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant c : natural := f(true, '0');
+begin
+end architecture;
+",
+    );
+
+    let help = help(&builder, &code, code.s1("'0'").start()).unwrap();
+    assert_eq!(
+        labels(&help),
+        vec![
+            ("function f(arg : in NATURAL) return NATURAL", None),
+            (
+                "function f(arg : in BOOLEAN; other : in BIT) return NATURAL",
+                Some(1)
+            ),
+        ]
+    );
+    assert_eq!(help.active_signature, 1);
+
+    let signature = &help.signatures[1];
+    assert_eq!(
+        &signature.label[signature.parameters[1].clone()],
+        "other : in BIT"
+    );
+}
+
+#[test]
+fn active_parameter_by_named_association() {
+    let mut builder = LibraryBuilder::new();
+    add_pkg(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+library common;
+use common.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  process
+  begin
+    p(value => '1', s => sig);
+  end process;
+end architecture;
+",
+    );
+
+    let help = help(&builder, &code, code.s1("sig)").start()).unwrap();
+    assert_eq!(
+        labels(&help),
+        vec![("procedure p(signal s : out BIT; value : in BIT)", Some(0))]
+    );
+}
+
+#[test]
+fn selected_name_and_local_subprograms() {
+    let mut builder = LibraryBuilder::new();
+    add_pkg(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+library common;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  function f(arg : bit) return natural is
+  begin
+    return 0;
+  end function;
+
+  constant c1 : natural := common.pkg.f(0);
+  constant c2 : natural := f('0');
+begin
+end architecture;
+",
+    );
+
+    let selected = help(&builder, &code, code.s1("f(0)").s1("0").start()).unwrap();
+    assert_eq!(
+        labels(&selected),
+        vec![
+            ("function f(arg : in NATURAL) return NATURAL", Some(0)),
+            (
+                "function f(arg : in BOOLEAN; other : in BIT) return NATURAL",
+                Some(0)
+            ),
+        ]
+    );
+
+    let local = help(&builder, &code, code.s1("f('0')").s1("'0'").start()).unwrap();
+    assert_eq!(
+        labels(&local),
+        vec![("function f(arg : in BIT) return NATURAL", Some(0))]
+    );
+}
+
+#[test]
+fn port_map_of_entity_instantiation() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+entity sub is
+  generic (width : natural := 8);
+  port (clk : in bit; data : out bit);
+end entity;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal clk, data : bit;
+begin
+  inst: entity work.sub
+    generic map (width => 4)
+    port map (clk, data);
+end architecture;
+",
+    );
+
+    let help = help(&builder, &code, code.s1("data);").start()).unwrap();
+    assert_eq!(
+        labels(&help),
+        vec![(
+            "entity lib.sub port (clk : in bit; data : out bit)",
+            Some(1)
+        )]
+    );
+
+    let help = self::help(&builder, &code, code.s1("4)").start()).unwrap();
+    assert_eq!(
+        labels(&help),
+        vec![("entity lib.sub generic (width : natural := 8)", Some(0))]
+    );
+}
+
+#[test]
+fn generic_map_of_component_instantiation() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "lib",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component comp is
+    generic (width : natural; depth : natural);
+  end component;
+begin
+  inst: comp
+    generic map (depth => 1, width => 2);
+end architecture;
+",
+    );
+
+    let help = help(&builder, &code, code.s1("2)").start()).unwrap();
+    assert_eq!(
+        labels(&help),
+        vec![(
+            "component comp generic (width : natural; depth : natural)",
+            Some(0)
+        )]
+    );
+}
+
+#[test]
+fn no_signature_help_outside_of_parenthesis() {
+    let mut builder = LibraryBuilder::new();
+    add_pkg(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+library common;
+use common.pkg.all;
+
+package other is
+  constant c : natural := f(0);
+end package;
+",
+    );
+
+    assert_eq!(help(&builder, &code, code.s1("c :").start()), None);
+    assert_eq!(help(&builder, &code, code.s1(";").end()), None);
+}
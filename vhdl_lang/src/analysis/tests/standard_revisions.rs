@@ -0,0 +1,91 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::VHDLStandard;
+
+#[test]
+fn implicit_declarations_added_in_vhdl2008() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard("libname", VHDLStandard::VHDL1993);
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy);
+  constant first : state_t := minimum(idle, busy);
+  constant img : string := to_string(idle);
+  constant int_img : string := to_string(natural'(1));
+end package;",
+    );
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![
+            missing(&code, "minimum", 1),
+            missing(&code, "to_string", 1),
+            missing(&code, "to_string", 2),
+        ],
+    );
+}
+
+#[test]
+fn implicit_declarations_in_vhdl2008() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "
+package pkg is
+  type state_t is (idle, busy);
+  constant first : state_t := minimum(idle, busy);
+  constant img : string := to_string(idle);
+end package;",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
+
+#[test]
+fn external_names_are_not_allowed_before_vhdl2008() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard("old", VHDLStandard::VHDL2002);
+    let code = "
+entity ent is
+end entity;
+
+architecture a of ent is
+  alias sig is <<signal .tb.dut.sig : bit>>;
+begin
+end architecture;";
+    let old = builder.code("old", code);
+    builder.code("libname", code);
+
+    check_diagnostics(
+        builder.analyze(),
+        vec![Diagnostic::not_in_standard(
+            old.s1("<<signal .tb.dut.sig : bit>>"),
+            "External name",
+            VHDLStandard::VHDL2002,
+        )],
+    );
+}
+
+#[test]
+fn keywords_of_later_revisions_are_identifiers() {
+    let mut builder = LibraryBuilder::new();
+    builder.set_standard("libname", VHDLStandard::VHDL1993);
+    builder.code(
+        "libname",
+        "
+package pkg is
+  constant context : natural := 0;
+  constant force : natural := context;
+  signal default : bit;
+end package;",
+    );
+
+    check_no_diagnostics(&builder.analyze());
+}
@@ -30,3 +30,29 @@ end my_ent;
 
     check_no_diagnostics(&diagnostics);
 }
+
+#[test]
+fn conditional_analysis_excludes_inactive_branches() {
+    let mut builder = LibraryBuilder::new();
+    builder.code(
+        "libname",
+        "\
+entity my_ent is
+end my_ent;
+
+architecture a of my_ent is
+`if TOOL_TYPE = \"SYNTHESIS\" then
+  signal sig : bit;
+  syntax error here
+`else
+  signal sig : boolean;
+`end if
+begin
+  sig <= true;
+end architecture;
+        ",
+    );
+    let (_, diagnostics) = builder.get_analyzed_root();
+
+    check_no_diagnostics(&diagnostics);
+}
@@ -0,0 +1,172 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::EntRef;
+use pretty_assertions::assert_eq;
+
+fn designators(ents: Vec<EntRef>) -> Vec<String> {
+    ents.into_iter()
+        .map(|ent| ent.designator().to_string())
+        .collect()
+}
+
+#[test]
+fn supertypes_and_subtypes() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type bits_t is ('0', '1', 'Z');
+  type vec_t is array (natural range <>) of bits_t;
+  function resolve(arg : vec_t) return bits_t;
+  subtype res_t is resolve bits_t;
+  subtype narrow_t is res_t range '0' to '1';
+  alias alias_t is bits_t;
+  subtype byte_t is vec_t(7 downto 0);
+end package;
+
+use work.pkg.all;
+
+package other is
+  subtype other_t is narrow_t;
+end package;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let ent = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+    };
+
+    assert_eq!(
+        designators(root.supertypes(ent("other_t"))),
+        vec!["narrow_t"]
+    );
+    assert_eq!(designators(root.supertypes(ent("narrow_t"))), vec!["res_t"]);
+    assert_eq!(designators(root.supertypes(ent("res_t"))), vec!["bits_t"]);
+    assert_eq!(designators(root.supertypes(ent("alias_t"))), vec!["bits_t"]);
+    assert_eq!(designators(root.supertypes(ent("byte_t"))), vec!["vec_t"]);
+    assert!(root.supertypes(ent("bits_t")).is_empty());
+
+    assert_eq!(
+        designators(root.subtypes(ent("bits_t"))),
+        vec!["vec_t", "res_t", "alias_t"]
+    );
+    assert_eq!(designators(root.subtypes(ent("res_t"))), vec!["narrow_t"]);
+    assert_eq!(designators(root.subtypes(ent("narrow_t"))), vec!["other_t"]);
+    assert_eq!(designators(root.subtypes(ent("vec_t"))), vec!["byte_t"]);
+    assert!(root.subtypes(ent("other_t")).is_empty());
+}
+
+#[test]
+fn element_types_of_records_and_arrays() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "libname",
+        "
+package pkg is
+  type bits_t is ('0', '1');
+  subtype narrow_t is bits_t;
+  type rec_t is record
+    a : bits_t;
+    b : bits_t;
+    c : narrow_t;
+  end record;
+  type recs_t is array (natural range <>) of rec_t;
+end package;
+
+package body pkg is
+  type body_vec_t is array (natural range <>) of narrow_t;
+end package body;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  type arch_rec_t is record
+    field : rec_t;
+  end record;
+begin
+end architecture;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+    let ent = |name: &str| {
+        root.search_reference(code.source(), code.s1(name).start())
+            .unwrap()
+    };
+
+    assert_eq!(
+        designators(root.supertypes(ent("rec_t"))),
+        vec!["bits_t", "narrow_t"]
+    );
+    assert_eq!(designators(root.supertypes(ent("recs_t"))), vec!["rec_t"]);
+    assert_eq!(
+        designators(root.supertypes(ent("body_vec_t"))),
+        vec!["narrow_t"]
+    );
+
+    assert_eq!(
+        designators(root.subtypes(ent("bits_t"))),
+        vec!["narrow_t", "rec_t"]
+    );
+    assert_eq!(
+        designators(root.subtypes(ent("narrow_t"))),
+        vec!["rec_t", "body_vec_t"]
+    );
+    assert_eq!(
+        designators(root.subtypes(ent("rec_t"))),
+        vec!["recs_t", "arch_rec_t"]
+    );
+}
+
+#[test]
+fn subtypes_are_found_in_dependent_units_of_other_libraries() {
+    let mut builder = LibraryBuilder::new();
+    let code = builder.code(
+        "common",
+        "
+package pkg is
+  type bits_t is ('0', '1');
+  alias bits_alias_t is bits_t;
+end package;
+",
+    );
+    builder.code(
+        "lib",
+        "
+library common;
+
+package pkg is
+  subtype narrow_t is common.pkg.bits_t;
+end package;
+
+package unrelated is
+  type bits_t is ('0', '1');
+  subtype narrow_t is bits_t;
+end package;
+",
+    );
+    let (root, diagnostics) = builder.get_analyzed_root();
+    check_no_diagnostics(&diagnostics);
+
+    let bits_t = root
+        .search_reference(code.source(), code.s1("bits_t").start())
+        .unwrap();
+    let subtypes = root.subtypes(bits_t);
+    assert_eq!(
+        designators(subtypes.clone()),
+        vec!["narrow_t", "bits_alias_t"]
+    );
+    assert_eq!(subtypes[0].library_name().unwrap().name_utf8(), "lib");
+    assert_eq!(subtypes[0].parent.unwrap().designator().to_string(), "pkg");
+}
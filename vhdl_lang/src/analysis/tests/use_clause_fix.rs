@@ -0,0 +1,163 @@
+//! This Source Code Form is subject to the terms of the Mozilla Public
+//! License, v. 2.0. If a copy of the MPL was not distributed with this file,
+//! You can obtain one at http://mozilla.org/MPL/2.0/.
+//!
+//! Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::*;
+use crate::data::Position;
+use crate::UseClauseFix;
+use pretty_assertions::assert_eq;
+
+fn add_common(builder: &mut LibraryBuilder) {
+    builder.code(
+        "common",
+        "
+package pkg is
+  constant c : natural := 0;
+  function f(arg : natural) return natural;
+  function f(arg : boolean) return natural;
+end package;
+
+entity ent_with_port is
+  port (c : bit);
+end entity;
+",
+    );
+}
+
+fn fixes(builder: &LibraryBuilder, code: &Code, name: &str) -> Vec<UseClauseFix> {
+    let (root, _) = builder.get_analyzed_root();
+    root.find_use_clause_fixes(code.source(), code.s1(name).start(), name)
+}
+
+#[test]
+fn adds_library_and_use_clause_before_design_unit() {
+    let mut builder = LibraryBuilder::new();
+    add_common(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+entity ent is
+end entity;
+
+architecture a of ent is
+  constant d : natural := c;
+begin
+end architecture;
+",
+    );
+
+    let pos = Position::new(4, 0);
+    assert_eq!(
+        fixes(&builder, &code, "c"),
+        vec![
+            UseClauseFix {
+                title: "Add use common.pkg.c".to_owned(),
+                pos,
+                text: "library common;\nuse common.pkg.c;\n\n".to_owned(),
+            },
+            UseClauseFix {
+                title: "Add use common.pkg.all".to_owned(),
+                pos,
+                text: "library common;\nuse common.pkg.all;\n\n".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn adds_context_clause_at_start_of_design_unit_on_shared_line() {
+    let mut builder = LibraryBuilder::new();
+    add_common(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+entity ent is
+end entity; architecture a of ent is
+  constant d : natural := c;
+begin
+end architecture;
+",
+    );
+
+    let (root, _) = builder.get_analyzed_root();
+    let fixes = root.find_use_clause_fixes(code.source(), code.s1("c;").start(), "c");
+    assert_eq!(
+        fixes.iter().map(|fix| fix.pos).collect::<Vec<_>>(),
+        vec![Position::new(2, 12), Position::new(2, 12)]
+    );
+}
+
+#[test]
+fn appends_to_existing_context_clause() {
+    let mut builder = LibraryBuilder::new();
+    add_common(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+library common;
+use common.pkg.c;
+
+entity ent is
+  generic (g : natural := f(0));
+end entity;
+",
+    );
+
+    let pos = Position::new(3, 0);
+    assert_eq!(
+        fixes(&builder, &code, "f"),
+        vec![
+            UseClauseFix {
+                title: "Add use common.pkg.f".to_owned(),
+                pos,
+                text: "use common.pkg.f;\n".to_owned(),
+            },
+            UseClauseFix {
+                title: "Add use common.pkg.all".to_owned(),
+                pos,
+                text: "use common.pkg.all;\n".to_owned(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn uses_work_for_package_in_same_library() {
+    let mut builder = LibraryBuilder::new();
+    add_common(&mut builder);
+    let code = builder.code(
+        "common",
+        "
+package other is
+  constant d : natural := c;
+end package;
+",
+    );
+
+    let fixes = fixes(&builder, &code, "c");
+    assert_eq!(
+        fixes
+            .iter()
+            .map(|fix| fix.text.as_str())
+            .collect::<Vec<_>>(),
+        vec!["use work.pkg.c;\n\n", "use work.pkg.all;\n\n"]
+    );
+}
+
+#[test]
+fn no_fixes_for_unknown_name() {
+    let mut builder = LibraryBuilder::new();
+    add_common(&mut builder);
+    let code = builder.code(
+        "lib",
+        "
+package other is
+  constant d : natural := missing;
+end package;
+",
+    );
+
+    assert_eq!(fixes(&builder, &code, "missing"), vec![]);
+}
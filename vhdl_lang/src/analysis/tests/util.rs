@@ -15,6 +15,7 @@ use std::sync::Arc;
 pub struct LibraryBuilder {
     code_builder: CodeBuilder,
     libraries: HashMap<Symbol, Vec<Code>>,
+    standards: HashMap<Symbol, VHDLStandard>,
 }
 
 impl LibraryBuilder {
@@ -22,9 +23,16 @@ impl LibraryBuilder {
         LibraryBuilder {
             code_builder: CodeBuilder::new(),
             libraries: HashMap::default(),
+            standards: HashMap::default(),
         }
     }
 
+    /// Parse and analyze the code of a library with a VHDL revision other than the default
+    pub fn set_standard(&mut self, library_name: &str, standard: VHDLStandard) {
+        let library_name = self.code_builder.symbol(library_name);
+        self.standards.insert(library_name, standard);
+    }
+
     fn add_code(&mut self, library_name: &str, code: Code) {
         let library_name = self.code_builder.symbol(library_name);
         match self.libraries.entry(library_name) {
@@ -75,9 +83,17 @@ end architecture;"
 
         add_standard_library(self.symbols(), &mut root);
 
+        for (library_name, standard) in self.standards.iter() {
+            root.set_library_standard(library_name.clone(), *standard);
+        }
+
         for (library_name, codes) in self.libraries.iter() {
+            let standard = root.standard_of(library_name);
             for code in codes {
-                root.add_design_file(library_name.clone(), code.design_file());
+                root.add_design_file(
+                    library_name.clone(),
+                    code.with_standard(standard).design_file(),
+                );
             }
         }
         root.analyze(&mut diagnostics);
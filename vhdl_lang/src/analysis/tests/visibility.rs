@@ -778,6 +778,11 @@ fn generics_are_visible_in_procedures_but_not_outside() {
     let (_, diagnostics) = builder.get_analyzed_root();
     assert_eq!(
         diagnostics,
-        vec![Diagnostic::error(code.s("T", 4), "No declaration of 'T'")]
+        vec![Diagnostic::new(
+            code.s("T", 4),
+            "No declaration of 'T'",
+            ErrorCode::Unresolved
+        )
+        .with_data("T")]
     )
 }
@@ -0,0 +1,67 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Relations between types and the subtypes, aliases and composite types declared from them
+
+use super::named_entity::{Type, TypeEnt};
+use super::root::DesignRoot;
+use crate::ast::search::FindAllEnt;
+use crate::EntRef;
+
+impl DesignRoot {
+    /// The types a type is declared from: the type mark of a subtype or type alias,
+    /// the element type of an array type and the element types of a record type.
+    /// Following the type marks through any chain of subtypes ends at the base type.
+    pub fn supertypes<'a>(&'a self, typ: EntRef<'a>) -> Vec<EntRef<'a>> {
+        supertypes(typ).into_iter().map(EntRef::from).collect()
+    }
+
+    /// The subtypes, type aliases and composite types declared from a type.
+    /// Only the unit declaring the type and the units depending on it are searched.
+    pub fn subtypes<'a>(&'a self, typ: EntRef<'a>) -> Vec<EntRef<'a>> {
+        let Some(typ) = TypeEnt::from_any(typ) else {
+            return Vec::new();
+        };
+        let mut searcher = FindAllEnt::new(self, |ent| {
+            supertypes(ent)
+                .iter()
+                .any(|supertype| supertype.id() == typ.id())
+        });
+        let _ = self.search_users_of(typ.into(), &mut searcher);
+
+        let mut subtypes = searcher.result;
+        subtypes.sort_by(|a, b| {
+            let key = |ent: &EntRef| {
+                ent.decl_pos()
+                    .map(|pos| (pos.file_name().to_owned(), pos.start()))
+            };
+            key(a).cmp(&key(b))
+        });
+        subtypes.dedup_by_key(|ent| ent.id());
+        subtypes
+    }
+}
+
+fn supertypes(ent: EntRef) -> Vec<TypeEnt> {
+    let Some(typ) = TypeEnt::from_any(ent) else {
+        return Vec::new();
+    };
+    let mut supertypes = match typ.kind() {
+        Type::Subtype(subtype) => vec![subtype.type_mark()],
+        Type::Alias(aliased) => vec![*aliased],
+        Type::Array { elem_type, .. } => vec![*elem_type],
+        Type::Record(region) => region.iter().map(|elem| elem.type_mark()).collect(),
+        _ => Vec::new(),
+    };
+    // Several record elements may have the same type
+    let mut seen = Vec::with_capacity(supertypes.len());
+    supertypes.retain(|typ| {
+        let is_new = !seen.contains(&typ.id());
+        seen.push(typ.id());
+        is_new
+    });
+    supertypes
+}
@@ -0,0 +1,189 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Context clauses that make declarations of other packages visible
+
+use super::root::{DesignRoot, LockedUnit};
+use crate::ast::{Designator, HasUnitId};
+use crate::data::*;
+use crate::syntax::{Kind, Token, Value};
+use crate::{AnyEntKind, Design};
+
+/// Context clauses to insert into the context clause of a design unit
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct UseClauseFix {
+    /// A short description such as `Add use ieee.numeric_std.all`
+    pub title: String,
+    /// The position where the text is inserted
+    pub pos: Position,
+    /// The library clause, when needed, and the use clause
+    pub text: String,
+}
+
+impl DesignRoot {
+    /// Find use clauses that would make declarations named `name` in any package visible
+    /// in the design unit at the cursor. For every package there is one fix using the name
+    /// itself and one using `all`.
+    pub fn find_use_clause_fixes(
+        &self,
+        source: &Source,
+        cursor: Position,
+        name: &str,
+    ) -> Vec<UseClauseFix> {
+        let Some(unit) = self.unit_at(source, cursor) else {
+            return Vec::new();
+        };
+        let name = Designator::Identifier(self.symbol_utf8(name));
+        let unit_library = unit.unit_id().library_name();
+        let context = ContextClauseTokens::new(&unit.tokens);
+
+        let mut packages: Vec<(Symbol, Symbol, String)> = self
+            .public_symbols()
+            .filter(|ent| ent.designator() == &name)
+            .filter_map(|ent| {
+                let package = ent.parent?;
+                if !matches!(package.kind(), AnyEntKind::Design(Design::Package(..))) {
+                    return None;
+                }
+                let Designator::Identifier(package_name) = package.designator() else {
+                    return None;
+                };
+                Some((
+                    package.library_name()?.clone(),
+                    package_name.clone(),
+                    ent.designator().to_string(),
+                ))
+            })
+            .collect();
+        packages.sort_by_key(|(library_name, package_name, _)| {
+            (library_name.name_utf8(), package_name.name_utf8())
+        });
+        packages.dedup_by(|a, b| a.0 == b.0 && a.1 == b.1);
+
+        let mut fixes = Vec::new();
+        for (library_name, package_name, designator) in packages {
+            let prefix = if &library_name == unit_library {
+                "work".to_owned()
+            } else {
+                library_name.to_string()
+            };
+
+            let library_clause = context
+                .library_clause(self, &library_name, unit_library)
+                .unwrap_or_default();
+
+            for suffix in [designator.as_str(), "all"] {
+                let use_clause = format!("use {prefix}.{package_name}.{suffix};");
+                let mut text = format!("{library_clause}{use_clause}\n");
+                if !context.has_items() {
+                    // Separate the new context clause from the design unit
+                    text.push('\n');
+                }
+                fixes.push(UseClauseFix {
+                    title: format!("Add {}", use_clause.trim_end_matches(';')),
+                    pos: context.insert_pos,
+                    text,
+                });
+            }
+        }
+        fixes
+    }
+
+    /// The design unit of the source that contains the cursor
+    pub(super) fn unit_at(&self, source: &Source, cursor: Position) -> Option<&LockedUnit> {
+        self.libraries()
+            .flat_map(|library| library.units())
+            .find(|unit| {
+                let (Some(first), Some(last)) = (unit.tokens.first(), unit.tokens.last()) else {
+                    return false;
+                };
+                &first.pos.source == source
+                    && first.pos.start() <= cursor
+                    && cursor <= last.pos.end()
+            })
+    }
+}
+
+/// The context clause of a design unit found from its tokens
+pub(super) struct ContextClauseTokens {
+    pub libraries: Vec<Symbol>,
+    /// The line of the last context item when there is a context clause
+    pub end_line: Option<u32>,
+    /// The start of the line after the context clause or the start of the design unit
+    /// when there is no context clause or the design unit continues on the same line
+    pub insert_pos: Position,
+}
+
+impl ContextClauseTokens {
+    pub fn new(tokens: &[Token]) -> ContextClauseTokens {
+        let mut libraries = Vec::new();
+        let mut last_semi: Option<&Token> = None;
+        let mut idx = 0;
+
+        while let Some(token) = tokens.get(idx) {
+            let is_context_item = match token.kind {
+                Kind::Library | Kind::Use => true,
+                // A context declaration is a design unit and not a context reference
+                Kind::Context => tokens.get(idx + 2).map(|token| token.kind) != Some(Kind::Is),
+                _ => false,
+            };
+            if !is_context_item {
+                break;
+            }
+
+            let is_library = token.kind == Kind::Library;
+            idx += 1;
+            while let Some(token) = tokens.get(idx) {
+                idx += 1;
+                match (&token.kind, &token.value) {
+                    (Kind::SemiColon, _) => {
+                        last_semi = Some(token);
+                        break;
+                    }
+                    (Kind::Identifier, Value::Identifier(library_name)) if is_library => {
+                        libraries.push(library_name.clone());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let end_line = last_semi.map(|semi| semi.pos.end().line);
+        let insert_pos = match (end_line, tokens.get(idx)) {
+            (Some(end_line), Some(unit)) if unit.pos.start().line == end_line => unit.pos.start(),
+            (Some(end_line), _) => Position::new(end_line + 1, 0),
+            (None, Some(unit)) => unit.pos.start(),
+            (None, None) => Position::default(),
+        };
+
+        ContextClauseTokens {
+            libraries,
+            end_line,
+            insert_pos,
+        }
+    }
+
+    pub fn has_items(&self) -> bool {
+        self.end_line.is_some()
+    }
+
+    /// The library clause needed to refer to a library from a design unit of `unit_library`
+    pub fn library_clause(
+        &self,
+        root: &DesignRoot,
+        library_name: &Symbol,
+        unit_library: &Symbol,
+    ) -> Option<String> {
+        if library_name == unit_library
+            || library_name == &root.symbol_utf8("std")
+            || self.libraries.contains(library_name)
+        {
+            None
+        } else {
+            Some(format!("library {library_name};\n"))
+        }
+    }
+}
@@ -61,6 +61,32 @@ impl<'a> Visibility<'a> {
         });
     }
 
+    /// Make the declarations of a region potentially visible one by one,
+    /// except for those where `exclude` returns true
+    pub fn make_potentially_visible_except(
+        &mut self,
+        visible_pos: Option<&SrcPos>,
+        region: &'a Region<'a>,
+        exclude: impl Fn(EntRef<'a>) -> bool,
+    ) {
+        for named_entities in region.entities.values() {
+            let entities: Vec<EntRef<'a>> = match named_entities {
+                NamedEntities::Single(ent) => vec![*ent],
+                NamedEntities::Overloaded(overloaded) => {
+                    overloaded.entities().map(|ent| ent.into()).collect()
+                }
+            };
+
+            for ent in entities.into_iter().filter(|ent| !exclude(ent)) {
+                let visible_ent = VisibleEntity {
+                    visible_pos: vec![visible_pos.cloned()],
+                    entity: ent,
+                };
+                self.insert(ent.designator().clone(), visible_ent);
+            }
+        }
+    }
+
     pub fn add_context_visibility(
         &mut self,
         visible_pos: Option<&SrcPos>,
@@ -173,6 +199,11 @@ impl<'a> Visible<'a> {
         };
     }
 
+    /// All visible named entities, including those that hide each other
+    pub fn entities(&self) -> impl Iterator<Item = EntRef<'a>> + '_ {
+        self.visible_entities.values().map(|ent| ent.entity)
+    }
+
     pub fn into_unambiguous(
         self,
         pos: &SrcPos,
@@ -197,9 +228,10 @@ impl<'a> Visible<'a> {
             Ok(Some(NamedEntities::new(named_entities.pop().unwrap())))
         } else {
             // Duplicate visible items hide each other
-            let mut error = Diagnostic::error(
+            let mut error = Diagnostic::new(
                 pos,
                 format!("Name '{designator}' is hidden by conflicting use clause"),
+                ErrorCode::ConflictingUseClause,
             );
 
             fn last_visible_pos(visible_entity: &VisibleEntity) -> u32 {
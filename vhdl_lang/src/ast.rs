@@ -131,6 +131,7 @@ pub enum AttributeDesignator {
     SimpleName,
     InstanceName,
     PathName,
+    Converse,
 }
 
 #[derive(PartialEq, Debug, Copy, Clone, Eq)]
@@ -534,6 +535,10 @@ pub enum EntityClass {
     Component,
     Constant,
     Type,
+    Subtype,
+    File,
+    Units,
+    Group,
     Label,
 }
 
@@ -553,6 +558,30 @@ pub enum Attribute {
     Declaration(AttributeDeclaration),
 }
 
+/// LRM 6.9 Group template declarations
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct EntityClassEntry {
+    pub entity_class: EntityClass,
+    /// Followed by `<>` meaning any number of constituents of this class
+    pub is_box: bool,
+}
+
+/// LRM 6.9 Group template declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupTemplateDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub entity_class_entries: Vec<EntityClassEntry>,
+}
+
+/// LRM 6.10 Group declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct GroupDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub group_template: WithPos<SelectedName>,
+    /// Names or character literals
+    pub constituents: Vec<WithPos<Name>>,
+}
+
 /// LRM 5.6.2 Protected type declarations
 #[derive(PartialEq, Debug, Clone)]
 pub struct ProtectedTypeDeclaration {
@@ -634,11 +663,19 @@ pub enum InterfaceType {
     Parameter,
 }
 
+/// LRM 6.4.2.3 Signal declarations
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum SignalKind {
+    Register,
+    Bus,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub struct ObjectDeclaration {
     pub class: ObjectClass,
     pub ident: WithDecl<Ident>,
     pub subtype_indication: SubtypeIndication,
+    pub signal_kind: Option<SignalKind>,
     pub expression: Option<WithPos<Expression>>,
 }
 
@@ -742,6 +779,7 @@ pub struct InterfaceObjectDeclaration {
     pub ident: WithDecl<Ident>,
     pub mode: Mode,
     pub subtype_indication: SubtypeIndication,
+    pub signal_kind: Option<SignalKind>,
     pub expression: Option<WithPos<Expression>>,
 }
 
@@ -775,6 +813,33 @@ pub enum InterfaceDeclaration {
     Subprogram(SubprogramDeclaration, Option<SubprogramDefault>),
     /// LRM 6.5.5 Interface package declaration
     Package(InterfacePackageDeclaration),
+    /// VHDL-2019 LRM 6.5.2 Interface object declarations with a mode view indication
+    View(InterfaceViewDeclaration),
+}
+
+/// VHDL-2019 LRM 6.5.2 Interface object declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct InterfaceViewDeclaration {
+    pub list_type: InterfaceType,
+    pub ident: WithDecl<Ident>,
+    pub view: ModeViewIndication,
+}
+
+/// VHDL-2019 LRM 6.5.2 Interface object declarations
+#[derive(PartialEq, Debug, Clone)]
+pub enum ModeViewIndicationKind {
+    /// view name [of subtype_indication]
+    Record,
+    /// view (name) of subtype_indication
+    Array,
+}
+
+/// VHDL-2019 LRM 6.5.2 Interface object declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewIndication {
+    pub kind: ModeViewIndicationKind,
+    pub name: WithPos<Name>,
+    pub subtype_indication: Option<SubtypeIndication>,
 }
 
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
@@ -814,6 +879,10 @@ pub enum Declaration {
     Use(UseClause),
     Package(PackageInstantiation),
     Configuration(ConfigurationSpecification),
+    GroupTemplate(GroupTemplateDeclaration),
+    Group(GroupDeclaration),
+    Disconnection(DisconnectionSpecification),
+    View(ModeViewDeclaration),
 }
 
 /// LRM 10.2 Wait statement
@@ -1302,6 +1371,46 @@ pub struct ConfigurationSpecification {
     pub vunit_bind_inds: Vec<VUnitBindingIndication>,
 }
 
+/// LRM 7.4 Disconnection specification
+#[derive(PartialEq, Debug, Clone)]
+pub enum GuardedSignalList {
+    Names(Vec<WithPos<Name>>),
+    Others,
+    All,
+}
+
+/// LRM 7.4 Disconnection specification
+#[derive(PartialEq, Debug, Clone)]
+pub struct DisconnectionSpecification {
+    pub signal_list: GuardedSignalList,
+    pub type_mark: WithPos<TypeMark>,
+    pub after: WithPos<Expression>,
+}
+
+/// VHDL-2019 LRM 6.5.2.2 Mode view declarations
+#[derive(PartialEq, Debug, Clone)]
+pub enum ElementMode {
+    Simple(WithPos<Mode>),
+    Record(WithPos<Name>),
+    Array(WithPos<Name>),
+}
+
+/// VHDL-2019 LRM 6.5.2.2 Mode view declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewElementDefinition {
+    pub names: Vec<WithRef<Ident>>,
+    pub mode: ElementMode,
+}
+
+/// VHDL-2019 LRM 6.5.2.2 Mode view declarations
+#[derive(PartialEq, Debug, Clone)]
+pub struct ModeViewDeclaration {
+    pub ident: WithDecl<Ident>,
+    pub typ: SubtypeIndication,
+    pub elements: Vec<ModeViewElementDefinition>,
+    pub end_ident_pos: Option<SrcPos>,
+}
+
 /// LRM 3.4 Configuration declarations
 #[derive(PartialEq, Debug, Clone)]
 pub enum ConfigurationDeclarativeItem {
@@ -1428,4 +1537,6 @@ pub enum AnyDesignUnit {
 #[derive(PartialEq, Debug, Clone, Default)]
 pub struct DesignFile {
     pub design_units: Vec<(Vec<Token>, AnyDesignUnit)>,
+    // Regions excluded by conditional analysis directives
+    pub inactive_regions: Vec<SrcPos>,
 }
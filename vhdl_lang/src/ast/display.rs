@@ -120,6 +120,7 @@ impl Display for AttributeDesignator {
             AttributeDesignator::SimpleName => write!(f, "simple_name"),
             AttributeDesignator::InstanceName => write!(f, "instance_name"),
             AttributeDesignator::PathName => write!(f, "path_name"),
+            AttributeDesignator::Converse => write!(f, "converse"),
         }
     }
 }
@@ -241,6 +242,134 @@ impl Display for AttributeDeclaration {
     }
 }
 
+impl Display for EntityClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let class = match self {
+            EntityClass::Entity => "entity",
+            EntityClass::Architecture => "architecture",
+            EntityClass::Configuration => "configuration",
+            EntityClass::Package => "package",
+            EntityClass::Signal => "signal",
+            EntityClass::Variable => "variable",
+            EntityClass::Procedure => "procedure",
+            EntityClass::Function => "function",
+            EntityClass::Component => "component",
+            EntityClass::Constant => "constant",
+            EntityClass::Type => "type",
+            EntityClass::Subtype => "subtype",
+            EntityClass::File => "file",
+            EntityClass::Units => "units",
+            EntityClass::Group => "group",
+            EntityClass::Label => "label",
+        };
+        write!(f, "{class}")
+    }
+}
+
+impl Display for EntityClassEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.is_box {
+            write!(f, "{} <>", self.entity_class)
+        } else {
+            write!(f, "{}", self.entity_class)
+        }
+    }
+}
+
+impl Display for GroupTemplateDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "group {} is (", self.ident)?;
+        for (i, entry) in self.entity_class_entries.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{entry}")?;
+        }
+        write!(f, ");")
+    }
+}
+
+impl Display for GroupDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "group {} : {} (", self.ident, self.group_template)?;
+        for (i, constituent) in self.constituents.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{constituent}")?;
+        }
+        write!(f, ");")
+    }
+}
+
+impl Display for DisconnectionSpecification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "disconnect ")?;
+        match self.signal_list {
+            GuardedSignalList::Names(ref names) => {
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}")?;
+                }
+            }
+            GuardedSignalList::Others => write!(f, "others")?,
+            GuardedSignalList::All => write!(f, "all")?,
+        }
+        write!(f, " : {} after {};", self.type_mark, self.after)
+    }
+}
+
+impl Display for ElementMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ElementMode::Simple(ref mode) => write!(f, "{mode}"),
+            ElementMode::Record(ref name) => write!(f, "view {name}"),
+            ElementMode::Array(ref name) => write!(f, "view ({name})"),
+        }
+    }
+}
+
+impl Display for ModeViewDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "view {} of {} is", self.ident, self.typ)?;
+        for element in &self.elements {
+            write!(f, "\n  ")?;
+            for (i, name) in element.names.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{name}")?;
+            }
+            write!(f, " : {};", element.mode)?;
+        }
+        write!(f, "\nend view;")
+    }
+}
+
+impl Display for ModeViewIndication {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self.kind {
+            ModeViewIndicationKind::Record => write!(f, "view {}", self.name)?,
+            ModeViewIndicationKind::Array => write!(f, "view ({})", self.name)?,
+        }
+        match self.subtype_indication {
+            Some(ref subtype_indication) => write!(f, " of {subtype_indication}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for InterfaceViewDeclaration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.list_type == InterfaceType::Parameter {
+            write!(f, "signal ")?;
+        }
+        write!(f, "{} : {}", self.ident, self.view)
+    }
+}
+
 impl Display for Choice {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -739,6 +868,15 @@ impl Display for ObjectClass {
     }
 }
 
+impl Display for SignalKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            SignalKind::Register => write!(f, "register"),
+            SignalKind::Bus => write!(f, "bus"),
+        }
+    }
+}
+
 impl Display for ObjectDeclaration {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         write!(
@@ -746,6 +884,9 @@ impl Display for ObjectDeclaration {
             "{} {} : {}",
             self.class, self.ident, self.subtype_indication,
         )?;
+        if let Some(signal_kind) = self.signal_kind {
+            write!(f, " {signal_kind}")?;
+        }
         match self.expression {
             Some(ref expr) => write!(f, " := {expr};"),
             None => write!(f, ";"),
@@ -927,6 +1068,9 @@ impl Display for InterfaceObjectDeclaration {
                 )?;
             }
         }
+        if let Some(signal_kind) = self.signal_kind {
+            write!(f, " {signal_kind}")?;
+        }
         match self.expression {
             Some(ref expr) => write!(f, " := {expr}"),
             None => Ok(()),
@@ -983,6 +1127,7 @@ impl Display for InterfaceDeclaration {
                 }
             }
             InterfaceDeclaration::Package(ref decl) => write!(f, "{decl}"),
+            InterfaceDeclaration::View(ref decl) => write!(f, "{decl}"),
         }
     }
 }
@@ -1777,6 +1922,12 @@ end units;",
         assert_format("constant foo : natural := 0;", Code::object_decl);
     }
 
+    #[test]
+    fn test_object_declaration_signal_kind() {
+        assert_format("signal foo : resolved_bit register;", Code::object_decl);
+        assert_format("signal foo : resolved_bit bus := '0';", Code::object_decl);
+    }
+
     #[test]
     fn test_file_declaration() {
         assert_format("file foo : text;", Code::file_decl);
@@ -1820,6 +1971,44 @@ end units;",
         assert_format("alias 'c' is 'b';", Code::alias_decl);
     }
 
+    #[test]
+    fn test_group_template_declaration() {
+        assert_format(
+            "group pin2pin is (signal, label <>);",
+            Code::group_template_decl,
+        );
+    }
+
+    #[test]
+    fn test_group_declaration() {
+        assert_format("group g : lib.pin2pin (clk, 'a');", Code::group_decl);
+    }
+
+    #[test]
+    fn test_disconnection_specification() {
+        assert_format(
+            "disconnect sig_a, sig_b : resolved_bit after 5 ns;",
+            Code::disconnection_spec,
+        );
+        assert_format(
+            "disconnect others : resolved_bit after 5 ns;",
+            Code::disconnection_spec,
+        );
+    }
+
+    #[test]
+    fn test_mode_view_declaration() {
+        assert_format(
+            "view master of bus_rec is
+  addr, wdata : out;
+  rdata : in;
+  sub : view sub_view;
+  subs : view (sub_view'converse);
+end view;",
+            |code| code.with_standard(VHDLStandard::VHDL2019).view_decl(),
+        );
+    }
+
     #[test]
     pub fn test_procedure_specification() {
         assert_format("procedure foo", Code::subprogram_decl);
@@ -1898,6 +2087,14 @@ end units;",
         assert_format("foo : in natural := bar(0)", Code::port);
     }
 
+    #[test]
+    fn test_interface_declaration_view_port() {
+        let port = |code: &Code| code.with_standard(VHDLStandard::VHDL2019).port();
+        assert_format("foo : view master", port);
+        assert_format("foo : view master'converse of bus_rec", port);
+        assert_format("foo : view (master) of bus_array", port);
+    }
+
     #[test]
     fn test_interface_declaration_file() {
         assert_format("file foo : text", Code::parameter);
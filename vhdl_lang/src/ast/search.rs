@@ -55,6 +55,10 @@ pub enum FoundDeclaration<'a> {
     Component(&'a mut ComponentDeclaration),
     Attribute(&'a mut AttributeDeclaration),
     Alias(&'a mut AliasDeclaration),
+    GroupTemplate(&'a mut GroupTemplateDeclaration),
+    Group(&'a mut GroupDeclaration),
+    View(&'a mut ModeViewDeclaration),
+    InterfaceView(&'a mut InterfaceViewDeclaration),
     SubprogramDecl(&'a mut SubprogramDeclaration),
     Subprogram(&'a mut SubprogramBody),
     SubprogramInstantiation(&'a mut SubprogramInstantiation),
@@ -295,6 +299,7 @@ impl Search for LabeledSequentialStatement {
                 )
                 .or_not_found());
         }
+        return_if_finished!(searcher.search_with_pos(ctx, &self.statement.pos));
         match self.statement.item {
             SequentialStatement::Return(ref mut ret) => {
                 let ReturnStatement { ref mut expression } = ret;
@@ -495,9 +500,10 @@ impl Search for LabeledConcurrentStatement {
                 )
                 .or_not_found());
         }
+        return_if_finished!(searcher.search_with_pos(ctx, &self.statement.pos));
         match self.statement.item {
             ConcurrentStatement::Block(ref mut block) => {
-                // @TODO guard condition
+                return_if_found!(block.guard_condition.search(ctx, searcher));
                 return_if_found!(block.decl.search(ctx, searcher));
                 return_if_found!(block.statements.search(ctx, searcher));
             }
@@ -1100,6 +1106,56 @@ impl Search for Declaration {
             Declaration::Configuration(_) => {
                 // @TODO
             }
+
+            Declaration::GroupTemplate(template) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::GroupTemplate(template))
+                    .or_not_found());
+            }
+
+            Declaration::Group(group) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::Group(group))
+                    .or_not_found());
+                let GroupDeclaration {
+                    ident: _,
+                    group_template,
+                    constituents,
+                } = group;
+                return_if_found!(group_template.search(ctx, searcher));
+                return_if_found!(constituents.search(ctx, searcher));
+            }
+
+            Declaration::Disconnection(disconnection) => {
+                let DisconnectionSpecification {
+                    signal_list,
+                    type_mark,
+                    after,
+                } = disconnection;
+                if let GuardedSignalList::Names(names) = signal_list {
+                    return_if_found!(names.search(ctx, searcher));
+                }
+                return_if_found!(type_mark.search(ctx, searcher));
+                return_if_found!(after.search(ctx, searcher));
+            }
+
+            Declaration::View(view) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::View(view))
+                    .or_not_found());
+                return_if_found!(view.typ.search(ctx, searcher));
+                for element in view.elements.iter_mut() {
+                    for name in element.names.iter_mut() {
+                        return_if_found!(searcher.search_ident_ref(ctx, name).or_not_found());
+                    }
+                    match element.mode {
+                        ElementMode::Simple(_) => {}
+                        ElementMode::Record(ref mut name) | ElementMode::Array(ref mut name) => {
+                            return_if_found!(name.search(ctx, searcher));
+                        }
+                    }
+                }
+            }
         }
         NotFound
     }
@@ -1152,6 +1208,13 @@ impl Search for InterfaceDeclaration {
                     .search_decl(ctx, FoundDeclaration::InterfaceFile(decl))
                     .or_not_found());
             }
+            InterfaceDeclaration::View(decl) => {
+                return_if_found!(searcher
+                    .search_decl(ctx, FoundDeclaration::InterfaceView(decl))
+                    .or_not_found());
+                return_if_found!(decl.view.name.search(ctx, searcher));
+                return_if_found!(decl.view.subtype_indication.search(ctx, searcher));
+            }
         };
         NotFound
     }
@@ -1546,6 +1609,8 @@ pub struct FindAllReferences<'a> {
     root: &'a DesignRoot,
     ent: EntRef<'a>,
     pub references: Vec<SrcPos>,
+    /// The references that are declarations
+    pub declarations: Vec<SrcPos>,
 }
 
 fn is_instance_of(ent: EntRef, other: EntRef) -> bool {
@@ -1602,6 +1667,7 @@ impl<'a> FindAllReferences<'a> {
             root,
             ent,
             references: Vec::new(),
+            declarations: Vec::new(),
         }
     }
 }
@@ -1613,6 +1679,7 @@ impl<'a> Searcher for FindAllReferences<'a> {
 
             if is_reference(self.ent, other) {
                 self.references.push(decl.pos().clone());
+                self.declarations.push(decl.pos().clone());
                 if let Some(pos) = decl.end_ident_pos() {
                     self.references.push(pos.clone());
                 }
@@ -1637,8 +1704,60 @@ impl<'a> Searcher for FindAllReferences<'a> {
     }
 }
 
+/// Find all declarations of and references to named entities within a source file
+pub struct FindAllInSource<'a> {
+    root: &'a DesignRoot,
+    source: Source,
+    pub result: Vec<(SrcPos, EntRef<'a>, bool)>,
+}
+
+impl<'a> FindAllInSource<'a> {
+    pub fn new(root: &'a DesignRoot, source: &Source) -> FindAllInSource<'a> {
+        FindAllInSource {
+            root,
+            source: source.clone(),
+            result: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Searcher for FindAllInSource<'a> {
+    fn search_decl(&mut self, _ctx: &dyn TokenAccess, decl: FoundDeclaration) -> SearchState {
+        if let Some(id) = decl.ent_id() {
+            let ent = self.root.get_ent(id);
+            self.result.push((decl.pos().clone(), ent, true));
+            if let Some(pos) = decl.end_ident_pos() {
+                self.result.push((pos.clone(), ent, false));
+            }
+        }
+        NotFinished
+    }
+
+    fn search_pos_with_ref(
+        &mut self,
+        _ctx: &dyn TokenAccess,
+        pos: &SrcPos,
+        reference: &mut Reference,
+    ) -> SearchState {
+        if let Some(id) = reference.as_ref() {
+            self.result
+                .push((pos.clone(), self.root.get_ent(*id), false));
+        };
+        NotFinished
+    }
+
+    // Assume source is searched first to filter out design units in other files
+    fn search_source(&mut self, _ctx: &dyn TokenAccess, source: &Source) -> SearchState {
+        if source == &self.source {
+            NotFinished
+        } else {
+            Finished(NotFound)
+        }
+    }
+}
+
 impl<'a> FoundDeclaration<'a> {
-    fn end_ident_pos(&self) -> Option<&SrcPos> {
+    pub fn end_ident_pos(&self) -> Option<&SrcPos> {
         match self {
             FoundDeclaration::InterfaceObject(_) => None,
             FoundDeclaration::ForIndex(..) => None,
@@ -1658,6 +1777,10 @@ impl<'a> FoundDeclaration<'a> {
             FoundDeclaration::Component(value) => value.end_ident_pos.as_ref(),
             FoundDeclaration::Attribute(..) => None,
             FoundDeclaration::Alias(..) => None,
+            FoundDeclaration::GroupTemplate(..) => None,
+            FoundDeclaration::Group(..) => None,
+            FoundDeclaration::View(value) => value.end_ident_pos.as_ref(),
+            FoundDeclaration::InterfaceView(..) => None,
             FoundDeclaration::Package(value) => value.end_ident_pos.as_ref(),
             FoundDeclaration::PackageBody(value) => value.end_ident_pos.as_ref(),
             FoundDeclaration::PackageInstance(..) => None,
@@ -1693,6 +1816,10 @@ impl<'a> FoundDeclaration<'a> {
             FoundDeclaration::Component(value) => &mut value.ident.decl,
             FoundDeclaration::Attribute(value) => &mut value.ident.decl,
             FoundDeclaration::Alias(value) => &mut value.designator.decl,
+            FoundDeclaration::GroupTemplate(value) => &mut value.ident.decl,
+            FoundDeclaration::Group(value) => &mut value.ident.decl,
+            FoundDeclaration::View(value) => &mut value.ident.decl,
+            FoundDeclaration::InterfaceView(value) => &mut value.ident.decl,
             FoundDeclaration::Package(value) => &mut value.ident.decl,
             FoundDeclaration::PackageBody(value) => &mut value.ident.decl,
             FoundDeclaration::PackageInstance(value) => &mut value.ident.decl,
@@ -1740,6 +1867,10 @@ impl<'a> HasEntityId for FoundDeclaration<'a> {
             FoundDeclaration::Component(value) => value.ident.decl,
             FoundDeclaration::Attribute(value) => value.ident.decl,
             FoundDeclaration::Alias(value) => value.designator.decl,
+            FoundDeclaration::GroupTemplate(value) => value.ident.decl,
+            FoundDeclaration::Group(value) => value.ident.decl,
+            FoundDeclaration::View(value) => value.ident.decl,
+            FoundDeclaration::InterfaceView(value) => value.ident.decl,
             FoundDeclaration::Package(value) => value.ident.decl,
             FoundDeclaration::PackageBody(value) => value.ident.decl,
             FoundDeclaration::PackageInstance(value) => value.ident.decl,
@@ -1775,6 +1906,10 @@ impl<'a> HasSrcPos for FoundDeclaration<'a> {
             FoundDeclaration::PhysicalTypeSecondary(value, _) => value.as_ref(),
             FoundDeclaration::Component(value) => value.ident.pos(),
             FoundDeclaration::Alias(value) => &value.designator.tree.pos,
+            FoundDeclaration::GroupTemplate(value) => value.ident.pos(),
+            FoundDeclaration::Group(value) => value.ident.pos(),
+            FoundDeclaration::View(value) => value.ident.pos(),
+            FoundDeclaration::InterfaceView(value) => value.ident.pos(),
             FoundDeclaration::Attribute(value) => value.ident.pos(),
             FoundDeclaration::Package(value) => value.ident.pos(),
             FoundDeclaration::PackageBody(value) => value.ident.pos(),
@@ -1850,6 +1985,18 @@ impl std::fmt::Display for FoundDeclaration<'_> {
             FoundDeclaration::Alias(ref value) => {
                 write!(f, "{value}")
             }
+            FoundDeclaration::GroupTemplate(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::Group(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::View(ref value) => {
+                write!(f, "{value}")
+            }
+            FoundDeclaration::InterfaceView(ref value) => {
+                write!(f, "{value}")
+            }
             FoundDeclaration::Attribute(ref value) => {
                 write!(f, "{value}")
             }
@@ -60,7 +60,7 @@ pub fn to_simple_name(name: WithPos<Name>) -> DiagnosticResult<Ident> {
             item: ident,
             pos: name.pos,
         }),
-        _ => Err(Diagnostic::error(&name, "Expected simple name")),
+        _ => Err(Diagnostic::syntax_error(&name, "Expected simple name")),
     }
 }
 
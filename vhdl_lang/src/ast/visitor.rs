@@ -225,6 +225,65 @@ pub trait Visitor {
     fn visit_attribute(&mut self, _node: &Attribute, _ctx: &dyn TokenAccess) -> VisitorResult {
         Continue
     }
+    fn visit_group_template_declaration(
+        &mut self,
+        _node: &GroupTemplateDeclaration,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_group_declaration(
+        &mut self,
+        _node: &GroupDeclaration,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_disconnection_specification(
+        &mut self,
+        _node: &DisconnectionSpecification,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_guarded_signal_list(
+        &mut self,
+        _node: &GuardedSignalList,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_mode_view_declaration(
+        &mut self,
+        _node: &ModeViewDeclaration,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_mode_view_element_definition(
+        &mut self,
+        _node: &ModeViewElementDefinition,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_element_mode(&mut self, _node: &ElementMode, _ctx: &dyn TokenAccess) -> VisitorResult {
+        Continue
+    }
+    fn visit_interface_view_declaration(
+        &mut self,
+        _node: &InterfaceViewDeclaration,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
+    fn visit_mode_view_indication(
+        &mut self,
+        _node: &ModeViewIndication,
+        _ctx: &dyn TokenAccess,
+    ) -> VisitorResult {
+        Continue
+    }
     fn visit_protected_type_declaration(
         &mut self,
         _node: &ProtectedTypeDeclaration,
@@ -1190,6 +1249,10 @@ impl ASTNode for Declaration {
             Declaration::Package(decl) => vec![decl],
             Declaration::Configuration(decl) => vec![decl],
             Declaration::SubprogramInstantiation(decl) => vec![decl],
+            Declaration::GroupTemplate(decl) => vec![decl],
+            Declaration::Group(decl) => vec![decl],
+            Declaration::Disconnection(decl) => vec![decl],
+            Declaration::View(decl) => vec![decl],
         }
     }
 }
@@ -1696,6 +1759,82 @@ impl ASTNode for SubtypeIndication {
     }
 }
 
+impl ASTNode for GroupTemplateDeclaration {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_group_template_declaration(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.ident]
+    }
+}
+
+impl ASTNode for GroupDeclaration {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_group_declaration(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.ident, &self.group_template, &self.constituents]
+    }
+}
+
+impl ASTNode for DisconnectionSpecification {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_disconnection_specification(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.signal_list, &self.type_mark, &self.after]
+    }
+}
+
+impl ASTNode for GuardedSignalList {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_guarded_signal_list(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        match self {
+            GuardedSignalList::Names(names) => vec![names],
+            GuardedSignalList::Others | GuardedSignalList::All => vec![],
+        }
+    }
+}
+
+impl ASTNode for ModeViewDeclaration {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_mode_view_declaration(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.ident, &self.typ, &self.elements]
+    }
+}
+
+impl ASTNode for ModeViewElementDefinition {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_mode_view_element_definition(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.names, &self.mode]
+    }
+}
+
+impl ASTNode for ElementMode {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_element_mode(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        match self {
+            ElementMode::Simple(_) => vec![],
+            ElementMode::Record(name) | ElementMode::Array(name) => vec![name],
+        }
+    }
+}
+
 impl ASTNode for AttributeSpecification {
     fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
         visitor.visit_attribute_specification(self, ctx)
@@ -1952,10 +2091,31 @@ impl ASTNode for InterfaceDeclaration {
             InterfaceDeclaration::Type(obj) => vec![obj],
             InterfaceDeclaration::Subprogram(decl, default) => vec![decl, default],
             InterfaceDeclaration::Package(pkg) => vec![pkg],
+            InterfaceDeclaration::View(decl) => vec![decl],
         }
     }
 }
 
+impl ASTNode for InterfaceViewDeclaration {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_interface_view_declaration(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.ident, &self.view]
+    }
+}
+
+impl ASTNode for ModeViewIndication {
+    fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
+        visitor.visit_mode_view_indication(self, ctx)
+    }
+
+    fn children(&self) -> Vec<&dyn ASTNode> {
+        vec![&self.name, &self.subtype_indication]
+    }
+}
+
 impl ASTNode for InterfaceObjectDeclaration {
     fn visit(&self, visitor: &mut dyn Visitor, ctx: &dyn TokenAccess) -> VisitorResult {
         visitor.visit_interface_object_declaration(self, ctx)
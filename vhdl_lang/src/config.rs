@@ -7,25 +7,49 @@
 //! Configuration of the design hierarchy and other settings
 
 use crate::data::*;
+use crate::syntax::{ConditionalAnalysis, FormatOptions, KeywordCase};
 use fnv::FnvHashMap;
 use std::env;
 use std::fs::File;
 use std::io;
 use std::io::prelude::*;
 use std::path::Path;
+use std::str::FromStr;
 use toml::Value;
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct Config {
     // A map from library name to file name
     libraries: FnvHashMap<String, LibraryConfig>,
+    // Configured severities of diagnostic codes
+    severities: SeverityMap,
+    // Formatting options when configured
+    format: Option<FormatOptions>,
+    // Library mapping of files not listed in any library
+    unmapped: UnmappedConfig,
+    // User-defined conditional analysis identifiers
+    conditional_analysis: FnvHashMap<String, String>,
+    // The VHDL revision of libraries without a standard of their own
+    standard: Option<VHDLStandard>,
+}
+
+/// The libraries of files that are not listed in any library such as new or scratch files
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+struct UnmappedConfig {
+    // The library of files not matching any pattern, work when not configured
+    default_library: Option<String>,
+    // A map from library name to file name patterns
+    libraries: FnvHashMap<String, Vec<glob::Pattern>>,
 }
 
 #[derive(Clone, PartialEq, Eq, Default, Debug)]
 pub struct LibraryConfig {
     name: String,
     patterns: Vec<String>,
+    // The glob patterns among the patterns, compiled when the configuration is parsed
+    globs: Vec<glob::Pattern>,
     pub(crate) is_third_party: bool,
+    standard: Option<VHDLStandard>,
 }
 
 impl LibraryConfig {
@@ -46,11 +70,7 @@ impl LibraryConfig {
 
         let mut result = Vec::new();
         for pattern in self.patterns.iter() {
-            let stripped_pattern = if cfg!(windows) {
-                pattern.strip_prefix("\\\\?\\").unwrap_or(pattern.as_str())
-            } else {
-                pattern.as_str()
-            };
+            let stripped_pattern = strip_verbatim_prefix(pattern);
 
             if is_literal(stripped_pattern) {
                 let file_path = Path::new(pattern);
@@ -119,10 +139,40 @@ impl LibraryConfig {
         result
     }
 
+    /// Returns true if a file name matches any of the patterns of the library,
+    /// the file does not have to be among the file names found when loading the configuration
+    pub fn matches(&self, file_name: &Path) -> bool {
+        let matches_literal = self
+            .patterns
+            .iter()
+            .filter(|pattern| is_literal(strip_verbatim_prefix(pattern)))
+            .any(|pattern| {
+                let path = Path::new(pattern);
+                path == file_name
+                    || dunce::canonicalize(path).is_ok_and(|abs_path| abs_path == file_name)
+            });
+
+        matches_literal
+            || self
+                .globs
+                .iter()
+                .any(|pattern| matches_path(pattern, file_name))
+    }
+
     /// Returns the name of the library
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
+
+    /// Returns true if the library is provided by a third party such as a tool vendor
+    pub fn is_third_party(&self) -> bool {
+        self.is_third_party
+    }
+
+    /// Returns the VHDL revision of the library when configured
+    pub fn standard(&self) -> Option<VHDLStandard> {
+        self.standard
+    }
 }
 
 impl Config {
@@ -143,19 +193,14 @@ impl Config {
                 .as_array()
                 .ok_or_else(|| format!("files for library {name} is not array"))?;
 
-            let mut patterns = Vec::new();
-            for file in file_arr.iter() {
-                let file = file
-                    .as_str()
-                    .ok_or_else(|| format!("not a string {file}"))?;
-
-                let path = parent.join(file);
-                let path = path
-                    .to_str()
-                    .ok_or_else(|| format!("Could not convert {path:?} to string"))?
-                    .to_owned();
-                patterns.push(path);
-            }
+            let patterns = parse_patterns(file_arr, parent)?;
+            // Invalid patterns are reported when looking for the files of the library
+            let globs = patterns
+                .iter()
+                .map(|pattern| strip_verbatim_prefix(pattern))
+                .filter(|pattern| !is_literal(pattern))
+                .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+                .collect();
 
             let mut is_third_party = false;
             if let Some(opt) = lib.get("is_third_party") {
@@ -168,17 +213,88 @@ impl Config {
                 }
             }
 
+            let standard = if let Some(standard) = lib.get("standard") {
+                Some(parse_standard(standard).map_err(|err| format!("{err} for library {name}"))?)
+            } else {
+                None
+            };
+
             libraries.insert(
                 name.to_owned(),
                 LibraryConfig {
                     name: name.to_owned(),
                     patterns,
+                    globs,
                     is_third_party,
+                    standard,
                 },
             );
         }
 
-        Ok(Config { libraries })
+        let mut severities = SeverityMap::default();
+        // The diagnostics table is an alias of the lint table
+        for table_name in ["lint", "diagnostics"] {
+            let Some(lint) = config.get(table_name) else {
+                continue;
+            };
+            let lint = lint
+                .as_table()
+                .ok_or_else(|| format!("{table_name} must be a table"))?;
+
+            for (name, severity) in lint.iter() {
+                let code = ErrorCode::from_str(name)?;
+                let severity = match severity {
+                    Value::Boolean(true) => Some(code.default_severity()),
+                    Value::Boolean(false) => None,
+                    Value::String(severity) if severity == "off" => None,
+                    Value::String(severity) => Some(Severity::from_str(severity)?),
+                    _ => {
+                        return Err(format!(
+                            "Expected severity of {name} to be a string or boolean"
+                        ))
+                    }
+                };
+                severities.set(code, severity);
+            }
+        }
+
+        let format = if let Some(format) = config.get("format") {
+            let format = format.as_table().ok_or("format must be a table")?;
+            Some(parse_format_options(format)?)
+        } else {
+            None
+        };
+
+        let unmapped = if let Some(unmapped) = config.get("unmapped") {
+            let unmapped = unmapped.as_table().ok_or("unmapped must be a table")?;
+            parse_unmapped(unmapped, parent)?
+        } else {
+            UnmappedConfig::default()
+        };
+
+        let mut conditional_analysis = FnvHashMap::default();
+        if let Some(identifiers) = config.get("conditional_analysis") {
+            let identifiers = identifiers
+                .as_table()
+                .ok_or("conditional_analysis must be a table")?;
+            for (name, value) in identifiers.iter() {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| format!("Expected {name} to be a string"))?;
+                conditional_analysis.insert(name.to_ascii_uppercase(), value.to_owned());
+            }
+        }
+
+        let standard = config.get("standard").map(parse_standard).transpose()?;
+
+        Ok(Config {
+            libraries,
+            severities,
+            format,
+            unmapped,
+            conditional_analysis,
+            standard,
+        })
     }
 
     pub fn read_file_path(file_name: &Path) -> io::Result<Config> {
@@ -199,6 +315,69 @@ impl Config {
         self.libraries.values()
     }
 
+    /// Returns the configured severities of diagnostic codes
+    pub fn severities(&self) -> &SeverityMap {
+        &self.severities
+    }
+
+    /// Returns the configured formatting options if there is a format section
+    pub fn format_options(&self) -> Option<&FormatOptions> {
+        self.format.as_ref()
+    }
+
+    /// Returns the standard conditional analysis identifiers with the user-defined values applied
+    pub fn conditional_analysis(&self) -> ConditionalAnalysis {
+        let mut conditions = ConditionalAnalysis::default();
+        for (name, value) in self.conditional_analysis.iter() {
+            conditions.set(name, value);
+        }
+        conditions
+    }
+
+    /// Returns the globally configured VHDL revision, VHDL-2008 when not configured
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard.unwrap_or_default()
+    }
+
+    /// Returns the VHDL revision of a library, the global revision when the library has none
+    pub fn standard_of(&self, library_name: &str) -> VHDLStandard {
+        self.get_library(library_name)
+            .and_then(|library| library.standard)
+            .unwrap_or_else(|| self.standard())
+    }
+
+    /// Returns the names of the libraries with a pattern matching a file name
+    pub fn library_names_of(&self, file_name: &Path) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .iter_libraries()
+            .filter(|library| library.matches(file_name))
+            .map(|library| library.name())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns the names of the libraries of a file that is not listed in any library.
+    /// These are the libraries with a matching pattern, otherwise the default library.
+    pub fn unmapped_library_names(&self, file_name: &Path) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .unmapped
+            .libraries
+            .iter()
+            .filter(|(_, patterns)| {
+                patterns
+                    .iter()
+                    .any(|pattern| matches_path(pattern, file_name))
+            })
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if names.is_empty() {
+            names.push(self.unmapped.default_library.as_deref().unwrap_or("work"));
+        }
+        names.sort_unstable();
+        names
+    }
+
     /// Append another config to self
     ///
     /// In case of conflict the appended config takes precedence
@@ -215,6 +394,27 @@ impl Config {
                 self.libraries.insert(library.name.clone(), library.clone());
             }
         }
+        self.severities.append(&config.severities);
+        if config.format.is_some() {
+            self.format = config.format.clone();
+        }
+        if config.unmapped.default_library.is_some() {
+            self.unmapped.default_library = config.unmapped.default_library.clone();
+        }
+        for (name, patterns) in config.unmapped.libraries.iter() {
+            self.unmapped
+                .libraries
+                .insert(name.clone(), patterns.clone());
+        }
+        self.conditional_analysis.extend(
+            config
+                .conditional_analysis
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        if config.standard.is_some() {
+            self.standard = config.standard;
+        }
     }
 
     /// Load configuration file from installation folder
@@ -295,6 +495,118 @@ impl Config {
     }
 }
 
+/// Parse file name patterns relative to the parent folder of the configuration file
+fn parse_patterns(patterns: &[Value], parent: &Path) -> Result<Vec<String>, String> {
+    let mut result = Vec::with_capacity(patterns.len());
+    for pattern in patterns.iter() {
+        let pattern = pattern
+            .as_str()
+            .ok_or_else(|| format!("not a string {pattern}"))?;
+
+        let path = parent.join(pattern);
+        let path = path
+            .to_str()
+            .ok_or_else(|| format!("Could not convert {path:?} to string"))?
+            .to_owned();
+        result.push(path);
+    }
+    Ok(result)
+}
+
+/// Glob patterns do not support the verbatim prefix of absolute Windows paths
+fn strip_verbatim_prefix(pattern: &str) -> &str {
+    if cfg!(windows) {
+        pattern.strip_prefix("\\\\?\\").unwrap_or(pattern)
+    } else {
+        pattern
+    }
+}
+
+fn parse_standard(value: &Value) -> Result<VHDLStandard, String> {
+    value
+        .as_str()
+        .ok_or_else(|| "Expected standard to be a string".to_owned())
+        .and_then(VHDLStandard::from_str)
+}
+
+fn parse_format_options(format: &toml::value::Table) -> Result<FormatOptions, String> {
+    let mut options = FormatOptions::default();
+    for (name, value) in format.iter() {
+        let as_size = |value: &Value| {
+            value
+                .as_integer()
+                .and_then(|value| usize::try_from(value).ok())
+                .ok_or_else(|| format!("Expected {name} to be a non-negative integer"))
+        };
+        let as_bool = |value: &Value| {
+            value
+                .as_bool()
+                .ok_or_else(|| format!("Expected {name} to be a boolean"))
+        };
+
+        match name.as_str() {
+            "indent_size" => options.indent_size = as_size(value)?,
+            "use_tabs" => options.use_tabs = as_bool(value)?,
+            "keyword_case" => {
+                let case = value
+                    .as_str()
+                    .ok_or_else(|| format!("Expected {name} to be a string"))?;
+                options.keyword_case = KeywordCase::from_str(case)?;
+            }
+            "align" => options.align = as_bool(value)?,
+            "max_blank_lines" => options.max_blank_lines = as_size(value)?,
+            _ => return Err(format!("Unknown format option '{name}'")),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_unmapped(unmapped: &toml::value::Table, parent: &Path) -> Result<UnmappedConfig, String> {
+    let mut result = UnmappedConfig::default();
+    for (name, value) in unmapped.iter() {
+        match name.as_str() {
+            "default_library" => {
+                let library = value
+                    .as_str()
+                    .ok_or_else(|| format!("Expected {name} to be a string"))?;
+                result.default_library = Some(library.to_owned());
+            }
+            "libraries" => {
+                let libraries = value
+                    .as_table()
+                    .ok_or_else(|| format!("Expected {name} to be a table"))?;
+                for (library, patterns) in libraries.iter() {
+                    let patterns = patterns.as_array().ok_or_else(|| {
+                        format!("patterns of unmapped library {library} is not array")
+                    })?;
+                    let mut library_patterns = Vec::new();
+                    for pattern in parse_patterns(patterns, parent)?.iter() {
+                        library_patterns.push(
+                            glob::Pattern::new(strip_verbatim_prefix(pattern))
+                                .map_err(|err| format!("Invalid pattern '{pattern}' {err}"))?,
+                        );
+                    }
+                    result
+                        .libraries
+                        .insert(library.to_owned(), library_patterns);
+                }
+            }
+            _ => return Err(format!("Unknown unmapped option '{name}'")),
+        }
+    }
+    Ok(result)
+}
+
+/// Match a file name against a pattern the same way as the files are found by globbing,
+/// that is `*` does not match a path separator while `**` matches any directories
+fn matches_path(pattern: &glob::Pattern, file_name: &Path) -> bool {
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..glob::MatchOptions::new()
+    };
+    pattern.matches_path_with(file_name, options)
+}
+
 /// Returns true if the pattern is a plain file name and not a glob pattern
 fn is_literal(pattern: &str) -> bool {
     for chr in pattern.chars() {
@@ -518,6 +830,37 @@ lib.files = [
         assert_files_eq(&file_names, &[file1, file2]);
         assert_eq!(messages, vec![]);
     }
+    #[test]
+    fn test_library_names_of_new_file() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+lib1.files = ['rtl/*.vhd', 'pkg.vhd']
+lib2.files = ['**/*.vhd']
+",
+            parent,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.library_names_of(&parent.join("rtl/ent.vhd")),
+            vec!["lib1", "lib2"]
+        );
+        assert_eq!(
+            config.library_names_of(&parent.join("pkg.vhd")),
+            vec!["lib1", "lib2"]
+        );
+        assert_eq!(
+            config.library_names_of(&parent.join("rtl/sub/ent.vhd")),
+            vec!["lib2"]
+        );
+        assert_eq!(
+            config.library_names_of(&parent.join("ent.vhdl")),
+            Vec::<&str>::new()
+        );
+    }
+
     #[test]
     fn test_warning_on_emtpy_glob_pattern() {
         let parent = Path::new("parent_folder");
@@ -543,4 +886,348 @@ lib.files = [
             ))]
         );
     }
+
+    #[test]
+    fn test_lint_severities() {
+        let config = Config::from_str(
+            "
+[libraries]
+
+[lint]
+unused = 'error'
+unnecessary_work_library = 'off'
+duplicate = false
+type_mismatch = true
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let severities = config.severities();
+        assert_eq!(
+            severities.severity_of(ErrorCode::Unused),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            severities.severity_of(ErrorCode::UnnecessaryWorkLibrary),
+            None
+        );
+        assert_eq!(severities.severity_of(ErrorCode::Duplicate), None);
+        assert_eq!(
+            severities.severity_of(ErrorCode::TypeMismatch),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            severities.severity_of(ErrorCode::UnassociatedContext),
+            Some(Severity::Warning)
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_table_is_alias_of_lint() {
+        let config = Config::from_str(
+            "
+[libraries]
+
+[diagnostics]
+unused = 'error'
+duplicate = false
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let severities = config.severities();
+        assert_eq!(
+            severities.severity_of(ErrorCode::Unused),
+            Some(Severity::Error)
+        );
+        assert_eq!(severities.severity_of(ErrorCode::Duplicate), None);
+
+        assert_eq!(
+            Config::from_str("diagnostics = 1\n[libraries]", Path::new("")),
+            Err("diagnostics must be a table".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_lint_errors() {
+        let parse =
+            |lint: &str| Config::from_str(&format!("[libraries]\n[lint]\n{lint}"), Path::new(""));
+        assert_eq!(
+            parse("no_such_code = 'error'"),
+            Err("Unknown diagnostic code 'no_such_code'".to_owned())
+        );
+        assert_eq!(
+            parse("unused = 'fatal'"),
+            Err("Unknown severity 'fatal'".to_owned())
+        );
+        assert_eq!(
+            parse("unused = 1"),
+            Err("Expected severity of unused to be a string or boolean".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_append_lint_severities() {
+        let mut config0 = Config::from_str(
+            "
+[libraries]
+[lint]
+unused = 'off'
+duplicate = 'warning'
+",
+            Path::new(""),
+        )
+        .unwrap();
+        let config1 = Config::from_str(
+            "
+[libraries]
+[lint]
+unused = 'hint'
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        config0.append(&config1, &mut Vec::new());
+        assert_eq!(
+            config0.severities().severity_of(ErrorCode::Unused),
+            Some(Severity::Hint)
+        );
+        assert_eq!(
+            config0.severities().severity_of(ErrorCode::Duplicate),
+            Some(Severity::Warning)
+        );
+    }
+
+    #[test]
+    fn test_format_options() {
+        let config = Config::from_str(
+            "
+[libraries]
+[format]
+indent_size = 4
+keyword_case = 'upper'
+align = false
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.format_options(),
+            Some(&FormatOptions {
+                indent_size: 4,
+                keyword_case: KeywordCase::Upper,
+                align: false,
+                ..FormatOptions::default()
+            })
+        );
+        assert_eq!(
+            Config::from_str("[libraries]", Path::new(""))
+                .unwrap()
+                .format_options(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_format_errors() {
+        let parse = |format: &str| {
+            Config::from_str(&format!("[libraries]\n[format]\n{format}"), Path::new(""))
+        };
+        assert_eq!(
+            parse("indent_size = -1"),
+            Err("Expected indent_size to be a non-negative integer".to_owned())
+        );
+        assert_eq!(
+            parse("keyword_case = 'camel'"),
+            Err(
+                "Expected keyword case to be 'lower', 'upper' or 'preserve', got 'camel'"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            parse("tabs = true"),
+            Err("Unknown format option 'tabs'".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_unmapped_library_names() {
+        let parent = Path::new("parent_folder");
+        let config = Config::from_str(
+            "
+[libraries]
+[unmapped]
+default_library = 'scratch'
+libraries.ip_lib = ['ip/**/*.vhd']
+libraries.sim_lib = ['ip/sim/*.vhd', 'tb_*.vhd']
+",
+            parent,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.unmapped_library_names(&parent.join("ip/core/core.vhd")),
+            vec!["ip_lib"]
+        );
+        assert_eq!(
+            config.unmapped_library_names(&parent.join("ip/sim/model.vhd")),
+            vec!["ip_lib", "sim_lib"]
+        );
+        assert_eq!(
+            config.unmapped_library_names(&parent.join("tb_top.vhd")),
+            vec!["sim_lib"]
+        );
+        assert_eq!(
+            config.unmapped_library_names(&parent.join("rtl/top.vhd")),
+            vec!["scratch"]
+        );
+        assert_eq!(
+            Config::default().unmapped_library_names(&parent.join("rtl/top.vhd")),
+            vec!["work"]
+        );
+    }
+
+    #[test]
+    fn test_append_unmapped() {
+        let mut config = Config::from_str(
+            "
+[libraries]
+[unmapped]
+default_library = 'scratch'
+libraries.ip_lib = ['ip/*.vhd']
+",
+            Path::new(""),
+        )
+        .unwrap();
+        let config1 = Config::from_str(
+            "
+[libraries]
+[unmapped]
+libraries.ip_lib = ['vendor/*.vhd']
+",
+            Path::new(""),
+        )
+        .unwrap();
+
+        let mut messages = vec![];
+        config.append(&config1, &mut messages);
+        assert_eq!(messages, vec![]);
+        assert_eq!(
+            config.unmapped_library_names(Path::new("vendor/ip.vhd")),
+            vec!["ip_lib"]
+        );
+        assert_eq!(
+            config.unmapped_library_names(Path::new("ip/ip.vhd")),
+            vec!["scratch"]
+        );
+    }
+
+    #[test]
+    fn test_unmapped_errors() {
+        let parse = |unmapped: &str| {
+            Config::from_str(
+                &format!("[libraries]\n[unmapped]\n{unmapped}"),
+                Path::new(""),
+            )
+        };
+        assert_eq!(
+            parse("default_library = 1"),
+            Err("Expected default_library to be a string".to_owned())
+        );
+        assert!(parse("libraries.lib = ['[']")
+            .unwrap_err()
+            .starts_with("Invalid pattern '['"));
+        assert_eq!(
+            parse("library = 'lib'"),
+            Err("Unknown unmapped option 'library'".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_standard() {
+        let mut config = Config::from_str(
+            "
+standard = '1993'
+[libraries]
+lib1.files = []
+lib2.files = []
+lib2.standard = '2019'
+",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(config.standard(), VHDLStandard::VHDL1993);
+        assert_eq!(config.standard_of("lib1"), VHDLStandard::VHDL1993);
+        assert_eq!(config.standard_of("lib2"), VHDLStandard::VHDL2019);
+        assert_eq!(config.standard_of("missing"), VHDLStandard::VHDL1993);
+        assert_eq!(Config::default().standard(), VHDLStandard::VHDL2008);
+
+        let config1 = Config::from_str("standard = '2002'\n[libraries]", Path::new("")).unwrap();
+        config.append(&config1, &mut Vec::new());
+        assert_eq!(config.standard_of("lib1"), VHDLStandard::VHDL2002);
+        assert_eq!(config.standard_of("lib2"), VHDLStandard::VHDL2019);
+    }
+
+    #[test]
+    fn test_standard_errors() {
+        assert_eq!(
+            Config::from_str("standard = '2000'\n[libraries]", Path::new("")),
+            Err("Expected standard to be '1993', '2002', '2008' or '2019', got '2000'".to_owned())
+        );
+        assert_eq!(
+            Config::from_str(
+                "[libraries]\nlib.files = []\nlib.standard = 2008",
+                Path::new("")
+            ),
+            Err("Expected standard to be a string for library lib".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_conditional_analysis() {
+        let mut config = Config::from_str(
+            "
+[libraries]
+[conditional_analysis]
+variant = 'sim'
+TOOL_TYPE = 'SYNTHESIS'
+",
+            Path::new(""),
+        )
+        .unwrap();
+        let config1 = Config::from_str(
+            "
+[libraries]
+[conditional_analysis]
+VARIANT = 'synth'
+",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(config.conditional_analysis().get("VARIANT"), Some("sim"));
+        assert_eq!(
+            config.conditional_analysis().get("tool_type"),
+            Some("SYNTHESIS")
+        );
+        assert_eq!(
+            config.conditional_analysis().get("TOOL_NAME"),
+            Some("vhdl_ls")
+        );
+
+        let mut messages = vec![];
+        config.append(&config1, &mut messages);
+        assert_eq!(config.conditional_analysis().get("variant"), Some("synth"));
+
+        assert_eq!(
+            Config::from_str(
+                "[libraries]\n[conditional_analysis]\nvariant = 1",
+                Path::new("")
+            ),
+            Err("Expected variant to be a string".to_owned())
+        );
+    }
 }
@@ -7,14 +7,20 @@
 
 mod contents;
 mod diagnostic;
+mod error_codes;
 mod latin_1;
 mod message;
+mod report;
 mod source;
+mod standard;
 mod symbol_table;
 
 pub use contents::*;
 pub use diagnostic::*;
+pub use error_codes::*;
 pub use latin_1::*;
 pub use message::*;
+pub use report::*;
 pub use source::*;
+pub use standard::*;
 pub use symbol_table::*;
@@ -4,10 +4,12 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use super::SrcPos;
+use super::{ErrorCode, SrcPos, VHDLStandard};
 use std::convert::{AsRef, Into};
+use std::fmt::Display;
+use std::str::FromStr;
 
-#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash)]
+#[derive(PartialEq, Debug, Clone, Copy, Eq, Hash, PartialOrd, Ord)]
 pub enum Severity {
     Hint,
     Info,
@@ -15,39 +17,107 @@ pub enum Severity {
     Error,
 }
 
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Hint => "hint",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Severity::Error),
+            "warning" => Ok(Severity::Warning),
+            "info" => Ok(Severity::Info),
+            "hint" => Ok(Severity::Hint),
+            _ => Err(format!("Unknown severity '{s}'")),
+        }
+    }
+}
+
 #[must_use]
 #[derive(PartialEq, Debug, Clone, Eq, Hash)]
 pub struct Diagnostic {
     pub pos: SrcPos,
     pub message: String,
     pub severity: Severity,
+    pub code: ErrorCode,
     pub related: Vec<(SrcPos, String)>,
+    /// Information for tools that is not part of the message,
+    /// such as the designator of an unresolved diagnostic
+    pub data: Option<String>,
 }
 
 impl Diagnostic {
-    pub fn new(item: impl AsRef<SrcPos>, msg: impl Into<String>, severity: Severity) -> Diagnostic {
+    /// Create a diagnostic with the default severity of the code
+    pub fn new(item: impl AsRef<SrcPos>, msg: impl Into<String>, code: ErrorCode) -> Diagnostic {
         Diagnostic {
             pos: item.as_ref().clone(),
             message: msg.into(),
-            severity,
+            severity: code.default_severity(),
+            code,
             related: vec![],
+            data: None,
         }
     }
 
     pub fn error(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
-        Self::new(item, msg, Severity::Error)
+        Self::new(item, msg, ErrorCode::Unspecified).with_severity(Severity::Error)
     }
 
     pub fn warning(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
-        Self::new(item, msg, Severity::Warning)
+        Self::new(item, msg, ErrorCode::Unspecified).with_severity(Severity::Warning)
     }
 
     pub fn hint(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
-        Self::new(item, msg, Severity::Hint)
+        Self::new(item, msg, ErrorCode::Unspecified).with_severity(Severity::Hint)
     }
 
     pub fn info(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
-        Self::new(item, msg, Severity::Info)
+        Self::new(item, msg, ErrorCode::Unspecified).with_severity(Severity::Info)
+    }
+
+    pub fn syntax_error(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
+        Self::new(item, msg, ErrorCode::SyntaxError)
+    }
+
+    pub fn mismatched_kinds(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
+        Self::new(item, msg, ErrorCode::MismatchedKinds)
+    }
+
+    pub fn internal(item: impl AsRef<SrcPos>, msg: impl Into<String>) -> Diagnostic {
+        Self::new(item, msg, ErrorCode::Internal)
+    }
+
+    /// A construct that is not part of the VHDL revision of the library
+    pub fn not_in_standard(
+        item: impl AsRef<SrcPos>,
+        construct: impl Display,
+        standard: VHDLStandard,
+    ) -> Diagnostic {
+        Self::new(
+            item,
+            format!("{construct} is not allowed in {standard}"),
+            ErrorCode::NotInStandard,
+        )
+    }
+
+    pub fn with_severity(self, severity: Severity) -> Diagnostic {
+        Diagnostic { severity, ..self }
+    }
+
+    pub fn with_data(self, data: impl Into<String>) -> Diagnostic {
+        Diagnostic {
+            data: Some(data.into()),
+            ..self
+        }
     }
 
     pub fn when(self, message: impl AsRef<str>) -> Diagnostic {
@@ -55,7 +125,9 @@ impl Diagnostic {
             message: format!("{}, when {}", &self.message, message.as_ref()),
             pos: self.pos,
             severity: self.severity,
+            code: self.code,
             related: vec![],
+            data: self.data,
         }
     }
 
@@ -86,11 +158,10 @@ impl Diagnostic {
         let mut diagnostics = Vec::with_capacity(self.related.len());
         let related = std::mem::take(&mut self.related);
         for (pos, msg) in related {
-            diagnostics.push(Diagnostic::new(
-                pos,
-                format!("related: {msg}"),
-                Severity::Hint,
-            ));
+            diagnostics.push(
+                Diagnostic::new(pos, format!("related: {msg}"), self.code)
+                    .with_severity(Severity::Hint),
+            );
         }
         diagnostics
     }
@@ -101,13 +172,11 @@ impl Diagnostic {
             result.push_str(&pos.show(&format!("related: {message}")));
             result.push('\n');
         }
-        let severity = match self.severity {
-            Severity::Error => &"error",
-            Severity::Warning => &"warning",
-            Severity::Info => &"info",
-            Severity::Hint => &"hint",
-        };
-        result.push_str(&self.pos.show(&format!("{}: {}", severity, self.message)));
+        result.push_str(
+            &self
+                .pos
+                .show(&format!("{}: {}", self.severity.as_str(), self.message)),
+        );
         result
     }
 }
@@ -135,6 +204,10 @@ impl<'a> dyn DiagnosticHandler + 'a {
         self.push(Diagnostic::info(item, msg));
     }
 
+    pub fn add(&mut self, item: impl AsRef<SrcPos>, msg: impl Into<String>, code: ErrorCode) {
+        self.push(Diagnostic::new(item, msg, code));
+    }
+
     pub fn push_result<T>(&mut self, diagnostic: Result<T, Diagnostic>) {
         if let Err(diagnostic) = diagnostic {
             self.push(diagnostic);
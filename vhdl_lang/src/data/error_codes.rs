@@ -0,0 +1,301 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::{Diagnostic, Severity};
+use fnv::FnvHashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A stable identifier for the kind of a diagnostic.
+/// The string representation is used in configuration files and tool output
+/// and must not change.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, PartialOrd, Ord)]
+pub enum ErrorCode {
+    // Syntax
+    SyntaxError,
+    UnassociatedContext,
+    InvalidPragma,
+    DirectiveWarning,
+    DirectiveError,
+    InactiveRegion,
+    NotInStandard,
+
+    // Analysis
+    CircularDependency,
+    Unresolved,
+    Duplicate,
+    DeclaredBefore,
+    ConflictingUseClause,
+    MismatchedKinds,
+    TypeMismatch,
+    InvalidCall,
+    AmbiguousCall,
+    AmbiguousExpression,
+    NoOverloadedWithSignature,
+    IllegalSignature,
+    SignatureRequired,
+    InvalidFormal,
+    InvalidFormalConversion,
+    Unassociated,
+    AlreadyAssociated,
+    TooManyArguments,
+    DimensionMismatch,
+    IllegalConstraint,
+    IllegalAttribute,
+    InvalidLiteral,
+    MissingFullTypeDeclaration,
+    MissingProtectedBody,
+    MissingDeferredDeclaration,
+    IllegalDeferredConstant,
+    DisallowedInSensitivityList,
+    IllegalTarget,
+    IllegalReturn,
+    IllegalLoopControl,
+    NoImplicitConversion,
+    Internal,
+
+    // Linting
+    Unused,
+    UnnecessaryWorkLibrary,
+
+    // Diagnostics created without a specific code
+    Unspecified,
+}
+
+impl ErrorCode {
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::SyntaxError,
+        ErrorCode::UnassociatedContext,
+        ErrorCode::InvalidPragma,
+        ErrorCode::DirectiveWarning,
+        ErrorCode::DirectiveError,
+        ErrorCode::InactiveRegion,
+        ErrorCode::NotInStandard,
+        ErrorCode::CircularDependency,
+        ErrorCode::Unresolved,
+        ErrorCode::Duplicate,
+        ErrorCode::DeclaredBefore,
+        ErrorCode::ConflictingUseClause,
+        ErrorCode::MismatchedKinds,
+        ErrorCode::TypeMismatch,
+        ErrorCode::InvalidCall,
+        ErrorCode::AmbiguousCall,
+        ErrorCode::AmbiguousExpression,
+        ErrorCode::NoOverloadedWithSignature,
+        ErrorCode::IllegalSignature,
+        ErrorCode::SignatureRequired,
+        ErrorCode::InvalidFormal,
+        ErrorCode::InvalidFormalConversion,
+        ErrorCode::Unassociated,
+        ErrorCode::AlreadyAssociated,
+        ErrorCode::TooManyArguments,
+        ErrorCode::DimensionMismatch,
+        ErrorCode::IllegalConstraint,
+        ErrorCode::IllegalAttribute,
+        ErrorCode::InvalidLiteral,
+        ErrorCode::MissingFullTypeDeclaration,
+        ErrorCode::MissingProtectedBody,
+        ErrorCode::MissingDeferredDeclaration,
+        ErrorCode::IllegalDeferredConstant,
+        ErrorCode::DisallowedInSensitivityList,
+        ErrorCode::IllegalTarget,
+        ErrorCode::IllegalReturn,
+        ErrorCode::IllegalLoopControl,
+        ErrorCode::NoImplicitConversion,
+        ErrorCode::Internal,
+        ErrorCode::Unused,
+        ErrorCode::UnnecessaryWorkLibrary,
+        ErrorCode::Unspecified,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::SyntaxError => "syntax_error",
+            ErrorCode::UnassociatedContext => "unassociated_context",
+            ErrorCode::InvalidPragma => "invalid_pragma",
+            ErrorCode::DirectiveWarning => "directive_warning",
+            ErrorCode::DirectiveError => "directive_error",
+            ErrorCode::InactiveRegion => "inactive_region",
+            ErrorCode::NotInStandard => "not_in_standard",
+            ErrorCode::CircularDependency => "circular_dependency",
+            ErrorCode::Unresolved => "unresolved",
+            ErrorCode::Duplicate => "duplicate",
+            ErrorCode::DeclaredBefore => "declared_before",
+            ErrorCode::ConflictingUseClause => "conflicting_use_clause",
+            ErrorCode::MismatchedKinds => "mismatched_kinds",
+            ErrorCode::TypeMismatch => "type_mismatch",
+            ErrorCode::InvalidCall => "invalid_call",
+            ErrorCode::AmbiguousCall => "ambiguous_call",
+            ErrorCode::AmbiguousExpression => "ambiguous_expression",
+            ErrorCode::NoOverloadedWithSignature => "no_overloaded_with_signature",
+            ErrorCode::IllegalSignature => "illegal_signature",
+            ErrorCode::SignatureRequired => "signature_required",
+            ErrorCode::InvalidFormal => "invalid_formal",
+            ErrorCode::InvalidFormalConversion => "invalid_formal_conversion",
+            ErrorCode::Unassociated => "unassociated",
+            ErrorCode::AlreadyAssociated => "already_associated",
+            ErrorCode::TooManyArguments => "too_many_arguments",
+            ErrorCode::DimensionMismatch => "dimension_mismatch",
+            ErrorCode::IllegalConstraint => "illegal_constraint",
+            ErrorCode::IllegalAttribute => "illegal_attribute",
+            ErrorCode::InvalidLiteral => "invalid_literal",
+            ErrorCode::MissingFullTypeDeclaration => "missing_full_type_declaration",
+            ErrorCode::MissingProtectedBody => "missing_protected_body",
+            ErrorCode::MissingDeferredDeclaration => "missing_deferred_declaration",
+            ErrorCode::IllegalDeferredConstant => "illegal_deferred_constant",
+            ErrorCode::DisallowedInSensitivityList => "disallowed_in_sensitivity_list",
+            ErrorCode::IllegalTarget => "illegal_target",
+            ErrorCode::IllegalReturn => "illegal_return",
+            ErrorCode::IllegalLoopControl => "illegal_loop_control",
+            ErrorCode::NoImplicitConversion => "no_implicit_conversion",
+            ErrorCode::Internal => "internal",
+            ErrorCode::Unused => "unused",
+            ErrorCode::UnnecessaryWorkLibrary => "unnecessary_work_library",
+            ErrorCode::Unspecified => "unspecified",
+        }
+    }
+
+    /// The severity of diagnostics with this code unless configured otherwise
+    pub fn default_severity(&self) -> Severity {
+        match self {
+            ErrorCode::UnassociatedContext
+            | ErrorCode::InvalidPragma
+            | ErrorCode::DirectiveWarning
+            | ErrorCode::Internal
+            | ErrorCode::Unused => Severity::Warning,
+            ErrorCode::UnnecessaryWorkLibrary | ErrorCode::InactiveRegion => Severity::Hint,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for ErrorCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ErrorCode::ALL
+            .iter()
+            .find(|code| code.as_str() == s)
+            .copied()
+            .ok_or_else(|| format!("Unknown diagnostic code '{s}'"))
+    }
+}
+
+/// Configured severities of diagnostic codes.
+/// Codes that are not configured keep their default severity.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct SeverityMap {
+    // None means that the diagnostic is disabled
+    overrides: FnvHashMap<ErrorCode, Option<Severity>>,
+}
+
+impl SeverityMap {
+    pub fn set(&mut self, code: ErrorCode, severity: Option<Severity>) {
+        self.overrides.insert(code, severity);
+    }
+
+    /// Returns the configured severity or None if the code is disabled
+    pub fn severity_of(&self, code: ErrorCode) -> Option<Severity> {
+        self.overrides
+            .get(&code)
+            .copied()
+            .unwrap_or(Some(code.default_severity()))
+    }
+
+    /// Append another map to self
+    ///
+    /// In case of conflict the appended map takes precedence
+    pub fn append(&mut self, other: &SeverityMap) {
+        self.overrides
+            .extend(other.overrides.iter().map(|(code, sev)| (*code, *sev)));
+    }
+
+    /// Update the severity of configured diagnostics and remove disabled diagnostics
+    ///
+    /// Diagnostics whose code is not configured keep the severity they were created with
+    pub fn apply(&self, diagnostics: &mut Vec<Diagnostic>) {
+        if self.overrides.is_empty() {
+            return;
+        }
+
+        diagnostics.retain_mut(|diagnostic| match self.overrides.get(&diagnostic.code) {
+            Some(Some(severity)) => {
+                diagnostic.severity = *severity;
+                true
+            }
+            Some(None) => false,
+            None => true,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn code_string_roundtrip() {
+        for code in ErrorCode::ALL {
+            assert_eq!(ErrorCode::from_str(code.as_str()), Ok(*code));
+        }
+        assert!(ErrorCode::from_str("no_such_code").is_err());
+    }
+
+    #[test]
+    fn severity_map_overrides_and_disables() {
+        let code = Code::new("foo bar baz");
+        let mut diagnostics = vec![
+            Diagnostic::new(code.s1("foo"), "unused", ErrorCode::Unused),
+            Diagnostic::new(code.s1("bar"), "unresolved", ErrorCode::Unresolved),
+            Diagnostic::new(code.s1("baz"), "duplicate", ErrorCode::Duplicate),
+        ];
+
+        let mut severities = SeverityMap::default();
+        severities.set(ErrorCode::Unused, Some(Severity::Error));
+        severities.set(ErrorCode::Unresolved, None);
+        severities.apply(&mut diagnostics);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(code.s1("foo"), "unused", ErrorCode::Unused)
+                    .with_severity(Severity::Error),
+                Diagnostic::new(code.s1("baz"), "duplicate", ErrorCode::Duplicate),
+            ]
+        );
+    }
+
+    #[test]
+    fn severity_map_keeps_severity_of_unconfigured_codes() {
+        let code = Code::new("foo bar");
+        let mut diagnostics = vec![
+            Diagnostic::new(code.s1("foo"), "unused", ErrorCode::Unused),
+            Diagnostic::new(code.s1("bar"), "unreachable", ErrorCode::Internal)
+                .with_severity(Severity::Error),
+        ];
+
+        let mut severities = SeverityMap::default();
+        severities.set(ErrorCode::Unused, Some(Severity::Hint));
+        severities.apply(&mut diagnostics);
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::new(code.s1("foo"), "unused", ErrorCode::Unused)
+                    .with_severity(Severity::Hint),
+                Diagnostic::new(code.s1("bar"), "unreachable", ErrorCode::Internal)
+                    .with_severity(Severity::Error),
+            ]
+        );
+    }
+}
@@ -0,0 +1,207 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Machine readable serialization of diagnostics
+
+use super::{Diagnostic, ErrorCode, Severity, SrcPos};
+use serde_json::{json, Value};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Serialize diagnostics as a JSON array
+///
+/// Lines and characters are zero-based like the language server protocol
+pub fn diagnostics_to_json(diagnostics: &[Diagnostic]) -> String {
+    let diagnostics: Vec<Value> = diagnostics.iter().map(diagnostic_to_json).collect();
+    to_pretty_string(&Value::Array(diagnostics))
+}
+
+fn diagnostic_to_json(diagnostic: &Diagnostic) -> Value {
+    let related: Vec<Value> = diagnostic
+        .related
+        .iter()
+        .map(|(pos, message)| {
+            json!({
+                "file": file_name(pos),
+                "range": range_to_json(pos),
+                "message": message,
+            })
+        })
+        .collect();
+
+    json!({
+        "file": file_name(&diagnostic.pos),
+        "range": range_to_json(&diagnostic.pos),
+        "severity": diagnostic.severity.as_str(),
+        "code": diagnostic.code.as_str(),
+        "message": diagnostic.message,
+        "related": related,
+    })
+}
+
+fn range_to_json(pos: &SrcPos) -> Value {
+    let range = pos.range();
+    json!({
+        "start": {"line": range.start.line, "character": range.start.character},
+        "end": {"line": range.end.line, "character": range.end.character},
+    })
+}
+
+/// Serialize diagnostics as a SARIF 2.1.0 log with a single run
+///
+/// Lines and columns are one-based as required by SARIF
+pub fn diagnostics_to_sarif(diagnostics: &[Diagnostic]) -> String {
+    let results: Vec<Value> = diagnostics.iter().map(diagnostic_to_sarif).collect();
+    let rules: Vec<Value> = ErrorCode::ALL
+        .iter()
+        .map(|code| {
+            json!({
+                "id": code.as_str(),
+                "defaultConfiguration": {"level": sarif_level(code.default_severity())},
+            })
+        })
+        .collect();
+
+    let log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "vhdl_lang",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    });
+    to_pretty_string(&log)
+}
+
+fn diagnostic_to_sarif(diagnostic: &Diagnostic) -> Value {
+    let related: Vec<Value> = diagnostic
+        .related
+        .iter()
+        .enumerate()
+        .map(|(id, (pos, message))| {
+            json!({
+                "id": id,
+                "physicalLocation": physical_location(pos),
+                "message": {"text": message},
+            })
+        })
+        .collect();
+
+    json!({
+        "ruleId": diagnostic.code.as_str(),
+        "level": sarif_level(diagnostic.severity),
+        "message": {"text": diagnostic.message},
+        "locations": [{"physicalLocation": physical_location(&diagnostic.pos)}],
+        "relatedLocations": related,
+    })
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Hint => "note",
+    }
+}
+
+fn physical_location(pos: &SrcPos) -> Value {
+    let range = pos.range();
+    json!({
+        "artifactLocation": {"uri": file_name(pos)},
+        "region": {
+            "startLine": range.start.line + 1,
+            "startColumn": range.start.character + 1,
+            "endLine": range.end.line + 1,
+            "endColumn": range.end.character + 1,
+        }
+    })
+}
+
+fn file_name(pos: &SrcPos) -> String {
+    pos.file_name().to_string_lossy().into_owned()
+}
+
+fn to_pretty_string(value: &Value) -> String {
+    // Serializing a Value cannot fail since all keys are strings
+    serde_json::to_string_pretty(value).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+    use std::path::Path;
+
+    fn parse(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn json_contains_position_severity_and_related() {
+        let code = Code::new_with_file_name(Path::new("file.vhd"), "hello\nworld\nline\n");
+        let diagnostic = Diagnostic::new(code.s1("line"), "Greetings", ErrorCode::Unresolved)
+            .related(code.s1("hello"), "From here");
+
+        assert_eq!(
+            parse(&diagnostics_to_json(&[diagnostic])),
+            json!([{
+                "file": "file.vhd",
+                "range": {
+                    "start": {"line": 2, "character": 0},
+                    "end": {"line": 2, "character": 4},
+                },
+                "severity": "error",
+                "code": "unresolved",
+                "message": "Greetings",
+                "related": [{
+                    "file": "file.vhd",
+                    "range": {
+                        "start": {"line": 0, "character": 0},
+                        "end": {"line": 0, "character": 5},
+                    },
+                    "message": "From here",
+                }],
+            }])
+        );
+    }
+
+    #[test]
+    fn sarif_uses_one_based_regions_and_levels() {
+        let code = Code::new_with_file_name(Path::new("file.vhd"), "hello\nworld\nline\n");
+        let diagnostics = vec![
+            Diagnostic::new(code.s1("world"), "Greetings", ErrorCode::Unused),
+            Diagnostic::new(code.s1("line"), "Hint", ErrorCode::UnnecessaryWorkLibrary),
+        ];
+
+        let log = parse(&diagnostics_to_sarif(&diagnostics));
+        assert_eq!(log["version"], "2.1.0");
+
+        let results = &log["runs"][0]["results"];
+        assert_eq!(results[0]["ruleId"], "unused");
+        assert_eq!(results[0]["level"], "warning");
+        assert_eq!(results[0]["message"]["text"], "Greetings");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": {"uri": "file.vhd"},
+                "region": {
+                    "startLine": 2,
+                    "startColumn": 1,
+                    "endLine": 2,
+                    "endColumn": 6,
+                }
+            })
+        );
+        assert_eq!(results[1]["level"], "note");
+    }
+}
@@ -0,0 +1,52 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The revision of the VHDL language that a library is written in
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub enum VHDLStandard {
+    VHDL1993,
+    VHDL2002,
+    #[default]
+    VHDL2008,
+    VHDL2019,
+}
+
+impl VHDLStandard {
+    /// The year of the revision, also used as the value of the VHDL_VERSION identifier
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VHDLStandard::VHDL1993 => "1993",
+            VHDLStandard::VHDL2002 => "2002",
+            VHDLStandard::VHDL2008 => "2008",
+            VHDLStandard::VHDL2019 => "2019",
+        }
+    }
+}
+
+impl Display for VHDLStandard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "VHDL-{}", self.as_str())
+    }
+}
+
+impl FromStr for VHDLStandard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1993" | "93" => Ok(VHDLStandard::VHDL1993),
+            "2002" | "02" => Ok(VHDLStandard::VHDL2002),
+            "2008" | "08" => Ok(VHDLStandard::VHDL2008),
+            "2019" | "19" => Ok(VHDLStandard::VHDL2019),
+            _ => Err(format!(
+                "Expected standard to be '1993', '2002', '2008' or '2019', got '{s}'"
+            )),
+        }
+    }
+}
@@ -20,14 +20,19 @@ mod syntax;
 
 pub use crate::config::Config;
 pub use crate::data::{
-    Diagnostic, Latin1String, Message, MessageHandler, MessagePrinter, MessageType,
-    NullDiagnostics, NullMessages, Position, Range, Severity, Source, SrcPos,
+    diagnostics_to_json, diagnostics_to_sarif, Diagnostic, ErrorCode, Latin1String, Message,
+    MessageHandler, MessagePrinter, MessageType, NullDiagnostics, NullMessages, Position, Range,
+    Severity, Source, SrcPos, VHDLStandard,
 };
 
 pub use crate::analysis::CompletionItem;
 pub use crate::analysis::{
-    AnyEnt, AnyEntKind, Concurrent, Design, EntHierarchy, EntRef, EntityId, Object, Overloaded,
-    Type,
+    describe_unit, AnyEnt, AnyEntKind, CallHierarchyCall, Concurrent, DependencyGraph, Design,
+    EntHierarchy, EntRef, EntityId, FoldingRange, FoldingRangeKind, Highlight, HighlightKind,
+    HoverInfo, InlayHint, InlayHintKind, InstanceHierarchy, Instantiation, InstantiationKind,
+    Object, ObjectInterface, Overloaded, SignatureHelp, SignatureLabel, Type, UseClauseFix,
+};
+pub use crate::project::{LibraryFile, Project, SourceFile};
+pub use crate::syntax::{
+    kind_str, ConditionalAnalysis, FormatOptions, KeywordCase, ParserResult, VHDLParser,
 };
-pub use crate::project::{Project, SourceFile};
-pub use crate::syntax::{kind_str, ParserResult, VHDLParser};
@@ -15,6 +15,7 @@ use crate::ast::search::Searcher;
 use crate::ast::Reference;
 use crate::ast::UnitId;
 use crate::data::DiagnosticHandler;
+use crate::data::ErrorCode;
 use crate::data::Symbol;
 use crate::syntax::TokenAccess;
 use crate::AnyEntKind;
@@ -235,9 +236,10 @@ impl UnusedDeclarationsLinter {
                     find_unused_declarations(root, library, unit.primary_name())
                         .into_iter()
                         .filter_map(|ent| {
-                            Some(Diagnostic::warning(
+                            Some(Diagnostic::new(
                                 ent.decl_pos()?,
                                 format!("Unused declaration of {}", ent.describe()),
+                                ErrorCode::Unused,
                             ))
                         })
                         .collect_vec()
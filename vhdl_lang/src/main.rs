@@ -9,10 +9,15 @@
 // Track here: https://github.com/rust-lang/rust-clippy/issues/1981
 #![allow(clippy::ptr_arg)]
 
-use clap::Parser;
-use std::path::Path;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
-use vhdl_lang::{Config, Diagnostic, MessagePrinter, NullMessages, Project, Severity};
+use vhdl_lang::{
+    describe_unit, diagnostics_to_json, diagnostics_to_sarif, Config, Diagnostic, Latin1String,
+    LibraryFile, Message, MessageHandler, MessagePrinter, NullMessages, Project, Severity, Source,
+    VHDLParser, VHDLStandard,
+};
 
 /// Run vhdl analysis
 #[derive(Parser, Debug)]
@@ -47,28 +52,205 @@ struct Args {
     /// This is used for development to test where the language server is blind
     #[arg(long)]
     count_unresolved: bool,
+
+    /// Format used to print diagnostics
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    /// Exit with a non-zero code if any diagnostic has at least this severity
+    #[arg(long, value_enum)]
+    fail_on: Option<SeverityThreshold>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the elaborated instance hierarchy below a top level entity
+    Hierarchy(HierarchyArgs),
+    /// Print the files in dependency order for compilation by external tools
+    CompileOrder(CompileOrderArgs),
+    /// Print the design units that a unit depends on or that depend on it
+    Dependencies(DependenciesArgs),
+    /// Format source files according to the format section of the configuration
+    Format(FormatArgs),
+}
+
+#[derive(clap::Args, Debug)]
+struct HierarchyArgs {
+    /// The top level entity given as library.entity
+    #[arg(long)]
+    top: String,
+
+    /// Format used to print the hierarchy
+    #[arg(long, value_enum, default_value_t = HierarchyFormat::Text)]
+    format: HierarchyFormat,
+}
+
+#[derive(clap::Args, Debug)]
+struct CompileOrderArgs {
+    /// Only include files required by this top level unit given as library.unit
+    #[arg(long)]
+    top: Option<String>,
+
+    /// Format used to print the compile order
+    #[arg(long, value_enum, default_value_t = CompileOrderFormat::Text)]
+    format: CompileOrderFormat,
+}
+
+#[derive(clap::Args, Debug)]
+struct DependenciesArgs {
+    /// The design unit given as library.primary or library.primary.secondary.
+    /// When omitted the dependencies between all units are printed
+    #[arg(long)]
+    unit: Option<String>,
+
+    /// Print the units that directly or indirectly depend on the unit
+    /// instead of the units it depends on
+    #[arg(long, default_value_t = false)]
+    users: bool,
+
+    /// Print a Graphviz DOT digraph instead of one unit per line
+    #[arg(long, default_value_t = false)]
+    dot: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct FormatArgs {
+    /// The files to format. By default all files of libraries that are not third party are formatted
+    files: Vec<PathBuf>,
+
+    /// Do not write the files but list the files that are not formatted
+    /// and exit with a non-zero code if there are any
+    #[arg(long, default_value_t = false)]
+    check: bool,
+
+    /// Print the formatted code to stdout instead of writing the files
+    #[arg(long, default_value_t = false, conflicts_with = "check")]
+    stdout: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum CompileOrderFormat {
+    /// One library and file name per line
+    Text,
+    /// JSON array of library and file name objects
+    Json,
+    /// Shell script analyzing the files with GHDL
+    Ghdl,
+    /// Shell script compiling the files with ModelSim/Questa vcom
+    Vcom,
+    /// Shell script analyzing the files with NVC
+    Nvc,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum HierarchyFormat {
+    /// Indented tree with one instance per line
+    Text,
+    /// Nested JSON objects
+    Json,
+    /// Graphviz DOT digraph
+    Dot,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    /// Human readable text with code context
+    Text,
+    /// JSON array of diagnostics
+    Json,
+    /// SARIF 2.1.0 log for code scanning tools
+    Sarif,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SeverityThreshold {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl From<SeverityThreshold> for Severity {
+    fn from(threshold: SeverityThreshold) -> Severity {
+        match threshold {
+            SeverityThreshold::Error => Severity::Error,
+            SeverityThreshold::Warning => Severity::Warning,
+            SeverityThreshold::Info => Severity::Info,
+            SeverityThreshold::Hint => Severity::Hint,
+        }
+    }
+}
+
+/// Print messages to stderr to keep stdout machine readable
+#[derive(Default)]
+struct StderrMessagePrinter;
+
+impl MessageHandler for StderrMessagePrinter {
+    fn push(&mut self, message: Message) {
+        eprintln!("{message}");
+    }
 }
 
 fn main() {
     let args = Args::parse();
+    if args.command.is_some() {
+        reject_analysis_args(&args);
+    }
     rayon::ThreadPoolBuilder::new()
         .num_threads(args.num_threads.unwrap_or(0))
         .build_global()
         .unwrap();
 
     let mut config = Config::default();
-    let mut msg_printer = MessagePrinter::default();
-    config.load_external_config(&mut msg_printer);
+    let mut msg_printer: Box<dyn MessageHandler> =
+        if args.output_format == OutputFormat::Text && args.command.is_none() {
+            Box::<MessagePrinter>::default()
+        } else {
+            Box::<StderrMessagePrinter>::default()
+        };
+    config.load_external_config(msg_printer.as_mut());
     config.append(
         &Config::read_file_path(Path::new(&args.config)).expect("Failed to read config file"),
-        &mut msg_printer,
+        msg_printer.as_mut(),
     );
 
+    match args.command {
+        Some(Command::Hierarchy(ref hierarchy_args)) => {
+            let code = show_hierarchy(config, hierarchy_args, msg_printer.as_mut());
+            std::process::exit(code);
+        }
+        Some(Command::CompileOrder(ref compile_order_args)) => {
+            let code = show_compile_order(config, compile_order_args, msg_printer.as_mut());
+            std::process::exit(code);
+        }
+        Some(Command::Dependencies(ref dependencies_args)) => {
+            let code = show_dependencies(config, dependencies_args, msg_printer.as_mut());
+            std::process::exit(code);
+        }
+        Some(Command::Format(ref format_args)) => {
+            let code = format_files(config, format_args, msg_printer.as_mut());
+            std::process::exit(code);
+        }
+        None => {}
+    }
+
+    // Keep stdout machine readable when the diagnostics are printed as JSON or SARIF
+    let report = |line: String| {
+        if args.output_format == OutputFormat::Text {
+            println!("{line}");
+        } else {
+            eprintln!("{line}");
+        }
+    };
+
     let start = SystemTime::now();
 
     let iterations = if args.bench {
         let iterations = 10;
-        println!("Running {iterations} iterations for benchmarking");
+        report(format!("Running {iterations} iterations for benchmarking"));
         for _ in 0..(iterations - 1) {
             let mut project = Project::from_config(config.clone(), &mut NullMessages);
             project.analyse();
@@ -78,7 +260,7 @@ fn main() {
         1
     };
 
-    let mut project = Project::from_config(config, &mut msg_printer);
+    let mut project = Project::from_config(config, msg_printer.as_mut());
     let mut diagnostics = project.analyse();
     let duration = start.elapsed().unwrap() / iterations;
 
@@ -86,7 +268,11 @@ fn main() {
         diagnostics.retain(|diag| diag.severity != Severity::Hint);
     }
 
-    show_diagnostics(&diagnostics);
+    match args.output_format {
+        OutputFormat::Text => show_diagnostics(&diagnostics),
+        OutputFormat::Json => println!("{}", diagnostics_to_json(&diagnostics)),
+        OutputFormat::Sarif => println!("{}", diagnostics_to_sarif(&diagnostics)),
+    }
 
     if args.perf || args.bench {
         let mut num_files = 0;
@@ -97,12 +283,14 @@ fn main() {
         }
         let duration_per_line = duration.checked_div(num_lines as u32).unwrap();
 
-        println!("Analyzed {num_files} files with {num_lines} lines of code");
-        println!(
+        report(format!(
+            "Analyzed {num_files} files with {num_lines} lines of code"
+        ));
+        report(format!(
             "Total time to run was {} ms with an average of {} ns per line",
             duration.as_millis(),
             duration_per_line.as_nanos()
-        );
+        ));
     }
 
     if args.dump_unresolved || args.count_unresolved {
@@ -110,17 +298,331 @@ fn main() {
 
         if args.dump_unresolved {
             for pos in unresolved.iter() {
-                println!("{}", pos.show("Unresolved"));
+                report(pos.show("Unresolved"));
             }
         }
 
         if args.count_unresolved {
-            println!("{} out of {} positions unresolved", unresolved.len(), total);
+            report(format!(
+                "{} out of {} positions unresolved",
+                unresolved.len(),
+                total
+            ));
         }
     }
 
+    let failed = args.fail_on.is_some_and(|threshold| {
+        let threshold = Severity::from(threshold);
+        diagnostics.iter().any(|diag| diag.severity >= threshold)
+    });
+
     // Exit without running Drop on entire allocated AST
-    std::process::exit(0);
+    std::process::exit(i32::from(failed));
+}
+
+/// Exit with a usage error when options that only apply to the analysis
+/// are combined with a subcommand
+fn reject_analysis_args(args: &Args) {
+    let used = [
+        ("--perf", args.perf),
+        ("--bench", args.bench),
+        ("--no-hint", args.no_hint),
+        ("--dump-unresolved", args.dump_unresolved),
+        ("--count-unresolved", args.count_unresolved),
+        ("--output-format", args.output_format != OutputFormat::Text),
+        ("--fail-on", args.fail_on.is_some()),
+    ];
+    if let Some((name, _)) = used.iter().find(|(_, used)| *used) {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                format!("the argument '{name}' cannot be used with a subcommand"),
+            )
+            .exit();
+    }
+}
+
+/// Split a top level unit given as library.unit
+fn parse_top(top: &str) -> Option<(&str, &str)> {
+    let result = top.split_once('.');
+    if result.is_none() {
+        eprintln!("Expected the top level as library.unit, got '{top}'");
+    }
+    result
+}
+
+/// Print the instance hierarchy and return the exit code
+fn show_hierarchy(config: Config, args: &HierarchyArgs, messages: &mut dyn MessageHandler) -> i32 {
+    let Some((library_name, entity_name)) = parse_top(&args.top) else {
+        return 2;
+    };
+
+    let mut project = Project::from_config(config, messages);
+    project.analyse();
+
+    if let Some(hierarchy) = project.instance_hierarchy(library_name, entity_name) {
+        match args.format {
+            HierarchyFormat::Text => print!("{}", hierarchy.to_text()),
+            HierarchyFormat::Json => println!("{}", hierarchy.to_json()),
+            HierarchyFormat::Dot => print!("{}", hierarchy.to_dot()),
+        }
+        0
+    } else {
+        eprintln!("No entity '{entity_name}' in library '{library_name}'");
+        1
+    }
+}
+
+/// Print the compile order and return the exit code
+fn show_compile_order(
+    config: Config,
+    args: &CompileOrderArgs,
+    messages: &mut dyn MessageHandler,
+) -> i32 {
+    let top = match args.top {
+        Some(ref top) => match parse_top(top) {
+            Some(top) => Some(top),
+            None => return 2,
+        },
+        None => None,
+    };
+
+    let mut project = Project::from_config(config, messages);
+    project.analyse();
+
+    let files = match project.compile_order(top) {
+        Ok(files) => files,
+        Err(err) => {
+            eprintln!("{err}");
+            return 1;
+        }
+    };
+
+    match args.format {
+        CompileOrderFormat::Text => {
+            for file in files.iter() {
+                println!("{} {}", file.library_name, file.file_name.to_string_lossy());
+            }
+        }
+        CompileOrderFormat::Json => {
+            let files: Vec<_> = files
+                .iter()
+                .map(|file| {
+                    serde_json::json!({
+                        "library": file.library_name,
+                        "file": file.file_name.to_string_lossy(),
+                    })
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&files).unwrap());
+        }
+        CompileOrderFormat::Ghdl | CompileOrderFormat::Vcom | CompileOrderFormat::Nvc => {
+            print!("{}", compile_script(args.format, &files));
+        }
+    }
+    0
+}
+
+/// Print the dependencies of a unit or of the whole design and return the exit code
+fn show_dependencies(
+    config: Config,
+    args: &DependenciesArgs,
+    messages: &mut dyn MessageHandler,
+) -> i32 {
+    let unit = match args.unit {
+        Some(ref unit) => {
+            let mut parts = unit.splitn(3, '.');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(library_name), Some(primary_name), secondary_name) => {
+                    Some((library_name, primary_name, secondary_name))
+                }
+                _ => {
+                    eprintln!(
+                        "Expected the unit as library.primary or library.primary.secondary, got '{unit}'"
+                    );
+                    return 2;
+                }
+            }
+        }
+        None => None,
+    };
+
+    let mut project = Project::from_config(config, messages);
+    project.analyse();
+    let graph = project.dependency_graph();
+
+    let Some((library_name, primary_name, secondary_name)) = unit else {
+        if args.dot {
+            print!("{}", graph.to_dot());
+        } else {
+            for unit_id in graph.units() {
+                let dependencies: Vec<_> = graph
+                    .dependencies_of(unit_id)
+                    .into_iter()
+                    .map(describe_unit)
+                    .collect();
+                println!("{}: {}", describe_unit(unit_id), dependencies.join(" "));
+            }
+        }
+        return 0;
+    };
+
+    let Some(unit_id) = project.find_unit_id(library_name, primary_name, secondary_name) else {
+        eprintln!(
+            "No unit '{}' in library '{library_name}'",
+            args.unit.as_deref().unwrap_or_default()
+        );
+        return 1;
+    };
+
+    let units = if args.users {
+        graph.transitive_users_of(&unit_id)
+    } else {
+        graph.transitive_dependencies_of(&unit_id)
+    };
+
+    if args.dot {
+        let subgraph = graph.subgraph(units.into_iter().chain(std::iter::once(&unit_id)));
+        print!("{}", subgraph.to_dot());
+    } else {
+        for unit_id in units {
+            println!("{}", describe_unit(unit_id));
+        }
+    }
+    0
+}
+
+/// Format the files and return the exit code
+fn format_files(config: Config, args: &FormatArgs, messages: &mut dyn MessageHandler) -> i32 {
+    let file_names = if args.files.is_empty() {
+        let mut file_names: Vec<PathBuf> = config
+            .iter_libraries()
+            .filter(|library| !library.is_third_party())
+            .flat_map(|library| library.file_names(messages))
+            .collect();
+        file_names.sort();
+        file_names.dedup();
+        file_names
+    } else {
+        args.files.clone()
+    };
+
+    let options = config.format_options().cloned().unwrap_or_default();
+    let parser = VHDLParser::default();
+    let mut code = 0;
+
+    for file_name in file_names.iter() {
+        let bytes = match std::fs::read(file_name) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                eprintln!("Could not read {}: {err}", file_name.to_string_lossy());
+                code = 1;
+                continue;
+            }
+        };
+        let original = Latin1String::from_vec(bytes).to_string();
+        let source = Source::inline(file_name, &original);
+        let standard = config
+            .library_names_of(file_name)
+            .into_iter()
+            .map(|library_name| config.standard_of(library_name))
+            .max()
+            .unwrap_or_else(|| config.standard());
+
+        let mut formatted = match parser.format_source(&source, standard, &options) {
+            Ok(formatted) => formatted,
+            Err(diagnostic) => {
+                eprintln!("{}", diagnostic.show());
+                code = 1;
+                continue;
+            }
+        };
+        if original.contains("\r\n") {
+            formatted = formatted.replace('\n', "\r\n");
+        }
+
+        if !args.stdout && formatted == original {
+            continue;
+        }
+        if args.check {
+            println!("{}", file_name.to_string_lossy());
+            code = 1;
+            continue;
+        }
+
+        // The formatted code only contains characters of the original latin-1 code
+        let result = Latin1String::from_utf8(&formatted)
+            .map_err(|err| err.message())
+            .and_then(|latin1| {
+                if args.stdout {
+                    std::io::stdout().write_all(&latin1.bytes)
+                } else {
+                    std::fs::write(file_name, latin1.bytes)
+                }
+                .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            eprintln!("Could not write {}: {err}", file_name.to_string_lossy());
+            code = 1;
+        }
+    }
+    code
+}
+
+/// Create a shell script that compiles the files in order with an external tool
+fn compile_script(format: CompileOrderFormat, files: &[LibraryFile]) -> String {
+    let mut script = String::from("#!/bin/sh\nset -e\n");
+
+    if format == CompileOrderFormat::Vcom {
+        let mut libraries: Vec<&str> = files.iter().map(|f| f.library_name.as_str()).collect();
+        libraries.sort_unstable();
+        libraries.dedup();
+        for library_name in libraries {
+            script.push_str(&format!("vlib {library_name}\n"));
+        }
+    }
+
+    for file in files {
+        let library_name = &file.library_name;
+        let file_name = shell_quote(&file.file_name.to_string_lossy());
+        let command = match format {
+            CompileOrderFormat::Ghdl => {
+                let std = match file.standard {
+                    VHDLStandard::VHDL1993 => "93",
+                    VHDLStandard::VHDL2002 => "02",
+                    VHDLStandard::VHDL2008 => "08",
+                    VHDLStandard::VHDL2019 => "19",
+                };
+                format!("ghdl -a --std={std} --work={library_name} {file_name}")
+            }
+            CompileOrderFormat::Vcom => {
+                let std = match file.standard {
+                    VHDLStandard::VHDL1993 => "-93",
+                    VHDLStandard::VHDL2002 => "-2002",
+                    VHDLStandard::VHDL2008 => "-2008",
+                    VHDLStandard::VHDL2019 => "-2019",
+                };
+                format!("vcom {std} -work {library_name} {file_name}")
+            }
+            CompileOrderFormat::Nvc => {
+                let std = match file.standard {
+                    VHDLStandard::VHDL1993 => "1993",
+                    VHDLStandard::VHDL2002 => "2002",
+                    VHDLStandard::VHDL2008 => "2008",
+                    VHDLStandard::VHDL2019 => "2019",
+                };
+                format!("nvc --std={std} --work={library_name} -a {file_name}")
+            }
+            CompileOrderFormat::Text | CompileOrderFormat::Json => unreachable!(),
+        };
+        script.push_str(&command);
+        script.push('\n');
+    }
+    script
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
 }
 
 fn show_diagnostics(diagnostics: &[Diagnostic]) {
@@ -4,15 +4,20 @@
 //
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
-use crate::analysis::{AnyEnt, CompletionItem, DesignRoot, EntRef};
-use crate::ast::DesignFile;
+use crate::analysis::{
+    AnyEnt, CallHierarchyCall, CompletionItem, DependencyGraph, DesignRoot, EntRef, FoldingRange,
+    Highlight, HoverInfo, InlayHint, InstanceHierarchy, Instantiation, InstantiationKind,
+    SignatureHelp, UseClauseFix,
+};
+use crate::ast::{DesignFile, UnitId, UnitKey};
 use crate::config::Config;
 use crate::lint::dead_code::UnusedDeclarationsLinter;
-use crate::syntax::VHDLParser;
+use crate::syntax::{FormatOptions, Suppressions, VHDLParser};
 use crate::{data::*, EntHierarchy, EntityId};
 use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 
 pub struct Project {
     parser: VHDLParser,
@@ -21,6 +26,7 @@ pub struct Project {
     files: FnvHashMap<PathBuf, SourceFile>,
     empty_libraries: FnvHashSet<Symbol>,
     lint: Option<UnusedDeclarationsLinter>,
+    report_inactive_regions: bool,
 }
 
 impl Project {
@@ -32,6 +38,7 @@ impl Project {
             empty_libraries: FnvHashSet::default(),
             parser,
             lint: None,
+            report_inactive_regions: false,
             config: Config::default(),
         }
     }
@@ -40,13 +47,21 @@ impl Project {
         self.lint = Some(UnusedDeclarationsLinter::default());
     }
 
+    /// Report the regions excluded by conditional analysis directives as diagnostics
+    /// such that an editor can grey them out
+    pub fn enable_inactive_region_reporting(&mut self) {
+        self.report_inactive_regions = true;
+    }
+
     /// Create instance from given configuration.
     /// Files referred by configuration are parsed into corresponding libraries.
     pub fn from_config(config: Config, messages: &mut dyn MessageHandler) -> Project {
         let mut project = Project::new();
+        project.parser.conditions = config.conditional_analysis();
         let files = project.load_files_from_config(&config, messages);
-        project.parse_and_add_files(files, messages);
         project.config = config;
+        project.configure_standards();
+        project.parse_and_add_files(files, messages);
         project
     }
 
@@ -55,6 +70,7 @@ impl Project {
     /// kept and parsed from in-memory source (required for incremental document updates).
     pub fn update_config(&mut self, config: Config, messages: &mut dyn MessageHandler) {
         self.parser = VHDLParser::default();
+        self.parser.conditions = config.conditional_analysis();
         self.root = DesignRoot::new(self.parser.symbols.clone());
 
         // Reset library associations for known files,
@@ -70,20 +86,60 @@ impl Project {
             .into_iter()
             .partition(|(file_name, _library_names)| self.files.contains_key(file_name));
 
+        self.config = config;
+        self.configure_standards();
+
         for (file_name, library_names) in known_files {
+            let standard = self.standard_of(&library_names);
             if let Some(source_file) = self.files.get_mut(&file_name) {
-                source_file.parser_diagnostics.clear();
                 source_file.library_names = library_names;
-                source_file.design_file = self
-                    .parser
-                    .parse_design_source(&source_file.source, &mut source_file.parser_diagnostics);
+                source_file.is_unmapped = false;
+                source_file.parse(&self.parser, standard);
+            }
+        }
+
+        // Files not listed in any library are mapped again with the new configuration
+        let unmapped: Vec<PathBuf> = self
+            .files
+            .iter()
+            .filter(|(_, source_file)| {
+                source_file.is_unmapped && source_file.library_names.is_empty()
+            })
+            .map(|(file_name, _)| file_name.clone())
+            .collect();
+        for file_name in unmapped {
+            let library_names = self.unmapped_library_names(&file_name);
+            let standard = self.standard_of(&library_names);
+            if let Some(source_file) = self.files.get_mut(&file_name) {
+                source_file.library_names = library_names;
+                source_file.parse(&self.parser, standard);
             }
         }
 
-        self.config = config;
         self.parse_and_add_files(new_files, messages);
     }
 
+    /// Analyze each library with the VHDL revision of the configuration
+    fn configure_standards(&mut self) {
+        self.root.set_default_standard(self.config.standard());
+        for library in self.config.iter_libraries() {
+            if let Some(standard) = library.standard() {
+                let library_name = self.root.symbol_utf8(library.name());
+                self.root.set_library_standard(library_name, standard);
+            }
+        }
+    }
+
+    /// The VHDL revision a file is parsed with.
+    /// A file in several libraries is parsed with the most recent revision among them.
+    fn standard_of(&self, library_names: &FnvHashSet<Symbol>) -> VHDLStandard {
+        library_names
+            .iter()
+            .map(|library_name| self.root.standard_of(library_name))
+            .max()
+            .unwrap_or_else(|| self.config.standard())
+    }
+
     fn load_files_from_config(
         &mut self,
         config: &Config,
@@ -127,20 +183,35 @@ impl Project {
     ) {
         use rayon::prelude::*;
 
+        let files_to_parse: Vec<_> = files_to_parse
+            .into_iter()
+            .map(|(file_name, library_names)| {
+                let standard = self.standard_of(&library_names);
+                (file_name, library_names, standard)
+            })
+            .collect();
+
         let parsed: Vec<_> = files_to_parse
             .into_par_iter()
             .map_init(
                 || &self.parser,
-                |parser, (file_name, library_names)| {
+                |parser, (file_name, library_names, standard)| {
                     let mut diagnostics = Vec::new();
-                    let result = parser.parse_design_file(&file_name, &mut diagnostics);
+                    let result = parser
+                        .parse_design_file(&file_name, standard, &mut diagnostics)
+                        .map(|(source, design_file)| {
+                            let suppressions =
+                                Suppressions::from_design_file(&design_file, &mut diagnostics);
+                            let inactive_regions = design_file.inactive_regions.clone();
+                            (source, design_file, suppressions, inactive_regions)
+                        });
                     (file_name, library_names, diagnostics, result)
                 },
             )
             .collect();
 
         for (file_name, library_names, parser_diagnostics, result) in parsed.into_iter() {
-            let (source, design_file) = match result {
+            let (source, design_file, suppressions, inactive_regions) = match result {
                 Ok(result) => result,
                 Err(err) => {
                     messages.push(Message::file_error(err.to_string(), &file_name));
@@ -153,13 +224,24 @@ impl Project {
                 SourceFile {
                     source,
                     library_names,
+                    is_unmapped: false,
                     parser_diagnostics,
                     design_file,
+                    suppressions,
+                    inactive_regions,
                 },
             );
         }
     }
 
+    fn unmapped_library_names(&self, file_name: &Path) -> FnvHashSet<Symbol> {
+        self.config
+            .unmapped_library_names(file_name)
+            .into_iter()
+            .map(|name| self.root.symbol_utf8(name))
+            .collect()
+    }
+
     pub fn library_mapping_of(&self, source: &Source) -> Vec<Symbol> {
         let file = if let Some(file) = self.files.get(source.file_name()) {
             file
@@ -186,30 +268,80 @@ impl Project {
                 source_file
             } else {
                 // File is not part of the project
-                // @TODO use config wildcards to map to library
-
-                // Add unmapped files to an anonymous library work
-                // To still get some semantic analysis for unmapped files
-                let mut library_names = FnvHashSet::default();
-                library_names.insert(self.root.symbol_utf8("work"));
+                // Map it using the configuration of unmapped files
+                // To still get semantic analysis for new or scratch files
+                let library_names = self.unmapped_library_names(source.file_name());
 
                 SourceFile {
                     source: source.clone(),
                     library_names,
+                    is_unmapped: true,
                     parser_diagnostics: vec![],
                     design_file: DesignFile::default(),
+                    suppressions: Suppressions::default(),
+                    inactive_regions: vec![],
                 }
             }
         };
-        source_file.parser_diagnostics.clear();
-        source_file.design_file = self
-            .parser
-            .parse_design_source(source, &mut source_file.parser_diagnostics);
+        let standard = self.standard_of(&source_file.library_names);
+        source_file.parse(&self.parser, standard);
         self.files
             .insert(source.file_name().to_owned(), source_file);
     }
 
+    /// Add a file created after the configuration was loaded to the libraries with a
+    /// matching pattern. A file that was already added as unmapped is moved to these libraries.
+    /// Returns true if the file was added.
+    pub fn add_file(&mut self, file_name: &Path, messages: &mut dyn MessageHandler) -> bool {
+        let library_names: FnvHashSet<Symbol> = self
+            .config
+            .library_names_of(file_name)
+            .into_iter()
+            .map(|name| self.root.symbol_utf8(name))
+            .collect();
+        if library_names.is_empty() {
+            return false;
+        }
+
+        let standard = self.standard_of(&library_names);
+        if let Some(source_file) = self.files.get_mut(file_name) {
+            if !source_file.is_unmapped {
+                return false;
+            }
+            for library_name in source_file.library_names.drain() {
+                self.root.remove_source(library_name, &source_file.source);
+            }
+            source_file.library_names = library_names;
+            source_file.is_unmapped = false;
+            source_file.parse(&self.parser, standard);
+        } else {
+            let mut files = FnvHashMap::default();
+            files.insert(file_name.to_owned(), library_names);
+            self.parse_and_add_files(files, messages);
+        }
+        self.files.contains_key(file_name)
+    }
+
+    /// Remove a deleted file from the project.
+    /// Returns true if the file was part of the project.
+    pub fn remove_file(&mut self, file_name: &Path) -> bool {
+        let Some(source_file) = self.files.remove(file_name) else {
+            return false;
+        };
+        for library_name in source_file.library_names.iter() {
+            self.root
+                .remove_source(library_name.clone(), &source_file.source);
+        }
+        true
+    }
+
     pub fn analyse(&mut self) -> Vec<Diagnostic> {
+        self.analyse_cancellable(&AtomicBool::new(false)).unwrap()
+    }
+
+    /// Analyse the project like [`Self::analyse`] but give up once `cancelled` is set,
+    /// see [`DesignRoot::analyze_cancellable`]. Returns `None` when cancelled.
+    pub fn analyse_cancellable(&mut self, cancelled: &AtomicBool) -> Option<Vec<Diagnostic>> {
         let mut diagnostics = Vec::new();
 
         for source_file in self.files.values_mut() {
@@ -225,19 +357,36 @@ impl Project {
             for diagnostic in source_file.parser_diagnostics.iter().cloned() {
                 diagnostics.push(diagnostic);
             }
+
+            if self.report_inactive_regions {
+                for pos in source_file.inactive_regions.iter() {
+                    diagnostics.push(Diagnostic::new(
+                        pos,
+                        "Excluded by conditional analysis",
+                        ErrorCode::InactiveRegion,
+                    ));
+                }
+            }
         }
 
         for library_name in self.empty_libraries.iter() {
             self.root.ensure_library(library_name.clone());
         }
 
-        let analyzed_units = self.root.analyze(&mut diagnostics);
+        let analyzed_units = self.root.analyze_cancellable(&mut diagnostics, cancelled)?;
 
         if let Some(ref mut lint) = self.lint {
             lint.lint(&self.root, &self.config, &analyzed_units, &mut diagnostics);
         }
 
-        diagnostics
+        diagnostics.retain(|diagnostic| {
+            !self
+                .files
+                .get(diagnostic.pos.source.file_name())
+                .is_some_and(|source_file| source_file.suppressions.is_suppressed(diagnostic))
+        });
+        self.config.severities().apply(&mut diagnostics);
+        Some(diagnostics)
     }
 
     /// Search for reference at position
@@ -279,6 +428,74 @@ impl Project {
         self.root.document_symbols(library_name, source)
     }
 
+    /// Elaborate the instance hierarchy below the entity `entity_name` in `library_name`
+    pub fn instance_hierarchy(
+        &self,
+        library_name: &str,
+        entity_name: &str,
+    ) -> Option<InstanceHierarchy> {
+        self.root.instance_hierarchy(
+            &self.root.symbol_utf8(library_name),
+            &self.root.symbol_utf8(entity_name),
+        )
+    }
+
+    /// Returns the dependencies between all design units.
+    /// Requires that the project has been analysed.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        self.root.dependency_graph()
+    }
+
+    /// Returns the id of the primary unit `primary_name` or
+    /// of its secondary unit `secondary_name` within a library
+    pub fn find_unit_id(
+        &self,
+        library_name: &str,
+        primary_name: &str,
+        secondary_name: Option<&str>,
+    ) -> Option<UnitId> {
+        let primary_name = self.root.symbol_utf8(primary_name);
+        let key = if let Some(secondary_name) = secondary_name {
+            UnitKey::Secondary(primary_name, self.root.symbol_utf8(secondary_name))
+        } else {
+            UnitKey::Primary(primary_name)
+        };
+        self.root
+            .unit_id(&self.root.symbol_utf8(library_name), &key)
+    }
+
+    /// Returns the files in an order where every file comes after the files it depends on.
+    /// Files of the std and ieee libraries are excluded as they are provided by the tools.
+    ///
+    /// When a top level unit is given as `(library_name, unit_name)` only the files
+    /// required to elaborate it are included. Requires that the project has been analysed.
+    pub fn compile_order(&self, top: Option<(&str, &str)>) -> Result<Vec<LibraryFile>, String> {
+        let top = if let Some((library_name, unit_name)) = top {
+            let unit_id = self
+                .root
+                .unit_id(
+                    &self.root.symbol_utf8(library_name),
+                    &UnitKey::Primary(self.root.symbol_utf8(unit_name)),
+                )
+                .ok_or_else(|| format!("No unit '{unit_name}' in library '{library_name}'"))?;
+            Some(unit_id)
+        } else {
+            None
+        };
+
+        Ok(self
+            .root
+            .compile_order(top.as_ref())
+            .into_iter()
+            .map(|(library_name, file_name)| LibraryFile {
+                standard: self.root.standard_of(&library_name),
+                library_name: library_name.name_utf8(),
+                file_name,
+            })
+            .filter(|file| !matches!(file.library_name.as_str(), "std" | "ieee"))
+            .collect())
+    }
+
     pub fn find_implementation<'a>(&'a self, source: &Source, cursor: Position) -> Vec<EntRef<'a>> {
         if let Some(ent) = self.find_declaration(source, cursor) {
             self.root.find_implementation(ent)
@@ -292,16 +509,110 @@ impl Project {
         self.root.format_declaration(ent)
     }
 
+    /// Collect the information to show when hovering over a named entity,
+    /// see [`DesignRoot::hover_info`]
+    pub fn hover_info(&self, ent: &AnyEnt) -> HoverInfo {
+        self.root.hover_info(ent)
+    }
+
     pub fn format_entity(&self, id: EntityId) -> Option<String> {
         let ent = self.root.get_ent(id);
         self.format_declaration(ent)
     }
 
+    /// Returns the formatting options of the configuration if it has a format section
+    pub fn format_options(&self) -> Option<&FormatOptions> {
+        self.config.format_options()
+    }
+
+    /// Format the code of a source file, see [`VHDLParser::format_source`]
+    pub fn format_source(
+        &self,
+        source: &Source,
+        options: &FormatOptions,
+    ) -> Result<String, Diagnostic> {
+        self.parser
+            .format_source(source, self.standard_of_source(source), options)
+    }
+
+    /// Format a range of lines of a source file, see [`VHDLParser::format_source_lines`]
+    pub fn format_source_lines(
+        &self,
+        source: &Source,
+        options: &FormatOptions,
+        lines: std::ops::RangeInclusive<u32>,
+    ) -> Result<String, Diagnostic> {
+        self.parser
+            .format_source_lines(source, self.standard_of_source(source), options, lines)
+    }
+
+    /// The VHDL revision of a source file, the configured revision for files not in the project
+    fn standard_of_source(&self, source: &Source) -> VHDLStandard {
+        self.files
+            .get(source.file_name())
+            .map(|source_file| self.standard_of(&source_file.library_names))
+            .unwrap_or_else(|| self.config.standard())
+    }
+
     /// Search for all references to the declaration at decl_pos
     pub fn find_all_references(&self, ent: &AnyEnt) -> Vec<SrcPos> {
         self.root.find_all_references(ent)
     }
 
+    /// Find all declarations of and references to named entities within a source file,
+    /// see [`DesignRoot::find_all_in_source`]
+    pub fn find_all_in_source(&self, source: &Source) -> Vec<(SrcPos, EntRef<'_>, bool)> {
+        self.root.find_all_in_source(source)
+    }
+
+    /// Find all declarations of and references to a named entity within a source file,
+    /// see [`DesignRoot::find_highlights`]
+    pub fn find_highlights(&self, source: &Source, ent: &AnyEnt) -> Vec<Highlight> {
+        self.root.find_highlights(source, ent)
+    }
+
+    /// The regions of a source file that can be folded, see [`DesignRoot::folding_ranges`]
+    pub fn folding_ranges(&self, source: &Source) -> Vec<FoldingRange> {
+        self.root.folding_ranges(source)
+    }
+
+    /// The ranges of the syntax tree surrounding the cursor,
+    /// see [`DesignRoot::selection_ranges`]
+    pub fn selection_ranges(&self, source: &Source, cursor: Position) -> Vec<SrcPos> {
+        self.root.selection_ranges(source, cursor)
+    }
+
+    /// The callers of a subprogram, see [`DesignRoot::incoming_calls`]
+    pub fn incoming_calls<'a>(&'a self, callee: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        self.root.incoming_calls(callee)
+    }
+
+    /// The subprograms called by a subprogram, process or design unit,
+    /// see [`DesignRoot::outgoing_calls`]
+    pub fn outgoing_calls<'a>(&'a self, caller: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        self.root.outgoing_calls(caller)
+    }
+
+    /// The entities instantiating an entity, see [`DesignRoot::instantiated_by`]
+    pub fn instantiated_by<'a>(&'a self, entity: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        self.root.instantiated_by(entity)
+    }
+
+    /// The entities instantiated by an entity, see [`DesignRoot::instantiates`]
+    pub fn instantiates<'a>(&'a self, entity: EntRef<'a>) -> Vec<CallHierarchyCall<'a>> {
+        self.root.instantiates(entity)
+    }
+
+    /// The types a type is declared from, see [`DesignRoot::supertypes`]
+    pub fn supertypes<'a>(&'a self, typ: EntRef<'a>) -> Vec<EntRef<'a>> {
+        self.root.supertypes(typ)
+    }
+
+    /// The types declared from a type, see [`DesignRoot::subtypes`]
+    pub fn subtypes<'a>(&'a self, typ: EntRef<'a>) -> Vec<EntRef<'a>> {
+        self.root.subtypes(typ)
+    }
+
     /// Get source positions that are not resolved to a declaration
     /// This is used for development to test where the language server is blind
     pub fn find_all_unresolved(&self) -> (usize, Vec<SrcPos>) {
@@ -317,7 +628,54 @@ impl Project {
         source: &Source,
         cursor: Position,
     ) -> Vec<CompletionItem> {
-        self.root.list_completion_options(source, cursor)
+        self.root
+            .list_completion_options(source, cursor, self.standard_of_source(source))
+    }
+
+    /// The signatures of the subprogram call or map aspect surrounding the cursor,
+    /// see [`DesignRoot::signature_help`]
+    pub fn signature_help(&self, source: &Source, cursor: Position) -> Option<SignatureHelp> {
+        self.root
+            .signature_help(source, cursor, self.standard_of_source(source))
+    }
+
+    /// The inlay hints of a source file, see [`DesignRoot::inlay_hints`]
+    pub fn inlay_hints(&self, source: &Source, range: Option<Range>) -> Vec<InlayHint> {
+        self.root.inlay_hints(source, range)
+    }
+
+    /// Find use clauses that would make declarations named `name` visible at the cursor,
+    /// see [`DesignRoot::find_use_clause_fixes`]
+    pub fn find_use_clause_fixes(
+        &self,
+        source: &Source,
+        cursor: Position,
+        name: &str,
+    ) -> Vec<UseClauseFix> {
+        self.root.find_use_clause_fixes(source, cursor, name)
+    }
+
+    /// The entities named `name` in any library
+    pub fn find_design_entities(&self, name: &str) -> Vec<EntRef<'_>> {
+        self.root.find_design_entities(name)
+    }
+
+    /// Generate an instantiation of an entity in the architecture at the cursor
+    /// using the indentation of the formatter options
+    pub fn generate_instantiation(
+        &self,
+        source: &Source,
+        cursor: Position,
+        entity: EntRef,
+        kind: InstantiationKind,
+    ) -> Option<Instantiation> {
+        let indent_unit = self
+            .format_options()
+            .cloned()
+            .unwrap_or_default()
+            .indent_unit();
+        self.root
+            .generate_instantiation(source, cursor, entity, kind, &indent_unit)
     }
 }
 
@@ -344,14 +702,37 @@ impl Default for Project {
     }
 }
 
+/// A file and the library it is compiled into
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LibraryFile {
+    pub library_name: String,
+    pub file_name: PathBuf,
+    pub standard: VHDLStandard,
+}
+
 pub struct SourceFile {
     library_names: FnvHashSet<Symbol>,
+    // Not listed in any library but mapped by the configuration of unmapped files
+    is_unmapped: bool,
     source: Source,
     design_file: DesignFile,
     parser_diagnostics: Vec<Diagnostic>,
+    suppressions: Suppressions,
+    // Regions excluded by conditional analysis directives, kept when the design file is analyzed
+    inactive_regions: Vec<SrcPos>,
 }
 
 impl SourceFile {
+    /// Parse the design file and the suppression pragmas from the in-memory source
+    fn parse(&mut self, parser: &VHDLParser, standard: VHDLStandard) {
+        self.parser_diagnostics.clear();
+        self.design_file =
+            parser.parse_design_source(&self.source, standard, &mut self.parser_diagnostics);
+        self.suppressions =
+            Suppressions::from_design_file(&self.design_file, &mut self.parser_diagnostics);
+        self.inactive_regions = self.design_file.inactive_regions.clone();
+    }
+
     fn take_design_file(&mut self) -> DesignFile {
         std::mem::take(&mut self.design_file)
     }
@@ -397,6 +778,241 @@ lib.files = ['file.vhd']
         check_no_diagnostics(&project.analyse());
     }
 
+    #[test]
+    fn lint_severities_are_applied() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            vhdl_file_path,
+            "
+library work;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+architecture a of ent is
+begin
+end architecture;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+
+[lint]
+unnecessary_work_library = 'off'
+duplicate = 'warning'
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ErrorCode::Duplicate);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn inactive_regions_are_reported_as_lints() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            vhdl_file_path,
+            "
+`if TOOL_TYPE = \"SYNTHESIS\" then
+entity ent is
+end entity;
+`end if
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config.clone(), &mut messages);
+        // Only reported when enabled by the language server
+        check_no_diagnostics(&project.analyse());
+
+        let mut project = Project::from_config(config, &mut messages);
+        project.enable_inactive_region_reporting();
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ErrorCode::InactiveRegion);
+        assert_eq!(diagnostics[0].severity, Severity::Hint);
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+
+[lint]
+inactive_region = 'off'
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut project = Project::from_config(config, &mut messages);
+        project.enable_inactive_region_reporting();
+        check_no_diagnostics(&project.analyse());
+    }
+
+    #[test]
+    fn cancelled_analysis_is_completed_by_next_analysis() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            vhdl_file_path,
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  component unused is
+  end component;
+begin
+  missing;
+end architecture;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        project.enable_unused_declaration_detection();
+        assert_eq!(project.analyse_cancellable(&AtomicBool::new(true)), None);
+
+        let mut codes: Vec<_> = project
+            .analyse()
+            .into_iter()
+            .map(|diagnostic| diagnostic.code)
+            .collect();
+        codes.sort_by_key(|code| code.as_str());
+        assert_eq!(codes, vec![ErrorCode::Unresolved, ErrorCode::Unused]);
+    }
+
+    #[test]
+    fn suppressed_diagnostics_are_removed() {
+        let root = tempfile::tempdir().unwrap();
+        let vhdl_file_path = root.path().join("file.vhd");
+        std::fs::write(
+            &vhdl_file_path,
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+-- vhdl_ls: disable-next-line duplicate
+architecture a of ent is
+begin
+end architecture;
+
+architecture a of ent is
+begin
+end architecture;
+        ",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['file.vhd']
+        ";
+
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].pos.range().start.line, 13);
+
+        // Suppressions follow in-memory updates of the source
+        let source = project
+            .get_source(&dunce::canonicalize(&vhdl_file_path).unwrap())
+            .unwrap();
+        source.change(
+            None,
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+end architecture;
+
+-- vhdl_ls: disable duplicate
+architecture a of ent is
+begin
+end architecture;
+
+architecture a of ent is
+begin
+end architecture;
+        ",
+        );
+        project.update_source(&source);
+        check_no_diagnostics(&project.analyse());
+    }
+
+    #[test]
+    fn libraries_are_parsed_with_their_standard() {
+        let root = tempfile::tempdir().unwrap();
+        for file_name in ["old.vhd", "new.vhd"] {
+            std::fs::write(
+                root.path().join(file_name),
+                "
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  process (all) is
+  begin
+  end process;
+end architecture;
+        ",
+            )
+            .unwrap();
+        }
+
+        let config_str = "
+standard = '1993'
+[libraries]
+old.files = ['old.vhd']
+new.files = ['new.vhd']
+new.standard = '2008'
+        ";
+        let config = Config::from_str(config_str, root.path()).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ErrorCode::NotInStandard);
+        assert!(diagnostics[0].pos.file_name().ends_with("old.vhd"));
+
+        let config = Config::from_str(&config_str.replace("1993", "2008"), root.path()).unwrap();
+        project.update_config(config, &mut messages);
+        check_no_diagnostics(&project.analyse());
+    }
+
     #[test]
     fn unmapped_libraries_are_analyzed() {
         let mut messages = Vec::new();
@@ -432,6 +1048,89 @@ end architecture;
         assert_eq!(diag.message, "Duplicate architecture 'rtl' of entity 'ent'")
     }
 
+    #[test]
+    fn unmapped_files_use_configured_library() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+        std::fs::create_dir(root.join("ip")).unwrap();
+
+        let pkg_path = root.join("ip").join("pkg.vhd");
+        std::fs::write(
+            &pkg_path,
+            "
+package pkg is
+end package;
+",
+        )
+        .unwrap();
+        let ent_path = root.join("ent.vhd");
+        std::fs::write(
+            &ent_path,
+            "
+library ip_lib;
+use ip_lib.pkg.all;
+
+entity ent is
+end entity;
+",
+        )
+        .unwrap();
+
+        let config = Config::from_str(
+            "
+[libraries]
+[unmapped]
+default_library = 'scratch'
+libraries.ip_lib = ['ip/*.vhd']
+",
+            &root,
+        )
+        .unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+
+        let pkg = Source::from_latin1_file(&pkg_path).unwrap();
+        let ent = Source::from_latin1_file(&ent_path).unwrap();
+        project.update_source(&pkg);
+        project.update_source(&ent);
+        check_no_diagnostics(&project.analyse());
+        assert_eq!(
+            project.library_mapping_of(&pkg),
+            vec![project.root.symbol_utf8("ip_lib")]
+        );
+        assert_eq!(
+            project.library_mapping_of(&ent),
+            vec![project.root.symbol_utf8("scratch")]
+        );
+
+        // Unmapped files are mapped again when the configuration changes
+        let config = Config::from_str(
+            "
+[libraries]
+[unmapped]
+libraries.vendor = ['ip/*.vhd']
+",
+            &root,
+        )
+        .unwrap();
+        project.update_config(config, &mut messages);
+        assert_eq!(messages, vec![]);
+        let diagnostics = project.analyse();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.pos.source == ent));
+        assert_eq!(
+            project.library_mapping_of(&pkg),
+            vec![project.root.symbol_utf8("vendor")]
+        );
+        assert_eq!(
+            project.library_mapping_of(&ent),
+            vec![project.root.symbol_utf8("work")]
+        );
+    }
+
     /// Test that the same file can be added to several libraries
     #[test]
     fn test_same_file_in_multiple_libraries() {
@@ -479,6 +1178,173 @@ use_lib.files = ['use_file.vhd']
         check_no_diagnostics(&project.analyse());
     }
 
+    #[test]
+    fn compile_order_of_top_level_unit() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+
+        let files = [
+            (
+                "a_top.vhd",
+                "
+library common;
+use common.pkg.all;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+  component leaf is
+  end component;
+begin
+  inst: component leaf;
+end architecture;
+",
+            ),
+            (
+                "b_leaf.vhd",
+                "
+entity leaf is
+end entity;
+
+architecture rtl of leaf is
+begin
+end architecture;
+",
+            ),
+            (
+                "c_pkg.vhd",
+                "
+package pkg is
+end package;
+",
+            ),
+            (
+                "d_unused.vhd",
+                "
+entity unused is
+end entity;
+",
+            ),
+        ];
+
+        for (file_name, contents) in files {
+            std::fs::write(root.join(file_name), contents).unwrap();
+        }
+
+        let config_str = "
+[libraries]
+lib.files = ['a_top.vhd', 'b_leaf.vhd', 'd_unused.vhd']
+common.files = ['c_pkg.vhd']
+common.standard = '1993'
+        ";
+
+        let config = Config::from_str(config_str, &root).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        check_no_diagnostics(&project.analyse());
+
+        let file = |library_name: &str, file_name: &str| LibraryFile {
+            library_name: library_name.to_owned(),
+            file_name: root.join(file_name),
+            standard: if library_name == "common" {
+                VHDLStandard::VHDL1993
+            } else {
+                VHDLStandard::VHDL2008
+            },
+        };
+
+        assert_eq!(
+            project.compile_order(Some(("lib", "top"))),
+            Ok(vec![
+                file("common", "c_pkg.vhd"),
+                file("lib", "a_top.vhd"),
+                file("lib", "b_leaf.vhd"),
+            ])
+        );
+
+        assert_eq!(
+            project.compile_order(None),
+            Ok(vec![
+                file("common", "c_pkg.vhd"),
+                file("lib", "a_top.vhd"),
+                file("lib", "b_leaf.vhd"),
+                file("lib", "d_unused.vhd"),
+            ])
+        );
+
+        assert_eq!(
+            project.compile_order(Some(("lib", "missing"))),
+            Err("No unit 'missing' in library 'lib'".to_owned())
+        );
+    }
+
+    #[test]
+    fn compile_order_excludes_standard_libraries() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(tempdir.path()).unwrap();
+
+        std::fs::write(
+            root.join("vendor.vhd"),
+            "
+package vendor_pkg is
+end package;
+",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("ieee.vhd"),
+            "
+package ieee_pkg is
+end package;
+",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("top.vhd"),
+            "
+library vendor, ieee;
+use vendor.vendor_pkg.all;
+use ieee.ieee_pkg.all;
+
+entity top is
+end entity;
+",
+        )
+        .unwrap();
+
+        let config_str = "
+[libraries]
+lib.files = ['top.vhd']
+vendor.files = ['vendor.vhd']
+vendor.is_third_party = true
+ieee.files = ['ieee.vhd']
+ieee.is_third_party = true
+        ";
+
+        let config = Config::from_str(config_str, &root).unwrap();
+        let mut messages = Vec::new();
+        let mut project = Project::from_config(config, &mut messages);
+        check_no_diagnostics(&project.analyse());
+
+        // Third party libraries other than std and ieee are not provided by the tools
+        assert_eq!(
+            project.compile_order(Some(("lib", "top"))),
+            Ok(vec![
+                LibraryFile {
+                    library_name: "vendor".to_owned(),
+                    file_name: root.join("vendor.vhd"),
+                    standard: VHDLStandard::VHDL2008,
+                },
+                LibraryFile {
+                    library_name: "lib".to_owned(),
+                    file_name: root.join("top.vhd"),
+                    standard: VHDLStandard::VHDL2008,
+                }
+            ])
+        );
+    }
+
     fn update(project: &mut Project, source: &mut Source, contents: &str) {
         std::fs::write(std::path::Path::new(source.file_name()), contents).unwrap();
         *source = Source::from_latin1_file(source.file_name()).unwrap();
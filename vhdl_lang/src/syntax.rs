@@ -16,7 +16,10 @@ mod configuration;
 mod context;
 mod declarative_part;
 mod design_unit;
+mod disconnection;
 mod expression;
+mod formatting;
+mod group_declaration;
 mod interface_declaration;
 mod names;
 mod object_declaration;
@@ -27,11 +30,15 @@ mod separated_list;
 mod sequential_statement;
 mod subprogram;
 mod subtype_indication;
+mod suppression;
 mod type_declaration;
+mod view_declaration;
 mod waveform;
 
 #[cfg(test)]
 pub mod test;
 
+pub use formatting::{FormatOptions, KeywordCase};
 pub use parser::{ParserResult, VHDLParser};
+pub use suppression::Suppressions;
 pub use tokens::*;
@@ -14,7 +14,7 @@ use crate::ast::{
     EntityTag, WithRef,
 };
 
-fn parse_entity_class(stream: &TokenStream) -> ParseResult<EntityClass> {
+pub fn parse_entity_class(stream: &TokenStream) -> ParseResult<EntityClass> {
     Ok(expect_token!(stream, token,
         Entity => EntityClass::Entity,
         Architecture => EntityClass::Architecture,
@@ -27,6 +27,10 @@ fn parse_entity_class(stream: &TokenStream) -> ParseResult<EntityClass> {
         Component => EntityClass::Component,
         Constant => EntityClass::Constant,
         Type => EntityClass::Type,
+        Subtype => EntityClass::Subtype,
+        File => EntityClass::File,
+        Units => EntityClass::Units,
+        Group => EntityClass::Group,
         Label => EntityClass::Label
     ))
 }
@@ -8,6 +8,7 @@ use super::tokens::{Kind, TokenStream};
 use crate::ast::Ident;
 use crate::data::Diagnostic;
 use crate::data::DiagnosticHandler;
+use crate::data::ErrorCode;
 use crate::data::WithPos;
 use crate::SrcPos;
 
@@ -40,9 +41,10 @@ pub fn check_end_identifier_mismatch<T: std::fmt::Display + std::cmp::PartialEq>
         if ident.item == end_ident.item {
             return Some(end_ident.pos);
         } else {
-            diagnostics.error(
+            diagnostics.add(
                 &end_ident.pos,
                 format!("End identifier mismatch, expected {}", ident.item),
+                ErrorCode::SyntaxError,
             );
         }
     }
@@ -59,19 +61,21 @@ pub fn check_label_identifier_mismatch(
             if ident.item == end_ident.item {
                 return Some(end_ident.pos);
             } else {
-                diagnostics.error(
+                diagnostics.add(
                     &end_ident.pos,
                     format!("End label mismatch, expected {}", ident.item),
+                    ErrorCode::SyntaxError,
                 );
             }
         }
     } else if let Some(end_ident) = end_ident {
-        diagnostics.error(
+        diagnostics.add(
             &end_ident.pos,
             format!(
                 "End label '{}' found for unlabeled statement",
                 end_ident.item
             ),
+            ErrorCode::SyntaxError,
         );
     }
     None
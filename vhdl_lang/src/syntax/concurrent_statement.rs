@@ -79,17 +79,17 @@ fn parse_block_header(
                 stream.skip();
                 if let Some(map_token) = stream.pop_if_kind(Map) {
                     if port_clause.is_some() || port_map.is_some() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             stream.get_token(map_token),
                             "Generic map must come before port clause and port map",
                         ));
                     } else if generic_clause.is_none() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             stream.get_token(map_token),
                             "Generic map declared without preceding generic clause",
                         ));
                     } else if generic_map.is_some() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             stream.get_token(map_token),
                             "Duplicate generic map",
                         ));
@@ -105,12 +105,13 @@ fn parse_block_header(
                     }
                 } else {
                     if generic_map.is_some() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             token,
                             "Generic clause must come before generic map",
                         ));
                     } else if generic_clause.is_some() {
-                        diagnostics.push(Diagnostic::error(token, "Duplicate generic clause"));
+                        diagnostics
+                            .push(Diagnostic::syntax_error(token, "Duplicate generic clause"));
                     }
                     let parsed_generic_list = parse_generic_interface_list(stream, diagnostics)?;
                     stream.expect_kind(SemiColon)?;
@@ -123,12 +124,12 @@ fn parse_block_header(
                 stream.skip();
                 if let Some(map_token) = stream.pop_if_kind(Map) {
                     if port_clause.is_none() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             stream.get_token(map_token),
                             "Port map declared without preceeding port clause",
                         ));
                     } else if port_map.is_some() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             stream.get_token(map_token),
                             "Duplicate port map",
                         ));
@@ -144,12 +145,12 @@ fn parse_block_header(
                     }
                 } else {
                     if port_map.is_some() {
-                        diagnostics.push(Diagnostic::error(
+                        diagnostics.push(Diagnostic::syntax_error(
                             token,
                             "Port clause declared after port map",
                         ));
                     } else if port_clause.is_some() {
-                        diagnostics.push(Diagnostic::error(token, "Duplicate port clause"));
+                        diagnostics.push(Diagnostic::syntax_error(token, "Duplicate port clause"));
                     }
                     let parsed_port_list = parse_port_interface_list(stream, diagnostics)?;
                     stream.expect_kind(SemiColon)?;
@@ -181,6 +182,9 @@ pub fn parse_process_statement(
     let sensitivity_list = if stream.skip_if_kind(LeftPar) {
         peek_token!(stream, token,
         All => {
+            if stream.standard() < VHDLStandard::VHDL2008 {
+                diagnostics.push(Diagnostic::not_in_standard(token, "Process sensitivity list 'all'", stream.standard()));
+            }
             stream.skip();
             stream.expect_kind(RightPar)?;
             Some(SensitivityList::All)
@@ -188,7 +192,7 @@ pub fn parse_process_statement(
         RightPar => {
             stream.skip();
             diagnostics.push(
-                Diagnostic::error(token, "Processes with sensitivity lists must contain at least one element.")
+                Diagnostic::syntax_error(token, "Processes with sensitivity lists must contain at least one element.")
             );
             Some(SensitivityList::Names(Vec::new()))
         },
@@ -222,7 +226,7 @@ pub fn parse_process_statement(
 
     if let Some(token) = stream.pop_if_kind(Postponed) {
         if !postponed {
-            diagnostics.push(Diagnostic::error(
+            diagnostics.push(Diagnostic::syntax_error(
                 stream.get_token(token),
                 "'postponed' at the end of non-postponed process.",
             ));
@@ -259,7 +263,7 @@ fn to_procedure_call(
                 target.pos,
             ),
         }),
-        Target::Aggregate(..) => Err(Diagnostic::error(
+        Target::Aggregate(..) => Err(Diagnostic::syntax_error(
             target,
             "Expected procedure call, got aggregate",
         )),
@@ -273,8 +277,7 @@ fn parse_assignment_known_target(
 ) -> ParseResult<ConcurrentStatement> {
     // @TODO postponed
     let postponed = false;
-    // @TODO guarded
-    let guarded = false;
+    let guarded = stream.skip_if_kind(Guarded);
     let delay_mechanism = parse_delay_mechanism(stream)?;
     Ok(ConcurrentStatement::Assignment(
         ConcurrentSignalAssignment {
@@ -309,8 +312,7 @@ fn parse_selected_signal_assignment(
     stream.expect_kind(Select)?;
     let target = parse_target(stream)?;
     stream.expect_kind(LTE)?;
-    // @TODO guarded
-    let guarded = false;
+    let guarded = stream.skip_if_kind(Guarded);
     let delay_mechanism = parse_delay_mechanism(stream)?;
     let rhs = AssignmentRightHand::Selected(parse_selection(stream, expression, parse_waveform)?);
     Ok(ConcurrentSignalAssignment {
@@ -1057,7 +1059,7 @@ end postponed process;",
         };
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s1("postponed"),
                 "'postponed' at the end of non-postponed process."
             )]
@@ -1112,7 +1114,7 @@ end process;",
         };
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s1(")"),
                 "Processes with sensitivity lists must contain at least one element."
             )]
@@ -1152,6 +1154,33 @@ end process;",
         );
     }
 
+    #[test]
+    fn test_process_statement_all_before_vhdl2008() {
+        let code = Code::new(
+            "\
+process (all) is
+begin
+end process;",
+        )
+        .with_standard(VHDLStandard::VHDL1993);
+        let (stmt, diagnostics) = code.with_stream_diagnostics(parse_labeled_concurrent_statement);
+        assert!(matches!(
+            stmt.statement.item,
+            ConcurrentStatement::Process(ProcessStatement {
+                sensitivity_list: Some(SensitivityList::All),
+                ..
+            })
+        ));
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::not_in_standard(
+                code.s1("all"),
+                "Process sensitivity list 'all'",
+                VHDLStandard::VHDL1993
+            )]
+        );
+    }
+
     #[test]
     fn test_concurrent_assert() {
         let code = Code::new("assert cond = true;");
@@ -1208,6 +1237,23 @@ end process;",
         );
     }
 
+    #[test]
+    fn test_guarded_concurrent_signal_assignment() {
+        let code = Code::new("foo <= guarded transport bar;");
+        let assign = ConcurrentSignalAssignment {
+            postponed: false,
+            guarded: true,
+            target: code.s1("foo").name().map_into(Target::Name),
+            delay_mechanism: Some(DelayMechanism::Transport),
+            rhs: AssignmentRightHand::Simple(code.s1("bar").waveform()),
+        };
+        let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
+        assert_eq!(
+            stmt.statement,
+            WithPos::new(ConcurrentStatement::Assignment(assign), code.pos())
+        );
+    }
+
     #[test]
     fn test_concurrent_signal_assignment_external_name() {
         let code = Code::new("<< signal dut.foo : std_logic >> <= bar(2 to 3);");
@@ -1260,6 +1306,17 @@ with x(0) + 1 select
         assert_eq!(stmt.statement.pos, code.pos());
     }
 
+    #[test]
+    fn parse_guarded_selected_signal_assignment() {
+        let code = Code::new("with x select foo <= guarded bar when others;");
+        let stmt = code.with_stream_no_diagnostics(parse_labeled_concurrent_statement);
+        let ConcurrentStatement::Assignment(assign) = stmt.statement.item else {
+            panic!("Expected assignment");
+        };
+        assert!(assign.guarded);
+        assert_eq!(assign.delay_mechanism, None);
+    }
+
     #[test]
     fn test_component_instantiation() {
         let code = Code::new("inst: component lib.foo.bar;");
@@ -1844,11 +1901,11 @@ end generate;",
         assert_eq!(
             diagnostics,
             vec![
-                Diagnostic::error(
+                Diagnostic::syntax_error(
                     code.s1("alt2"),
                     "End label 'alt2' found for unlabeled statement"
                 ),
-                Diagnostic::error(code.s1("alt4"), "End label mismatch, expected alt3")
+                Diagnostic::syntax_error(code.s1("alt4"), "End label mismatch, expected alt3")
             ]
         );
     }
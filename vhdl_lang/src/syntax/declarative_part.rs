@@ -10,11 +10,14 @@ use super::common::ParseResult;
 use super::component_declaration::parse_component_declaration;
 use super::configuration::parse_configuration_specification;
 use super::context::parse_use_clause;
+use super::disconnection::parse_disconnection_specification;
+use super::group_declaration::parse_group_declaration;
 use super::names::parse_selected_name;
 use super::object_declaration::{parse_file_declaration, parse_object_declaration};
 use super::subprogram::parse_subprogram;
 use super::tokens::{Kind::*, *};
 use super::type_declaration::parse_type_declaration;
+use super::view_declaration::parse_mode_view_declaration;
 use crate::ast::{ContextClause, Declaration, PackageInstantiation};
 use crate::data::DiagnosticHandler;
 use crate::syntax::concurrent_statement::parse_map_aspect;
@@ -46,13 +49,14 @@ pub fn is_declarative_part(stream: &TokenStream, begin_is_end: bool) -> ParseRes
 fn check_declarative_part(token: &Token, may_end: bool, may_begin: bool) -> ParseResult<()> {
     match token.kind {
         Use | Type | Subtype | Shared | Constant | Signal | Variable | File | Component
-        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For => Ok(()),
+        | Attribute | Alias | Impure | Pure | Function | Procedure | Package | For | Group
+        | Disconnect | View => Ok(()),
         Begin if may_begin => Ok(()),
         End if may_end => Ok(()),
         _ => {
             let decl_kinds = [
                 Use, Type, Subtype, Shared, Constant, Signal, Variable, File, Component, Attribute,
-                Alias, Impure, Pure, Function, Procedure, Package, For,
+                Alias, Impure, Pure, Function, Procedure, Package, For, Group, Disconnect, View,
             ];
 
             Err(token.kinds_error(&decl_kinds))
@@ -85,6 +89,9 @@ pub fn parse_declarative_part(
                 | Attribute
                 | Use
                 | Alias
+                | Group
+                | Disconnect
+                | View
                 | Begin
                 | End
         )
@@ -93,7 +100,8 @@ pub fn parse_declarative_part(
     while let Some(token) = stream.peek() {
         match token.kind {
             Begin | End => break,
-            Type | Subtype | Component | Impure | Pure | Function | Procedure | Package | For => {
+            Type | Subtype | Component | Impure | Pure | Function | Procedure | Package | For
+            | View => {
                 let decl = match token.kind {
                     Type | Subtype => {
                         parse_type_declaration(stream, diagnostics).map(Declaration::Type)?
@@ -105,6 +113,9 @@ pub fn parse_declarative_part(
                         .map(Declaration::Package)?,
                     For => parse_configuration_specification(stream, diagnostics)
                         .map(Declaration::Configuration)?,
+                    View => {
+                        parse_mode_view_declaration(stream, diagnostics).map(Declaration::View)?
+                    }
                     _ => unreachable!(),
                 };
                 declarations.push(decl);
@@ -129,10 +140,14 @@ pub fn parse_declarative_part(
                 }
             }
 
-            Use | Alias => {
+            Use | Alias | Group | Disconnect => {
                 let decl: ParseResult<Declaration> = match token.kind {
                     Use => parse_use_clause(stream, diagnostics).map(Declaration::Use),
                     Alias => parse_alias_declaration(stream).map(Declaration::Alias),
+                    Group => parse_group_declaration(stream),
+                    Disconnect => {
+                        parse_disconnection_specification(stream).map(Declaration::Disconnection)
+                    }
                     _ => unreachable!(),
                 };
                 match decl.or_recover_until(stream, diagnostics, is_recover_token) {
@@ -147,7 +162,8 @@ pub fn parse_declarative_part(
             _ => {
                 diagnostics.push(token.kinds_error(&[
                     Type, Subtype, Component, Impure, Pure, Function, Procedure, Package, For,
-                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias,
+                    File, Shared, Constant, Signal, Variable, Attribute, Use, Alias, Group,
+                    Disconnect, View,
                 ]));
                 stream.skip_until(is_recover_token)?;
                 continue;
@@ -224,18 +240,19 @@ constant x: natural := 5;
                 class: ObjectClass::Constant,
                 ident: code.s1("x").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("5").expr())
             })])
         );
 
         assert_eq!(
             msgs,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s1("var").pos(),
                 "Expected 'type', 'subtype', 'component', 'impure', 'pure', \
                  'function', 'procedure', 'package', 'for', 'file', \
                  'shared', 'constant', 'signal', 'variable', 'attribute', \
-                 'use' or 'alias'"
+                 'use', 'alias', 'group', 'disconnect' or 'view'"
             )]
         );
     }
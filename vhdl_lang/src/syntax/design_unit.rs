@@ -187,7 +187,7 @@ pub fn parse_design_file(
             Context => match parse_context(stream, diagnostics) {
                 Ok(DeclarationOrReference::Declaration(context_decl)) => {
                     if !context_clause.is_empty() {
-                        let mut diagnostic = Diagnostic::error(&context_decl.ident, "Context declaration may not be preceeded by a context clause");
+                        let mut diagnostic = Diagnostic::syntax_error(&context_decl.ident, "Context declaration may not be preceeded by a context clause");
 
                         for context_item in context_clause.iter() {
                             diagnostic.add_related(context_item.pos(stream), context_item_message(context_item, "may not come before context declaration"));
@@ -266,13 +266,17 @@ pub fn parse_design_file(
     }
 
     for context_item in context_clause {
-        diagnostics.push(Diagnostic::warning(
+        diagnostics.push(Diagnostic::new(
             &context_item.pos(stream),
             context_item_message(&context_item, "not associated with any design unit"),
+            ErrorCode::UnassociatedContext,
         ));
     }
 
-    Ok(DesignFile { design_units })
+    Ok(DesignFile {
+        design_units,
+        inactive_regions: stream.inactive_regions().to_vec(),
+    })
 }
 
 #[cfg(test)]
@@ -740,7 +744,8 @@ end entity;
                         statements: vec![],
                         end_ident_pos: None,
                     }))
-                )]
+                )],
+                inactive_regions: vec![],
             }
         );
     }
@@ -758,24 +763,28 @@ context lib.ctx;
         check_diagnostics(
             diagnostics,
             vec![
-                Diagnostic::warning(
+                Diagnostic::new(
                     code.s1("library lib;"),
                     "Library clause not associated with any design unit",
+                    ErrorCode::UnassociatedContext,
                 ),
-                Diagnostic::warning(
+                Diagnostic::new(
                     code.s1("use lib.foo;"),
                     "Use clause not associated with any design unit",
+                    ErrorCode::UnassociatedContext,
                 ),
-                Diagnostic::warning(
+                Diagnostic::new(
                     code.s1("context lib.ctx;"),
                     "Context reference not associated with any design unit",
+                    ErrorCode::UnassociatedContext,
                 ),
             ],
         );
         assert_eq!(
             design_file,
             DesignFile {
-                design_units: vec![]
+                design_units: vec![],
+                inactive_regions: vec![],
             }
         );
     }
@@ -798,7 +807,7 @@ end entity;
         let (design_file, diagnostics) = code.with_stream_diagnostics(parse_design_file);
         check_diagnostics(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s1("ctx"),
                 "Context declaration may not be preceeded by a context clause",
             )
@@ -0,0 +1,84 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::common::ParseResult;
+use super::expression::parse_expression;
+use super::names::{parse_name, parse_type_mark};
+use super::tokens::{Kind::*, TokenStream};
+use crate::ast::{DisconnectionSpecification, GuardedSignalList};
+
+/// LRM 7.4 Disconnection specification
+pub fn parse_disconnection_specification(
+    stream: &TokenStream,
+) -> ParseResult<DisconnectionSpecification> {
+    stream.expect_kind(Disconnect)?;
+    let signal_list = if stream.skip_if_kind(Others) {
+        GuardedSignalList::Others
+    } else if stream.skip_if_kind(All) {
+        GuardedSignalList::All
+    } else {
+        let mut names = vec![parse_name(stream)?];
+        while stream.skip_if_kind(Comma) {
+            names.push(parse_name(stream)?);
+        }
+        GuardedSignalList::Names(names)
+    };
+    stream.expect_kind(Colon)?;
+    let type_mark = parse_type_mark(stream)?;
+    stream.expect_kind(After)?;
+    let after = parse_expression(stream)?;
+    stream.expect_kind(SemiColon)?;
+    Ok(DisconnectionSpecification {
+        signal_list,
+        type_mark,
+        after,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn disconnection_specification() {
+        let code = Code::new("disconnect sig_a, sig_b : std_logic after 5 ns;");
+        assert_eq!(
+            code.with_stream(parse_disconnection_specification),
+            DisconnectionSpecification {
+                signal_list: GuardedSignalList::Names(vec![
+                    code.s1("sig_a").name(),
+                    code.s1("sig_b").name()
+                ]),
+                type_mark: code.s1("std_logic").type_mark(),
+                after: code.s1("5 ns").expr(),
+            }
+        );
+    }
+
+    #[test]
+    fn disconnection_specification_others_and_all() {
+        let code = Code::new("disconnect others : bit after 1 ns;");
+        assert_eq!(
+            code.with_stream(parse_disconnection_specification),
+            DisconnectionSpecification {
+                signal_list: GuardedSignalList::Others,
+                type_mark: code.s1("bit").type_mark(),
+                after: code.s1("1 ns").expr(),
+            }
+        );
+
+        let code = Code::new("disconnect all : bit after 1 ns;");
+        assert_eq!(
+            code.with_stream(parse_disconnection_specification),
+            DisconnectionSpecification {
+                signal_list: GuardedSignalList::All,
+                type_mark: code.s1("bit").type_mark(),
+                after: code.s1("1 ns").expr(),
+            }
+        );
+    }
+}
@@ -0,0 +1,1396 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Formatting of VHDL source code
+//!
+//! The formatter keeps the line structure of the code and all comments. It re-computes the
+//! indentation of each line from the nesting of the design units and statements, normalizes
+//! the spacing between tokens and the casing of keywords and aligns the colons of consecutive
+//! declarations and the arrows of consecutive associations.
+//!
+//! The formatted code is tokenized again and compared to the original code such that
+//! formatting can never change the meaning of the code.
+
+use super::parser::VHDLParser;
+use super::tokens::{Comment, Kind, Symbols, Token, Tokenizer};
+use crate::data::*;
+use fnv::FnvHashSet;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use Kind::*;
+
+/// The casing of keywords in formatted code
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum KeywordCase {
+    #[default]
+    Lower,
+    Upper,
+    /// Keep the casing of the original code
+    Preserve,
+}
+
+impl FromStr for KeywordCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lower" => Ok(KeywordCase::Lower),
+            "upper" => Ok(KeywordCase::Upper),
+            "preserve" => Ok(KeywordCase::Preserve),
+            _ => Err(format!(
+                "Expected keyword case to be 'lower', 'upper' or 'preserve', got '{s}'"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FormatOptions {
+    /// The number of spaces per indentation level
+    pub indent_size: usize,
+    /// Indent using tabs instead of spaces
+    pub use_tabs: bool,
+    pub keyword_case: KeywordCase,
+    /// Align the colons of consecutive declarations and the arrows of consecutive associations
+    pub align: bool,
+    /// The maximum number of consecutive blank lines that are kept
+    pub max_blank_lines: usize,
+}
+
+impl std::default::Default for FormatOptions {
+    fn default() -> FormatOptions {
+        FormatOptions {
+            indent_size: 2,
+            use_tabs: false,
+            keyword_case: KeywordCase::default(),
+            align: true,
+            max_blank_lines: 1,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// The text of one indentation level
+    pub fn indent_unit(&self) -> String {
+        if self.use_tabs {
+            "\t".to_owned()
+        } else {
+            " ".repeat(self.indent_size)
+        }
+    }
+}
+
+impl VHDLParser {
+    /// Format the code of a source file.
+    /// Files with syntax errors are not formatted and the first error is returned instead.
+    pub fn format_source(
+        &self,
+        source: &Source,
+        standard: VHDLStandard,
+        options: &FormatOptions,
+    ) -> Result<String, Diagnostic> {
+        let lines = self.formatted_lines(source, standard, options)?;
+        let mut result = String::new();
+        for line in collapse_blank_lines(&lines, options.max_blank_lines)
+            .into_iter()
+            .skip_while(|line| line.text.is_empty())
+        {
+            result.push_str(&line.text);
+            result.push('\n');
+        }
+
+        let trimmed_len = result.trim_end().len();
+        result.truncate(trimmed_len);
+        if !result.is_empty() {
+            result.push('\n');
+        }
+        Ok(result)
+    }
+
+    /// Format a range of lines of a source file where the first and last lines are included.
+    /// Returns the new text of the lines where each line is terminated by a newline.
+    pub fn format_source_lines(
+        &self,
+        source: &Source,
+        standard: VHDLStandard,
+        options: &FormatOptions,
+        lines: RangeInclusive<u32>,
+    ) -> Result<String, Diagnostic> {
+        let formatted: Vec<_> = self
+            .formatted_lines(source, standard, options)?
+            .into_iter()
+            .filter(|line| lines.contains(&line.line))
+            .collect();
+
+        let mut result = String::new();
+        for line in collapse_blank_lines(&formatted, options.max_blank_lines) {
+            result.push_str(&line.text);
+            result.push('\n');
+        }
+        Ok(result)
+    }
+
+    fn formatted_lines(
+        &self,
+        source: &Source,
+        standard: VHDLStandard,
+        options: &FormatOptions,
+    ) -> Result<Vec<FormattedLine>, Diagnostic> {
+        let mut diagnostics = Vec::new();
+        self.parse_design_source(source, standard, &mut diagnostics);
+        if let Some(diagnostic) = diagnostics
+            .into_iter()
+            .find(|diagnostic| diagnostic.code == ErrorCode::SyntaxError)
+        {
+            return Err(diagnostic);
+        }
+
+        let lexed = Lexed::new(&self.symbols, source, standard)?;
+        let lines = {
+            let contents = source.contents();
+            Formatter::new(&lexed, &contents, options).format()
+        };
+
+        // Formatting must only change the whitespace and the keyword casing
+        let text: String = lines
+            .iter()
+            .map(|line| format!("{}\n", line.text))
+            .collect();
+        let formatted_source = Source::inline(source.file_name(), &text);
+        let formatted = Lexed::new(&self.symbols, &formatted_source, standard)?;
+        let mismatch = lexed
+            .signature
+            .iter()
+            .zip(formatted.signature.iter())
+            .position(|((original, _), (formatted, _))| original != formatted)
+            .or_else(|| {
+                (lexed.signature.len() != formatted.signature.len())
+                    .then_some(lexed.signature.len().min(formatted.signature.len()))
+            });
+        if let Some(idx) = mismatch {
+            let pos = lexed.signature.get(idx).map(|(_, pos)| pos.clone());
+            return Err(Diagnostic::internal(
+                pos.unwrap_or_else(|| source.pos(Position::default(), Position::default())),
+                "Formatting would change the tokens of the file",
+            ));
+        }
+
+        Ok(lines)
+    }
+}
+
+struct FormattedLine {
+    // The line in the original code
+    line: u32,
+    text: String,
+}
+
+fn collapse_blank_lines(lines: &[FormattedLine], max_blank_lines: usize) -> Vec<&FormattedLine> {
+    let mut result = Vec::with_capacity(lines.len());
+    let mut blank_lines = 0;
+    for line in lines.iter() {
+        if line.text.is_empty() {
+            blank_lines += 1;
+            if blank_lines > max_blank_lines {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        result.push(line);
+    }
+    result
+}
+
+/// What formatting must not change
+#[derive(PartialEq, Debug)]
+enum Signature {
+    Token(Kind, super::tokens::Value),
+    Comment(Vec<String>),
+}
+
+struct Item {
+    start: Position,
+    end: Position,
+    // The index of the token or None for comments
+    token: Option<usize>,
+    multi_line_comment: bool,
+}
+
+/// The tokens and comments of a source file in order of appearance
+struct Lexed {
+    tokens: Vec<Token>,
+    items: Vec<Item>,
+    // Lines with tool directives are kept as is
+    directive_lines: FnvHashSet<u32>,
+    signature: Vec<(Signature, SrcPos)>,
+}
+
+impl Lexed {
+    fn new(
+        symbols: &Symbols,
+        source: &Source,
+        standard: VHDLStandard,
+    ) -> Result<Lexed, Diagnostic> {
+        let contents = source.contents();
+        let mut tokenizer =
+            Tokenizer::new(symbols, source, ContentReader::new(&contents), standard);
+
+        let mut lexed = Lexed {
+            tokens: Vec::new(),
+            items: Vec::new(),
+            directive_lines: FnvHashSet::default(),
+            signature: Vec::new(),
+        };
+
+        while let Some(token) = tokenizer.pop()? {
+            if let Some(ref comments) = token.comments {
+                for comment in comments.leading.iter() {
+                    lexed.add_comment(source, comment);
+                }
+            }
+
+            lexed.signature.push((
+                Signature::Token(token.kind, token.value.clone()),
+                token.pos.clone(),
+            ));
+            let trailing = token
+                .comments
+                .as_ref()
+                .and_then(|comments| comments.trailing.clone());
+
+            if token.kind == GraveAccent {
+                let text = tokenizer.text_until_newline()?;
+                lexed
+                    .signature
+                    .push((Signature::Token(text.kind, text.value.clone()), text.pos));
+                lexed.directive_lines.insert(token.pos.start().line);
+            } else {
+                lexed.items.push(Item {
+                    start: token.pos.start(),
+                    end: token.pos.end(),
+                    token: Some(lexed.tokens.len()),
+                    multi_line_comment: false,
+                });
+                lexed.tokens.push(token);
+            }
+
+            if let Some(ref comment) = trailing {
+                lexed.add_comment(source, comment);
+            }
+        }
+
+        for comment in tokenizer.get_final_comments().unwrap_or_default().iter() {
+            lexed.add_comment(source, comment);
+        }
+        Ok(lexed)
+    }
+
+    fn add_comment(&mut self, source: &Source, comment: &Comment) {
+        self.signature.push((
+            // Trailing whitespace is removed by formatting
+            Signature::Comment(
+                comment
+                    .value
+                    .split('\n')
+                    .map(|line| line.trim_end().to_owned())
+                    .collect(),
+            ),
+            source.pos(comment.range.start, comment.range.end),
+        ));
+        self.items.push(Item {
+            start: comment.range.start,
+            end: comment.range.end,
+            token: None,
+            multi_line_comment: comment.multi_line
+                && comment.range.start.line != comment.range.end.line,
+        });
+    }
+}
+
+/// The text of a line between two character offsets counted in UTF-16 code units
+fn slice_line(line: &str, start: u32, end: Option<u32>) -> &str {
+    let mut offset = 0;
+    let mut start_idx = line.len();
+    let mut end_idx = line.len();
+    for (idx, chr) in line.char_indices() {
+        if offset == start {
+            start_idx = idx;
+        }
+        if Some(offset) == end {
+            end_idx = idx;
+            break;
+        }
+        offset += chr.len_utf16() as u32;
+    }
+    line[start_idx.min(end_idx)..end_idx].trim_end()
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum FrameKind {
+    DesignUnit,
+    Configuration,
+    // Block and component configurations
+    ConfigurationFor,
+    Statement,
+    Case,
+    Record,
+}
+
+/// A construct that is closed by 'end'
+struct Frame {
+    kind: FrameKind,
+    indent: usize,
+    // True after the first alternative of a case statement
+    in_alternative: bool,
+    // True for generate statements where 'end' without 'generate' closes an alternative
+    generate: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AlignKind {
+    Colon,
+    Arrow,
+}
+
+enum PieceKind {
+    Token {
+        kind: Kind,
+        unary: bool,
+        label_colon: bool,
+    },
+    Comment,
+}
+
+struct Piece {
+    text: String,
+    kind: PieceKind,
+}
+
+impl Piece {
+    fn token_kind(&self) -> Option<Kind> {
+        if let PieceKind::Token { kind, .. } = self.kind {
+            Some(kind)
+        } else {
+            None
+        }
+    }
+}
+
+enum Line {
+    Blank,
+    Verbatim(String),
+    Code {
+        indent: usize,
+        // The pieces and the number of spaces before each piece
+        pieces: Vec<(usize, Piece)>,
+        // Whether the line starts within parenthesis
+        in_parens: bool,
+    },
+}
+
+struct Formatter<'a> {
+    lexed: &'a Lexed,
+    contents: &'a Contents,
+    options: &'a FormatOptions,
+
+    frames: Vec<Frame>,
+    // The indentation of the content of each open parenthesis
+    parens: Vec<usize>,
+    at_statement_start: bool,
+    statement_indent: usize,
+    line_indent: usize,
+    after_end: bool,
+    after_label: bool,
+    expect_label_colon: bool,
+    in_when_header: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn new(lexed: &'a Lexed, contents: &'a Contents, options: &'a FormatOptions) -> Self {
+        Formatter {
+            lexed,
+            contents,
+            options,
+            frames: Vec::new(),
+            parens: Vec::new(),
+            at_statement_start: true,
+            statement_indent: 0,
+            line_indent: 0,
+            after_end: false,
+            after_label: false,
+            expect_label_colon: false,
+            in_when_header: false,
+        }
+    }
+
+    fn format(mut self) -> Vec<FormattedLine> {
+        let num_lines = self.contents.num_lines();
+        let mut lines: Vec<Line> = (0..num_lines).map(|_| Line::Blank).collect();
+        let mut verbatim: FnvHashSet<u32> = self.lexed.directive_lines.clone();
+        for item in self.lexed.items.iter() {
+            if item.multi_line_comment {
+                verbatim.extend(item.start.line + 1..=item.end.line);
+            }
+        }
+
+        let mut items = self.lexed.items.iter().peekable();
+        for (lineno, line) in lines.iter_mut().enumerate() {
+            let lineno = lineno as u32;
+            let mut line_items = Vec::new();
+            while let Some(item) = items.next_if(|item| item.start.line <= lineno) {
+                line_items.push(item);
+            }
+
+            if verbatim.contains(&lineno) {
+                for item in line_items.iter() {
+                    if let Some(idx) = item.token {
+                        self.process(idx);
+                    }
+                }
+                let text = self.contents.get_line(lineno as usize).unwrap_or_default();
+                *line = Line::Verbatim(text.trim_end().to_owned());
+            } else if !line_items.is_empty() {
+                *line = self.format_line(lineno, &line_items);
+            }
+        }
+
+        if self.options.align {
+            align(&mut lines);
+        }
+
+        lines
+            .into_iter()
+            .enumerate()
+            .map(|(lineno, line)| FormattedLine {
+                line: lineno as u32,
+                text: self.line_text(line),
+            })
+            .collect()
+    }
+
+    fn line_text(&self, line: Line) -> String {
+        match line {
+            Line::Blank => String::new(),
+            Line::Verbatim(text) => text,
+            Line::Code { indent, pieces, .. } => {
+                let mut text = self.options.indent_unit().repeat(indent);
+                for (idx, (spaces, piece)) in pieces.iter().enumerate() {
+                    if idx > 0 {
+                        text.push_str(&" ".repeat(*spaces));
+                    }
+                    text.push_str(&piece.text);
+                }
+                text
+            }
+        }
+    }
+
+    fn format_line(&mut self, lineno: u32, items: &[&Item]) -> Line {
+        let in_parens = !self.parens.is_empty();
+        self.line_indent = self.indent_of_line(items[0].token);
+
+        let line = self.contents.get_line(lineno as usize).unwrap_or_default();
+        let mut pieces: Vec<(usize, Piece)> = Vec::with_capacity(items.len());
+        for item in items {
+            let end = (item.end.line == lineno).then_some(item.end.character);
+            let text = slice_line(line, item.start.character, end);
+
+            let piece = if let Some(idx) = item.token {
+                let label_colon = self.process(idx);
+                let kind = self.lexed.tokens[idx].kind;
+                Piece {
+                    text: self.token_text(kind, text),
+                    kind: PieceKind::Token {
+                        kind,
+                        unary: matches!(kind, Plus | Minus) && !self.follows_operand(idx),
+                        label_colon,
+                    },
+                }
+            } else {
+                Piece {
+                    text: text.to_owned(),
+                    kind: PieceKind::Comment,
+                }
+            };
+
+            let spaces = pieces
+                .last()
+                .map(|(_, prev)| usize::from(needs_space(prev, &piece)))
+                .unwrap_or_default();
+            pieces.push((spaces, piece));
+        }
+
+        Line::Code {
+            indent: self.line_indent,
+            pieces,
+            in_parens,
+        }
+    }
+
+    fn token_text(&self, kind: Kind, text: &str) -> String {
+        if !is_keyword(kind) {
+            return text.to_owned();
+        }
+        match self.options.keyword_case {
+            KeywordCase::Lower => text.to_ascii_lowercase(),
+            KeywordCase::Upper => text.to_ascii_uppercase(),
+            KeywordCase::Preserve => text.to_owned(),
+        }
+    }
+
+    /// True if the token before a plus or minus ends an operand which makes it a binary operator
+    fn follows_operand(&self, idx: usize) -> bool {
+        let Some(prev) = idx.checked_sub(1).map(|idx| &self.lexed.tokens[idx]) else {
+            return false;
+        };
+        let after_tick = idx >= 2 && self.lexed.tokens[idx - 2].kind == Tick;
+        ends_operand(prev.kind) || (after_tick && is_keyword(prev.kind))
+    }
+
+    fn top(&self) -> Option<&Frame> {
+        self.frames.last()
+    }
+
+    fn frame_indent(&self) -> usize {
+        self.top().map(|frame| frame.indent).unwrap_or_default()
+    }
+
+    fn body_indent(&self) -> usize {
+        match self.top() {
+            Some(frame) if frame.in_alternative => frame.indent + 2,
+            Some(frame) => frame.indent + 1,
+            None => 0,
+        }
+    }
+
+    /// The indentation of the alternatives of a generate statement,
+    /// the alternatives of a case generate are indented like 'when'
+    fn alternative_indent(&self) -> usize {
+        match self.top() {
+            Some(frame) if frame.in_alternative => frame.indent + 1,
+            _ => self.frame_indent(),
+        }
+    }
+
+    fn indent_of_line(&self, first_token: Option<usize>) -> usize {
+        if let Some(&indent) = self.parens.last() {
+            return if first_token.and_then(|idx| self.kind_at(idx)) == Some(RightPar) {
+                indent.saturating_sub(1)
+            } else {
+                indent
+            };
+        }
+
+        let Some(idx) = first_token else {
+            // Comments are indented like the code that follows
+            return if self.at_statement_start {
+                self.body_indent()
+            } else {
+                self.statement_indent + 1
+            };
+        };
+
+        match self.lexed.tokens[idx].kind {
+            End if self.is_alternative_end(idx) => self.alternative_indent(),
+            Begin if self.top().is_some_and(|frame| frame.generate) => self.alternative_indent(),
+            End | Begin => self.frame_indent(),
+            Else | Elsif if self.at_statement_start => self.frame_indent(),
+            When if self.at_statement_start
+                && self.top().map(|frame| frame.kind) == Some(FrameKind::Case) =>
+            {
+                self.frame_indent() + 1
+            }
+            _ if self.at_statement_start => self.body_indent(),
+            _ => self.statement_indent + 1,
+        }
+    }
+
+    fn push(&mut self, kind: FrameKind, indent: usize) {
+        self.frames.push(Frame {
+            kind,
+            indent,
+            in_alternative: false,
+            generate: false,
+        });
+    }
+
+    fn kind_at(&self, idx: usize) -> Option<Kind> {
+        self.lexed.tokens.get(idx).map(|token| token.kind)
+    }
+
+    /// Find the first of the kinds outside of parenthesis before the end of the statement
+    fn find_in_statement(&self, idx: usize, kinds: &[Kind]) -> Option<usize> {
+        let mut depth = 0_usize;
+        for (offset, token) in self.lexed.tokens[idx..].iter().enumerate() {
+            match token.kind {
+                LeftPar => depth += 1,
+                RightPar => depth = depth.saturating_sub(1),
+                kind if depth == 0 && kinds.contains(&kind) => return Some(idx + offset),
+                SemiColon if depth == 0 => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    fn is_subprogram_body(&self, idx: usize) -> bool {
+        self.find_in_statement(idx, &[Is])
+            .is_some_and(|is_idx| self.kind_at(is_idx + 1) != Some(New))
+    }
+
+    /// True if 'end' closes an alternative of a generate statement such as 'end alt;'
+    fn is_alternative_end(&self, idx: usize) -> bool {
+        self.top().is_some_and(|frame| frame.generate) && self.kind_at(idx + 1) != Some(Generate)
+    }
+
+    /// True for the label of an alternative of a generate statement such as 'elsif alt:'
+    fn is_alternative_label(&self, idx: usize) -> bool {
+        self.kind_at(idx + 1) == Some(Colon)
+            && idx
+                .checked_sub(1)
+                .is_some_and(|prev| matches!(self.kind_at(prev), Some(If | Elsif | When)))
+    }
+
+    fn ends_with_end_for(&self, idx: usize) -> bool {
+        self.find_in_statement(idx, &[SemiColon])
+            .is_some_and(|semi_idx| {
+                self.kind_at(semi_idx + 1) == Some(End) && self.kind_at(semi_idx + 2) == Some(For)
+            })
+    }
+
+    /// Update the nesting with the next token.
+    /// Returns true if the token is the colon after a statement label.
+    fn process(&mut self, idx: usize) -> bool {
+        let kind = self.lexed.tokens[idx].kind;
+        let start = std::mem::replace(&mut self.at_statement_start, false);
+        let after_label = std::mem::replace(&mut self.after_label, false);
+        let expect_label_colon = std::mem::replace(&mut self.expect_label_colon, false);
+        if start {
+            self.statement_indent = self.line_indent;
+        }
+
+        if self.after_end {
+            if kind == SemiColon {
+                self.after_end = false;
+                self.at_statement_start = true;
+            }
+            return false;
+        }
+
+        match kind {
+            LeftPar => {
+                self.parens.push(self.line_indent + 1);
+                return false;
+            }
+            RightPar => {
+                self.parens.pop();
+                return false;
+            }
+            _ if !self.parens.is_empty() => return false,
+            _ => {}
+        }
+
+        let top_kind = self.top().map(|frame| frame.kind);
+        let indent = self.statement_indent;
+
+        match kind {
+            SemiColon | Begin | Then | Is => {
+                self.at_statement_start = true;
+            }
+            Generate => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.generate = true;
+                }
+                self.at_statement_start = true;
+            }
+            Colon if expect_label_colon => {
+                self.at_statement_start = true;
+                self.after_label = true;
+                return true;
+            }
+            Identifier if start => {
+                self.expect_label_colon =
+                    self.kind_at(idx + 1) == Some(Colon) && top_kind != Some(FrameKind::Record);
+            }
+            // Alternative labels are written like statement labels
+            Identifier if self.is_alternative_label(idx) => {
+                self.expect_label_colon = true;
+            }
+            End => {
+                if !self.is_alternative_end(idx) {
+                    self.frames.pop();
+                }
+                self.after_end = true;
+            }
+            Loop => {
+                if start {
+                    self.push(FrameKind::Statement, indent);
+                }
+                self.at_statement_start = true;
+            }
+            Else if start => {
+                self.at_statement_start = true;
+            }
+            When if start && top_kind == Some(FrameKind::Case) => {
+                if let Some(frame) = self.frames.last_mut() {
+                    frame.in_alternative = true;
+                }
+                self.in_when_header = true;
+            }
+            RightArrow if self.in_when_header => {
+                self.in_when_header = false;
+                self.at_statement_start = true;
+            }
+            If | While | Block if start => self.push(FrameKind::Statement, indent),
+            Process if start || idx > 0 && self.kind_at(idx - 1) == Some(Postponed) => {
+                self.push(FrameKind::Statement, indent)
+            }
+            Case if start => self.push(FrameKind::Case, indent),
+            For => {
+                let in_configuration = matches!(
+                    top_kind,
+                    Some(FrameKind::Configuration | FrameKind::ConfigurationFor)
+                );
+                if start && self.find_in_statement(idx, &[Loop, Generate]).is_some() {
+                    self.push(FrameKind::Statement, indent);
+                } else if in_configuration || (start && self.ends_with_end_for(idx)) {
+                    self.statement_indent = self.line_indent;
+                    self.push(FrameKind::ConfigurationFor, self.line_indent);
+                }
+            }
+            Entity | Component if start && !after_label => self.push(FrameKind::DesignUnit, indent),
+            Configuration if start && !after_label => self.push(FrameKind::Configuration, indent),
+            Architecture if start => self.push(FrameKind::DesignUnit, indent),
+            Package | Context if start => {
+                let is_instance_or_reference = self.kind_at(idx + 1) != Some(Body)
+                    && (self.kind_at(idx + 2) != Some(Is) || self.kind_at(idx + 3) == Some(New));
+                if !is_instance_or_reference {
+                    self.push(FrameKind::DesignUnit, indent);
+                }
+            }
+            Pure | Impure | Function | Procedure if start && self.is_subprogram_body(idx) => {
+                self.push(FrameKind::Statement, indent);
+            }
+            Record | Protected | Units => {
+                let frame_kind = if kind == Record {
+                    FrameKind::Record
+                } else {
+                    FrameKind::DesignUnit
+                };
+                self.push(frame_kind, self.line_indent);
+                self.at_statement_start = true;
+            }
+            _ => {}
+        }
+        false
+    }
+}
+
+fn is_keyword(kind: Kind) -> bool {
+    !matches!(
+        kind,
+        Identifier | AbstractLiteral | StringLiteral | BitString | Character | Text
+    ) && super::tokens::kind_str(kind).starts_with(|chr: char| chr.is_ascii_alphabetic())
+        && !matches!(kind, Plus | Minus)
+}
+
+fn ends_operand(kind: Kind) -> bool {
+    matches!(
+        kind,
+        Identifier
+            | AbstractLiteral
+            | StringLiteral
+            | BitString
+            | Character
+            | RightPar
+            | RightSquare
+            | All
+            | Null
+            | Others
+            | Open
+            | GtGt
+    )
+}
+
+/// Tokens that are written without spaces around them
+fn is_tight(kind: Kind) -> bool {
+    matches!(kind, Dot | Circ | CommAt)
+}
+
+fn needs_space(prev: &Piece, next: &Piece) -> bool {
+    let space = match (&prev.kind, &next.kind) {
+        (PieceKind::Comment, _) | (_, PieceKind::Comment) => true,
+        (
+            PieceKind::Token {
+                kind: prev_kind,
+                unary,
+                ..
+            },
+            PieceKind::Token {
+                kind: next_kind,
+                label_colon,
+                ..
+            },
+        ) => {
+            let (prev_kind, next_kind) = (*prev_kind, *next_kind);
+            if *label_colon
+                || matches!(next_kind, SemiColon | Comma | RightPar | RightSquare | Tick)
+                || *unary
+                || matches!(prev_kind, LeftPar | LeftSquare | Tick)
+                || is_tight(prev_kind)
+            {
+                false
+            } else if is_tight(next_kind) {
+                prev_kind == LtLt || (is_keyword(prev_kind) && !ends_operand(prev_kind))
+            } else if next_kind == LeftPar {
+                !matches!(
+                    prev_kind,
+                    Identifier | RightPar | RightSquare | StringLiteral | Tick
+                )
+            } else {
+                true
+            }
+        }
+    };
+
+    // Never join tokens into a different token or a comment
+    space || {
+        let last = prev.text.chars().last().unwrap_or_default();
+        let first = next.text.chars().next().unwrap_or_default();
+        let is_word = |chr: char| chr.is_alphanumeric() || chr == '_';
+        (is_word(last) && is_word(first))
+            || matches!((last, first), ('-', '-') | ('/', '*') | ('*', '/'))
+    }
+}
+
+/// The piece to align and the kind of alignment of a line
+fn alignment_of(line: &Line) -> Option<(usize, usize, AlignKind)> {
+    let Line::Code {
+        indent,
+        pieces,
+        in_parens,
+    } = line
+    else {
+        return None;
+    };
+
+    let first_kind = pieces.first()?.1.token_kind()?;
+    let mut depth = 0_usize;
+    for (idx, (_, piece)) in pieces.iter().enumerate() {
+        match piece.kind {
+            PieceKind::Token {
+                kind: Colon,
+                label_colon: false,
+                ..
+            } if depth == 0 => {
+                let declaration = matches!(
+                    first_kind,
+                    Identifier | Signal | Constant | Variable | File | Shared
+                );
+                return declaration.then_some((*indent, idx, AlignKind::Colon));
+            }
+            PieceKind::Token {
+                kind: RightArrow, ..
+            } if depth == 0 => {
+                let association = *in_parens && matches!(first_kind, Identifier | Others);
+                return association.then_some((*indent, idx, AlignKind::Arrow));
+            }
+            PieceKind::Token { kind: LeftPar, .. } => depth += 1,
+            PieceKind::Token { kind: RightPar, .. } => {
+                if depth == 0 {
+                    return None;
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+fn width(pieces: &[(usize, Piece)]) -> usize {
+    pieces
+        .iter()
+        .enumerate()
+        .map(|(idx, (spaces, piece))| {
+            let spaces = if idx > 0 { *spaces } else { 0 };
+            spaces + piece.text.chars().count()
+        })
+        .sum()
+}
+
+fn is_mode(piece: &Piece) -> bool {
+    matches!(
+        piece.token_kind(),
+        Some(In | Out | InOut | Buffer | Linkage)
+    )
+}
+
+/// Align the colons or arrows of consecutive lines with the same indentation
+fn align(lines: &mut [Line]) {
+    let mut start = 0;
+    while start < lines.len() {
+        let Some((indent, _, kind)) = alignment_of(&lines[start]) else {
+            start += 1;
+            continue;
+        };
+
+        let mut end = start + 1;
+        while end < lines.len()
+            && alignment_of(&lines[end]).is_some_and(|(other_indent, _, other_kind)| {
+                other_indent == indent && other_kind == kind
+            })
+        {
+            end += 1;
+        }
+
+        if end - start > 1 {
+            align_group(&mut lines[start..end]);
+        }
+        start = end;
+    }
+}
+
+fn align_group(lines: &mut [Line]) {
+    let positions: Vec<usize> = lines
+        .iter()
+        .map(|line| {
+            alignment_of(line)
+                .map(|(_, idx, _)| idx)
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let mut column = 0;
+    let mut mode_width = 0;
+    for (line, &idx) in lines.iter().zip(positions.iter()) {
+        if let Line::Code { pieces, .. } = line {
+            column = column.max(width(&pieces[..idx]));
+            if let Some((_, mode)) = pieces.get(idx + 1).filter(|(_, piece)| is_mode(piece)) {
+                mode_width = mode_width.max(mode.text.len());
+            }
+        }
+    }
+
+    for (line, &idx) in lines.iter_mut().zip(positions.iter()) {
+        if let Line::Code { pieces, .. } = line {
+            let padding = column - width(&pieces[..idx]);
+            pieces[idx].0 += padding;
+
+            let has_mode = pieces.get(idx + 1).is_some_and(|(_, piece)| is_mode(piece));
+            if has_mode {
+                let padding = mode_width - pieces[idx + 1].1.text.len();
+                if let Some((spaces, _)) = pieces.get_mut(idx + 2) {
+                    *spaces += padding;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn format_with(code: &str, options: &FormatOptions) -> String {
+        let parser = VHDLParser::default();
+        let source = Source::inline(Path::new("file.vhd"), code);
+        parser
+            .format_source(&source, VHDLStandard::default(), options)
+            .unwrap()
+    }
+
+    fn format(code: &str) -> String {
+        format_with(code, &FormatOptions::default())
+    }
+
+    #[test]
+    fn indents_design_units_and_statements() {
+        assert_eq!(
+            format(
+                "
+ENTITY ent IS
+GENERIC(width:natural:=8);
+    PORT (
+  clk:in std_logic;
+  data  :  out std_logic_vector(width-1 downto 0)
+  );
+END ENTITY;
+
+architecture rtl of ent is
+signal cnt:natural;
+begin
+process(clk)
+variable v : natural;
+begin
+if rising_edge(clk) then
+for i in 0 to 3 loop
+v:=v+i;
+end loop;
+elsif cnt=0 then
+case cnt is
+when 0 =>
+null;
+when others =>
+cnt <= -1;
+end case;
+else
+cnt<=cnt+1;
+end if;
+end process;
+end architecture;
+"
+            ),
+            "entity ent is
+  generic (width : natural := 8);
+  port (
+    clk  : in  std_logic;
+    data : out std_logic_vector(width - 1 downto 0)
+  );
+end entity;
+
+architecture rtl of ent is
+  signal cnt : natural;
+begin
+  process (clk)
+    variable v : natural;
+  begin
+    if rising_edge(clk) then
+      for i in 0 to 3 loop
+        v := v + i;
+      end loop;
+    elsif cnt = 0 then
+      case cnt is
+        when 0 =>
+          null;
+        when others =>
+          cnt <= -1;
+      end case;
+    else
+      cnt <= cnt + 1;
+    end if;
+  end process;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn preserves_comments() {
+        assert_eq!(
+            format(
+                "
+-- header comment
+package pkg is
+-- leading comment
+constant c : natural := 0; -- trailing comment
+    /* multi
+   line */
+end package;
+-- final comment
+"
+            ),
+            "-- header comment
+package pkg is
+  -- leading comment
+  constant c : natural := 0; -- trailing comment
+  /* multi
+   line */
+end package;
+-- final comment
+"
+        );
+    }
+
+    #[test]
+    fn aligns_associations_and_instances() {
+        assert_eq!(
+            format(
+                "
+architecture rtl of ent is
+begin
+inst: entity work.sub
+generic map(
+g => 1
+)
+port map (
+clk => clk,
+data_out => data
+);
+end architecture;
+"
+            ),
+            "architecture rtl of ent is
+begin
+  inst: entity work.sub
+    generic map (
+      g => 1
+    )
+    port map (
+      clk      => clk,
+      data_out => data
+    );
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn conditional_and_selected_assignments_are_not_blocks() {
+        assert_eq!(
+            format(
+                "
+architecture rtl of ent is
+begin
+a <= b when c = '1' else
+d;
+with sel select
+y <= a when \"0\",
+b when others;
+gen: for i in 0 to 1 generate
+x(i) <= '0';
+end generate;
+end architecture;
+"
+            ),
+            "architecture rtl of ent is
+begin
+  a <= b when c = '1' else
+    d;
+  with sel select
+    y <= a when \"0\",
+    b when others;
+  gen: for i in 0 to 1 generate
+    x(i) <= '0';
+  end generate;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn if_generate_alternatives() {
+        assert_eq!(
+            format(
+                "
+architecture rtl of ent is
+begin
+gen: if alt1 : c = '1' generate
+signal s : bit;
+begin
+s <= '1';
+end alt1;
+elsif alt2 :c = '0' generate
+x <= '0';
+end;
+else alt3:generate
+x <= '1';
+end alt3;
+end generate gen;
+y <= x;
+end architecture;
+"
+            ),
+            "architecture rtl of ent is
+begin
+  gen: if alt1: c = '1' generate
+    signal s : bit;
+  begin
+    s <= '1';
+  end alt1;
+  elsif alt2: c = '0' generate
+    x <= '0';
+  end;
+  else alt3: generate
+    x <= '1';
+  end alt3;
+  end generate gen;
+  y <= x;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn case_generate_alternatives() {
+        assert_eq!(
+            format(
+                "
+architecture rtl of ent is
+begin
+gen: case sel generate
+when alt1 : '0' =>
+x <= '0';
+end alt1;
+when alt2: others =>
+signal s : bit;
+begin
+x <= s;
+end;
+end generate;
+y <= x;
+end architecture;
+"
+            ),
+            "architecture rtl of ent is
+begin
+  gen: case sel generate
+    when alt1: '0' =>
+      x <= '0';
+    end alt1;
+    when alt2: others =>
+      signal s : bit;
+    begin
+      x <= s;
+    end;
+  end generate;
+  y <= x;
+end architecture;
+"
+        );
+    }
+
+    #[test]
+    fn subprograms_types_and_configurations() {
+        assert_eq!(
+            format(
+                "
+package body pkg is
+type rec_t is record
+a:bit;
+bb:bit;
+end record;
+function f(x : natural) return natural;
+function f(x : natural) return natural is
+begin
+return x'length-1;
+end function;
+end package body;
+
+configuration cfg of ent is
+for rtl
+for inst : comp
+use entity work.sub;
+end for;
+end for;
+end configuration;
+"
+            ),
+            "package body pkg is
+  type rec_t is record
+    a  : bit;
+    bb : bit;
+  end record;
+  function f(x : natural) return natural;
+  function f(x : natural) return natural is
+  begin
+    return x'length - 1;
+  end function;
+end package body;
+
+configuration cfg of ent is
+  for rtl
+    for inst : comp
+      use entity work.sub;
+    end for;
+  end for;
+end configuration;
+"
+        );
+    }
+
+    #[test]
+    fn keyword_case_indentation_and_blank_lines() {
+        let options = FormatOptions {
+            indent_size: 4,
+            keyword_case: KeywordCase::Upper,
+            align: false,
+            max_blank_lines: 0,
+            ..FormatOptions::default()
+        };
+        assert_eq!(
+            format_with(
+                "
+library ieee;
+
+
+package Pkg is
+constant a : natural := 0;
+constant bcd : natural := 0;
+end package;
+",
+                &options
+            ),
+            "LIBRARY ieee;
+PACKAGE Pkg IS
+    CONSTANT a : natural := 0;
+    CONSTANT bcd : natural := 0;
+END PACKAGE;
+"
+        );
+    }
+
+    #[test]
+    fn format_range_of_lines() {
+        let parser = VHDLParser::default();
+        let source = Source::inline(
+            Path::new("file.vhd"),
+            "package pkg is
+constant a : natural := 0;
+constant b : natural := 0;
+end package;
+",
+        );
+        assert_eq!(
+            parser
+                .format_source_lines(
+                    &source,
+                    VHDLStandard::default(),
+                    &FormatOptions::default(),
+                    1..=1
+                )
+                .unwrap(),
+            "  constant a : natural := 0;\n"
+        );
+    }
+
+    #[test]
+    fn does_not_format_code_with_syntax_errors() {
+        let parser = VHDLParser::default();
+        let source = Source::inline(Path::new("file.vhd"), "entity ent is\n");
+        let err = parser
+            .format_source(&source, VHDLStandard::default(), &FormatOptions::default())
+            .unwrap_err();
+        assert_eq!(err.code, ErrorCode::SyntaxError);
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let code = "
+entity ent is
+  port (
+    a : in bit
+  );
+end entity;
+
+architecture rtl of ent is
+begin
+  blk: block is
+  begin
+    process is
+    begin
+      wait until a = '1';
+      report \"msg\" severity note;
+    end process;
+  end block;
+end architecture;
+";
+        let formatted = format(code);
+        assert_eq!(format(&formatted), formatted);
+    }
+}
@@ -0,0 +1,121 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::attributes::parse_entity_class;
+use super::common::ParseResult;
+use super::names::{parse_name, parse_selected_name};
+use super::tokens::{Kind::*, TokenStream};
+use crate::ast::{Declaration, EntityClassEntry, GroupDeclaration, GroupTemplateDeclaration};
+
+/// LRM 6.9 Group template declarations and LRM 6.10 Group declarations
+pub fn parse_group_declaration(stream: &TokenStream) -> ParseResult<Declaration> {
+    stream.expect_kind(Group)?;
+    let ident = stream.expect_ident()?;
+    Ok(expect_token!(stream, token,
+        Is => {
+            stream.expect_kind(LeftPar)?;
+            let mut entity_class_entries = Vec::new();
+            loop {
+                let entity_class = parse_entity_class(stream)?;
+                let is_box = stream.skip_if_kind(BOX);
+                entity_class_entries.push(EntityClassEntry { entity_class, is_box });
+                if !stream.skip_if_kind(Comma) {
+                    break;
+                }
+            }
+            stream.expect_kind(RightPar)?;
+            stream.expect_kind(SemiColon)?;
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: ident.into(),
+                entity_class_entries,
+            })
+        },
+        Colon => {
+            let group_template = parse_selected_name(stream)?;
+            stream.expect_kind(LeftPar)?;
+            let mut constituents = Vec::new();
+            loop {
+                constituents.push(parse_name(stream)?);
+                if !stream.skip_if_kind(Comma) {
+                    break;
+                }
+            }
+            stream.expect_kind(RightPar)?;
+            stream.expect_kind(SemiColon)?;
+            Declaration::Group(GroupDeclaration {
+                ident: ident.into(),
+                group_template,
+                constituents,
+            })
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::EntityClass;
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn group_template_declaration() {
+        let code = Code::new("group pin2pin is (signal, signal);");
+        assert_eq!(
+            code.with_stream(parse_group_declaration),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: code.s1("pin2pin").decl_ident(),
+                entity_class_entries: vec![
+                    EntityClassEntry {
+                        entity_class: EntityClass::Signal,
+                        is_box: false
+                    },
+                    EntityClassEntry {
+                        entity_class: EntityClass::Signal,
+                        is_box: false
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn group_template_declaration_with_box() {
+        let code = Code::new("group path is (label, group <>);");
+        assert_eq!(
+            code.with_stream(parse_group_declaration),
+            Declaration::GroupTemplate(GroupTemplateDeclaration {
+                ident: code.s1("path").decl_ident(),
+                entity_class_entries: vec![
+                    EntityClassEntry {
+                        entity_class: EntityClass::Label,
+                        is_box: false
+                    },
+                    EntityClassEntry {
+                        entity_class: EntityClass::Group,
+                        is_box: true
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn group_declaration() {
+        let code = Code::new("group g : work.pkg.pin2pin (clk, q, 'a');");
+        assert_eq!(
+            code.with_stream(parse_group_declaration),
+            Declaration::Group(GroupDeclaration {
+                ident: code.s1("g :").s1("g").decl_ident(),
+                group_template: code.s1("work.pkg.pin2pin").selected_name(),
+                constituents: vec![
+                    code.s1("clk").name(),
+                    code.s1("q").name(),
+                    code.s1("'a'").name()
+                ]
+            })
+        );
+    }
+}
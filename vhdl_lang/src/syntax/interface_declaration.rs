@@ -10,11 +10,12 @@ use super::object_declaration::{parse_file_declaration_no_semi, parse_optional_a
 use super::subprogram::parse_subprogram_declaration_no_semi;
 use super::subtype_indication::parse_subtype_indication;
 use super::tokens::{Kind::*, *};
+use super::view_declaration::parse_mode_view_indication;
 /// LRM 6.5 Interface declarations
 use crate::ast::*;
 use crate::data::*;
 
-fn parse_optional_mode(stream: &TokenStream) -> ParseResult<Option<WithPos<Mode>>> {
+pub fn parse_optional_mode(stream: &TokenStream) -> ParseResult<Option<WithPos<Mode>>> {
     let token = stream.peek_expect()?;
     let mode = match token.kind {
         In => Mode::In,
@@ -59,13 +60,13 @@ fn parse_interface_file_declaration(
     let file_objects = parse_file_declaration_no_semi(stream)?;
     for file_object in file_objects.iter() {
         if file_object.open_info.is_some() {
-            return Err(Diagnostic::error(
+            return Err(Diagnostic::syntax_error(
                 &file_object.ident,
                 "interface_file_declaration may not have file open information",
             ));
         }
         if file_object.file_name.is_some() {
-            return Err(Diagnostic::error(
+            return Err(Diagnostic::syntax_error(
                 &file_object.ident,
                 "interface_file_declaration may not have file name",
             ));
@@ -94,6 +95,10 @@ fn parse_interface_object_declaration(
 
     stream.expect_kind(Colon)?;
 
+    if stream.next_kind_is(View) {
+        return parse_interface_view_declaration(stream, list_type, explicit_object_class, idents);
+    }
+
     let mode_with_pos = parse_optional_mode(stream)?;
     let mode = mode_with_pos
         .as_ref()
@@ -114,13 +119,18 @@ fn parse_interface_object_declaration(
     };
 
     let subtype = parse_subtype_indication(stream)?;
+    let signal_kind = if object_class == ObjectClass::Signal && stream.skip_if_kind(Bus) {
+        Some(SignalKind::Bus)
+    } else {
+        None
+    };
     let expr = parse_optional_assignment(stream)?;
 
     // @TODO maybe move this to a semantic check?
     for ident in idents.iter() {
         if object_class == ObjectClass::Constant && mode != Mode::In {
             let pos = mode_pos.as_ref().unwrap_or(&ident.pos);
-            return Err(Diagnostic::error(
+            return Err(Diagnostic::syntax_error(
                 pos,
                 "Interface constant declaration may only have mode=in",
             ));
@@ -128,7 +138,7 @@ fn parse_interface_object_declaration(
 
         if list_type == InterfaceType::Port && object_class != ObjectClass::Signal {
             let pos = object_class_pos.unwrap_or(&ident.pos);
-            return Err(Diagnostic::error(
+            return Err(Diagnostic::syntax_error(
                 pos,
                 "Port list only allows signal object class",
             ));
@@ -136,7 +146,7 @@ fn parse_interface_object_declaration(
 
         if list_type == InterfaceType::Generic && object_class != ObjectClass::Constant {
             let pos = object_class_pos.unwrap_or(&ident.pos);
-            return Err(Diagnostic::error(
+            return Err(Diagnostic::syntax_error(
                 pos,
                 "Generic list only allows constant object class",
             ));
@@ -152,12 +162,52 @@ fn parse_interface_object_declaration(
                 class: object_class,
                 ident: ident.into(),
                 subtype_indication: subtype.clone(),
+                signal_kind,
                 expression: expr.clone(),
             })
         })
         .collect())
 }
 
+/// VHDL-2019 LRM 6.5.2 A signal whose mode is given by a mode view indication
+fn parse_interface_view_declaration(
+    stream: &TokenStream,
+    list_type: InterfaceType,
+    explicit_object_class: Option<WithPos<ObjectClass>>,
+    idents: Vec<Ident>,
+) -> ParseResult<Vec<InterfaceDeclaration>> {
+    let view_token = stream.peek_expect()?;
+    match (list_type, explicit_object_class) {
+        (InterfaceType::Port, None) => {}
+        (InterfaceType::Port | InterfaceType::Parameter, Some(class))
+            if class.item == ObjectClass::Signal => {}
+        (_, Some(class)) => {
+            return Err(Diagnostic::syntax_error(
+                class.pos,
+                "Mode view indication is only allowed for signals",
+            ));
+        }
+        (_, None) => {
+            return Err(Diagnostic::syntax_error(
+                view_token,
+                "Mode view indication is only allowed for signals",
+            ));
+        }
+    }
+
+    let view = parse_mode_view_indication(stream)?;
+    Ok(idents
+        .into_iter()
+        .map(|ident| {
+            InterfaceDeclaration::View(InterfaceViewDeclaration {
+                list_type,
+                ident: ident.into(),
+                view: view.clone(),
+            })
+        })
+        .collect())
+}
+
 fn parse_subprogram_default(stream: &TokenStream) -> ParseResult<Option<SubprogramDefault>> {
     if stream.skip_if_kind(Is) {
         let default = {
@@ -229,31 +279,50 @@ fn parse_interface_declaration(
         },
         File => parse_interface_file_declaration(stream),
         Type => {
+            check_generic_standard(stream, token, "Interface type declaration", diagnostics);
             stream.skip();
             let ident = stream.expect_ident()?;
             Ok(vec![InterfaceDeclaration::Type(WithDecl::new(ident))])
         },
         Function | Procedure | Impure | Pure => {
+            check_generic_standard(stream, token, "Interface subprogram declaration", diagnostics);
             let decl = parse_subprogram_declaration_no_semi(stream, diagnostics)?;
             let default = parse_subprogram_default(stream)?;
 
             Ok(vec![InterfaceDeclaration::Subprogram(decl, default)])
         },
         Package => {
+            check_generic_standard(stream, token, "Interface package declaration", diagnostics);
             Ok(vec![InterfaceDeclaration::Package (parse_interface_package(stream, diagnostics)?)])
         }
     )
 }
 
+/// Generic types, subprograms and packages were added in VHDL-2008
+fn check_generic_standard(
+    stream: &TokenStream,
+    token: &Token,
+    construct: &str,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    if stream.standard() < VHDLStandard::VHDL2008 {
+        diagnostics.push(Diagnostic::not_in_standard(
+            token,
+            construct,
+            stream.standard(),
+        ));
+    }
+}
+
 /// Parse ; separator in generic or port lists.
-/// Expect ; for all but the last item
+/// Expect ; for all but the last item, VHDL-2019 also allows it after the last item
 fn parse_semicolon_separator(stream: &TokenStream) -> ParseResult<()> {
     peek_token!(
         stream, token,
         SemiColon => {
             stream.skip();
-            if stream.next_kind_is(RightPar) {
-                return Err(Diagnostic::error(&token.pos,
+            if stream.next_kind_is(RightPar) && stream.standard() < VHDLStandard::VHDL2019 {
+                return Err(Diagnostic::syntax_error(&token.pos,
                         format!("Last interface element may not end with {}",
                         kinds_str(&[SemiColon]))));
             }
@@ -408,6 +477,7 @@ mod tests {
                     class: ObjectClass::Constant,
                     ident: code.s1("foo").decl_ident(),
                     subtype_indication: code.s1("natural").subtype_indication(),
+                    signal_kind: None,
                     expression: None
                 }),
                 InterfaceDeclaration::Object(InterfaceObjectDeclaration {
@@ -416,6 +486,7 @@ mod tests {
                     class: ObjectClass::Constant,
                     ident: code.s1("bar").decl_ident(),
                     subtype_indication: code.s1("natural").subtype_indication(),
+                    signal_kind: None,
                     expression: None
                 })
             ]
@@ -433,6 +504,7 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("std_logic").subtype_indication(),
+                signal_kind: None,
                 expression: None
             })
         );
@@ -455,7 +527,7 @@ mod tests {
         let code = Code::new("file foo : text open read_mode");
         assert_eq!(
             code.with_stream_err(parse_parameter),
-            Diagnostic::error(
+            Diagnostic::syntax_error(
                 code.s1("foo"),
                 "interface_file_declaration may not have file open information"
             )
@@ -467,7 +539,7 @@ mod tests {
         let code = Code::new("file foo : text is \"file_name\"");
         assert_eq!(
             code.with_stream_err(parse_parameter),
-            Diagnostic::error(
+            Diagnostic::syntax_error(
                 code.s1("foo"),
                 "interface_file_declaration may not have file name"
             )
@@ -485,11 +557,29 @@ mod tests {
                 class: ObjectClass::Signal,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("std_logic").subtype_indication(),
+                signal_kind: None,
                 expression: None
             })
         );
     }
 
+    #[test]
+    fn parses_port_of_kind_bus() {
+        let code = Code::new("signal foo : inout resolved_bit bus := '0'");
+        assert_eq!(
+            code.with_stream(parse_port),
+            InterfaceDeclaration::Object(InterfaceObjectDeclaration {
+                list_type: InterfaceType::Port,
+                mode: Mode::InOut,
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").decl_ident(),
+                subtype_indication: code.s1("resolved_bit").subtype_indication(),
+                signal_kind: Some(SignalKind::Bus),
+                expression: Some(code.s1("'0'").expr())
+            })
+        );
+    }
+
     fn to_interface_object(interface_decl: InterfaceDeclaration) -> InterfaceObjectDeclaration {
         match interface_decl {
             InterfaceDeclaration::Object(object) => object,
@@ -553,6 +643,7 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("std_logic").subtype_indication(),
+                signal_kind: None,
                 expression: None
             })
         );
@@ -569,6 +660,7 @@ mod tests {
                 class: ObjectClass::Signal,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("std_logic").subtype_indication(),
+                signal_kind: None,
                 expression: None
             })
         );
@@ -579,7 +671,7 @@ mod tests {
         let code = Code::new("foo : out boolean");
         assert_eq!(
             code.with_partial_stream(parse_generic),
-            Err(Diagnostic::error(
+            Err(Diagnostic::syntax_error(
                 &code.s1("out").pos(),
                 "Interface constant declaration may only have mode=in"
             ))
@@ -622,13 +714,55 @@ bar : natural)",
         );
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s(";", 2),
                 "Last interface element may not end with ';'"
             )]
         );
     }
 
+    #[test]
+    fn test_parse_generic_interface_list_last_semi_colon_in_vhdl2019() {
+        let code = Code::new(
+            "\
+(constant foo : std_logic;
+ bar : natural;
+)",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_generic_interface_list),
+            vec![
+                code.s1("constant foo : std_logic").generic(),
+                code.s1("bar : natural").generic()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_interface_type_before_vhdl2008() {
+        let code =
+            Code::new("(type elem_t; constant c : elem_t)").with_standard(VHDLStandard::VHDL2002);
+        let (result, diagnostics) = code.with_stream_diagnostics(parse_generic_interface_list);
+
+        assert_eq!(
+            result,
+            vec![
+                InterfaceDeclaration::Type(code.s1("elem_t").decl_ident()),
+                code.s1("constant c : elem_t").generic()
+            ]
+        );
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::not_in_standard(
+                code.s1("type"),
+                "Interface type declaration",
+                VHDLStandard::VHDL2002
+            )]
+        );
+    }
+
     #[test]
     fn test_parse_port_interface_list() {
         let code = Code::new(
@@ -693,20 +827,59 @@ bar : natural)",
         let (_, diagnostics) = code.with_stream_diagnostics(parse_generic_interface_list);
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s1("signal"),
                 "Generic list only allows constant object class"
             )]
         );
     }
 
+    #[test]
+    fn parses_port_with_mode_view() {
+        let code =
+            Code::new("(foo, bar : view master of bus_rec)").with_standard(VHDLStandard::VHDL2019);
+        let view = ModeViewIndication {
+            kind: ModeViewIndicationKind::Record,
+            name: code.s1("master").name(),
+            subtype_indication: Some(code.s1("bus_rec").subtype_indication()),
+        };
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_port_interface_list),
+            vec![
+                InterfaceDeclaration::View(InterfaceViewDeclaration {
+                    list_type: InterfaceType::Port,
+                    ident: code.s1("foo").decl_ident(),
+                    view: view.clone(),
+                }),
+                InterfaceDeclaration::View(InterfaceViewDeclaration {
+                    list_type: InterfaceType::Port,
+                    ident: code.s1("bar").decl_ident(),
+                    view,
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_generic_interface_no_view() {
+        let code = Code::new("(c1 : view master)").with_standard(VHDLStandard::VHDL2019);
+        let (_, diagnostics) = code.with_stream_diagnostics(parse_generic_interface_list);
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::syntax_error(
+                code.s1("view"),
+                "Mode view indication is only allowed for signals"
+            )]
+        );
+    }
+
     #[test]
     fn test_parse_port_interface_no_constant() {
         let code = Code::new("(constant c1 : natural)");
         let (_, diagnostics) = code.with_stream_diagnostics(parse_port_interface_list);
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(
+            vec![Diagnostic::syntax_error(
                 code.s1("constant"),
                 "Port list only allows signal object class"
             )]
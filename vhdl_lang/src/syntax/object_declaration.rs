@@ -22,6 +22,17 @@ pub fn parse_optional_assignment(stream: &TokenStream) -> ParseResult<Option<Wit
     }
 }
 
+/// LRM 6.4.2.3 Signal declarations
+fn parse_signal_kind(stream: &TokenStream) -> Option<SignalKind> {
+    if stream.skip_if_kind(Register) {
+        Some(SignalKind::Register)
+    } else if stream.skip_if_kind(Bus) {
+        Some(SignalKind::Bus)
+    } else {
+        None
+    }
+}
+
 fn parse_object_declaration_kind(
     stream: &TokenStream,
     class: ObjectClass,
@@ -45,6 +56,11 @@ fn parse_object_declaration_kind(
     let idents = parse_identifier_list(stream)?;
     stream.expect_kind(Colon)?;
     let subtype = parse_subtype_indication(stream)?;
+    let signal_kind = if class == ObjectClass::Signal {
+        parse_signal_kind(stream)
+    } else {
+        None
+    };
     let opt_expression = parse_optional_assignment(stream)?;
 
     Ok(idents
@@ -53,6 +69,7 @@ fn parse_object_declaration_kind(
             class,
             ident: ident.into(),
             subtype_indication: subtype.clone(),
+            signal_kind,
             expression: opt_expression.clone(),
         })
         .collect())
@@ -126,6 +143,7 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
@@ -140,11 +158,39 @@ mod tests {
                 class: ObjectClass::Signal,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
     }
 
+    #[test]
+    fn parses_signal_kind() {
+        let code = Code::new("signal foo : resolved_bit register;");
+        assert_eq!(
+            code.with_stream(parse_object_declaration),
+            vec![ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").decl_ident(),
+                subtype_indication: code.s1("resolved_bit").subtype_indication(),
+                signal_kind: Some(SignalKind::Register),
+                expression: None
+            }]
+        );
+
+        let code = Code::new("signal foo : resolved_bit bus := '0';");
+        assert_eq!(
+            code.with_stream(parse_object_declaration),
+            vec![ObjectDeclaration {
+                class: ObjectClass::Signal,
+                ident: code.s1("foo").decl_ident(),
+                subtype_indication: code.s1("resolved_bit").subtype_indication(),
+                signal_kind: Some(SignalKind::Bus),
+                expression: Some(code.s1("'0'").expr())
+            }]
+        );
+    }
+
     #[test]
     fn parses_variable() {
         let code = Code::new("variable foo : natural;");
@@ -154,6 +200,7 @@ mod tests {
                 class: ObjectClass::Variable,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
@@ -168,6 +215,7 @@ mod tests {
                 class: ObjectClass::SharedVariable,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: None
             }]
         );
@@ -224,6 +272,7 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("0").expr())
             }]
         );
@@ -238,12 +287,14 @@ mod tests {
                 class: ObjectClass::Constant,
                 ident: code.s1("foo").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("0").expr()),
             },
             ObjectDeclaration {
                 class: ObjectClass::Constant,
                 ident: code.s1("bar").decl_ident(),
                 subtype_indication: code.s1("natural").subtype_indication(),
+                signal_kind: None,
                 expression: Some(code.s1("0").expr()),
             },
         ];
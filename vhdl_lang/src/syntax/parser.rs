@@ -5,7 +5,7 @@
 // Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
 
 use super::design_unit::parse_design_file;
-use super::tokens::{Symbols, TokenStream, Tokenizer};
+use super::tokens::{ConditionalAnalysis, Symbols, TokenStream, Tokenizer};
 use crate::ast::DesignFile;
 use crate::data::*;
 use std::io;
@@ -14,6 +14,8 @@ use std::sync::Arc;
 #[derive(Default)]
 pub struct VHDLParser {
     pub symbols: Arc<Symbols>,
+    // Values of the identifiers in conditional analysis directives
+    pub conditions: ConditionalAnalysis,
 }
 
 pub type ParserResult = Result<(Source, DesignFile), io::Error>;
@@ -26,11 +28,17 @@ impl VHDLParser {
     pub fn parse_design_source(
         &self,
         source: &Source,
+        standard: VHDLStandard,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> DesignFile {
         let contents = source.contents();
-        let tokenizer = Tokenizer::new(&self.symbols, source, ContentReader::new(&contents));
-        let stream = TokenStream::new(tokenizer, diagnostics);
+        let tokenizer = Tokenizer::new(
+            &self.symbols,
+            source,
+            ContentReader::new(&contents),
+            standard,
+        );
+        let stream = TokenStream::new(tokenizer, &self.conditions, diagnostics);
 
         match parse_design_file(&stream, diagnostics) {
             Ok(design_file) => design_file,
@@ -44,10 +52,11 @@ impl VHDLParser {
     pub fn parse_design_file(
         &self,
         file_name: &Path,
+        standard: VHDLStandard,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> ParserResult {
         let source = Source::from_latin1_file(file_name)?;
-        let design_file = self.parse_design_source(&source, diagnostics);
+        let design_file = self.parse_design_source(&source, standard, diagnostics);
         Ok((source, design_file))
     }
 }
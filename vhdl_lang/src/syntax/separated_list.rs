@@ -5,7 +5,7 @@
 // Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
 
 use crate::ast::{IdentList, NameList, SeparatedList, WithRef};
-use crate::data::{DiagnosticHandler, DiagnosticResult};
+use crate::data::{DiagnosticHandler, DiagnosticResult, ErrorCode};
 use crate::syntax::common::ParseResult;
 use crate::syntax::names::parse_name;
 use crate::syntax::Kind::Comma;
@@ -24,9 +24,10 @@ fn skip_extraneous_tokens(
         while let Some(separator_tok) = stream.pop_if_kind(separator) {
             end_pos = stream.get_pos(separator_tok)
         }
-        diagnostics.error(
+        diagnostics.add(
             start_pos.combine(end_pos),
             format!("Extraneous '{}'", kind_str(separator)),
+            ErrorCode::SyntaxError,
         );
     }
 }
@@ -118,7 +119,7 @@ mod test {
         let (res, diagnostics) = code.with_partial_stream_diagnostics(parse_ident_list);
         assert_eq!(
             res,
-            Err(Diagnostic::error(code.eof_pos(), "Unexpected EOF"))
+            Err(Diagnostic::syntax_error(code.eof_pos(), "Unexpected EOF"))
         );
         assert!(diagnostics.is_empty());
     }
@@ -177,7 +178,10 @@ mod test {
         );
         assert_eq!(
             diag,
-            vec![Diagnostic::error(code.s(",", 2).pos(), "Extraneous ','")]
+            vec![Diagnostic::syntax_error(
+                code.s(",", 2).pos(),
+                "Extraneous ','"
+            )]
         )
     }
 
@@ -198,7 +202,10 @@ mod test {
         );
         assert_eq!(
             diag,
-            vec![Diagnostic::error(code.s(",,,", 2).pos(), "Extraneous ','")]
+            vec![Diagnostic::syntax_error(
+                code.s(",,,", 2).pos(),
+                "Extraneous ','"
+            )]
         )
     }
 
@@ -228,7 +235,10 @@ mod test {
         );
         assert_eq!(
             diag,
-            vec![Diagnostic::error(code.s1(")"), "Expected {expression}")]
+            vec![Diagnostic::syntax_error(
+                code.s1(")"),
+                "Expected {expression}"
+            )]
         );
     }
 
@@ -241,7 +251,7 @@ mod test {
             .expect_err("Should not parse OK");
         assert_eq!(
             diag,
-            Diagnostic::error(code.s1("1"), "Expected '{identifier}'")
+            Diagnostic::syntax_error(code.s1("1"), "Expected '{identifier}'")
         );
     }
 }
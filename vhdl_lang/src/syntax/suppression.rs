@@ -0,0 +1,271 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Inline suppression of diagnostics using pragma comments
+//!
+//! ```vhdl
+//! -- vhdl_ls: disable-next-line unused
+//! -- vhdl_ls: disable unused, type_mismatch
+//! -- vhdl_ls: enable unused
+//! ```
+//!
+//! A pragma without diagnostic codes applies to all codes.
+//! A `disable` without matching `enable` lasts until the end of the file.
+
+use super::tokens::{Comment, Token};
+use crate::ast::DesignFile;
+use crate::data::*;
+use fnv::FnvHashSet;
+use std::str::FromStr;
+
+const PRAGMA_PREFIX: &str = "vhdl_ls:";
+
+#[derive(Clone, Debug)]
+struct SuppressedLines {
+    // First and last suppressed line, inclusive
+    start: u32,
+    end: u32,
+    codes: FnvHashSet<ErrorCode>,
+}
+
+/// The diagnostics suppressed by pragma comments within a single file
+#[derive(Clone, Default, Debug)]
+pub struct Suppressions {
+    lines: Vec<SuppressedLines>,
+}
+
+impl Suppressions {
+    /// Collect the pragma comments of all tokens within a design file.
+    /// Malformed pragmas are reported as diagnostics.
+    pub fn from_design_file(
+        design_file: &DesignFile,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Suppressions {
+        let comments = design_file
+            .design_units
+            .iter()
+            .flat_map(|(tokens, _)| tokens.iter())
+            .flat_map(comments_of);
+
+        let mut suppressions = Suppressions::default();
+        let mut disabled: Vec<(u32, FnvHashSet<ErrorCode>)> = Vec::new();
+
+        for (pos, comment) in comments {
+            let pragma = if let Some(pragma) = comment.value.trim().strip_prefix(PRAGMA_PREFIX) {
+                pragma
+            } else {
+                continue;
+            };
+
+            let mut words = pragma
+                .split(|chr: char| chr.is_whitespace() || chr == ',')
+                .filter(|word| !word.is_empty());
+            let directive = words.next().unwrap_or_default();
+            let codes = parse_codes(&pos, words.collect(), diagnostics);
+            let line = pos.range().start.line;
+
+            match directive {
+                "disable-next-line" => {
+                    let next_line = pos.range().end.line + 1;
+                    suppressions.lines.push(SuppressedLines {
+                        start: next_line,
+                        end: next_line,
+                        codes,
+                    });
+                }
+                "disable" => {
+                    disabled.push((line, codes));
+                }
+                "enable" => {
+                    for (start, disabled_codes) in std::mem::take(&mut disabled) {
+                        let remaining: FnvHashSet<_> =
+                            disabled_codes.difference(&codes).copied().collect();
+                        suppressions.lines.push(SuppressedLines {
+                            start,
+                            end: line,
+                            codes: disabled_codes,
+                        });
+                        if !remaining.is_empty() {
+                            disabled.push((line, remaining));
+                        }
+                    }
+                }
+                _ => diagnostics.add(
+                    &pos,
+                    format!(
+                        "Unknown pragma '{directive}', expected disable, disable-next-line or enable"
+                    ),
+                    ErrorCode::InvalidPragma,
+                ),
+            }
+        }
+
+        for (start, codes) in disabled {
+            suppressions.lines.push(SuppressedLines {
+                start,
+                end: u32::MAX,
+                codes,
+            });
+        }
+
+        suppressions
+    }
+
+    pub fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+        let line = diagnostic.pos.range().start.line;
+        self.lines.iter().any(|lines| {
+            lines.start <= line && line <= lines.end && lines.codes.contains(&diagnostic.code)
+        })
+    }
+}
+
+/// The comments of a token in the order they appear in the source
+fn comments_of(token: &Token) -> impl Iterator<Item = (SrcPos, &Comment)> {
+    token
+        .comments
+        .iter()
+        .flat_map(|comments| comments.leading.iter().chain(comments.trailing.iter()))
+        .map(|comment| {
+            let pos = token.pos.source.pos(comment.range.start, comment.range.end);
+            (pos, comment)
+        })
+}
+
+/// Parse the diagnostic codes of a pragma where no codes means all codes
+fn parse_codes(
+    pos: &SrcPos,
+    words: Vec<&str>,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> FnvHashSet<ErrorCode> {
+    if words.is_empty() {
+        return ErrorCode::ALL.iter().copied().collect();
+    }
+
+    let mut codes = FnvHashSet::default();
+    for word in words {
+        match ErrorCode::from_str(word) {
+            Ok(code) => {
+                codes.insert(code);
+            }
+            Err(msg) => diagnostics.add(pos, msg, ErrorCode::InvalidPragma),
+        }
+    }
+    codes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::test::{check_diagnostics, check_no_diagnostics, Code};
+
+    fn suppressions(code: &Code) -> Suppressions {
+        let mut diagnostics = Vec::new();
+        let suppressions = Suppressions::from_design_file(&code.design_file(), &mut diagnostics);
+        check_no_diagnostics(&diagnostics);
+        suppressions
+    }
+
+    #[test]
+    fn disable_next_line() {
+        let code = Code::new(
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  -- vhdl_ls: disable-next-line unused
+  signal foo : bit;
+  signal bar : bit;
+begin
+end architecture;
+",
+        );
+        let suppressions = suppressions(&code);
+
+        let foo = Diagnostic::new(code.s1("foo"), "unused", ErrorCode::Unused);
+        let bar = Diagnostic::new(code.s1("bar"), "unused", ErrorCode::Unused);
+        let foo_mismatch = Diagnostic::new(code.s1("foo"), "mismatch", ErrorCode::TypeMismatch);
+        assert!(suppressions.is_suppressed(&foo));
+        assert!(!suppressions.is_suppressed(&bar));
+        assert!(!suppressions.is_suppressed(&foo_mismatch));
+    }
+
+    #[test]
+    fn disable_and_enable_region() {
+        let code = Code::new(
+            "
+entity ent is
+end entity;
+
+architecture a of ent is
+  -- vhdl_ls: disable unused, type_mismatch
+  signal foo : bit;
+  -- vhdl_ls: enable unused
+  signal bar : bit;
+  -- vhdl_ls: enable
+  signal baz : bit;
+begin
+end architecture;
+",
+        );
+        let suppressions = suppressions(&code);
+
+        let diagnostic = |name, error_code| Diagnostic::new(code.s1(name), "msg", error_code);
+
+        assert!(suppressions.is_suppressed(&diagnostic("foo", ErrorCode::Unused)));
+        assert!(suppressions.is_suppressed(&diagnostic("foo", ErrorCode::TypeMismatch)));
+        assert!(!suppressions.is_suppressed(&diagnostic("bar", ErrorCode::Unused)));
+        assert!(suppressions.is_suppressed(&diagnostic("bar", ErrorCode::TypeMismatch)));
+        assert!(!suppressions.is_suppressed(&diagnostic("baz", ErrorCode::TypeMismatch)));
+    }
+
+    #[test]
+    fn disable_without_codes_until_end_of_file() {
+        let code = Code::new(
+            "
+-- vhdl_ls: disable
+entity ent is
+end entity;
+",
+        );
+        let suppressions = suppressions(&code);
+
+        assert!(suppressions.is_suppressed(&Diagnostic::new(
+            code.s1("ent"),
+            "msg",
+            ErrorCode::Duplicate
+        )));
+    }
+
+    #[test]
+    fn invalid_pragmas() {
+        let code = Code::new(
+            "
+-- vhdl_ls: disable no_such_code
+-- vhdl_ls: silence unused
+entity ent is
+end entity;
+",
+        );
+        let mut diagnostics = Vec::new();
+        Suppressions::from_design_file(&code.design_file(), &mut diagnostics);
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::new(
+                    code.s1("-- vhdl_ls: disable no_such_code"),
+                    "Unknown diagnostic code 'no_such_code'",
+                    ErrorCode::InvalidPragma,
+                ),
+                Diagnostic::new(
+                    code.s1("-- vhdl_ls: silence unused"),
+                    "Unknown pragma 'silence', expected disable, disable-next-line or enable",
+                    ErrorCode::InvalidPragma,
+                ),
+            ],
+        );
+    }
+}
@@ -11,7 +11,9 @@ use super::concurrent_statement::parse_labeled_concurrent_statement;
 use super::context::{parse_library_clause, parse_use_clause};
 use super::declarative_part::parse_declarative_part;
 use super::design_unit::{parse_design_file, parse_entity_declaration};
+use super::disconnection::parse_disconnection_specification;
 use super::expression::{parse_aggregate, parse_choices, parse_expression};
+use super::group_declaration::parse_group_declaration;
 use super::interface_declaration::{parse_generic, parse_parameter, parse_port};
 use super::names::{
     parse_association_list, parse_designator, parse_name, parse_selected_name, parse_type_mark,
@@ -22,8 +24,9 @@ use super::separated_list::{parse_ident_list, parse_name_list};
 use super::sequential_statement::parse_sequential_statement;
 use super::subprogram::{parse_signature, parse_subprogram_declaration_no_semi};
 use super::subtype_indication::parse_subtype_indication;
-use super::tokens::{Comment, Kind, Symbols, Token, TokenStream, Tokenizer};
+use super::tokens::{Comment, ConditionalAnalysis, Kind, Symbols, Token, TokenStream, Tokenizer};
 use super::type_declaration::parse_type_declaration;
+use super::view_declaration::parse_mode_view_declaration;
 use super::waveform::parse_waveform;
 use crate::ast;
 use crate::ast::*;
@@ -85,6 +88,7 @@ impl CodeBuilder {
         let code = Code {
             symbols: self.symbols.clone(),
             pos,
+            standard: VHDLStandard::default(),
         };
 
         // Ensure symbol table is populated
@@ -121,6 +125,7 @@ impl<T> SeparatedList<T> {
 pub struct Code {
     pub symbols: Arc<Symbols>,
     pos: SrcPos,
+    standard: VHDLStandard,
 }
 
 impl Code {
@@ -136,6 +141,19 @@ impl Code {
         Code {
             symbols: self.symbols.clone(),
             pos: SrcPos::new(self.pos.source.clone(), range),
+            standard: self.standard,
+        }
+    }
+
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
+    /// The same code parsed in another VHDL revision
+    pub fn with_standard(&self, standard: VHDLStandard) -> Code {
+        Code {
+            standard,
+            ..self.clone()
         }
     }
 
@@ -210,7 +228,8 @@ impl Code {
         {
             let contents = self.pos.source.contents();
             let reader = ContentReader::new(&contents);
-            let mut tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader);
+            let mut tokenizer =
+                Tokenizer::new(&self.symbols, &self.pos.source, reader, self.standard);
             loop {
                 let token = tokenizer.pop();
 
@@ -242,8 +261,12 @@ impl Code {
         );
         let contents = source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &source, reader);
-        let stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
+        let tokenizer = Tokenizer::new(&self.symbols, &source, reader, self.standard);
+        let stream = TokenStream::new(
+            tokenizer,
+            &ConditionalAnalysis::default(),
+            &mut NoDiagnostics,
+        );
         forward(&stream, self.pos.start());
         stream.peek().expect("No token found");
         stream.get_token_id()
@@ -261,8 +284,12 @@ impl Code {
         );
         let contents = source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &source, reader);
-        let mut stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
+        let tokenizer = Tokenizer::new(&self.symbols, &source, reader, self.standard);
+        let mut stream = TokenStream::new(
+            tokenizer,
+            &ConditionalAnalysis::default(),
+            &mut NoDiagnostics,
+        );
         forward(&stream, self.pos.start());
         parse_fun(&mut stream)
     }
@@ -286,8 +313,12 @@ impl Code {
     {
         let contents = self.pos.source.contents();
         let reader = ContentReader::new(&contents);
-        let tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader);
-        let mut stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
+        let tokenizer = Tokenizer::new(&self.symbols, &self.pos.source, reader, self.standard);
+        let mut stream = TokenStream::new(
+            tokenizer,
+            &ConditionalAnalysis::default(),
+            &mut NoDiagnostics,
+        );
         parse_fun(&mut stream)
     }
 
@@ -456,6 +487,28 @@ impl Code {
         self.parse_ok(parse_alias_declaration)
     }
 
+    pub fn group_template_decl(&self) -> GroupTemplateDeclaration {
+        match self.with_stream(parse_group_declaration) {
+            Declaration::GroupTemplate(decl) => decl,
+            decl => panic!("Expected group template declaration, got {decl:?}"),
+        }
+    }
+
+    pub fn group_decl(&self) -> GroupDeclaration {
+        match self.with_stream(parse_group_declaration) {
+            Declaration::Group(decl) => decl,
+            decl => panic!("Expected group declaration, got {decl:?}"),
+        }
+    }
+
+    pub fn disconnection_spec(&self) -> DisconnectionSpecification {
+        self.with_stream(parse_disconnection_specification)
+    }
+
+    pub fn view_decl(&self) -> ModeViewDeclaration {
+        self.with_stream_no_diagnostics(parse_mode_view_declaration)
+    }
+
     pub fn component_decl(&self) -> ComponentDeclaration {
         self.with_stream_no_diagnostics(parse_component_declaration)
     }
@@ -675,8 +728,27 @@ pub fn without_releated(diagnostics: &[Diagnostic]) -> Vec<Diagnostic> {
     diagnostics
 }
 
+// Expected diagnostics without a specific code match a diagnostic with any code and data
+fn without_unspecified_codes(got: Vec<Diagnostic>, expected: &[Diagnostic]) -> Vec<Diagnostic> {
+    got.into_iter()
+        .map(|diagnostic| {
+            let unspecified = Diagnostic {
+                code: ErrorCode::Unspecified,
+                data: None,
+                ..diagnostic.clone()
+            };
+            if !expected.contains(&diagnostic) && expected.contains(&unspecified) {
+                unspecified
+            } else {
+                diagnostic
+            }
+        })
+        .collect()
+}
+
 /// Check diagnostics are equal without considering order
 pub fn check_diagnostics(got: Vec<Diagnostic>, expected: Vec<Diagnostic>) {
+    let got = without_unspecified_codes(got, &expected);
     let mut expected = diagnostics_to_map(expected);
     let mut got = diagnostics_to_map(got);
 
@@ -753,8 +825,8 @@ mod tests {
     fn check_diagnostics_ok() {
         let code = Code::new("foo bar");
         check_diagnostics(
-            vec![Diagnostic::error(code.s1("foo"), "hello")],
-            vec![Diagnostic::error(code.s1("foo"), "hello")],
+            vec![Diagnostic::syntax_error(code.s1("foo"), "hello")],
+            vec![Diagnostic::syntax_error(code.s1("foo"), "hello")],
         )
     }
 
@@ -763,23 +835,32 @@ mod tests {
         let code = Code::new("foo bar");
         check_diagnostics(
             vec![
-                Diagnostic::error(code.s1("foo"), "hello"),
-                Diagnostic::error(code.s1("bar"), "msg"),
+                Diagnostic::syntax_error(code.s1("foo"), "hello"),
+                Diagnostic::syntax_error(code.s1("bar"), "msg"),
             ],
             vec![
-                Diagnostic::error(code.s1("bar"), "msg"),
-                Diagnostic::error(code.s1("foo"), "hello"),
+                Diagnostic::syntax_error(code.s1("bar"), "msg"),
+                Diagnostic::syntax_error(code.s1("foo"), "hello"),
             ],
         )
     }
 
+    #[test]
+    fn check_diagnostics_ok_unspecified_code() {
+        let code = Code::new("foo bar");
+        check_diagnostics(
+            vec![Diagnostic::syntax_error(code.s1("foo"), "hello")],
+            vec![Diagnostic::error(code.s1("foo"), "hello")],
+        )
+    }
+
     #[test]
     #[should_panic]
     fn check_diagnostics_not_ok_mismatch() {
         let code = Code::new("foo bar");
         check_diagnostics(
-            vec![Diagnostic::error(code.s1("bar"), "msg")],
-            vec![Diagnostic::error(code.s1("foo"), "hello")],
+            vec![Diagnostic::syntax_error(code.s1("bar"), "msg")],
+            vec![Diagnostic::syntax_error(code.s1("foo"), "hello")],
         )
     }
 
@@ -789,10 +870,10 @@ mod tests {
         let code = Code::new("foo bar");
         check_diagnostics(
             vec![
-                Diagnostic::error(code.s1("bar"), "msg"),
-                Diagnostic::error(code.s1("bar"), "msg"),
+                Diagnostic::syntax_error(code.s1("bar"), "msg"),
+                Diagnostic::syntax_error(code.s1("bar"), "msg"),
             ],
-            vec![Diagnostic::error(code.s1("bar"), "msg")],
+            vec![Diagnostic::syntax_error(code.s1("bar"), "msg")],
         )
     }
 
@@ -801,10 +882,10 @@ mod tests {
     fn check_diagnostics_not_ok_missing() {
         let code = Code::new("foo bar");
         check_diagnostics(
-            vec![Diagnostic::error(code.s1("bar"), "msg")],
+            vec![Diagnostic::syntax_error(code.s1("bar"), "msg")],
             vec![
-                Diagnostic::error(code.s1("bar"), "msg"),
-                Diagnostic::error(code.s1("bar"), "missing"),
+                Diagnostic::syntax_error(code.s1("bar"), "msg"),
+                Diagnostic::syntax_error(code.s1("bar"), "missing"),
             ],
         )
     }
@@ -815,10 +896,10 @@ mod tests {
         let code = Code::new("foo bar");
         check_diagnostics(
             vec![
-                Diagnostic::error(code.s1("bar"), "msg"),
-                Diagnostic::error(code.s1("bar"), "unexpected"),
+                Diagnostic::syntax_error(code.s1("bar"), "msg"),
+                Diagnostic::syntax_error(code.s1("bar"), "unexpected"),
             ],
-            vec![Diagnostic::error(code.s1("bar"), "msg")],
+            vec![Diagnostic::syntax_error(code.s1("bar"), "msg")],
         )
     }
 }
@@ -0,0 +1,629 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+//! Conditional analysis directives, LRM 24.2
+
+use super::tokenizer::Kind::*;
+use super::tokenizer::*;
+use crate::data::{DiagnosticHandler, DiagnosticResult, ErrorCode, VHDLStandard};
+use crate::{Diagnostic, SrcPos};
+use fnv::FnvHashMap;
+
+/// The values of the conditional analysis identifiers, LRM 24.2.3
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConditionalAnalysis {
+    // Conditional analysis identifiers are case insensitive and stored in upper case
+    values: FnvHashMap<String, String>,
+}
+
+impl std::default::Default for ConditionalAnalysis {
+    /// The standard conditional analysis identifiers
+    fn default() -> Self {
+        let mut conditions = ConditionalAnalysis {
+            values: FnvHashMap::default(),
+        };
+        conditions.set("VHDL_VERSION", VHDLStandard::default().as_str());
+        conditions.set("TOOL_TYPE", "SIMULATION");
+        conditions.set("TOOL_VENDOR", "VHDL-LS");
+        conditions.set("TOOL_NAME", "vhdl_ls");
+        conditions.set("TOOL_EDITION", "");
+        conditions.set("TOOL_VERSION", env!("CARGO_PKG_VERSION"));
+        conditions
+    }
+}
+
+impl ConditionalAnalysis {
+    /// Define the value of an identifier, overriding a standard identifier of the same name
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.values
+            .insert(name.to_ascii_uppercase(), value.to_owned());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values
+            .get(&name.to_ascii_uppercase())
+            .map(|value| value.as_str())
+    }
+
+    /// Evaluate the tokens of a conditional analysis expression
+    fn evaluate(&self, start_pos: &SrcPos, tokens: &[Token]) -> DiagnosticResult<bool> {
+        let mut idx = 0;
+        let value = self.expression(start_pos, tokens, &mut idx)?;
+        if let Some(token) = tokens.get(idx) {
+            return Err(Diagnostic::syntax_error(
+                token,
+                "Expected 'and', 'or', 'xor', 'xnor' or 'then'",
+            ));
+        }
+        Ok(value)
+    }
+
+    /// relation { logical_operator relation }
+    /// Mixing logical operators requires parenthesis as for VHDL expressions
+    fn expression(
+        &self,
+        start_pos: &SrcPos,
+        tokens: &[Token],
+        idx: &mut usize,
+    ) -> DiagnosticResult<bool> {
+        let mut value = self.relation(start_pos, tokens, idx)?;
+        let mut operator: Option<Kind> = None;
+        while let Some(token) = tokens.get(*idx) {
+            if !matches!(token.kind, And | Or | Xor | Xnor) {
+                break;
+            }
+            if operator.is_some_and(|kind| kind != token.kind) {
+                return Err(Diagnostic::syntax_error(
+                    token,
+                    "Mixed logical operators require parenthesis",
+                ));
+            }
+            operator = Some(token.kind);
+            *idx += 1;
+            let rhs = self.relation(&token.pos, tokens, idx)?;
+            value = match token.kind {
+                And => value && rhs,
+                Or => value || rhs,
+                Xor => value != rhs,
+                _ => value == rhs,
+            };
+        }
+        Ok(value)
+    }
+
+    /// ( expression ) | not ( expression ) | identifier relational_operator string_literal
+    fn relation(
+        &self,
+        start_pos: &SrcPos,
+        tokens: &[Token],
+        idx: &mut usize,
+    ) -> DiagnosticResult<bool> {
+        let Some(token) = tokens.get(*idx) else {
+            return Err(Diagnostic::syntax_error(
+                start_pos,
+                "Expected conditional analysis expression",
+            ));
+        };
+        *idx += 1;
+        match token.kind {
+            Not => {
+                expect_kind(&token.pos, tokens, idx, LeftPar)?;
+                let value = self.expression(&token.pos, tokens, idx)?;
+                expect_kind(&token.pos, tokens, idx, RightPar)?;
+                Ok(!value)
+            }
+            LeftPar => {
+                let value = self.expression(&token.pos, tokens, idx)?;
+                expect_kind(&token.pos, tokens, idx, RightPar)?;
+                Ok(value)
+            }
+            Identifier => {
+                let name = match token.value {
+                    Value::Identifier(ref symbol) => symbol.name_utf8(),
+                    _ => unreachable!(),
+                };
+                let operator = tokens
+                    .get(*idx)
+                    .filter(|op| matches!(op.kind, EQ | NE | LT | LTE | GT | GTE));
+                let Some(operator) = operator else {
+                    return Err(Diagnostic::syntax_error(
+                        tokens.get(*idx).map_or(&token.pos, |tok| &tok.pos),
+                        "Expected relational operator",
+                    ));
+                };
+                *idx += 1;
+                let string = expect_kind(&operator.pos, tokens, idx, StringLiteral)?;
+                let string = match string.value {
+                    Value::String(ref string) => string.to_string(),
+                    _ => unreachable!(),
+                };
+                let Some(value) = self.get(&name) else {
+                    return Err(Diagnostic::new(
+                        token,
+                        format!("Undefined conditional analysis identifier '{name}'"),
+                        ErrorCode::Unresolved,
+                    ));
+                };
+                let value = value.as_bytes();
+                let string = string.as_bytes();
+                Ok(match operator.kind {
+                    EQ => value == string,
+                    NE => value != string,
+                    LT => value < string,
+                    LTE => value <= string,
+                    GT => value > string,
+                    _ => value >= string,
+                })
+            }
+            _ => Err(Diagnostic::syntax_error(
+                token,
+                "Expected identifier, 'not' or '('",
+            )),
+        }
+    }
+}
+
+fn expect_kind<'t>(
+    pos: &SrcPos,
+    tokens: &'t [Token],
+    idx: &mut usize,
+    kind: Kind,
+) -> DiagnosticResult<&'t Token> {
+    match tokens.get(*idx) {
+        Some(token) if token.kind == kind => {
+            *idx += 1;
+            Ok(token)
+        }
+        Some(token) => Err(Diagnostic::syntax_error(
+            token,
+            format!("Expected '{}'", kind_str(kind)),
+        )),
+        None => Err(Diagnostic::syntax_error(
+            pos,
+            format!("Expected '{}'", kind_str(kind)),
+        )),
+    }
+}
+
+/// An `if directive and the branches seen so far
+struct ConditionalBlock {
+    if_pos: SrcPos,
+    // The region enclosing the `if directive is analyzed
+    parent_active: bool,
+    // The current branch is analyzed
+    active: bool,
+    // One of the branches so far was analyzed
+    taken: bool,
+    has_else: bool,
+}
+
+/// The nesting of conditional analysis directives while tokenizing a design file
+pub(super) struct ConditionalBlocks {
+    conditions: ConditionalAnalysis,
+    blocks: Vec<ConditionalBlock>,
+    // The tokens skipped since the region became inactive
+    inactive: Option<SrcPos>,
+    inactive_regions: Vec<SrcPos>,
+}
+
+impl ConditionalBlocks {
+    /// VHDL_VERSION is the revision of the design file
+    pub fn new(conditions: &ConditionalAnalysis, standard: VHDLStandard) -> ConditionalBlocks {
+        let mut conditions = conditions.clone();
+        conditions.set("VHDL_VERSION", standard.as_str());
+        ConditionalBlocks {
+            conditions,
+            blocks: Vec::new(),
+            inactive: None,
+            inactive_regions: Vec::new(),
+        }
+    }
+
+    /// True if the tokens at this point are analyzed
+    pub fn is_active(&self) -> bool {
+        self.blocks.last().is_none_or(|block| block.active)
+    }
+
+    /// Record a token that is not analyzed
+    pub fn skip(&mut self, token: &Token) {
+        self.inactive = Some(match self.inactive.take() {
+            Some(pos) => pos.combine(token),
+            None => token.pos.clone(),
+        });
+    }
+
+    /// Handle the `if, `elsif, `else or `end directive starting with `keyword`
+    pub fn handle_directive(
+        &mut self,
+        keyword: Token,
+        tokenizer: &mut Tokenizer,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        match keyword.kind {
+            If => {
+                let parent_active = self.is_active();
+                let active = self.condition(&keyword, tokenizer, parent_active, diagnostics);
+                self.blocks.push(ConditionalBlock {
+                    if_pos: keyword.pos,
+                    parent_active,
+                    active,
+                    taken: active,
+                    has_else: false,
+                });
+            }
+            Elsif => {
+                let Some(block) = self.current_block(&keyword, diagnostics) else {
+                    let _ = Self::condition_tokens(&keyword, tokenizer);
+                    return;
+                };
+                let evaluate = block.parent_active && !block.taken;
+                let active = self.condition(&keyword, tokenizer, evaluate, diagnostics);
+                self.enter_branch(active);
+            }
+            Else => {
+                if let Some(block) = self.current_block(&keyword, diagnostics) {
+                    let active = block.parent_active && !block.taken;
+                    block.has_else = true;
+                    self.enter_branch(active);
+                }
+            }
+            _ => {
+                self.end_directive(&keyword, tokenizer, diagnostics);
+                if self.blocks.pop().is_none() {
+                    diagnostics.add(
+                        keyword,
+                        "`end directive without matching `if",
+                        ErrorCode::SyntaxError,
+                    );
+                }
+                self.close_inactive_region();
+            }
+        }
+    }
+
+    /// Handle a `warning or `error directive, the message is reported when the directive is analyzed
+    pub fn handle_report(
+        &mut self,
+        directive: Token,
+        tokenizer: &mut Tokenizer,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let is_error = match directive.value {
+            Value::Identifier(ref symbol) => symbol.name_utf8().eq_ignore_ascii_case("error"),
+            _ => false,
+        };
+        let message = match tokenizer.pop() {
+            Ok(Some(token)) if token.kind == StringLiteral => token,
+            Ok(Some(token)) => {
+                diagnostics.add(token, "Expected string literal", ErrorCode::SyntaxError);
+                let _ = tokenizer.text_until_newline();
+                return;
+            }
+            Ok(None) => {
+                diagnostics.add(directive, "Expected string literal", ErrorCode::SyntaxError);
+                return;
+            }
+            Err(err) => {
+                diagnostics.push(err);
+                return;
+            }
+        };
+        let Value::String(ref text) = message.value else {
+            unreachable!();
+        };
+        let code = if is_error {
+            ErrorCode::DirectiveError
+        } else {
+            ErrorCode::DirectiveWarning
+        };
+        diagnostics.add(directive.pos.combine(&message), text.to_string(), code);
+    }
+
+    /// Report unterminated `if directives and return the regions that are not analyzed
+    pub fn finish(mut self, diagnostics: &mut dyn DiagnosticHandler) -> Vec<SrcPos> {
+        for block in self.blocks.drain(..) {
+            diagnostics.add(
+                block.if_pos,
+                "Missing `end directive for `if",
+                ErrorCode::SyntaxError,
+            );
+        }
+        self.close_inactive_region();
+        self.inactive_regions
+    }
+
+    fn current_block(
+        &mut self,
+        keyword: &Token,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> Option<&mut ConditionalBlock> {
+        let Some(block) = self.blocks.last_mut() else {
+            diagnostics.add(
+                keyword,
+                format!("`{} directive without matching `if", kind_str(keyword.kind)),
+                ErrorCode::SyntaxError,
+            );
+            return None;
+        };
+        if block.has_else {
+            diagnostics.add(
+                keyword,
+                format!("`{} directive after `else", kind_str(keyword.kind)),
+                ErrorCode::SyntaxError,
+            );
+            return None;
+        }
+        Some(block)
+    }
+
+    /// Switch the innermost block to the next branch
+    fn enter_branch(&mut self, active: bool) {
+        if let Some(block) = self.blocks.last_mut() {
+            block.active = active;
+            block.taken |= active;
+        }
+        self.close_inactive_region();
+    }
+
+    fn close_inactive_region(&mut self) {
+        if self.is_active() {
+            if let Some(pos) = self.inactive.take() {
+                self.inactive_regions.push(pos);
+            }
+        }
+    }
+
+    /// Read the condition of an `if or `elsif directive, it is only evaluated when `evaluate` is true
+    fn condition(
+        &self,
+        keyword: &Token,
+        tokenizer: &mut Tokenizer,
+        evaluate: bool,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) -> bool {
+        let result = Self::condition_tokens(keyword, tokenizer).and_then(|tokens| {
+            if evaluate {
+                self.conditions.evaluate(&keyword.pos, &tokens)
+            } else {
+                Ok(false)
+            }
+        });
+        match result {
+            Ok(value) => value,
+            Err(err) => {
+                if evaluate {
+                    diagnostics.push(err);
+                }
+                false
+            }
+        }
+    }
+
+    /// The tokens of a condition up to the 'then' keyword
+    fn condition_tokens(
+        keyword: &Token,
+        tokenizer: &mut Tokenizer,
+    ) -> DiagnosticResult<Vec<Token>> {
+        let mut tokens = Vec::new();
+        loop {
+            match tokenizer.pop()? {
+                Some(token) if token.kind == Then => return Ok(tokens),
+                Some(token) => tokens.push(token),
+                None => return Err(Diagnostic::syntax_error(keyword, "Expected 'then'")),
+            }
+        }
+    }
+
+    /// The `end directive is optionally followed by 'if' on the same line
+    fn end_directive(
+        &self,
+        keyword: &Token,
+        tokenizer: &mut Tokenizer,
+        diagnostics: &mut dyn DiagnosticHandler,
+    ) {
+        let text = match tokenizer.text_until_newline() {
+            Ok(text) => text,
+            Err(err) => {
+                diagnostics.push(err);
+                return;
+            }
+        };
+        let Value::Text(ref content) = text.value else {
+            unreachable!();
+        };
+        let content = content.to_string();
+        let content = content.split("--").next().unwrap_or_default().trim();
+        if !(content.is_empty() || content.eq_ignore_ascii_case("if")) {
+            diagnostics.add(
+                keyword.pos.combine(&text),
+                "Expected 'if' or end of line after `end",
+                ErrorCode::SyntaxError,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::ContentReader;
+    use crate::syntax::test::{check_diagnostics, check_no_diagnostics, Code};
+    use crate::syntax::tokens::TokenStream;
+
+    /// The positions of the analyzed tokens, the inactive regions and the diagnostics
+    fn tokenize(
+        code: &Code,
+        conditions: &ConditionalAnalysis,
+    ) -> (Vec<SrcPos>, Vec<SrcPos>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let source = code.source();
+        let contents = source.contents();
+        let tokenizer = Tokenizer::new(
+            &code.symbols,
+            source,
+            ContentReader::new(&contents),
+            VHDLStandard::default(),
+        );
+        let stream = TokenStream::new(tokenizer, conditions, &mut diagnostics);
+        let mut positions = Vec::new();
+        while let Some(token) = stream.peek() {
+            positions.push(token.pos.clone());
+            stream.skip();
+        }
+        (positions, stream.inactive_regions().to_vec(), diagnostics)
+    }
+
+    #[test]
+    fn selects_first_branch_with_true_condition() {
+        let code = Code::new(
+            "\
+`if TOOL_TYPE = \"SYNTHESIS\" then
+alpha
+`elsif tool_name = \"vhdl_ls\" then
+beta
+`elsif TOOL_NAME /= \"\" then
+gamma
+`else
+delta
+`end if
+omega",
+        );
+        let (tokens, regions, diagnostics) = tokenize(&code, &ConditionalAnalysis::default());
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(tokens, vec![code.s1("beta").pos(), code.s1("omega").pos()]);
+        assert_eq!(
+            regions,
+            vec![code.s1("alpha").pos(), code.s1("gamma\n`else\ndelta").pos()]
+        );
+    }
+
+    #[test]
+    fn nested_directives_within_inactive_branch() {
+        let code = Code::new(
+            "\
+`if VARIANT = \"sim\" then
+  `if TOOL_VERSION > \"\" then
+  alpha
+  `else
+  beta
+  `end
+`else
+  gamma
+`end if",
+        );
+        let mut conditions = ConditionalAnalysis::default();
+        conditions.set("variant", "synth");
+        let (tokens, regions, diagnostics) = tokenize(&code, &conditions);
+        check_no_diagnostics(&diagnostics);
+        assert_eq!(tokens, vec![code.s1("gamma").pos()]);
+        assert_eq!(regions, vec![code.s1("alpha\n  `else\n  beta").pos()]);
+    }
+
+    #[test]
+    fn evaluates_logical_operators() {
+        let conditions = ConditionalAnalysis::default();
+        let check = |condition: &str, expected: bool| {
+            let code = Code::new(&format!("`if {condition} then\nalpha\n`end if"));
+            let (tokens, _, diagnostics) = tokenize(&code, &conditions);
+            check_no_diagnostics(&diagnostics);
+            assert_eq!(!tokens.is_empty(), expected, "{condition}");
+        };
+        check("VHDL_VERSION >= \"2008\" and TOOL_NAME = \"vhdl_ls\"", true);
+        check("VHDL_VERSION < \"2008\" or TOOL_NAME /= \"vhdl_ls\"", false);
+        check("not (VHDL_VERSION = \"1993\")", true);
+        check("VHDL_VERSION = \"2008\" xor VHDL_VERSION = \"2008\"", false);
+        check(
+            "(VHDL_VERSION = \"1993\" or VHDL_VERSION = \"2008\") and TOOL_EDITION = \"\"",
+            true,
+        );
+    }
+
+    #[test]
+    fn error_on_invalid_conditions() {
+        let code = Code::new(
+            "\
+`if UNDEFINED = \"1\" then
+`end if
+`if VHDL_VERSION = \"2019\" and TOOL_NAME = \"\" or TOOL_TYPE = \"\" then
+`end if
+`if VHDL_VERSION then
+`end if",
+        );
+        let (_, _, diagnostics) = tokenize(&code, &ConditionalAnalysis::default());
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::new(
+                    code.s1("UNDEFINED"),
+                    "Undefined conditional analysis identifier 'UNDEFINED'",
+                    ErrorCode::Unresolved,
+                ),
+                Diagnostic::syntax_error(
+                    code.s1("or"),
+                    "Mixed logical operators require parenthesis",
+                ),
+                Diagnostic::syntax_error(
+                    code.sb("VHDL_VERSION", " then"),
+                    "Expected relational operator",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn error_on_unbalanced_directives() {
+        let code = Code::new(
+            "\
+`else
+`end if
+`if TOOL_NAME = \"vhdl_ls\" then
+`else
+`elsif TOOL_NAME = \"\" then
+`end if
+`if TOOL_NAME = \"vhdl_ls\" then
+`end if then
+`if TOOL_EDITION = \"\" then",
+        );
+        let (_, _, diagnostics) = tokenize(&code, &ConditionalAnalysis::default());
+        check_diagnostics(
+            diagnostics,
+            vec![
+                Diagnostic::syntax_error(code.s1("else"), "`else directive without matching `if"),
+                Diagnostic::syntax_error(code.s1("end"), "`end directive without matching `if"),
+                Diagnostic::syntax_error(code.s1("elsif"), "`elsif directive after `else"),
+                Diagnostic::syntax_error(
+                    code.s1("end if then"),
+                    "Expected 'if' or end of line after `end",
+                ),
+                Diagnostic::syntax_error(
+                    code.sb("if", " TOOL_EDITION"),
+                    "Missing `end directive for `if",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn reports_warning_and_error_directives_when_active() {
+        let code = Code::new(
+            "\
+`if TOOL_TYPE = \"SYNTHESIS\" then
+`error \"Not supported\"
+`else
+`warning \"Simulation only\"
+`end if",
+        );
+        let (_, _, diagnostics) = tokenize(&code, &ConditionalAnalysis::default());
+        check_diagnostics(
+            diagnostics,
+            vec![Diagnostic::new(
+                code.s1("warning \"Simulation only\""),
+                "Simulation only",
+                ErrorCode::DirectiveWarning,
+            )],
+        );
+    }
+}
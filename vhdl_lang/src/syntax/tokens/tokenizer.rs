@@ -96,6 +96,12 @@ pub enum Kind {
     Procedure,
     Vunit,
     Parameter,
+    Group,
+    Guarded,
+    Register,
+    Bus,
+    Disconnect,
+    View,
 
     // Unary operators
     Abs,
@@ -348,6 +354,12 @@ pub fn kind_str(kind: Kind) -> &'static str {
         Procedure => "procedure",
         Vunit => "vunit",
         Parameter => "parameter",
+        Group => "group",
+        Guarded => "guarded",
+        Register => "register",
+        Bus => "bus",
+        Disconnect => "disconnect",
+        View => "view",
 
         // Unary operators
         Abs => "abs",
@@ -539,7 +551,7 @@ impl From<Token> for SrcPos {
 }
 
 pub fn kinds_error<T: AsRef<SrcPos>>(pos: T, kinds: &[Kind]) -> Diagnostic {
-    Diagnostic::error(
+    Diagnostic::syntax_error(
         pos.as_ref(),
         format!("Expected {}", kinds_str(kinds)).as_str(),
     )
@@ -625,7 +637,10 @@ impl Token {
         if let Some(op) = Operator::from_latin1(string.item) {
             Ok(WithPos::new(op, string.pos))
         } else {
-            Err(Diagnostic::error(&string.pos, "Invalid operator symbol"))
+            Err(Diagnostic::syntax_error(
+                &string.pos,
+                "Invalid operator symbol",
+            ))
         }
     }
 }
@@ -1171,6 +1186,7 @@ fn parse_basic_identifier_or_keyword(
     buffer: &mut Latin1String,
     reader: &mut ContentReader,
     symbols: &Symbols,
+    standard: VHDLStandard,
 ) -> Result<(Kind, Value), TokenError> {
     buffer.bytes.clear();
     while let Some(b) = reader.peek()? {
@@ -1185,7 +1201,7 @@ fn parse_basic_identifier_or_keyword(
         }
     }
 
-    Ok(symbols.insert_or_keyword(buffer))
+    Ok(symbols.insert_or_keyword(buffer, standard))
 }
 
 /// Assumes leading ' has already been consumed
@@ -1326,16 +1342,26 @@ impl Symbols {
         &self.symtab
     }
 
-    fn insert_or_keyword(&self, name: &Latin1String) -> (Kind, Value) {
+    /// Keywords that are not reserved in the VHDL revision are identifiers
+    fn insert_or_keyword(&self, name: &Latin1String, standard: VHDLStandard) -> (Kind, Value) {
         let symbol = self.symtab.insert(name);
-        if let Some(kind) = self.keywords.get(symbol.id) {
-            (*kind, Value::NoValue)
-        } else {
-            (Identifier, Value::Identifier(symbol))
+        match self.keywords.get(symbol.id) {
+            Some(kind) if reserved_since(*kind) <= standard => (*kind, Value::NoValue),
+            _ => (Identifier, Value::Identifier(symbol)),
         }
     }
 }
 
+/// The revision that introduced a reserved word
+fn reserved_since(kind: Kind) -> VHDLStandard {
+    match kind {
+        Protected => VHDLStandard::VHDL2002,
+        Context | Default | Force | Release | Parameter | Vunit => VHDLStandard::VHDL2008,
+        View => VHDLStandard::VHDL2019,
+        _ => VHDLStandard::VHDL1993,
+    }
+}
+
 impl std::default::Default for Symbols {
     fn default() -> Symbols {
         let keywords_init = [
@@ -1437,6 +1463,12 @@ impl std::default::Default for Symbols {
             ("rem", Rem),
             ("vunit", Vunit),
             ("parameter", Parameter),
+            ("group", Group),
+            ("guarded", Guarded),
+            ("register", Register),
+            ("bus", Bus),
+            ("disconnect", Disconnect),
+            ("view", View),
         ];
 
         let attributes = [
@@ -1510,6 +1542,7 @@ impl std::default::Default for Symbols {
             ("simple_name", AttributeDesignator::SimpleName),
             ("instance_name", AttributeDesignator::InstanceName),
             ("path_name", AttributeDesignator::PathName),
+            ("converse", AttributeDesignator::Converse),
         ];
 
         let symtab = SymbolTable::default();
@@ -1544,6 +1577,7 @@ pub struct Tokenizer<'a> {
     pub source: &'a Source,
     reader: ContentReader<'a>,
     final_comments: Option<Vec<Comment>>,
+    standard: VHDLStandard,
 }
 
 impl<'a> Tokenizer<'a> {
@@ -1551,6 +1585,7 @@ impl<'a> Tokenizer<'a> {
         symbols: &'a Symbols,
         source: &'a Source,
         reader: ContentReader<'a>,
+        standard: VHDLStandard,
     ) -> Tokenizer<'a> {
         Tokenizer {
             symbols,
@@ -1559,15 +1594,24 @@ impl<'a> Tokenizer<'a> {
             source,
             reader,
             final_comments: None,
+            standard,
         }
     }
 
+    /// The VHDL revision of the tokenized source
+    pub fn standard(&self) -> VHDLStandard {
+        self.standard
+    }
+
     pub fn attribute(&self, sym: Symbol) -> AttributeDesignator {
-        self.symbols
-            .attributes
-            .get(&sym)
-            .cloned()
-            .unwrap_or_else(|| AttributeDesignator::Ident(sym))
+        match self.symbols.attributes.get(&sym) {
+            // 'converse is a user defined attribute before VHDL-2019
+            Some(AttributeDesignator::Converse) if self.standard < VHDLStandard::VHDL2019 => {
+                AttributeDesignator::Ident(sym)
+            }
+            Some(attr) => attr.clone(),
+            None => AttributeDesignator::Ident(sym),
+        }
     }
 
     fn parse_token(&mut self) -> Result<Option<(Kind, Value)>, TokenError> {
@@ -1597,6 +1641,7 @@ impl<'a> Tokenizer<'a> {
                         &mut self.buffer,
                         &mut self.reader,
                         self.symbols,
+                        self.standard,
                     )?
                 }
             }
@@ -1834,7 +1879,7 @@ impl<'a> Tokenizer<'a> {
             Ok(token) => Ok(token),
             Err(err) => {
                 self.state.start = self.reader.state();
-                Err(Diagnostic::error(
+                Err(Diagnostic::syntax_error(
                     &self.source.pos(err.range.start, err.range.end),
                     err.message,
                 ))
@@ -1851,7 +1896,7 @@ impl<'a> Tokenizer<'a> {
         let start_pos = self.reader.pos();
         if let Err(err) = read_until_newline(&mut self.buffer, &mut self.reader) {
             self.state.start = self.reader.state();
-            return Err(Diagnostic::error(
+            return Err(Diagnostic::syntax_error(
                 &self.source.pos(err.range.start, err.range.end),
                 err.message,
             ));
@@ -1950,6 +1995,28 @@ end entity"
         assert_eq!(kinds_tokenize("arCHitecture"), vec![Architecture]);
     }
 
+    #[test]
+    fn tokenize_keywords_of_standard() {
+        let code = Code::new("protected context force view");
+        let kinds_in = |standard| kinds(&code.with_standard(standard).tokenize());
+        assert_eq!(
+            kinds_in(VHDLStandard::VHDL1993),
+            vec![Identifier, Identifier, Identifier, Identifier]
+        );
+        assert_eq!(
+            kinds_in(VHDLStandard::VHDL2002),
+            vec![Protected, Identifier, Identifier, Identifier]
+        );
+        assert_eq!(
+            kinds_in(VHDLStandard::VHDL2008),
+            vec![Protected, Context, Force, Identifier]
+        );
+        assert_eq!(
+            kinds_in(VHDLStandard::VHDL2019),
+            vec![Protected, Context, Force, View]
+        );
+    }
+
     #[test]
     fn tokenize_identifier() {
         let code = Code::new("my_ident");
@@ -2075,11 +2142,11 @@ my_other_ident",
         assert_eq!(
             tokens,
             vec![
-                Err(Diagnostic::error(
+                Err(Diagnostic::syntax_error(
                     &code.s1("€"),
                     "Found invalid latin-1 character '€'"
                 )),
-                Err(Diagnostic::error(
+                Err(Diagnostic::syntax_error(
                     &code.s1("\u{1F4A3}"),
                     "Found invalid latin-1 character '\u{1F4A3}'"
                 ))
@@ -2094,7 +2161,7 @@ my_other_ident",
 
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Integer literals may not have negative exponent"
             ))]
@@ -2233,7 +2300,10 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(&code.pos(), "Multi line string"))]
+            vec![Err(Diagnostic::syntax_error(
+                &code.pos(),
+                "Multi line string"
+            ))]
         );
     }
 
@@ -2244,7 +2314,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Reached EOF before end quote"
             ))]
@@ -2322,7 +2392,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Invalid bit string literal"
             ))]
@@ -2332,7 +2402,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Invalid bit string literal"
             ))]
@@ -2371,7 +2441,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1("k"),
                 "Invalid integer character 'k'"
             ))]
@@ -2385,7 +2455,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1("1"),
                 "Base must be at least 2 and at most 16, got 1"
             ))]
@@ -2394,7 +2464,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1("17"),
                 "Base must be at least 2 and at most 16, got 17"
             ))]
@@ -2404,7 +2474,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s("3", 2),
                 "Illegal digit '3' for base 3"
             ))]
@@ -2413,7 +2483,7 @@ my_other_ident",
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1("f"),
                 "Illegal digit 'f' for base 15"
             ))]
@@ -2592,7 +2662,7 @@ comment
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Integer too large for 64-bit unsigned"
             ))]
@@ -2603,7 +2673,7 @@ comment
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Integer too large for 64-bit unsigned"
             ))]
@@ -2615,7 +2685,7 @@ comment
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1(&exponent_str),
                 "Exponent too large for 32-bits signed"
             ))]
@@ -2627,7 +2697,7 @@ comment
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1(&exponent_str),
                 "Exponent too large for 32-bits signed"
             ))]
@@ -2638,7 +2708,7 @@ comment
         let (tokens, _) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.pos(),
                 "Integer too large for 64-bit unsigned"
             ))]
@@ -2671,7 +2741,7 @@ comment
                     pos: code.s1("begin").pos(),
                     comments: None,
                 }),
-                Err(Diagnostic::error(&code.s1("!"), "Illegal token")),
+                Err(Diagnostic::syntax_error(&code.s1("!"), "Illegal token")),
                 Ok(Token {
                     kind: End,
                     value: Value::NoValue,
@@ -2703,7 +2773,7 @@ comment
         let (tokens, final_comments) = code.tokenize_result();
         assert_eq!(
             tokens,
-            vec![Err(Diagnostic::error(
+            vec![Err(Diagnostic::syntax_error(
                 &code.s1("/* final"),
                 "Incomplete multi-line comment"
             ))]
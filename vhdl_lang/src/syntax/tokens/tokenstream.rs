@@ -6,10 +6,11 @@
 
 use std::cell::Cell;
 
+use super::conditional_analysis::{ConditionalAnalysis, ConditionalBlocks};
 use super::tokenizer::Kind::*;
 use super::tokenizer::*;
 use crate::ast::{AttributeDesignator, Ident, RangeAttribute, TypeAttribute};
-use crate::data::{DiagnosticHandler, DiagnosticResult, WithPos};
+use crate::data::{DiagnosticHandler, DiagnosticResult, ErrorCode, VHDLStandard, WithPos};
 use crate::{Diagnostic, SrcPos};
 
 pub struct TokenStream<'a> {
@@ -20,6 +21,8 @@ pub struct TokenStream<'a> {
     // when getting it via `TokenStream::get_token_id()`
     // It is updated in the `slice_tokens` method
     token_offset: Cell<usize>,
+    // Regions excluded by conditional analysis directives
+    inactive_regions: Vec<SrcPos>,
 }
 
 impl<'a> TokenStream<'a> {
@@ -28,23 +31,38 @@ impl<'a> TokenStream<'a> {
     /// `identifier { any chars until newline }
     /// ```
     /// This needs special handling as the text that follows the identifier is arbitrary.
+    /// Conditional analysis directives are handled by `blocks`.
     fn handle_tool_directive(
         grave_accent: Token,
         tokenizer: &mut Tokenizer,
+        blocks: &mut ConditionalBlocks,
         diagnostics: &mut dyn DiagnosticHandler,
     ) {
         let start_pos = grave_accent.pos.clone();
         match tokenizer.pop() {
+            Ok(Some(tok)) if matches!(tok.kind, If | Elsif | Else | End) => {
+                blocks.handle_directive(tok, tokenizer, diagnostics);
+                return;
+            }
+            Ok(Some(tok)) if blocks.is_active() && is_report_directive(&tok) => {
+                blocks.handle_report(tok, tokenizer, diagnostics);
+                return;
+            }
+            _ if !blocks.is_active() => {
+                // Other directives within excluded regions are skipped without diagnostics
+                let _ = tokenizer.text_until_newline();
+                return;
+            }
             Ok(Some(tok)) => {
                 if tok.kind != Identifier {
-                    diagnostics.error(tok, "Expecting identifier");
+                    diagnostics.add(tok, "Expecting identifier", ErrorCode::SyntaxError);
                     let _ = tokenizer.text_until_newline(); // skip potentially invalid tokens
                     return;
                 }
             }
             Err(err) => diagnostics.push(err),
             Ok(None) => {
-                diagnostics.error(start_pos, "Expecting identifier");
+                diagnostics.add(start_pos, "Expecting identifier", ErrorCode::SyntaxError);
                 return;
             }
         }
@@ -54,29 +72,56 @@ impl<'a> TokenStream<'a> {
         }
     }
 
+    /// Tokenize and drop the tokens excluded by conditional analysis directives.
+    /// Diagnostics within excluded regions are not reported.
     pub fn new(
         mut tokenizer: Tokenizer<'a>,
+        conditions: &ConditionalAnalysis,
         diagnostics: &mut dyn DiagnosticHandler,
     ) -> TokenStream<'a> {
         let mut tokens = Vec::new();
+        let mut blocks = ConditionalBlocks::new(conditions, tokenizer.standard());
         loop {
             match tokenizer.pop() {
-                Ok(Some(token)) if token.kind == GraveAccent => {
-                    TokenStream::handle_tool_directive(token, &mut tokenizer, diagnostics)
+                Ok(Some(token)) if token.kind == GraveAccent => TokenStream::handle_tool_directive(
+                    token,
+                    &mut tokenizer,
+                    &mut blocks,
+                    diagnostics,
+                ),
+                Ok(Some(token)) if blocks.is_active() => {
+                    check_operator_standard(&token, tokenizer.standard(), diagnostics);
+                    tokens.push(token)
                 }
-                Ok(Some(token)) => tokens.push(token),
+                Ok(Some(token)) => blocks.skip(&token),
                 Ok(None) => break,
-                Err(err) => diagnostics.push(err),
+                Err(err) => {
+                    if blocks.is_active() {
+                        diagnostics.push(err)
+                    }
+                }
             }
         }
+        let inactive_regions = blocks.finish(diagnostics);
         TokenStream {
             tokenizer,
             idx: Cell::new(0),
             tokens,
             token_offset: Cell::new(0),
+            inactive_regions,
         }
     }
 
+    /// The regions excluded by conditional analysis directives
+    pub fn inactive_regions(&self) -> &[SrcPos] {
+        &self.inactive_regions
+    }
+
+    /// The VHDL revision of the design file
+    pub fn standard(&self) -> VHDLStandard {
+        self.tokenizer.standard()
+    }
+
     pub fn state(&self) -> usize {
         self.get_idx()
     }
@@ -112,7 +157,7 @@ impl<'a> TokenStream<'a> {
 
     fn eof_error(&self) -> Diagnostic {
         let end = self.tokenizer.source.contents().end();
-        Diagnostic::error(
+        Diagnostic::syntax_error(
             self.tokenizer.source.pos(end, end.next_char()),
             "Unexpected EOF",
         )
@@ -283,6 +328,37 @@ impl<'a> TokenStream<'a> {
     }
 }
 
+/// The condition operator and the matching relational operators were added in VHDL-2008
+fn check_operator_standard(
+    token: &Token,
+    standard: VHDLStandard,
+    diagnostics: &mut dyn DiagnosticHandler,
+) {
+    if standard < VHDLStandard::VHDL2008
+        && matches!(
+            token.kind,
+            QueQue | QueEQ | QueNE | QueLT | QueLTE | QueGT | QueGTE
+        )
+    {
+        diagnostics.push(Diagnostic::not_in_standard(
+            token,
+            format!("Operator '{}'", kind_str(token.kind)),
+            standard,
+        ));
+    }
+}
+
+/// The `warning and `error directives, LRM 24.2.4
+fn is_report_directive(token: &Token) -> bool {
+    match token.value {
+        Value::Identifier(ref symbol) => {
+            let name = symbol.name_utf8();
+            name.eq_ignore_ascii_case("warning") || name.eq_ignore_ascii_case("error")
+        }
+        _ => false,
+    }
+}
+
 impl<'a> TokenAccess for TokenStream<'a> {
     fn get_token(&self, id: TokenId) -> &Token {
         self.tokens[self.token_offset.get()..].get_token(id)
@@ -345,14 +421,32 @@ mod tests {
         ($code:ident, $stream:ident) => {
             let source = $code.source();
             let contents = source.contents();
-            let tokenizer = Tokenizer::new(&$code.symbols, source, ContentReader::new(&contents));
-            let $stream = TokenStream::new(tokenizer, &mut NoDiagnostics);
+            let tokenizer = Tokenizer::new(
+                &$code.symbols,
+                source,
+                ContentReader::new(&contents),
+                $code.standard(),
+            );
+            let $stream = TokenStream::new(
+                tokenizer,
+                &ConditionalAnalysis::default(),
+                &mut NoDiagnostics,
+            );
         };
         ($code:ident, $stream:ident, $diagnostics:ident) => {
             let source = $code.source();
             let contents = source.contents();
-            let tokenizer = Tokenizer::new(&$code.symbols, source, ContentReader::new(&contents));
-            let $stream = TokenStream::new(tokenizer, &mut $diagnostics);
+            let tokenizer = Tokenizer::new(
+                &$code.symbols,
+                source,
+                ContentReader::new(&contents),
+                $code.standard(),
+            );
+            let $stream = TokenStream::new(
+                tokenizer,
+                &ConditionalAnalysis::default(),
+                &mut $diagnostics,
+            );
         };
     }
 
@@ -422,7 +516,7 @@ mod tests {
         stream.skip();
         assert_eq!(
             stream.peek_expect(),
-            Err(Diagnostic::error(code.eof_pos(), "Unexpected EOF"))
+            Err(Diagnostic::syntax_error(code.eof_pos(), "Unexpected EOF"))
         );
     }
 
@@ -447,7 +541,7 @@ mod tests {
 
         assert_eq!(
             stream.peek_expect(),
-            Err(Diagnostic::error(code.eof_pos(), "Unexpected EOF"))
+            Err(Diagnostic::syntax_error(code.eof_pos(), "Unexpected EOF"))
         );
     }
 
@@ -459,7 +553,7 @@ mod tests {
         stream.skip();
         assert_eq!(
             stream.peek_expect(),
-            Err(Diagnostic::error(code.eof_pos(), "Unexpected EOF"))
+            Err(Diagnostic::syntax_error(code.eof_pos(), "Unexpected EOF"))
         );
     }
 
@@ -471,7 +565,7 @@ mod tests {
         stream.skip();
         assert_eq!(
             stream.peek_expect(),
-            Err(Diagnostic::error(code.eof_pos(), "Unexpected EOF"))
+            Err(Diagnostic::syntax_error(code.eof_pos(), "Unexpected EOF"))
         );
     }
 
@@ -503,7 +597,10 @@ mod tests {
         new_stream!(code, _stream, diagnostics);
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(code.s1("123"), "Expecting identifier")]
+            vec![Diagnostic::syntax_error(
+                code.s1("123"),
+                "Expecting identifier"
+            )]
         )
     }
 
@@ -514,7 +611,10 @@ mod tests {
         new_stream!(code, _stream, diagnostics);
         assert_eq!(
             diagnostics,
-            vec![Diagnostic::error(code.s1("`"), "Expecting identifier")]
+            vec![Diagnostic::syntax_error(
+                code.s1("`"),
+                "Expecting identifier"
+            )]
         )
     }
 
@@ -553,6 +653,25 @@ end arch;
         );
     }
 
+    #[test]
+    fn matching_operators_are_not_allowed_before_vhdl2008() {
+        let code = Code::new("a ?= b ?? c").with_standard(VHDLStandard::VHDL1993);
+        let mut diagnostics: Vec<Diagnostic> = vec![];
+        new_stream!(code, _stream, diagnostics);
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::not_in_standard(code.s1("?="), "Operator '?='", VHDLStandard::VHDL1993),
+                Diagnostic::not_in_standard(code.s1("??"), "Operator '??'", VHDLStandard::VHDL1993),
+            ]
+        );
+
+        let code = code.with_standard(VHDLStandard::VHDL2008);
+        let mut diagnostics: Vec<Diagnostic> = vec![];
+        new_stream!(code, _stream, diagnostics);
+        assert_eq!(diagnostics, vec![]);
+    }
+
     #[test]
     fn indexing_tokens_after_slicing() {
         let code = Code::new("1 2 abc; () +");
@@ -0,0 +1,181 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2023, Olof Kraigher olof.kraigher@gmail.com
+
+use super::common::{check_end_identifier_mismatch, ParseResult};
+use super::interface_declaration::parse_optional_mode;
+use super::names::{parse_identifier_list, parse_name};
+use super::subtype_indication::parse_subtype_indication;
+use super::tokens::{Kind::*, TokenStream};
+use crate::ast::*;
+use crate::data::DiagnosticHandler;
+
+/// VHDL-2019 LRM 6.5.2.2 Mode view declarations
+pub fn parse_mode_view_declaration(
+    stream: &TokenStream,
+    diagnostics: &mut dyn DiagnosticHandler,
+) -> ParseResult<ModeViewDeclaration> {
+    stream.expect_kind(View)?;
+    let ident = WithDecl::new(stream.expect_ident()?);
+    stream.expect_kind(Of)?;
+    let typ = parse_subtype_indication(stream)?;
+    stream.expect_kind(Is)?;
+
+    let mut elements = Vec::new();
+    while !stream.next_kind_is(End) {
+        elements.push(parse_mode_view_element_definition(stream)?);
+    }
+    stream.expect_kind(End)?;
+    stream.expect_kind(View)?;
+    let end_ident = stream.pop_optional_ident();
+    stream.expect_kind(SemiColon)?;
+
+    Ok(ModeViewDeclaration {
+        end_ident_pos: check_end_identifier_mismatch(&ident.tree, end_ident, diagnostics),
+        ident,
+        typ,
+        elements,
+    })
+}
+
+fn parse_mode_view_element_definition(
+    stream: &TokenStream,
+) -> ParseResult<ModeViewElementDefinition> {
+    let names = parse_identifier_list(stream)?
+        .into_iter()
+        .map(WithRef::new)
+        .collect();
+    stream.expect_kind(Colon)?;
+    let mode = if stream.skip_if_kind(View) {
+        if stream.skip_if_kind(LeftPar) {
+            let name = parse_name(stream)?;
+            stream.expect_kind(RightPar)?;
+            ElementMode::Array(name)
+        } else {
+            ElementMode::Record(parse_name(stream)?)
+        }
+    } else if let Some(mode) = parse_optional_mode(stream)? {
+        ElementMode::Simple(mode)
+    } else {
+        let token = stream.peek_expect()?;
+        return Err(token.kinds_error(&[In, Out, InOut, Buffer, Linkage, View]));
+    };
+    stream.expect_kind(SemiColon)?;
+    Ok(ModeViewElementDefinition { names, mode })
+}
+
+/// VHDL-2019 LRM 6.5.2 Interface object declarations
+pub fn parse_mode_view_indication(stream: &TokenStream) -> ParseResult<ModeViewIndication> {
+    stream.expect_kind(View)?;
+    if stream.skip_if_kind(LeftPar) {
+        let name = parse_name(stream)?;
+        stream.expect_kind(RightPar)?;
+        stream.expect_kind(Of)?;
+        Ok(ModeViewIndication {
+            kind: ModeViewIndicationKind::Array,
+            name,
+            subtype_indication: Some(parse_subtype_indication(stream)?),
+        })
+    } else {
+        let name = parse_name(stream)?;
+        let subtype_indication = if stream.skip_if_kind(Of) {
+            Some(parse_subtype_indication(stream)?)
+        } else {
+            None
+        };
+        Ok(ModeViewIndication {
+            kind: ModeViewIndicationKind::Record,
+            name,
+            subtype_indication,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{VHDLStandard, WithPos};
+    use crate::syntax::test::Code;
+
+    #[test]
+    fn mode_view_declaration() {
+        let code = Code::new(
+            "\
+view master of bus_rec is
+  addr, wdata : out;
+  rdata : in;
+  sub : view sub_view;
+  subs : view (sub_view);
+end view master;",
+        )
+        .with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_stream_no_diagnostics(parse_mode_view_declaration),
+            ModeViewDeclaration {
+                ident: code.s1("master").decl_ident(),
+                typ: code.s1("bus_rec").subtype_indication(),
+                elements: vec![
+                    ModeViewElementDefinition {
+                        names: vec![
+                            WithRef::new(code.s1("addr").ident()),
+                            WithRef::new(code.s1("wdata").ident())
+                        ],
+                        mode: ElementMode::Simple(WithPos::new(Mode::Out, code.s1("out").pos())),
+                    },
+                    ModeViewElementDefinition {
+                        names: vec![WithRef::new(code.s1("rdata").ident())],
+                        mode: ElementMode::Simple(WithPos::new(
+                            Mode::In,
+                            code.s1("in;").s1("in").pos()
+                        )),
+                    },
+                    ModeViewElementDefinition {
+                        names: vec![WithRef::new(code.s1("sub :").s1("sub").ident())],
+                        mode: ElementMode::Record(code.s1("sub_view").name()),
+                    },
+                    ModeViewElementDefinition {
+                        names: vec![WithRef::new(code.s1("subs").ident())],
+                        mode: ElementMode::Array(code.s("sub_view", 2).name()),
+                    },
+                ],
+                end_ident_pos: Some(code.s("master", 2).pos()),
+            }
+        );
+    }
+
+    #[test]
+    fn mode_view_indication() {
+        let code = Code::new("view master").with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_stream(parse_mode_view_indication),
+            ModeViewIndication {
+                kind: ModeViewIndicationKind::Record,
+                name: code.s1("master").name(),
+                subtype_indication: None,
+            }
+        );
+
+        let code =
+            Code::new("view master'converse of bus_rec").with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_stream(parse_mode_view_indication),
+            ModeViewIndication {
+                kind: ModeViewIndicationKind::Record,
+                name: code.s1("master'converse").name(),
+                subtype_indication: Some(code.s1("bus_rec").subtype_indication()),
+            }
+        );
+
+        let code = Code::new("view (master) of bus_array").with_standard(VHDLStandard::VHDL2019);
+        assert_eq!(
+            code.with_stream(parse_mode_view_indication),
+            ModeViewIndication {
+                kind: ModeViewIndicationKind::Array,
+                name: code.s1("master").name(),
+                subtype_indication: Some(code.s1("bus_array").subtype_indication()),
+            }
+        );
+    }
+}
@@ -0,0 +1,234 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this file,
+// You can obtain one at http://mozilla.org/MPL/2.0/.
+//
+// Copyright (c) 2018, Olof Kraigher olof.kraigher@gmail.com
+
+//! Analysis of a second copy of the project on a worker thread.
+//!
+//! The server answers requests from the project of the last completed analysis while the
+//! other copy is brought up to date and analyzed in the background. When that analysis
+//! completes the two copies are swapped, the previously served copy then lags behind by
+//! the changes of the analysis and is updated before it is analyzed the next time.
+//! Messages are only reported the first time a change is applied.
+
+use crossbeam_channel::{bounded, Receiver};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use vhdl_lang::{Config, Diagnostic, Message, MessageHandler, NullMessages, Project, Source};
+
+/// A change of the project that has to be applied to both copies of the project
+#[derive(Clone)]
+pub enum ProjectChange {
+    Source(Source),
+    AddFile(PathBuf),
+    RemoveFile(PathBuf),
+    Config(Config),
+}
+
+impl ProjectChange {
+    fn apply(self, project: &mut Project, messages: &mut dyn MessageHandler) {
+        match self {
+            ProjectChange::Source(source) => {
+                let is_new = project.get_source(source.file_name()).is_none();
+                project.update_source(&source);
+                if is_new {
+                    let library_names: Vec<String> = project
+                        .library_mapping_of(&source)
+                        .iter()
+                        .map(|name| name.name_utf8())
+                        .collect();
+                    messages.push(Message::warning(format!(
+                        "Opening file {} that is not part of the project, analyzing it in library {}",
+                        source.file_name().to_string_lossy(),
+                        library_names.join(", ")
+                    )));
+                }
+            }
+            ProjectChange::AddFile(file_name) => {
+                project.add_file(&file_name, messages);
+            }
+            ProjectChange::RemoveFile(file_name) => {
+                project.remove_file(&file_name);
+            }
+            ProjectChange::Config(config) => project.update_config(config, messages),
+        }
+    }
+}
+
+#[derive(Clone)]
+struct QueuedChange {
+    change: ProjectChange,
+    // The messages of a change were already reported when applied to the other copy
+    report: bool,
+}
+
+/// The copy of the project that is not served
+enum BackBuffer {
+    // Loaded by the first analysis in the background to not delay the initial analysis
+    Unloaded(Config),
+    Loaded(Box<Project>),
+}
+
+impl BackBuffer {
+    fn load(self) -> Project {
+        match self {
+            BackBuffer::Unloaded(config) => {
+                let mut project = Project::from_config(config, &mut NullMessages);
+                project.enable_unused_declaration_detection();
+                project.enable_inactive_region_reporting();
+                project
+            }
+            BackBuffer::Loaded(project) => *project,
+        }
+    }
+}
+
+/// The outcome of an analysis in the background
+pub struct AnalysisResult {
+    pub project: Project,
+    /// None when the analysis was cancelled
+    pub diagnostics: Option<Vec<Diagnostic>>,
+    /// Messages from applying the changes, reported even when the analysis was cancelled
+    pub messages: Vec<Message>,
+}
+
+struct RunningAnalysis {
+    cancelled: Arc<AtomicBool>,
+    result: Receiver<AnalysisResult>,
+    // The changes applied to the project before it was analyzed
+    changes: Vec<QueuedChange>,
+}
+
+pub struct BackgroundAnalysis {
+    // None while the project is analyzed
+    back: Option<BackBuffer>,
+    // Changes that have not been applied to the project that is not served
+    changes: Vec<QueuedChange>,
+    running: Option<RunningAnalysis>,
+}
+
+impl BackgroundAnalysis {
+    /// The served project was just loaded from `config`
+    pub fn new(config: Config) -> BackgroundAnalysis {
+        BackgroundAnalysis {
+            back: Some(BackBuffer::Unloaded(config)),
+            changes: Vec::new(),
+            running: None,
+        }
+    }
+
+    /// Record a change that is pending for both copies of the project
+    pub fn push(&mut self, change: ProjectChange) {
+        let mut report = true;
+        if let ProjectChange::Source(ref source) = change {
+            // The latest contents are read when the change is applied
+            self.changes.retain(|earlier| match earlier.change {
+                ProjectChange::Source(ref earlier_source)
+                    if earlier_source.file_name() == source.file_name() =>
+                {
+                    report &= earlier.report;
+                    false
+                }
+                _ => true,
+            });
+        }
+        self.changes.push(QueuedChange { change, report });
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Apply the recorded changes to the project that is not served and analyze it
+    /// on a worker thread. Does nothing while an analysis is running.
+    pub fn start(&mut self) {
+        if self.is_running() {
+            return;
+        }
+        let Some(back) = self.back.take() else {
+            return;
+        };
+        let changes = std::mem::take(&mut self.changes);
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (sender, result) = bounded(1);
+
+        let worker_changes = changes.clone();
+        let worker_cancelled = cancelled.clone();
+        thread::spawn(move || {
+            let mut project = back.load();
+            let mut messages = Vec::new();
+            for queued in worker_changes {
+                if queued.report {
+                    queued.change.apply(&mut project, &mut messages);
+                } else {
+                    queued.change.apply(&mut project, &mut NullMessages);
+                }
+            }
+            let diagnostics = project.analyse_cancellable(&worker_cancelled);
+            // The receiver is gone when the server has been dropped
+            let _ = sender.send(AnalysisResult {
+                project,
+                diagnostics,
+                messages,
+            });
+        });
+
+        self.running = Some(RunningAnalysis {
+            cancelled,
+            result,
+            changes,
+        });
+    }
+
+    /// Stop the running analysis before it analyzes the next design unit
+    pub fn cancel(&self) {
+        if let Some(ref running) = self.running {
+            running.cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// The channel receiving the result of the running analysis
+    pub fn result(&self) -> Option<Receiver<AnalysisResult>> {
+        self.running.as_ref().map(|running| running.result.clone())
+    }
+
+    /// Wait for the result of the running analysis
+    #[cfg(test)]
+    pub fn wait(&self) -> Option<AnalysisResult> {
+        let running = self.running.as_ref()?;
+        Some(
+            running
+                .result
+                .recv()
+                .expect("Background analysis has panicked"),
+        )
+    }
+
+    /// Take back the analyzed project after the analysis was cancelled,
+    /// the changes it has seen are not queued again.
+    pub fn cancelled(&mut self, project: Project) {
+        self.running = None;
+        self.back = Some(BackBuffer::Loaded(Box::new(project)));
+    }
+
+    /// Take the previously served project after swapping it with the analyzed project,
+    /// it has not seen the changes of the completed analysis yet.
+    pub fn completed(&mut self, previously_served: Project) {
+        if let Some(running) = self.running.take() {
+            let newer_changes = std::mem::take(&mut self.changes);
+            self.changes = running
+                .changes
+                .into_iter()
+                .map(|queued| QueuedChange {
+                    report: false,
+                    ..queued
+                })
+                .collect();
+            self.changes.extend(newer_changes);
+        }
+        self.back = Some(BackBuffer::Loaded(Box::new(previously_served)));
+    }
+}
@@ -8,6 +8,7 @@
 #[macro_use]
 extern crate log;
 
+mod background_analysis;
 mod rpc_channel;
 mod stdio_server;
 mod vhdl_server;
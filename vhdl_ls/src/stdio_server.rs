@@ -8,15 +8,22 @@
 //! It also contains the main event loop for handling incoming messages from the LSP client and
 //! dispatching them to the appropriate server methods.
 
-use lsp_server::{Connection, ExtractError, Request, RequestId};
-use lsp_types::{notification, request, InitializeParams};
+use crossbeam_channel::select;
+use lsp_server::{Connection, ExtractError, Message, Request, RequestId};
+use lsp_types::notification::Notification;
+use lsp_types::{notification, request, CancelParams, InitializeParams, NumberOrString};
 use serde_json::Value;
 
+use std::collections::VecDeque;
+use std::time::Duration;
 use std::{cell::RefCell, rc::Rc};
 
 use crate::rpc_channel::{RpcChannel, SharedRpcChannel};
-use crate::vhdl_server::VHDLServer;
 use crate::vhdl_server::VHDLServerSettings;
+use crate::vhdl_server::{InstantiatedByRequest, InstantiatesRequest, VHDLServer};
+
+/// Time without incoming messages after a change before the project is analyzed again
+const ANALYSIS_DEBOUNCE: Duration = Duration::from_millis(200);
 
 /// Set up the IO channel for `stdio` and start the VHDL language server.
 pub fn start(settings: VHDLServerSettings) {
@@ -83,31 +90,99 @@ impl ConnectionRpcChannel {
     /// Main event loop handling incoming messages from the client.
     fn main_event_loop(&self, mut server: VHDLServer) {
         info!("Language server initialized, waiting for messages ...");
-        while let Ok(message) = self.connection.receiver.recv() {
+        let mut queue = VecDeque::new();
+        while let Some(message) = self.next_message(&mut server, &mut queue) {
             trace!("Received message: {:?}", message);
             match message {
-                lsp_server::Message::Request(request) => {
-                    match self.connection.handle_shutdown(&request) {
-                        Ok(shutdown) => {
-                            if shutdown {
-                                server.shutdown_server();
-                            } else {
-                                self.handle_request(&mut server, request)
-                            }
+                Message::Request(request) => match self.connection.handle_shutdown(&request) {
+                    Ok(shutdown) => {
+                        if shutdown {
+                            server.shutdown_server();
+                        } else {
+                            self.handle_request(&mut server, request)
                         }
-                        Err(err) => panic!("{err:?}"),
                     }
-                }
-                lsp_server::Message::Notification(notification) => {
+                    Err(err) => panic!("{err:?}"),
+                },
+                Message::Notification(notification) => {
                     self.handle_notification(&mut server, notification);
                 }
-                lsp_server::Message::Response(response) => {
-                    self.handle_response(&mut server, response)
-                }
+                Message::Response(response) => self.handle_response(&mut server, response),
             };
         }
     }
 
+    /// Get the next message to handle.
+    /// Changed files are analyzed on a worker thread once no message has arrived for
+    /// [`ANALYSIS_DEBOUNCE`] such that typing is not interrupted by analysis of the whole
+    /// project. Messages are handled while the analysis runs, further changes cancel it.
+    fn next_message(
+        &self,
+        server: &mut VHDLServer,
+        queue: &mut VecDeque<Message>,
+    ) -> Option<Message> {
+        loop {
+            queue.extend(self.connection.receiver.try_iter());
+            self.cancel_requests(queue);
+            if let Some(message) = queue.pop_front() {
+                return Some(message);
+            }
+
+            if let Some(analysis_result) = server.background_analysis_result() {
+                select! {
+                    recv(self.connection.receiver) -> message => queue.push_back(message.ok()?),
+                    recv(analysis_result) -> result => server.finish_background_analysis(
+                        result.expect("Background analysis has panicked"),
+                    ),
+                }
+            } else if server.has_pending_changes() {
+                match self.connection.receiver.recv_timeout(ANALYSIS_DEBOUNCE) {
+                    Ok(message) => queue.push_back(message),
+                    Err(err) if err.is_timeout() => server.start_background_analysis(),
+                    Err(_) => return None,
+                }
+            } else {
+                queue.push_back(self.connection.receiver.recv().ok()?);
+            }
+        }
+    }
+
+    /// Respond to queued requests that were cancelled by the client
+    /// before being handled, the cancellation of handled requests is ignored.
+    fn cancel_requests(&self, queue: &mut VecDeque<Message>) {
+        let mut cancelled = Vec::new();
+        queue.retain(|message| match message {
+            Message::Notification(notification)
+                if notification.method == notification::Cancel::METHOD =>
+            {
+                if let Ok(params) =
+                    serde_json::from_value::<CancelParams>(notification.params.clone())
+                {
+                    cancelled.push(match params.id {
+                        NumberOrString::Number(id) => RequestId::from(id),
+                        NumberOrString::String(id) => RequestId::from(id),
+                    });
+                }
+                false
+            }
+            _ => true,
+        });
+
+        for id in cancelled {
+            let position = queue.iter().position(
+                |message| matches!(message, Message::Request(request) if request.id == id),
+            );
+            if let Some(position) = position {
+                queue.remove(position);
+                self.send_response(lsp_server::Response::new_err(
+                    id,
+                    lsp_server::ErrorCode::RequestCanceled as i32,
+                    "Request cancelled".to_string(),
+                ));
+            }
+        }
+    }
+
     /// Send responses (to requests sent by the client) back to the client.
     fn send_response(&self, response: lsp_server::Response) {
         trace!("Sending response: {:?}", response);
@@ -198,6 +273,126 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<request::SignatureHelpRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_signature_help(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::InlayHintRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_inlay_hint(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::DocumentHighlightRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_document_highlight(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::FoldingRangeRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_folding_range(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::SelectionRangeRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_selection_range(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CallHierarchyPrepare>(request) {
+            Ok((id, params)) => {
+                let result = server.prepare_call_hierarchy(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CallHierarchyIncomingCalls>(request) {
+            Ok((id, params)) => {
+                let result = server.call_hierarchy_incoming_calls(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CallHierarchyOutgoingCalls>(request) {
+            Ok((id, params)) => {
+                let result = server.call_hierarchy_outgoing_calls(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<InstantiatedByRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.instantiated_by(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<InstantiatesRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.instantiates(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::TypeHierarchyPrepare>(request) {
+            Ok((id, params)) => {
+                let result = server.prepare_type_hierarchy(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::TypeHierarchySupertypes>(request) {
+            Ok((id, params)) => {
+                let result = server.type_hierarchy_supertypes(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::TypeHierarchySubtypes>(request) {
+            Ok((id, params)) => {
+                let result = server.type_hierarchy_subtypes(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::DocumentDiagnosticRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.text_document_diagnostic(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::WorkspaceDiagnosticRequest>(request) {
+            Ok((id, params)) => {
+                let result = server.workspace_diagnostic(&params);
+                self.send_response(lsp_server::Response::new_ok(id, result));
+                return;
+            }
+            Err(request) => request,
+        };
         let request = match extract::<request::References>(request) {
             Ok((id, params)) => {
                 let result = server.text_document_references(&params);
@@ -222,6 +417,54 @@ impl ConnectionRpcChannel {
             }
             Err(request) => request,
         };
+        let request = match extract::<request::Formatting>(request) {
+            Ok((id, params)) => {
+                let res = server.text_document_formatting(&params);
+                self.send_response(lsp_server::Response::new_ok(id, res));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::RangeFormatting>(request) {
+            Ok((id, params)) => {
+                let res = server.text_document_range_formatting(&params);
+                self.send_response(lsp_server::Response::new_ok(id, res));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::CodeActionRequest>(request) {
+            Ok((id, params)) => {
+                let res = server.text_document_code_action(&params);
+                self.send_response(lsp_server::Response::new_ok(id, res));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::ExecuteCommand>(request) {
+            Ok((id, params)) => {
+                server.workspace_execute_command(&params);
+                self.send_response(lsp_server::Response::new_ok(id, ()));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::SemanticTokensFullRequest>(request) {
+            Ok((id, params)) => {
+                let res = server.text_document_semantic_tokens_full(&params);
+                self.send_response(lsp_server::Response::new_ok(id, res));
+                return;
+            }
+            Err(request) => request,
+        };
+        let request = match extract::<request::SemanticTokensRangeRequest>(request) {
+            Ok((id, params)) => {
+                let res = server.text_document_semantic_tokens_range(&params);
+                self.send_response(lsp_server::Response::new_ok(id, res));
+                return;
+            }
+            Err(request) => request,
+        };
 
         debug!("Unhandled request: {:?}", request);
         self.send_response(lsp_server::Response::new_err(
@@ -282,3 +525,47 @@ impl ConnectionRpcChannel {
         // outgoing requests do not require confirmation by the client.
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_requests_are_not_handled() {
+        let (server_connection, client_connection) = Connection::memory();
+        let rpc = ConnectionRpcChannel::new(server_connection);
+
+        let request = |id: i32| {
+            Message::Request(Request::new(
+                RequestId::from(id),
+                "textDocument/hover".to_owned(),
+                Value::Null,
+            ))
+        };
+        let cancel = |id: i32| {
+            Message::Notification(lsp_server::Notification::new(
+                notification::Cancel::METHOD.to_owned(),
+                CancelParams {
+                    id: NumberOrString::Number(id),
+                },
+            ))
+        };
+        // Request 3 was handled already
+        let mut queue = VecDeque::from(vec![request(1), cancel(1), request(2), cancel(3)]);
+        rpc.cancel_requests(&mut queue);
+
+        assert_eq!(queue.len(), 1);
+        assert!(matches!(&queue[0], Message::Request(request) if request.id == RequestId::from(2)));
+        match client_connection.receiver.try_recv() {
+            Ok(Message::Response(response)) => {
+                assert_eq!(response.id, RequestId::from(1));
+                assert_eq!(
+                    response.error.map(|error| error.code),
+                    Some(lsp_server::ErrorCode::RequestCanceled as i32)
+                );
+            }
+            message => panic!("Expected a response, got {message:?}"),
+        }
+        assert!(client_connection.receiver.try_recv().is_err());
+    }
+}
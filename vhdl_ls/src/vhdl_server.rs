@@ -6,19 +6,53 @@
 
 use lsp_types::*;
 
-use fnv::FnvHashMap;
+use fnv::{FnvHashMap, FnvHashSet};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use vhdl_lang::ast::{Designator, ObjectClass};
+use vhdl_lang::ast::{Designator, Mode, ObjectClass};
 
+use crate::background_analysis::{AnalysisResult, BackgroundAnalysis, ProjectChange};
 use crate::rpc_channel::SharedRpcChannel;
+use crossbeam_channel::Receiver;
 use std::io;
 use std::path::{Path, PathBuf};
 use vhdl_lang::{
-    kind_str, AnyEntKind, Concurrent, Config, Diagnostic, EntHierarchy, EntRef, EntityId, Message,
-    MessageHandler, Object, Overloaded, Project, Severity, Source, SrcPos, Type,
+    kind_str, AnyEntKind, CallHierarchyCall, Concurrent, Config, Design, Diagnostic, EntHierarchy,
+    EntRef, EntityId, ErrorCode, FormatOptions, HighlightKind, HoverInfo, InstantiationKind,
+    Message, MessageHandler, Object, ObjectInterface, Overloaded, Project, Severity, Source,
+    SrcPos, Type,
 };
 
+/// Command that inserts an instantiation of an entity
+const INSTANTIATE_COMMAND: &str = "vhdl_ls.instantiate";
+
+/// Token of the progress reported while analyzing the project after initialization
+const ANALYSIS_PROGRESS_TOKEN: &str = "vhdl_ls/analysis";
+
+/// Request for the entities whose architectures instantiate the entity at a position
+pub enum InstantiatedByRequest {}
+
+impl request::Request for InstantiatedByRequest {
+    type Params = TextDocumentPositionParams;
+    type Result = Option<Vec<CallHierarchyIncomingCall>>;
+    const METHOD: &'static str = "vhdl_ls/instantiatedBy";
+}
+
+/// Request for the entities instantiated by the architectures of the entity at a position
+pub enum InstantiatesRequest {}
+
+impl request::Request for InstantiatesRequest {
+    type Params = TextDocumentPositionParams;
+    type Result = Option<Vec<CallHierarchyOutgoingCall>>;
+    const METHOD: &'static str = "vhdl_ls/instantiates";
+}
+
+/// The diagnostics of a file, the result id changes whenever the diagnostics change
+struct FileDiagnostics {
+    result_id: String,
+    diagnostics: Vec<lsp_types::Diagnostic>,
+}
+
 #[derive(Default, Clone)]
 pub struct VHDLServerSettings {
     pub no_lint: bool,
@@ -31,7 +65,18 @@ pub struct VHDLServer {
     // To have well defined unit tests that are not affected by environment
     use_external_config: bool,
     project: Project,
-    files_with_notifications: FnvHashMap<Url, ()>,
+    // The diagnostics of each file from the last analysis,
+    // files which had diagnostics before keep an empty entry
+    diagnostics: FnvHashMap<Url, FileDiagnostics>,
+    next_result_id: u64,
+    // Changes since the last analysis
+    pending_changes: Vec<ProjectChange>,
+    // Copy of the project analyzed in the background while requests are served from `project`
+    background: BackgroundAnalysis,
+    // Pending changes being analyzed in the background
+    analyzed_changes: Vec<ProjectChange>,
+    // Opened files with CRLF line endings, the contents of a source only keep the LF
+    crlf_files: FnvHashSet<PathBuf>,
     init_params: Option<InitializeParams>,
     config_file: Option<PathBuf>,
 }
@@ -43,7 +88,12 @@ impl VHDLServer {
             settings,
             use_external_config: true,
             project: Project::new(),
-            files_with_notifications: FnvHashMap::default(),
+            diagnostics: FnvHashMap::default(),
+            next_result_id: 0,
+            pending_changes: Vec::new(),
+            background: BackgroundAnalysis::new(Config::default()),
+            analyzed_changes: Vec::new(),
+            crlf_files: FnvHashSet::default(),
             init_params: None,
             config_file: None,
         }
@@ -56,7 +106,12 @@ impl VHDLServer {
             settings: Default::default(),
             use_external_config,
             project: Project::new(),
-            files_with_notifications: FnvHashMap::default(),
+            diagnostics: FnvHashMap::default(),
+            next_result_id: 0,
+            pending_changes: Vec::new(),
+            background: BackgroundAnalysis::new(Config::default()),
+            analyzed_changes: Vec::new(),
+            crlf_files: FnvHashSet::default(),
             init_params: None,
             config_file: None,
         }
@@ -110,8 +165,10 @@ impl VHDLServer {
     pub fn initialize_request(&mut self, init_params: InitializeParams) -> InitializeResult {
         self.config_file = self.root_uri_config_file(&init_params);
         let config = self.load_config();
+        self.background = BackgroundAnalysis::new(config.clone());
         self.project = Project::from_config(config, &mut self.message_filter());
         self.project.enable_unused_declaration_detection();
+        self.project.enable_inactive_region_reporting();
         self.init_params = Some(init_params);
         let trigger_chars: Vec<String> = r".".chars().map(|ch| ch.to_string()).collect();
 
@@ -138,6 +195,40 @@ impl VHDLServer {
                 }),
                 ..Default::default()
             }),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["(".to_owned(), ",".to_owned()]),
+                retrigger_characters: Some(vec![" ".to_owned()]),
+                work_done_progress_options: Default::default(),
+            }),
+            inlay_hint_provider: Some(OneOf::Left(true)),
+            document_highlight_provider: Some(OneOf::Left(true)),
+            folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+            diagnostic_provider: Some(DiagnosticServerCapabilities::Options(DiagnosticOptions {
+                identifier: Some("vhdl ls".to_owned()),
+                inter_file_dependencies: true,
+                workspace_diagnostics: true,
+                work_done_progress_options: Default::default(),
+            })),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            document_range_formatting_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: SemanticTokensLegend {
+                        token_types: SEMANTIC_TOKEN_TYPES.to_vec(),
+                        token_modifiers: SEMANTIC_TOKEN_MODIFIERS.to_vec(),
+                    },
+                    range: Some(true),
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    work_done_progress_options: Default::default(),
+                }),
+            ),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![INSTANTIATE_COMMAND.to_owned()],
+                work_done_progress_options: Default::default(),
+            }),
             ..Default::default()
         };
 
@@ -185,38 +276,189 @@ impl VHDLServer {
     /// Register capabilities on the client side:
     /// - watch workspace config file for changes
     fn register_capabilities(&mut self) {
+        let mut registrations = Vec::new();
         if self.client_supports_did_change_watched_files() {
             let register_options = DidChangeWatchedFilesRegistrationOptions {
-                watchers: vec![FileSystemWatcher {
-                    glob_pattern: GlobPattern::String("**/vhdl_ls.toml".to_owned()),
-                    kind: None,
-                }],
-            };
-            let params = RegistrationParams {
-                registrations: vec![Registration {
-                    id: "workspace/didChangeWatchedFiles".to_owned(),
-                    method: "workspace/didChangeWatchedFiles".to_owned(),
-                    register_options: serde_json::to_value(register_options).ok(),
-                }],
+                watchers: vec![
+                    FileSystemWatcher {
+                        glob_pattern: GlobPattern::String("**/vhdl_ls.toml".to_owned()),
+                        kind: None,
+                    },
+                    // New and deleted files matching the patterns of a library
+                    FileSystemWatcher {
+                        glob_pattern: GlobPattern::String("**/*.{vhd,vhdl}".to_owned()),
+                        kind: Some(WatchKind::Create | WatchKind::Delete),
+                    },
+                ],
             };
-            self.rpc.send_request("client/registerCapability", params);
+            registrations.push(Registration {
+                id: "workspace/didChangeWatchedFiles".to_owned(),
+                method: "workspace/didChangeWatchedFiles".to_owned(),
+                register_options: serde_json::to_value(register_options).ok(),
+            });
+        }
+        // The server capabilities of lsp-types do not have a type hierarchy provider yet
+        if self.client_supports_type_hierarchy() {
+            registrations.push(Registration {
+                id: "textDocument/prepareTypeHierarchy".to_owned(),
+                method: "textDocument/prepareTypeHierarchy".to_owned(),
+                register_options: serde_json::to_value(TypeHierarchyRegistrationOptions::default())
+                    .ok(),
+            });
+        }
+        if !registrations.is_empty() {
+            self.rpc.send_request(
+                "client/registerCapability",
+                RegistrationParams { registrations },
+            );
         }
     }
 
     pub fn initialized_notification(&mut self) {
         self.register_capabilities();
+        let progress_token = self.create_progress_token(ANALYSIS_PROGRESS_TOKEN);
+        if let Some(ref token) = progress_token {
+            self.send_progress(
+                token,
+                WorkDoneProgress::Begin(WorkDoneProgressBegin {
+                    title: "Analyzing VHDL project".to_owned(),
+                    cancellable: Some(false),
+                    message: Some(format!("{} files", self.project.files().count())),
+                    percentage: None,
+                }),
+            );
+        }
         self.publish_diagnostics();
+        if let Some(ref token) = progress_token {
+            self.send_progress(
+                token,
+                WorkDoneProgress::End(WorkDoneProgressEnd { message: None }),
+            );
+        }
+    }
+
+    /// Ask the client to create a token for reporting work done progress
+    fn create_progress_token(&self, token: &str) -> Option<ProgressToken> {
+        if !self.client_supports_work_done_progress() {
+            return None;
+        }
+        let token = ProgressToken::String(token.to_owned());
+        self.rpc.send_request(
+            "window/workDoneProgress/create",
+            WorkDoneProgressCreateParams {
+                token: token.clone(),
+            },
+        );
+        Some(token)
+    }
+
+    fn send_progress(&self, token: &ProgressToken, progress: WorkDoneProgress) {
+        self.rpc.send_notification(
+            "$/progress",
+            ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(progress),
+            },
+        );
+    }
+
+    /// Whether there are changes that have not been analyzed yet
+    pub fn has_pending_changes(&self) -> bool {
+        !self.pending_changes.is_empty()
+    }
+
+    /// Analyze all changes in the background and wait for the diagnostics to be published
+    #[cfg(test)]
+    pub fn analyse_pending_changes(&mut self) {
+        loop {
+            if let Some(result) = self.background.wait() {
+                self.finish_background_analysis(result);
+            }
+            if !self.has_pending_changes() {
+                return;
+            }
+            self.start_background_analysis();
+        }
+    }
+
+    /// Queue a change for the next analysis in the background, cancelling a running analysis
+    /// since its result would be outdated
+    fn queue_change(&mut self, change: ProjectChange) {
+        self.background.cancel();
+        self.background.push(change.clone());
+        if let ProjectChange::Source(ref source) = change {
+            self.pending_changes.retain(|pending| match pending {
+                ProjectChange::Source(pending) => pending.file_name() != source.file_name(),
+                _ => true,
+            });
+        }
+        self.pending_changes.push(change);
+    }
+
+    /// The source of a file including changes that have not been analyzed yet
+    fn get_source(&self, file_name: &Path) -> Option<Source> {
+        self.pending_changes
+            .iter()
+            .rev()
+            .chain(self.analyzed_changes.iter().rev())
+            .find_map(|change| match change {
+                ProjectChange::Source(source) if source.file_name() == file_name => {
+                    Some(source.clone())
+                }
+                _ => None,
+            })
+            .or_else(|| self.project.get_source(file_name))
+    }
+
+    /// Analyze the files changed since the last analysis on a worker thread.
+    /// Until [`Self::finish_background_analysis`] requests are served from the last analysis.
+    pub fn start_background_analysis(&mut self) {
+        if self.background.is_running() || !self.has_pending_changes() {
+            return;
+        }
+        self.analyzed_changes = std::mem::take(&mut self.pending_changes);
+        self.background.start();
+    }
+
+    /// The channel receiving the result of the analysis running in the background
+    pub fn background_analysis_result(&self) -> Option<Receiver<AnalysisResult>> {
+        self.background.result()
+    }
+
+    /// Serve requests from the project analyzed in the background and publish its diagnostics.
+    /// When the analysis was cancelled its changes are pending again.
+    pub fn finish_background_analysis(&mut self, result: AnalysisResult) {
+        for message in result.messages {
+            self.message(message);
+        }
+        match result.diagnostics {
+            Some(diagnostics) => {
+                let previously_served = std::mem::replace(&mut self.project, result.project);
+                self.background.completed(previously_served);
+                self.analyzed_changes.clear();
+                self.publish_analysis_diagnostics(diagnostics);
+            }
+            None => {
+                self.background.cancelled(result.project);
+                let newer_changes = std::mem::take(&mut self.pending_changes);
+                self.pending_changes = std::mem::take(&mut self.analyzed_changes);
+                self.pending_changes.extend(newer_changes);
+            }
+        }
     }
 
     pub fn text_document_did_change_notification(&mut self, params: &DidChangeTextDocumentParams) {
         let file_name = uri_to_file_name(&params.text_document.uri);
-        if let Some(source) = self.project.get_source(&file_name) {
+        if let Some(source) = self.get_source(&file_name) {
             for content_change in params.content_changes.iter() {
+                if content_change.range.is_none() {
+                    self.update_line_endings(&file_name, &content_change.text);
+                }
                 let range = content_change.range.map(from_lsp_range);
                 source.change(range.as_ref(), &content_change.text);
             }
-            self.project.update_source(&source);
-            self.publish_diagnostics();
+            // The analysis is deferred until no more changes arrive
+            self.queue_change(ProjectChange::Source(source));
         } else {
             self.message(Message::error(format!(
                 "Changing file {} that is not part of the project",
@@ -225,22 +467,35 @@ impl VHDLServer {
         }
     }
 
+    fn update_line_endings(&mut self, file_name: &Path, text: &str) {
+        if text.contains("\r\n") {
+            self.crlf_files.insert(file_name.to_owned());
+        } else {
+            self.crlf_files.remove(file_name);
+        }
+    }
+
+    /// Formatted text only contains LF, use the line endings of the document instead
+    fn with_line_endings(&self, file_name: &Path, text: String) -> String {
+        if self.crlf_files.contains(file_name) {
+            text.replace('\n', "\r\n")
+        } else {
+            text
+        }
+    }
+
     pub fn text_document_did_open_notification(&mut self, params: &DidOpenTextDocumentParams) {
         let TextDocumentItem { uri, text, .. } = &params.text_document;
         let file_name = uri_to_file_name(uri);
-        if let Some(source) = self.project.get_source(&file_name) {
+        self.update_line_endings(&file_name, text);
+        // Files that are not part of the project are reported when they are analyzed
+        let source = if let Some(source) = self.get_source(&file_name) {
             source.change(None, text);
-            self.project.update_source(&source);
-            self.publish_diagnostics();
+            source
         } else {
-            self.message(Message::warning(format!(
-                "Opening file {} that is not part of the project",
-                file_name.to_string_lossy()
-            )));
-            self.project
-                .update_source(&Source::inline(&file_name, text));
-            self.publish_diagnostics();
-        }
+            Source::inline(&file_name, text)
+        };
+        self.queue_change(ProjectChange::Source(source));
     }
 
     pub fn workspace_did_change_watched_files(&mut self, params: &DidChangeWatchedFilesParams) {
@@ -254,10 +509,18 @@ impl VHDLServer {
                     "Configuration file has changed, reloading project...",
                 ));
                 let config = self.load_config();
+                self.queue_change(ProjectChange::Config(config));
+                // Reloading the configuration also finds new and deleted files
+                return;
+            }
+        }
 
-                self.project
-                    .update_config(config, &mut self.message_filter());
-                self.publish_diagnostics();
+        for change in params.changes.iter() {
+            let file_name = uri_to_file_name(&change.uri);
+            if change.typ == FileChangeType::CREATED {
+                self.queue_change(ProjectChange::AddFile(file_name));
+            } else if change.typ == FileChangeType::DELETED {
+                self.queue_change(ProjectChange::RemoveFile(file_name));
             }
         }
     }
@@ -376,6 +639,59 @@ impl VHDLServer {
         try_fun().unwrap_or(false)
     }
 
+    fn client_supports_type_hierarchy(&self) -> bool {
+        let try_fun = || {
+            self.init_params
+                .as_ref()?
+                .capabilities
+                .text_document
+                .as_ref()?
+                .type_hierarchy
+                .as_ref()?
+                .dynamic_registration
+        };
+        try_fun().unwrap_or(false)
+    }
+
+    fn client_supports_work_done_progress(&self) -> bool {
+        let try_fun = || {
+            self.init_params
+                .as_ref()?
+                .capabilities
+                .window
+                .as_ref()?
+                .work_done_progress
+        };
+        try_fun().unwrap_or(false)
+    }
+
+    fn client_supports_pull_diagnostics(&self) -> bool {
+        let try_fun = || {
+            self.init_params
+                .as_ref()?
+                .capabilities
+                .text_document
+                .as_ref()?
+                .diagnostic
+                .as_ref()
+        };
+        try_fun().is_some()
+    }
+
+    fn client_supports_diagnostic_refresh(&self) -> bool {
+        let try_fun = || {
+            self.init_params
+                .as_ref()?
+                .capabilities
+                .workspace
+                .as_ref()?
+                .diagnostic
+                .as_ref()?
+                .refresh_support
+        };
+        try_fun().unwrap_or(false)
+    }
+
     fn client_supports_snippets(&self) -> bool {
         let try_fun = || {
             self.init_params
@@ -408,7 +724,10 @@ impl VHDLServer {
 
     fn publish_diagnostics(&mut self) {
         let diagnostics = self.project.analyse();
+        self.publish_analysis_diagnostics(diagnostics);
+    }
 
+    fn publish_analysis_diagnostics(&mut self, diagnostics: Vec<Diagnostic>) {
         if self.settings.no_lint {
             return;
         }
@@ -422,38 +741,141 @@ impl VHDLServer {
             }
         };
 
-        let mut files_with_notifications = std::mem::take(&mut self.files_with_notifications);
-        for (file_uri, diagnostics) in diagnostics_by_uri(diagnostics).into_iter() {
-            let mut lsp_diagnostics = Vec::new();
-            for diagnostic in diagnostics {
-                lsp_diagnostics.push(to_lsp_diagnostic(diagnostic));
-            }
+        let file_diagnostics: FnvHashMap<Url, Vec<lsp_types::Diagnostic>> =
+            diagnostics_by_uri(diagnostics)
+                .into_iter()
+                .map(|(uri, diagnostics)| {
+                    (
+                        uri,
+                        diagnostics.into_iter().map(to_lsp_diagnostic).collect(),
+                    )
+                })
+                .collect();
+        // Files which no longer have any diagnostics need to be cleared
+        let cleared: Vec<Url> = self
+            .diagnostics
+            .keys()
+            .filter(|uri| !file_diagnostics.contains_key(uri))
+            .cloned()
+            .collect();
+
+        let mut has_changed = false;
+        for (uri, diagnostics) in file_diagnostics {
+            has_changed |= self.update_file_diagnostics(uri, diagnostics);
+        }
+        for uri in cleared {
+            has_changed |= self.update_file_diagnostics(uri, Vec::new());
+        }
+
+        if has_changed
+            && self.client_supports_pull_diagnostics()
+            && self.client_supports_diagnostic_refresh()
+        {
+            self.rpc.send_request("workspace/diagnostic/refresh", ());
+        }
+    }
+
+    /// Update the diagnostics of a file and publish them unless the client pulls them.
+    /// Returns true if the diagnostics have changed.
+    fn update_file_diagnostics(
+        &mut self,
+        uri: Url,
+        diagnostics: Vec<lsp_types::Diagnostic>,
+    ) -> bool {
+        if self
+            .diagnostics
+            .get(&uri)
+            .is_some_and(|file| file.diagnostics == diagnostics)
+        {
+            return false;
+        }
 
+        if !self.client_supports_pull_diagnostics() {
             let publish_diagnostics = PublishDiagnosticsParams {
-                uri: file_uri.clone(),
-                diagnostics: lsp_diagnostics,
+                uri: uri.clone(),
+                diagnostics: diagnostics.clone(),
                 version: None,
             };
-
             self.rpc
                 .send_notification("textDocument/publishDiagnostics", publish_diagnostics);
-
-            self.files_with_notifications.insert(file_uri.clone(), ());
         }
 
-        for (file_uri, _) in files_with_notifications.drain() {
-            // File has no longer any diagnosics, publish empty notification to clear them
-            if !self.files_with_notifications.contains_key(&file_uri) {
-                let publish_diagnostics = PublishDiagnosticsParams {
-                    uri: file_uri.clone(),
-                    diagnostics: vec![],
-                    version: None,
-                };
+        self.next_result_id += 1;
+        self.diagnostics.insert(
+            uri,
+            FileDiagnostics {
+                result_id: self.next_result_id.to_string(),
+                diagnostics,
+            },
+        );
+        true
+    }
 
-                self.rpc
-                    .send_notification("textDocument/publishDiagnostics", publish_diagnostics);
+    pub fn text_document_diagnostic(
+        &mut self,
+        params: &DocumentDiagnosticParams,
+    ) -> DocumentDiagnosticReportResult {
+        // Pending changes are reported once their analysis has finished
+        // since the client is then asked to pull the diagnostics again
+        let report = match self.diagnostics.get(&params.text_document.uri) {
+            Some(file) if params.previous_result_id.as_ref() == Some(&file.result_id) => {
+                DocumentDiagnosticReport::Unchanged(RelatedUnchangedDocumentDiagnosticReport {
+                    related_documents: None,
+                    unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                        result_id: file.result_id.clone(),
+                    },
+                })
             }
-        }
+            file => DocumentDiagnosticReport::Full(RelatedFullDocumentDiagnosticReport {
+                related_documents: None,
+                full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                    result_id: file.map(|file| file.result_id.clone()),
+                    items: file.map_or_else(Vec::new, |file| file.diagnostics.clone()),
+                },
+            }),
+        };
+        DocumentDiagnosticReportResult::Report(report)
+    }
+
+    pub fn workspace_diagnostic(
+        &mut self,
+        params: &WorkspaceDiagnosticParams,
+    ) -> WorkspaceDiagnosticReportResult {
+        let previous_result_ids: FnvHashMap<&Url, &String> = params
+            .previous_result_ids
+            .iter()
+            .map(|previous| (&previous.uri, &previous.value))
+            .collect();
+
+        let mut files: Vec<_> = self.diagnostics.iter().collect();
+        files.sort_by_key(|(uri, _)| uri.as_str());
+        let items = files
+            .into_iter()
+            .map(|(uri, file)| {
+                if previous_result_ids.get(uri) == Some(&&file.result_id) {
+                    WorkspaceDocumentDiagnosticReport::Unchanged(
+                        WorkspaceUnchangedDocumentDiagnosticReport {
+                            uri: uri.clone(),
+                            version: None,
+                            unchanged_document_diagnostic_report:
+                                UnchangedDocumentDiagnosticReport {
+                                    result_id: file.result_id.clone(),
+                                },
+                        },
+                    )
+                } else {
+                    WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                        uri: uri.clone(),
+                        version: None,
+                        full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                            result_id: Some(file.result_id.clone()),
+                            items: file.diagnostics.clone(),
+                        },
+                    })
+                }
+            })
+            .collect();
+        WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items })
     }
 
     pub fn text_document_declaration(
@@ -671,17 +1093,180 @@ impl VHDLServer {
             .project
             .find_declaration(&source, from_lsp_pos(params.position))?;
 
-        let value = self.project.format_declaration(ent)?;
+        let info = self.project.hover_info(ent);
+        let value = hover_markdown(&info)?;
 
         Some(Hover {
             contents: HoverContents::Markup(MarkupContent {
                 kind: MarkupKind::Markdown,
-                value: format!("```vhdl\n{value}\n```"),
+                value,
             }),
             range: None,
         })
     }
 
+    pub fn text_document_signature_help(
+        &mut self,
+        params: &SignatureHelpParams,
+    ) -> Option<SignatureHelp> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let help = self
+            .project
+            .signature_help(&source, from_lsp_pos(position.position))?;
+
+        let signatures = help
+            .signatures
+            .into_iter()
+            .map(|signature| {
+                // Offsets are in UTF-16 code units
+                let offset = |idx: usize| signature.label[..idx].encode_utf16().count() as u32;
+                SignatureInformation {
+                    parameters: Some(
+                        signature
+                            .parameters
+                            .iter()
+                            .map(|range| ParameterInformation {
+                                label: ParameterLabel::LabelOffsets([
+                                    offset(range.start),
+                                    offset(range.end),
+                                ]),
+                                documentation: None,
+                            })
+                            .collect(),
+                    ),
+                    active_parameter: signature.active_parameter.map(|idx| idx as u32),
+                    documentation: None,
+                    label: signature.label,
+                }
+            })
+            .collect();
+
+        Some(SignatureHelp {
+            signatures,
+            active_signature: Some(help.active_signature as u32),
+            active_parameter: None,
+        })
+    }
+
+    pub fn text_document_inlay_hint(&mut self, params: &InlayHintParams) -> Option<Vec<InlayHint>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let hints = self
+            .project
+            .inlay_hints(&source, Some(from_lsp_range(params.range)))
+            .into_iter()
+            .map(|hint| {
+                let (kind, padding_left, padding_right) = match hint.kind {
+                    vhdl_lang::InlayHintKind::Parameter => {
+                        (Some(InlayHintKind::PARAMETER), false, true)
+                    }
+                    vhdl_lang::InlayHintKind::Type => (Some(InlayHintKind::TYPE), false, false),
+                    // There is no kind for labels in the protocol
+                    vhdl_lang::InlayHintKind::EndLabel => (None, true, false),
+                };
+                InlayHint {
+                    position: to_lsp_pos(hint.pos),
+                    label: InlayHintLabel::String(hint.label),
+                    kind,
+                    text_edits: None,
+                    tooltip: None,
+                    padding_left: Some(padding_left),
+                    padding_right: Some(padding_right),
+                    data: None,
+                }
+            })
+            .collect();
+        Some(hints)
+    }
+
+    pub fn text_document_semantic_tokens_full(
+        &mut self,
+        params: &SemanticTokensParams,
+    ) -> Option<SemanticTokensResult> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        Some(SemanticTokensResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: self.semantic_tokens(&source, None),
+        }))
+    }
+
+    pub fn text_document_semantic_tokens_range(
+        &mut self,
+        params: &SemanticTokensRangeParams,
+    ) -> Option<SemanticTokensRangeResult> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        Some(SemanticTokensRangeResult::Tokens(SemanticTokens {
+            result_id: None,
+            data: self.semantic_tokens(&source, Some(from_lsp_range(params.range))),
+        }))
+    }
+
+    /// Semantic tokens of all declarations and references of named entities within a range
+    fn semantic_tokens(
+        &self,
+        source: &Source,
+        range: Option<vhdl_lang::Range>,
+    ) -> Vec<SemanticToken> {
+        let mut data = Vec::new();
+        let mut prev = lsp_types::Position::new(0, 0);
+        for (pos, ent, is_declaration) in self.project.find_all_in_source(source) {
+            let (start, end) = (pos.start(), pos.end());
+            if start.line != end.line || start.character >= end.character {
+                continue;
+            }
+            if range.is_some_and(|range| end <= range.start || range.end <= start) {
+                continue;
+            }
+            // Tokens may not overlap
+            if start.line == prev.line && start.character < prev.character {
+                continue;
+            }
+            // Operators are left to the syntax highlighting of the client
+            if matches!(ent.designator(), Designator::OperatorSymbol(_)) {
+                continue;
+            }
+            let Some((token_type, mut modifiers)) = semantic_token(ent) else {
+                continue;
+            };
+            if is_declaration {
+                modifiers.push(SemanticTokenModifier::DECLARATION);
+            }
+
+            let delta_line = start.line - prev.line;
+            let delta_start = if delta_line == 0 {
+                start.character - prev.character
+            } else {
+                start.character
+            };
+            data.push(SemanticToken {
+                delta_line,
+                delta_start,
+                length: end.character - start.character,
+                token_type: SEMANTIC_TOKEN_TYPES
+                    .iter()
+                    .position(|typ| *typ == token_type)
+                    .unwrap() as u32,
+                token_modifiers_bitset: modifiers.iter().fold(0, |bitset, modifier| {
+                    let idx = SEMANTIC_TOKEN_MODIFIERS
+                        .iter()
+                        .position(|other| other == modifier)
+                        .unwrap();
+                    bitset | (1 << idx)
+                }),
+            });
+            prev = to_lsp_pos(start);
+        }
+        data
+    }
+
     pub fn text_document_references(&mut self, params: &ReferenceParams) -> Vec<Location> {
         let ent = self
             .project
@@ -706,19 +1291,486 @@ impl VHDLServer {
         }
     }
 
-    fn message_filter(&self) -> MessageFilter {
-        MessageFilter {
-            silent: self.settings.silent,
-            rpc: self.rpc.clone(),
-        }
+    pub fn text_document_document_highlight(
+        &mut self,
+        params: &DocumentHighlightParams,
+    ) -> Option<Vec<DocumentHighlight>> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let ent = self
+            .project
+            .find_declaration(&source, from_lsp_pos(position.position))?;
+        let highlights = self
+            .project
+            .find_highlights(&source, ent)
+            .into_iter()
+            .map(|highlight| DocumentHighlight {
+                range: to_lsp_range(highlight.pos.range()),
+                kind: Some(match highlight.kind {
+                    HighlightKind::Declaration => DocumentHighlightKind::TEXT,
+                    HighlightKind::Read => DocumentHighlightKind::READ,
+                    HighlightKind::Write => DocumentHighlightKind::WRITE,
+                }),
+            })
+            .collect();
+        Some(highlights)
     }
 
-    fn message(&self, msg: Message) {
-        self.message_filter().push(msg);
+    pub fn text_document_folding_range(
+        &mut self,
+        params: &FoldingRangeParams,
+    ) -> Option<Vec<FoldingRange>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let ranges = self
+            .project
+            .folding_ranges(&source)
+            .into_iter()
+            .map(|range| FoldingRange {
+                start_line: range.start_line,
+                start_character: None,
+                end_line: range.end_line,
+                end_character: None,
+                kind: match range.kind {
+                    vhdl_lang::FoldingRangeKind::Code => None,
+                    vhdl_lang::FoldingRangeKind::Comment => Some(FoldingRangeKind::Comment),
+                    vhdl_lang::FoldingRangeKind::Imports => Some(FoldingRangeKind::Imports),
+                },
+                collapsed_text: None,
+            })
+            .collect();
+        Some(ranges)
     }
-}
 
-fn entity_to_completion_item(ent: EntRef) -> CompletionItem {
+    pub fn text_document_selection_range(
+        &mut self,
+        params: &SelectionRangeParams,
+    ) -> Option<Vec<SelectionRange>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let ranges = params
+            .positions
+            .iter()
+            .map(|position| {
+                let cursor = from_lsp_pos(*position);
+                // Nest the ranges starting from the outermost one
+                let mut selection: Option<SelectionRange> = None;
+                for pos in self.project.selection_ranges(&source, cursor).iter().rev() {
+                    selection = Some(SelectionRange {
+                        range: to_lsp_range(pos.range()),
+                        parent: selection.map(Box::new),
+                    });
+                }
+                // Every position requires a range
+                selection.unwrap_or(SelectionRange {
+                    range: lsp_types::Range::new(*position, *position),
+                    parent: None,
+                })
+            })
+            .collect();
+        Some(ranges)
+    }
+
+    pub fn prepare_call_hierarchy(
+        &mut self,
+        params: &CallHierarchyPrepareParams,
+    ) -> Option<Vec<CallHierarchyItem>> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let ent = self
+            .project
+            .find_declaration(&source, from_lsp_pos(position.position))?;
+        if !is_subprogram(ent) {
+            return None;
+        }
+        Some(vec![call_hierarchy_item(ent.declaration())?])
+    }
+
+    /// The named entity of a call hierarchy item returned by [`Self::prepare_call_hierarchy`]
+    /// or as the caller or callee of a call
+    fn call_hierarchy_ent(&self, item: &CallHierarchyItem) -> Option<EntRef<'_>> {
+        let source = self.project.get_source(&uri_to_file_name(&item.uri))?;
+        self.project
+            .find_declaration(&source, from_lsp_pos(item.selection_range.start))
+    }
+
+    pub fn call_hierarchy_incoming_calls(
+        &mut self,
+        params: &CallHierarchyIncomingCallsParams,
+    ) -> Option<Vec<CallHierarchyIncomingCall>> {
+        let ent = self.call_hierarchy_ent(&params.item)?;
+        Some(to_incoming_calls(self.project.incoming_calls(ent)))
+    }
+
+    pub fn call_hierarchy_outgoing_calls(
+        &mut self,
+        params: &CallHierarchyOutgoingCallsParams,
+    ) -> Option<Vec<CallHierarchyOutgoingCall>> {
+        let ent = self.call_hierarchy_ent(&params.item)?;
+        Some(to_outgoing_calls(self.project.outgoing_calls(ent)))
+    }
+
+    /// The entity declared or implemented by an architecture at a position
+    fn entity_at(&self, params: &TextDocumentPositionParams) -> Option<EntRef<'_>> {
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&params.text_document.uri))?;
+        let ent = self
+            .project
+            .find_declaration(&source, from_lsp_pos(params.position))?;
+        match ent.kind() {
+            AnyEntKind::Design(Design::Entity(..)) => Some(ent),
+            AnyEntKind::Design(Design::Architecture(entity)) => Some(entity),
+            _ => None,
+        }
+    }
+
+    pub fn instantiated_by(
+        &mut self,
+        params: &TextDocumentPositionParams,
+    ) -> Option<Vec<CallHierarchyIncomingCall>> {
+        let entity = self.entity_at(params)?;
+        Some(to_incoming_calls(self.project.instantiated_by(entity)))
+    }
+
+    pub fn instantiates(
+        &mut self,
+        params: &TextDocumentPositionParams,
+    ) -> Option<Vec<CallHierarchyOutgoingCall>> {
+        let entity = self.entity_at(params)?;
+        Some(to_outgoing_calls(self.project.instantiates(entity)))
+    }
+
+    pub fn prepare_type_hierarchy(
+        &mut self,
+        params: &TypeHierarchyPrepareParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let position = &params.text_document_position_params;
+        let source = self
+            .project
+            .get_source(&uri_to_file_name(&position.text_document.uri))?;
+        let ent = self
+            .project
+            .find_declaration(&source, from_lsp_pos(position.position))?;
+        if !matches!(ent.kind(), AnyEntKind::Type(_)) {
+            return None;
+        }
+        Some(vec![type_hierarchy_item(ent)?])
+    }
+
+    /// The type of a type hierarchy item returned by [`Self::prepare_type_hierarchy`]
+    /// or as a supertype or subtype
+    fn type_hierarchy_ent(&self, item: &TypeHierarchyItem) -> Option<EntRef<'_>> {
+        let source = self.project.get_source(&uri_to_file_name(&item.uri))?;
+        self.project
+            .find_declaration(&source, from_lsp_pos(item.selection_range.start))
+    }
+
+    pub fn type_hierarchy_supertypes(
+        &mut self,
+        params: &TypeHierarchySupertypesParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let typ = self.type_hierarchy_ent(&params.item)?;
+        Some(
+            self.project
+                .supertypes(typ)
+                .into_iter()
+                .filter_map(type_hierarchy_item)
+                .collect(),
+        )
+    }
+
+    pub fn type_hierarchy_subtypes(
+        &mut self,
+        params: &TypeHierarchySubtypesParams,
+    ) -> Option<Vec<TypeHierarchyItem>> {
+        let typ = self.type_hierarchy_ent(&params.item)?;
+        Some(
+            self.project
+                .subtypes(typ)
+                .into_iter()
+                .filter_map(type_hierarchy_item)
+                .collect(),
+        )
+    }
+
+    pub fn text_document_code_action(
+        &mut self,
+        params: &CodeActionParams,
+    ) -> Option<CodeActionResponse> {
+        let uri = &params.text_document.uri;
+        let source = self.project.get_source(&uri_to_file_name(uri))?;
+
+        let mut actions = Vec::new();
+        for diagnostic in params.context.diagnostics.iter() {
+            let Some(name) = unresolved_name(diagnostic) else {
+                continue;
+            };
+            for fix in self.project.find_use_clause_fixes(
+                &source,
+                from_lsp_pos(diagnostic.range.start),
+                name,
+            ) {
+                let pos = to_lsp_pos(fix.pos);
+                let edit = TextEdit {
+                    range: lsp_types::Range::new(pos, pos),
+                    new_text: fix.text,
+                };
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: fix.title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }));
+            }
+
+            // A statement consisting only of the name of an entity is replaced by an instance
+            let line = diagnostic.range.start.line;
+            let is_statement = source
+                .contents()
+                .get_line(line as usize)
+                .is_some_and(|text| text.trim().eq_ignore_ascii_case(&format!("{name};")));
+            if !is_statement {
+                continue;
+            }
+            for ent in self.project.find_design_entities(name) {
+                let Some(library_name) = ent.library_name() else {
+                    continue;
+                };
+                for (kind, title) in [
+                    (
+                        InstantiationKind::Entity,
+                        format!("Instantiate entity {library_name}.{}", ent.designator()),
+                    ),
+                    (
+                        InstantiationKind::Component,
+                        format!("Instantiate component {}", ent.designator()),
+                    ),
+                ] {
+                    let Some(edits) = self.instantiation_edits(&source, line, ent, kind, true)
+                    else {
+                        continue;
+                    };
+                    actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                        title,
+                        kind: Some(CodeActionKind::QUICKFIX),
+                        diagnostics: Some(vec![diagnostic.clone()]),
+                        edit: Some(WorkspaceEdit {
+                            changes: Some(HashMap::from([(uri.clone(), edits)])),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+        Some(actions)
+    }
+
+    /// The edits that insert an instance at a line or replace the line when `replace_line` is set
+    fn instantiation_edits(
+        &self,
+        source: &Source,
+        line: u32,
+        ent: EntRef,
+        kind: InstantiationKind,
+        replace_line: bool,
+    ) -> Option<Vec<TextEdit>> {
+        let instantiation = self.project.generate_instantiation(
+            source,
+            vhdl_lang::Position::new(line, 0),
+            ent,
+            kind,
+        )?;
+
+        let mut edits: Vec<_> = instantiation
+            .inserts
+            .into_iter()
+            .map(|(pos, new_text)| TextEdit {
+                range: lsp_types::Range::new(to_lsp_pos(pos), to_lsp_pos(pos)),
+                new_text,
+            })
+            .collect();
+        let end_line = if replace_line { line + 1 } else { line };
+        edits.push(TextEdit {
+            range: lsp_types::Range::new(
+                lsp_types::Position::new(line, 0),
+                lsp_types::Position::new(end_line, 0),
+            ),
+            new_text: instantiation.statement,
+        });
+        Some(edits)
+    }
+
+    /// Execute the `vhdl_ls.instantiate` command with the arguments
+    /// uri, position, the entity name as `library.entity` and `entity` or `component`.
+    /// The instance is inserted at the line of the position by a `workspace/applyEdit` request.
+    pub fn workspace_execute_command(&mut self, params: &ExecuteCommandParams) {
+        if params.command != INSTANTIATE_COMMAND {
+            self.message(Message::error(format!(
+                "Unknown command '{}'",
+                params.command
+            )));
+            return;
+        }
+
+        let Ok((uri, position, name, kind)) =
+            serde_json::from_value::<(Url, lsp_types::Position, String, String)>(
+                serde_json::Value::Array(params.arguments.clone()),
+            )
+        else {
+            self.message(Message::error(format!(
+                "Expected arguments of {INSTANTIATE_COMMAND} to be uri, position, entity name and kind"
+            )));
+            return;
+        };
+
+        let kind = match kind.as_str() {
+            "entity" => InstantiationKind::Entity,
+            "component" => InstantiationKind::Component,
+            _ => {
+                self.message(Message::error(format!(
+                    "Expected instantiation kind to be 'entity' or 'component', got '{kind}'"
+                )));
+                return;
+            }
+        };
+
+        let (library_name, entity_name) = match name.split_once('.') {
+            Some((library_name, entity_name)) => (Some(library_name), entity_name),
+            None => (None, name.as_str()),
+        };
+        let Some(source) = self.project.get_source(&uri_to_file_name(&uri)) else {
+            self.message(Message::error(format!(
+                "Instantiating in file {} that is not part of the project",
+                uri_to_file_name(&uri).to_string_lossy()
+            )));
+            return;
+        };
+        let edits = self
+            .project
+            .find_design_entities(entity_name)
+            .into_iter()
+            .find(|ent| {
+                library_name.is_none_or(|library_name| {
+                    ent.library_name()
+                        .is_some_and(|name| name.name_utf8().eq_ignore_ascii_case(library_name))
+                })
+            })
+            .and_then(|ent| self.instantiation_edits(&source, position.line, ent, kind, false));
+
+        if let Some(edits) = edits {
+            let params = ApplyWorkspaceEditParams {
+                label: Some(format!("Instantiate {name}")),
+                edit: WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri, edits)])),
+                    ..Default::default()
+                },
+            };
+            self.rpc.send_request("workspace/applyEdit", params);
+        } else {
+            self.message(Message::error(format!(
+                "Could not instantiate entity {name} at this position"
+            )));
+        }
+    }
+
+    /// The formatting options of the configuration file take precedence over those of the client
+    fn format_options(&self, options: &FormattingOptions) -> FormatOptions {
+        self.project
+            .format_options()
+            .cloned()
+            .unwrap_or_else(|| FormatOptions {
+                indent_size: options.tab_size as usize,
+                use_tabs: !options.insert_spaces,
+                ..Default::default()
+            })
+    }
+
+    pub fn text_document_formatting(
+        &mut self,
+        params: &DocumentFormattingParams,
+    ) -> Option<Vec<TextEdit>> {
+        let file_name = uri_to_file_name(&params.text_document.uri);
+        // Format the latest contents even if they have not been analyzed yet
+        let source = self.get_source(&file_name)?;
+        let options = self.format_options(&params.options);
+        // Files with syntax errors are left untouched
+        let new_text = self.project.format_source(&source, &options).ok()?;
+        let new_text = self.with_line_endings(&file_name, new_text);
+        let end = {
+            let contents = source.contents();
+            let num_lines = contents.num_lines();
+            match contents.get_line(num_lines.saturating_sub(1)) {
+                // The line break of the last line belongs to the line after it
+                Some(line) if line.ends_with('\n') => lsp_types::Position::new(num_lines as u32, 0),
+                _ => to_lsp_pos(contents.end()),
+            }
+        };
+
+        Some(vec![TextEdit {
+            range: lsp_types::Range {
+                start: lsp_types::Position::new(0, 0),
+                end,
+            },
+            new_text,
+        }])
+    }
+
+    pub fn text_document_range_formatting(
+        &mut self,
+        params: &DocumentRangeFormattingParams,
+    ) -> Option<Vec<TextEdit>> {
+        let file_name = uri_to_file_name(&params.text_document.uri);
+        // Format the latest contents even if they have not been analyzed yet
+        let source = self.get_source(&file_name)?;
+        let options = self.format_options(&params.options);
+        let start = params.range.start.line;
+        // The end of a range is exclusive, a range ending at the start of a line
+        // such as a selection of whole lines does not include that line
+        let end = match params.range.end {
+            lsp_types::Position { line, character: 0 } if line > start => line - 1,
+            lsp_types::Position { line, .. } => line,
+        };
+        let new_text = self
+            .project
+            .format_source_lines(&source, &options, start..=end)
+            .ok()?;
+        let new_text = self.with_line_endings(&file_name, new_text);
+
+        // Whole lines are replaced including their line breaks
+        Some(vec![TextEdit {
+            range: lsp_types::Range {
+                start: lsp_types::Position::new(start, 0),
+                end: lsp_types::Position::new(end + 1, 0),
+            },
+            new_text,
+        }])
+    }
+
+    fn message_filter(&self) -> MessageFilter {
+        MessageFilter {
+            silent: self.settings.silent,
+            rpc: self.rpc.clone(),
+        }
+    }
+
+    fn message(&self, msg: Message) {
+        self.message_filter().push(msg);
+    }
+}
+
+fn entity_to_completion_item(ent: EntRef) -> CompletionItem {
     CompletionItem {
         label: ent.designator.to_string(),
         detail: Some(ent.describe()),
@@ -756,6 +1808,8 @@ fn entity_kind_to_completion_kind(kind: &AnyEntKind) -> CompletionItemKind {
         AnyEntKind::LoopParameter(_) => CompletionItemKind::MODULE,
         AnyEntKind::PhysicalLiteral(_) => CompletionItemKind::UNIT,
         AnyEntKind::DeferredConstant(_) => CompletionItemKind::CONSTANT,
+        AnyEntKind::GroupTemplate(_) | AnyEntKind::Group => CompletionItemKind::STRUCT,
+        AnyEntKind::View(_) => CompletionItemKind::INTERFACE,
         AnyEntKind::Library => CompletionItemKind::MODULE,
         AnyEntKind::Design(_) => CompletionItemKind::MODULE,
     }
@@ -802,6 +1856,73 @@ fn to_lsp_message_type(message_type: &vhdl_lang::MessageType) -> MessageType {
     }
 }
 
+fn is_subprogram(ent: EntRef) -> bool {
+    matches!(
+        ent.kind(),
+        AnyEntKind::Overloaded(Overloaded::Subprogram(_) | Overloaded::SubprogramDecl(_))
+    ) && !ent.is_implicit()
+}
+
+fn call_hierarchy_item(ent: EntRef) -> Option<CallHierarchyItem> {
+    let decl_pos = ent.decl_pos()?;
+    Some(CallHierarchyItem {
+        name: ent.designator().to_string(),
+        kind: to_symbol_kind(ent.kind()),
+        tags: None,
+        detail: Some(ent.describe()),
+        uri: file_name_to_uri(decl_pos.source.file_name()),
+        range: to_lsp_range(decl_pos.range),
+        selection_range: to_lsp_range(decl_pos.range),
+        data: None,
+    })
+}
+
+fn type_hierarchy_item(ent: EntRef) -> Option<TypeHierarchyItem> {
+    let decl_pos = ent.decl_pos()?;
+    Some(TypeHierarchyItem {
+        name: ent.designator().to_string(),
+        kind: to_symbol_kind(ent.kind()),
+        tags: None,
+        detail: Some(ent.describe()),
+        uri: file_name_to_uri(decl_pos.source.file_name()),
+        range: to_lsp_range(decl_pos.range),
+        selection_range: to_lsp_range(decl_pos.range),
+        data: None,
+    })
+}
+
+fn to_incoming_calls(calls: Vec<CallHierarchyCall>) -> Vec<CallHierarchyIncomingCall> {
+    calls
+        .into_iter()
+        .filter_map(|call| {
+            Some(CallHierarchyIncomingCall {
+                from: call_hierarchy_item(call.ent)?,
+                from_ranges: call
+                    .ranges
+                    .iter()
+                    .map(|pos| to_lsp_range(pos.range))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+fn to_outgoing_calls(calls: Vec<CallHierarchyCall>) -> Vec<CallHierarchyOutgoingCall> {
+    calls
+        .into_iter()
+        .filter_map(|call| {
+            Some(CallHierarchyOutgoingCall {
+                to: call_hierarchy_item(call.ent)?,
+                from_ranges: call
+                    .ranges
+                    .iter()
+                    .map(|pos| to_lsp_range(pos.range))
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
 fn srcpos_to_location(pos: &SrcPos) -> Location {
     let uri = file_name_to_uri(pos.source.file_name());
     Location {
@@ -874,6 +1995,45 @@ fn uri_to_file_name(uri: &Url) -> PathBuf {
     uri.to_file_path().unwrap()
 }
 
+/// The name of a declaration that could not be found as reported by an unresolved diagnostic
+fn unresolved_name(diagnostic: &lsp_types::Diagnostic) -> Option<&str> {
+    if diagnostic.code != Some(NumberOrString::String("unresolved".to_owned())) {
+        return None;
+    }
+    diagnostic.data.as_ref()?.as_str()
+}
+
+/// The declaration as a code block followed by its documentation and details
+fn hover_markdown(info: &HoverInfo) -> Option<String> {
+    let mut sections = Vec::new();
+    if let Some(ref declaration) = info.declaration {
+        sections.push(format!("```vhdl\n{declaration}\n```"));
+    }
+    if let Some(ref documentation) = info.documentation {
+        sections.push(documentation.clone());
+    }
+    let details: Vec<_> = info
+        .details
+        .iter()
+        .cloned()
+        .chain(
+            info.location
+                .iter()
+                .map(|location| format!("Declared in {location}")),
+        )
+        .map(|detail| format!("- {detail}"))
+        .collect();
+    if !details.is_empty() {
+        sections.push(details.join("\n"));
+    }
+
+    if info.declaration.is_none() && info.documentation.is_none() {
+        // Nothing is known about the entity besides where it is
+        return None;
+    }
+    Some(sections.join("\n\n---\n\n"))
+}
+
 fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
     let severity = match diagnostic.severity {
         Severity::Error => DiagnosticSeverity::ERROR,
@@ -902,10 +2062,14 @@ fn to_lsp_diagnostic(diagnostic: Diagnostic) -> lsp_types::Diagnostic {
     lsp_types::Diagnostic {
         range: to_lsp_range(diagnostic.pos.range()),
         severity: Some(severity),
-        code: None,
+        code: Some(NumberOrString::String(diagnostic.code.to_string())),
         source: Some("vhdl ls".to_owned()),
         message: diagnostic.message,
         related_information,
+        data: diagnostic.data.map(serde_json::Value::String),
+        // Regions excluded by conditional analysis directives are greyed out
+        tags: (diagnostic.code == ErrorCode::InactiveRegion)
+            .then(|| vec![DiagnosticTag::UNNECESSARY]),
         ..Default::default()
     }
 }
@@ -957,6 +2121,140 @@ fn type_kind(t: &Type) -> SymbolKind {
     }
 }
 
+const SEMANTIC_TOKEN_TYPES: &[SemanticTokenType] = &[
+    SemanticTokenType::NAMESPACE,
+    SemanticTokenType::CLASS,
+    SemanticTokenType::TYPE,
+    SemanticTokenType::ENUM,
+    SemanticTokenType::STRUCT,
+    SemanticTokenType::TYPE_PARAMETER,
+    SemanticTokenType::PARAMETER,
+    SemanticTokenType::VARIABLE,
+    SemanticTokenType::PROPERTY,
+    SemanticTokenType::ENUM_MEMBER,
+    SemanticTokenType::FUNCTION,
+    SemanticTokenType::DECORATOR,
+    SemanticTokenType::INTERFACE,
+];
+
+const SIGNAL: SemanticTokenModifier = SemanticTokenModifier::new("signal");
+const SUBTYPE: SemanticTokenModifier = SemanticTokenModifier::new("subtype");
+const PROCEDURE: SemanticTokenModifier = SemanticTokenModifier::new("procedure");
+const MODE_IN: SemanticTokenModifier = SemanticTokenModifier::new("in");
+const MODE_OUT: SemanticTokenModifier = SemanticTokenModifier::new("out");
+const MODE_INOUT: SemanticTokenModifier = SemanticTokenModifier::new("inout");
+const MODE_BUFFER: SemanticTokenModifier = SemanticTokenModifier::new("buffer");
+const MODE_LINKAGE: SemanticTokenModifier = SemanticTokenModifier::new("linkage");
+
+const SEMANTIC_TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
+    SemanticTokenModifier::DECLARATION,
+    SemanticTokenModifier::READONLY,
+    SemanticTokenModifier::STATIC,
+    SIGNAL,
+    SUBTYPE,
+    PROCEDURE,
+    MODE_IN,
+    MODE_OUT,
+    MODE_INOUT,
+    MODE_BUFFER,
+    MODE_LINKAGE,
+];
+
+/// The semantic token type and modifiers of a named entity, labels have none
+fn semantic_token(ent: EntRef) -> Option<(SemanticTokenType, Vec<SemanticTokenModifier>)> {
+    let token = match ent.kind() {
+        AnyEntKind::ExternalAlias { class, .. } => {
+            object_class_token(ObjectClass::from(*class), None)
+        }
+        AnyEntKind::ObjectAlias { base_object, .. } => {
+            let object = base_object.object();
+            object_class_token(object.class, object.iface)
+        }
+        AnyEntKind::Object(object) => object_class_token(object.class, object.iface),
+        AnyEntKind::LoopParameter(_) | AnyEntKind::DeferredConstant(_) => (
+            SemanticTokenType::VARIABLE,
+            vec![SemanticTokenModifier::READONLY],
+        ),
+        AnyEntKind::PhysicalLiteral(_) => (SemanticTokenType::ENUM_MEMBER, vec![]),
+        AnyEntKind::File(_) => (SemanticTokenType::VARIABLE, vec![]),
+        AnyEntKind::InterfaceFile(_) => (SemanticTokenType::PARAMETER, vec![]),
+        AnyEntKind::Component(_) => (SemanticTokenType::CLASS, vec![]),
+        AnyEntKind::Attribute(_) => (SemanticTokenType::DECORATOR, vec![]),
+        AnyEntKind::Overloaded(overloaded) => match overloaded {
+            Overloaded::EnumLiteral(_) => (SemanticTokenType::ENUM_MEMBER, vec![]),
+            Overloaded::Alias(base) => return semantic_token((*base).into()),
+            Overloaded::SubprogramDecl(signature)
+            | Overloaded::Subprogram(signature)
+            | Overloaded::InterfaceSubprogram(signature) => {
+                if signature.return_type().is_some() {
+                    (SemanticTokenType::FUNCTION, vec![])
+                } else {
+                    (SemanticTokenType::FUNCTION, vec![PROCEDURE])
+                }
+            }
+        },
+        AnyEntKind::Type(typ) => match typ {
+            Type::Interface => (SemanticTokenType::TYPE_PARAMETER, vec![]),
+            Type::Enum(_) => (SemanticTokenType::ENUM, vec![]),
+            Type::Record(_) => (SemanticTokenType::STRUCT, vec![]),
+            Type::Subtype(_) => (SemanticTokenType::TYPE, vec![SUBTYPE]),
+            Type::Alias(base) => return semantic_token((*base).into()),
+            _ => (SemanticTokenType::TYPE, vec![]),
+        },
+        AnyEntKind::ElementDeclaration(_) => (SemanticTokenType::PROPERTY, vec![]),
+        AnyEntKind::GroupTemplate(_) | AnyEntKind::Group => (SemanticTokenType::STRUCT, vec![]),
+        AnyEntKind::View(_) => (SemanticTokenType::INTERFACE, vec![]),
+        AnyEntKind::Sequential(_) | AnyEntKind::Concurrent(_) => return None,
+        AnyEntKind::Library => (SemanticTokenType::NAMESPACE, vec![]),
+        AnyEntKind::Design(design) => match design {
+            vhdl_lang::Design::Entity(..)
+            | vhdl_lang::Design::Architecture(..)
+            | vhdl_lang::Design::Configuration => (SemanticTokenType::CLASS, vec![]),
+            vhdl_lang::Design::Package(..)
+            | vhdl_lang::Design::PackageBody
+            | vhdl_lang::Design::UninstPackage(..)
+            | vhdl_lang::Design::PackageInstance(_)
+            | vhdl_lang::Design::Context(_) => (SemanticTokenType::NAMESPACE, vec![]),
+        },
+    };
+    Some(token)
+}
+
+fn object_class_token(
+    class: ObjectClass,
+    iface: Option<ObjectInterface>,
+) -> (SemanticTokenType, Vec<SemanticTokenModifier>) {
+    let mut modifiers = Vec::new();
+    if class == ObjectClass::Signal {
+        modifiers.push(SIGNAL);
+    }
+    let token_type = match iface {
+        Some(ObjectInterface::Generic) => {
+            modifiers.push(SemanticTokenModifier::READONLY);
+            SemanticTokenType::PARAMETER
+        }
+        Some(ObjectInterface::Port(mode) | ObjectInterface::Parameter(mode)) => {
+            modifiers.push(match mode {
+                Mode::In => MODE_IN,
+                Mode::Out => MODE_OUT,
+                Mode::InOut => MODE_INOUT,
+                Mode::Buffer => MODE_BUFFER,
+                Mode::Linkage => MODE_LINKAGE,
+            });
+            SemanticTokenType::PARAMETER
+        }
+        None => {
+            match class {
+                ObjectClass::Constant => modifiers.push(SemanticTokenModifier::READONLY),
+                ObjectClass::SharedVariable => modifiers.push(SemanticTokenModifier::STATIC),
+                ObjectClass::Signal | ObjectClass::Variable => {}
+            }
+            SemanticTokenType::VARIABLE
+        }
+    };
+    (token_type, modifiers)
+}
+
 fn to_symbol_kind(kind: &AnyEntKind) -> SymbolKind {
     match kind {
         AnyEntKind::ExternalAlias { class, .. } => object_class_kind(ObjectClass::from(*class)),
@@ -972,6 +2270,8 @@ fn to_symbol_kind(kind: &AnyEntKind) -> SymbolKind {
         AnyEntKind::Overloaded(o) => overloaded_kind(o),
         AnyEntKind::Type(t) => type_kind(t),
         AnyEntKind::ElementDeclaration(_) => SymbolKind::FIELD,
+        AnyEntKind::GroupTemplate(_) | AnyEntKind::Group => SymbolKind::STRUCT,
+        AnyEntKind::View(_) => SymbolKind::INTERFACE,
         AnyEntKind::Sequential(_) => SymbolKind::NAMESPACE,
         AnyEntKind::Concurrent(Some(Concurrent::Instance)) => SymbolKind::MODULE,
         AnyEntKind::Concurrent(_) => SymbolKind::NAMESPACE,
@@ -1082,20 +2382,27 @@ end entity ent;
         mock.expect_warning_contains("is not part of the project");
 
         server.text_document_did_open_notification(&did_open);
+        server.analyse_pending_changes();
     }
 
     #[test]
-    fn did_open_with_diagnostics_and_change_without() {
+    fn text_document_formatting() {
         let (mock, mut server) = setup_server();
-
         let (_tempdir, root_uri) = temp_root_uri();
         expect_missing_config_messages(&mock);
         initialize_server(&mut server, root_uri.clone());
 
         let file_url = root_uri.join("ent.vhd").unwrap();
         let code = "
-entity ent is
-end entity ent2;
+ENTITY ent IS
+END ENTITY ent;
+
+ARCHITECTURE rtl OF ent IS
+BEGIN
+blk:BLOCK
+BEGIN
+END BLOCK blk;
+END ARCHITECTURE rtl;
 "
         .to_owned();
 
@@ -1108,59 +2415,182 @@ end entity ent2;
             },
         };
 
-        let publish_diagnostics = PublishDiagnosticsParams {
-            uri: file_url.clone(),
-            diagnostics: vec![lsp_types::Diagnostic {
-                range: Range {
-                    start: lsp_types::Position {
-                        line: 2,
-                        character: "end entity ".len() as u32,
-                    },
-                    end: lsp_types::Position {
-                        line: 2,
-                        character: "end entity ent2".len() as u32,
-                    },
-                },
-                code: None,
-                severity: Some(DiagnosticSeverity::ERROR),
-                source: Some("vhdl ls".to_owned()),
-                message: "End identifier mismatch, expected ent".to_owned(),
-                ..Default::default()
-            }],
-            version: None,
-        };
-
         mock.expect_warning_contains("is not part of the project");
-
-        mock.expect_notification("textDocument/publishDiagnostics", publish_diagnostics);
         server.text_document_did_open_notification(&did_open);
+        server.analyse_pending_changes();
 
-        let code = "
-entity ent is
-end entity ent;
-"
-        .to_owned();
-
-        let did_change = DidChangeTextDocumentParams {
-            text_document: VersionedTextDocumentIdentifier {
-                uri: file_url.clone(),
-                version: 1,
-            },
-            content_changes: vec![TextDocumentContentChangeEvent {
-                range: None,
-                range_length: None,
-                text: code,
-            }],
+        let options = FormattingOptions {
+            tab_size: 4,
+            insert_spaces: true,
+            ..Default::default()
         };
+        let edits = server.text_document_formatting(&DocumentFormattingParams {
+            text_document: TextDocumentIdentifier::new(file_url.clone()),
+            options: options.clone(),
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(
+            edits,
+            Some(vec![TextEdit {
+                range: lsp_types::Range::new(Position::new(0, 0), Position::new(10, 0)),
+                new_text: "entity ent is\nend entity ent;\n\narchitecture rtl of ent is\nbegin\n    blk: block\n    begin\n    end block blk;\nend architecture rtl;\n".to_owned(),
+            }])
+        );
 
-        let publish_diagnostics = PublishDiagnosticsParams {
-            uri: file_url,
-            diagnostics: vec![],
-            version: None,
-        };
+        let edits = server.text_document_range_formatting(&DocumentRangeFormattingParams {
+            text_document: TextDocumentIdentifier::new(file_url.clone()),
+            range: lsp_types::Range::new(Position::new(6, 0), Position::new(8, 3)),
+            options: options.clone(),
+            work_done_progress_params: Default::default(),
+        });
+        let expected = Some(vec![TextEdit {
+            range: lsp_types::Range::new(Position::new(6, 0), Position::new(9, 0)),
+            new_text: "    blk: block\n    begin\n    end block blk;\n".to_owned(),
+        }]);
+        assert_eq!(edits, expected);
+
+        // A range ending at the start of a line does not include that line
+        let edits = server.text_document_range_formatting(&DocumentRangeFormattingParams {
+            text_document: TextDocumentIdentifier::new(file_url),
+            range: lsp_types::Range::new(Position::new(6, 0), Position::new(9, 0)),
+            options,
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(edits, expected);
+    }
+
+    #[test]
+    fn text_document_formatting_keeps_crlf_line_endings() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        expect_missing_config_messages(&mock);
+        initialize_server(&mut server, root_uri.clone());
+
+        let file_url = root_uri.join("ent.vhd").unwrap();
+        let did_open = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: file_url.clone(),
+                language_id: "vhdl".to_owned(),
+                version: 0,
+                text: "ENTITY ent IS\r\nEND ENTITY ent;\r\n".to_owned(),
+            },
+        };
+        mock.expect_warning_contains("is not part of the project");
+        server.text_document_did_open_notification(&did_open);
+        server.analyse_pending_changes();
+
+        let options = FormattingOptions {
+            tab_size: 2,
+            insert_spaces: true,
+            ..Default::default()
+        };
+        let edits = server.text_document_formatting(&DocumentFormattingParams {
+            text_document: TextDocumentIdentifier::new(file_url.clone()),
+            options: options.clone(),
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(
+            edits,
+            Some(vec![TextEdit {
+                range: lsp_types::Range::new(Position::new(0, 0), Position::new(2, 0)),
+                new_text: "entity ent is\r\nend entity ent;\r\n".to_owned(),
+            }])
+        );
+
+        let edits = server.text_document_range_formatting(&DocumentRangeFormattingParams {
+            text_document: TextDocumentIdentifier::new(file_url),
+            range: lsp_types::Range::new(Position::new(1, 0), Position::new(1, 3)),
+            options,
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(
+            edits,
+            Some(vec![TextEdit {
+                range: lsp_types::Range::new(Position::new(1, 0), Position::new(2, 0)),
+                new_text: "end entity ent;\r\n".to_owned(),
+            }])
+        );
+    }
+
+    #[test]
+    fn did_open_with_diagnostics_and_change_without() {
+        let (mock, mut server) = setup_server();
+
+        let (_tempdir, root_uri) = temp_root_uri();
+        expect_missing_config_messages(&mock);
+        initialize_server(&mut server, root_uri.clone());
+
+        let file_url = root_uri.join("ent.vhd").unwrap();
+        let code = "
+entity ent is
+end entity ent2;
+"
+        .to_owned();
+
+        let did_open = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: file_url.clone(),
+                language_id: "vhdl".to_owned(),
+                version: 0,
+                text: code,
+            },
+        };
+
+        let publish_diagnostics = PublishDiagnosticsParams {
+            uri: file_url.clone(),
+            diagnostics: vec![lsp_types::Diagnostic {
+                range: Range {
+                    start: lsp_types::Position {
+                        line: 2,
+                        character: "end entity ".len() as u32,
+                    },
+                    end: lsp_types::Position {
+                        line: 2,
+                        character: "end entity ent2".len() as u32,
+                    },
+                },
+                code: Some(NumberOrString::String("syntax_error".to_owned())),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("vhdl ls".to_owned()),
+                message: "End identifier mismatch, expected ent".to_owned(),
+                ..Default::default()
+            }],
+            version: None,
+        };
+
+        mock.expect_warning_contains("is not part of the project");
+
+        mock.expect_notification("textDocument/publishDiagnostics", publish_diagnostics);
+        server.text_document_did_open_notification(&did_open);
+        server.analyse_pending_changes();
+
+        let code = "
+entity ent is
+end entity ent;
+"
+        .to_owned();
+
+        let did_change = DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: file_url.clone(),
+                version: 1,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: None,
+                range_length: None,
+                text: code,
+            }],
+        };
+
+        let publish_diagnostics = PublishDiagnosticsParams {
+            uri: file_url,
+            diagnostics: vec![],
+            version: None,
+        };
 
         mock.expect_notification("textDocument/publishDiagnostics", publish_diagnostics);
         server.text_document_did_change_notification(&did_change);
+        server.analyse_pending_changes();
     }
 
     fn write_file(root_uri: &Url, file_name: impl AsRef<str>, contents: impl AsRef<str>) -> Url {
@@ -1213,7 +2643,7 @@ lib.files = [
                         character: "architecture rtl of ent2".len() as u32,
                     },
                 },
-                code: None,
+                code: Some(NumberOrString::String("unresolved".to_owned())),
                 severity: Some(DiagnosticSeverity::ERROR),
                 source: Some("vhdl ls".to_owned()),
                 message: "No primary unit \'ent2\' within library \'lib\'".to_owned(),
@@ -1228,6 +2658,348 @@ lib.files = [
         initialize_server(&mut server, root_uri);
     }
 
+    #[test]
+    fn code_action_adds_use_clause() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+  component comp is
+  end component;
+end package;
+",
+        );
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+begin
+  inst: component comp;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+common.files = ['pkg.vhd']
+lib.files = ['ent.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No declaration of 'comp'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let diagnostic = lsp_types::Diagnostic {
+            range: Range::new(Position::new(5, 18), Position::new(5, 22)),
+            code: Some(NumberOrString::String("unresolved".to_owned())),
+            message: "No declaration of 'comp'".to_owned(),
+            data: Some(serde_json::Value::String("comp".to_owned())),
+            ..Default::default()
+        };
+        let response = server
+            .text_document_code_action(&CodeActionParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                range: diagnostic.range,
+                context: CodeActionContext {
+                    diagnostics: vec![diagnostic],
+                    only: None,
+                    trigger_kind: None,
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+
+        let edits: Vec<_> = response
+            .into_iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => {
+                    let mut changes = action.edit.unwrap().changes.unwrap();
+                    let mut edits = changes.remove(&file_uri).unwrap();
+                    assert_eq!(edits.len(), 1);
+                    let edit = edits.remove(0);
+                    assert_eq!(
+                        edit.range,
+                        Range::new(Position::new(3, 0), Position::new(3, 0))
+                    );
+                    (action.title, edit.new_text)
+                }
+                CodeActionOrCommand::Command(_) => panic!("Expected code action"),
+            })
+            .collect();
+        assert_eq!(
+            edits,
+            vec![
+                (
+                    "Add use common.pkg.comp".to_owned(),
+                    "library common;\nuse common.pkg.comp;\n\n".to_owned()
+                ),
+                (
+                    "Add use common.pkg.all".to_owned(),
+                    "library common;\nuse common.pkg.all;\n\n".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn instantiate_entity() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(
+            &root_uri,
+            "leaf.vhd",
+            "\
+entity leaf is
+end entity;
+",
+        );
+        let file_uri = write_file(
+            &root_uri,
+            "top.vhd",
+            "\
+entity top is
+end entity;
+
+architecture a of top is
+begin
+  leaf;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['leaf.vhd', 'top.vhd']
+",
+        );
+
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No declaration of 'leaf'",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let diagnostic = lsp_types::Diagnostic {
+            range: Range::new(Position::new(5, 2), Position::new(5, 6)),
+            code: Some(NumberOrString::String("unresolved".to_owned())),
+            message: "No declaration of 'leaf'".to_owned(),
+            data: Some(serde_json::Value::String("leaf".to_owned())),
+            ..Default::default()
+        };
+        let response = server
+            .text_document_code_action(&CodeActionParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                range: diagnostic.range,
+                context: CodeActionContext {
+                    diagnostics: vec![diagnostic],
+                    only: None,
+                    trigger_kind: None,
+                },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+
+        let actions: Vec<_> = response
+            .into_iter()
+            .map(|action| match action {
+                CodeActionOrCommand::CodeAction(action) => {
+                    let mut changes = action.edit.unwrap().changes.unwrap();
+                    (action.title, changes.remove(&file_uri).unwrap())
+                }
+                CodeActionOrCommand::Command(_) => panic!("Expected code action"),
+            })
+            .collect();
+        let replace_statement = |new_text: &str| TextEdit {
+            range: Range::new(Position::new(5, 0), Position::new(6, 0)),
+            new_text: new_text.to_owned(),
+        };
+        assert_eq!(
+            actions,
+            vec![
+                (
+                    "Instantiate entity lib.leaf".to_owned(),
+                    vec![replace_statement("  leaf_inst: entity work.leaf;\n")]
+                ),
+                (
+                    "Instantiate component leaf".to_owned(),
+                    vec![
+                        TextEdit {
+                            range: Range::new(Position::new(4, 0), Position::new(4, 0)),
+                            new_text: "  component leaf is\n  end component;\n\n".to_owned(),
+                        },
+                        replace_statement("  leaf_inst: component leaf;\n")
+                    ]
+                ),
+            ]
+        );
+
+        let apply_edit = ApplyWorkspaceEditParams {
+            label: Some("Instantiate lib.leaf".to_owned()),
+            edit: WorkspaceEdit {
+                changes: Some(HashMap::from([(
+                    file_uri.clone(),
+                    vec![TextEdit {
+                        range: Range::new(Position::new(5, 0), Position::new(5, 0)),
+                        new_text: "  leaf_inst: entity work.leaf;\n".to_owned(),
+                    }],
+                )])),
+                ..Default::default()
+            },
+        };
+        mock.expect_request("workspace/applyEdit", apply_edit);
+        server.workspace_execute_command(&ExecuteCommandParams {
+            command: "vhdl_ls.instantiate".to_owned(),
+            arguments: vec![
+                serde_json::to_value(&file_uri).unwrap(),
+                serde_json::to_value(Position::new(5, 0)).unwrap(),
+                serde_json::Value::from("lib.leaf"),
+                serde_json::Value::from("entity"),
+            ],
+            work_done_progress_params: Default::default(),
+        });
+    }
+
+    #[test]
+    fn semantic_tokens() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+  generic (width : natural := 1);
+  port (clk : in bit; q : out bit);
+end entity;
+
+architecture a of ent is
+  signal s : bit;
+  constant c : boolean := width = 1;
+begin
+  q <= s when c else clk;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let Some(SemanticTokensResult::Tokens(tokens)) =
+            server.text_document_semantic_tokens_full(&SemanticTokensParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+        else {
+            panic!("Expected semantic tokens");
+        };
+
+        // Decode the relative positions and the legend indexes
+        let mut pos = (0, 0);
+        let decoded: Vec<_> = tokens
+            .data
+            .iter()
+            .map(|token| {
+                pos = if token.delta_line == 0 {
+                    (pos.0, pos.1 + token.delta_start)
+                } else {
+                    (pos.0 + token.delta_line, token.delta_start)
+                };
+                let modifiers: Vec<_> = SEMANTIC_TOKEN_MODIFIERS
+                    .iter()
+                    .enumerate()
+                    .filter(|(idx, _)| token.token_modifiers_bitset & (1 << idx) != 0)
+                    .map(|(_, modifier)| modifier.as_str())
+                    .collect();
+                (
+                    pos.0,
+                    pos.1,
+                    token.length,
+                    SEMANTIC_TOKEN_TYPES[token.token_type as usize].as_str(),
+                    modifiers.join(" "),
+                )
+            })
+            .collect();
+
+        let expected = vec![
+            (0, 7, 3, "class", "declaration"),
+            (1, 11, 5, "parameter", "declaration readonly"),
+            (1, 19, 7, "type", "subtype"),
+            (2, 8, 3, "parameter", "declaration signal in"),
+            (2, 17, 3, "enum", ""),
+            (2, 22, 1, "parameter", "declaration signal out"),
+            (2, 30, 3, "enum", ""),
+            (5, 13, 1, "class", "declaration"),
+            (5, 18, 3, "class", ""),
+            (6, 9, 1, "variable", "declaration signal"),
+            (6, 13, 3, "enum", ""),
+            (7, 11, 1, "variable", "declaration readonly"),
+            (7, 15, 7, "enum", ""),
+            (7, 26, 5, "parameter", "readonly"),
+            (9, 2, 1, "parameter", "signal out"),
+            (9, 7, 1, "variable", "signal"),
+            (9, 14, 1, "variable", "readonly"),
+            (9, 21, 3, "parameter", "signal in"),
+        ];
+        assert_eq!(
+            decoded,
+            expected
+                .into_iter()
+                .map(|(line, character, length, typ, modifiers)| (
+                    line,
+                    character,
+                    length,
+                    typ,
+                    modifiers.to_owned()
+                ))
+                .collect::<Vec<_>>()
+        );
+
+        let Some(SemanticTokensRangeResult::Tokens(tokens)) = server
+            .text_document_semantic_tokens_range(&SemanticTokensRangeParams {
+                text_document: TextDocumentIdentifier::new(file_uri),
+                range: Range::new(Position::new(9, 0), Position::new(10, 0)),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+        else {
+            panic!("Expected semantic tokens");
+        };
+        assert_eq!(tokens.data.len(), 4);
+        assert_eq!(
+            (tokens.data[0].delta_line, tokens.data[0].delta_start),
+            (9, 2)
+        );
+    }
+
     #[test]
     fn initialize_with_bad_config() {
         let (mock, mut server) = setup_server();
@@ -1317,6 +3089,7 @@ lib.files = [
         };
 
         server.text_document_did_open_notification(&did_open);
+        server.analyse_pending_changes();
 
         let response = server.text_document_declaration(&TextDocumentPositionParams {
             text_document: TextDocumentIdentifier { uri: file_url2 },
@@ -1356,10 +3129,16 @@ lib.files = [
         );
 
         let register_options = DidChangeWatchedFilesRegistrationOptions {
-            watchers: vec![FileSystemWatcher {
-                glob_pattern: GlobPattern::String("**/vhdl_ls.toml".to_owned()),
-                kind: None,
-            }],
+            watchers: vec![
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String("**/vhdl_ls.toml".to_owned()),
+                    kind: None,
+                },
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String("**/*.{vhd,vhdl}".to_owned()),
+                    kind: Some(WatchKind::Create | WatchKind::Delete),
+                },
+            ],
         };
         let register_capability = RegistrationParams {
             registrations: vec![Registration {
@@ -1438,7 +3217,7 @@ lib.files = [
                         character: "architecture rtl of ent".len() as u32,
                     },
                 },
-                code: None,
+                code: Some(NumberOrString::String("unresolved".to_owned())),
                 severity: Some(DiagnosticSeverity::ERROR),
                 source: Some("vhdl ls".to_owned()),
                 message: "No primary unit \'ent\' within library \'lib\'".to_owned(),
@@ -1482,5 +3261,1146 @@ lib.files = [
                 uri: config_uri,
             }],
         });
+        server.analyse_pending_changes();
+    }
+
+    #[test]
+    fn new_and_deleted_files_are_discovered() {
+        let (mock, mut server) = setup_server();
+        let (tempdir, root_uri) = temp_root_uri();
+        let ent_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+use work.pkg.all;
+
+entity ent is
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['*.vhd']
+",
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No primary unit 'pkg' within library 'lib'",
+        );
+        initialize_server(&mut server, root_uri.clone());
+
+        // A new file matching the pattern of the library
+        let pkg_uri = write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+end package;
+",
+        );
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: ent_uri,
+                diagnostics: vec![],
+                version: None,
+            },
+        );
+        server.workspace_did_change_watched_files(&DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                typ: FileChangeType::CREATED,
+                uri: pkg_uri.clone(),
+            }],
+        });
+        server.analyse_pending_changes();
+
+        // A new file not matching any pattern is ignored
+        std::fs::create_dir(tempdir.path().join("sub")).unwrap();
+        let other_uri = write_file(&root_uri, "sub/other.vhd", "package pkg is");
+        server.workspace_did_change_watched_files(&DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                typ: FileChangeType::CREATED,
+                uri: other_uri,
+            }],
+        });
+        server.analyse_pending_changes();
+
+        std::fs::remove_file(uri_to_file_name(&pkg_uri)).unwrap();
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "No primary unit 'pkg' within library 'lib'",
+        );
+        server.workspace_did_change_watched_files(&DidChangeWatchedFilesParams {
+            changes: vec![FileEvent {
+                typ: FileChangeType::DELETED,
+                uri: pkg_uri,
+            }],
+        });
+        server.analyse_pending_changes();
+    }
+
+    #[test]
+    fn hover_shows_documentation_and_details() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  -- Toggles every cycle
+  signal toggle : bit;
+begin
+  toggle <= not toggle;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let hover = server.text_document_hover(&TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier::new(file_uri),
+            position: Position::new(7, 2),
+        });
+        assert_eq!(
+            hover,
+            Some(Hover {
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "\
+```vhdl
+signal toggle : bit;
+```
+
+---
+
+Toggles every cycle
+
+---
+
+- Signal
+- Type: `BIT`
+- Declared in architecture a of lib.ent"
+                        .to_owned(),
+                }),
+                range: None,
+            })
+        );
+    }
+
+    #[test]
+    fn signature_help_while_typing_call() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+package pkg is
+  procedure p(signal s : out bit; value : bit);
+end package;
+
+use work.pkg.all;
+
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  process
+  begin
+    p(sig, 
+  end process;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "Expected {expression}",
+        );
+        initialize_server(&mut server, root_uri);
+
+        let help = server.text_document_signature_help(&SignatureHelpParams {
+            context: None,
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier::new(file_uri),
+                position: Position::new(14, 11),
+            },
+            work_done_progress_params: Default::default(),
+        });
+        assert_eq!(
+            help,
+            Some(SignatureHelp {
+                signatures: vec![SignatureInformation {
+                    label: "procedure p(signal s : out BIT; value : in BIT)".to_owned(),
+                    documentation: None,
+                    parameters: Some(vec![
+                        ParameterInformation {
+                            label: ParameterLabel::LabelOffsets([12, 30]),
+                            documentation: None,
+                        },
+                        ParameterInformation {
+                            label: ParameterLabel::LabelOffsets([32, 46]),
+                            documentation: None,
+                        },
+                    ]),
+                    active_parameter: Some(1),
+                }],
+                active_signature: Some(0),
+                active_parameter: None,
+            })
+        );
+    }
+
+    #[test]
+    fn inlay_hints_for_positional_arguments_and_loop_parameters() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  function f(arg : natural) return natural is
+  begin
+    return arg;
+  end function;
+  constant c : natural := f(1);
+begin
+  assert c = 1;
+  gen: for i in 0 to 1 generate
+  end generate;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let hints: Vec<_> = server
+            .text_document_inlay_hint(&InlayHintParams {
+                work_done_progress_params: Default::default(),
+                text_document: TextDocumentIdentifier::new(file_uri),
+                range: Range::new(Position::new(0, 0), Position::new(14, 0)),
+            })
+            .unwrap()
+            .into_iter()
+            .map(|hint| {
+                let InlayHintLabel::String(label) = hint.label else {
+                    panic!("Expected a string label");
+                };
+                (hint.position, label, hint.kind, hint.padding_right)
+            })
+            .collect();
+        assert_eq!(
+            hints,
+            vec![
+                (
+                    Position::new(8, 28),
+                    "arg =>".to_owned(),
+                    Some(InlayHintKind::PARAMETER),
+                    Some(true)
+                ),
+                (
+                    Position::new(11, 12),
+                    ": INTEGER".to_owned(),
+                    Some(InlayHintKind::TYPE),
+                    Some(false)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn document_highlight_folding_and_selection_ranges() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  sig <= not sig;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let highlights = server.text_document_document_highlight(&DocumentHighlightParams {
+            text_document_position_params: TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                position: Position::new(6, 2),
+            },
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        assert_eq!(
+            highlights,
+            Some(vec![
+                DocumentHighlight {
+                    range: Range::new(Position::new(4, 9), Position::new(4, 12)),
+                    kind: Some(DocumentHighlightKind::TEXT),
+                },
+                DocumentHighlight {
+                    range: Range::new(Position::new(6, 2), Position::new(6, 5)),
+                    kind: Some(DocumentHighlightKind::WRITE),
+                },
+                DocumentHighlight {
+                    range: Range::new(Position::new(6, 13), Position::new(6, 16)),
+                    kind: Some(DocumentHighlightKind::READ),
+                },
+            ])
+        );
+
+        let folding_ranges: Vec<_> = server
+            .text_document_folding_range(&FoldingRangeParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap()
+            .into_iter()
+            .map(|range| (range.start_line, range.end_line))
+            .collect();
+        assert_eq!(folding_ranges, vec![(0, 1), (3, 7)]);
+
+        let selection = server
+            .text_document_selection_range(&SelectionRangeParams {
+                text_document: TextDocumentIdentifier::new(file_uri),
+                positions: vec![Position::new(6, 14)],
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+        let mut ranges = Vec::new();
+        let mut selection = selection.first();
+        while let Some(range) = selection {
+            ranges.push(range.range);
+            selection = range.parent.as_deref();
+        }
+        assert_eq!(
+            ranges,
+            vec![
+                Range::new(Position::new(6, 13), Position::new(6, 16)),
+                Range::new(Position::new(6, 9), Position::new(6, 16)),
+                Range::new(Position::new(6, 2), Position::new(6, 17)),
+                Range::new(Position::new(3, 0), Position::new(7, 17)),
+            ]
+        );
+    }
+
+    #[test]
+    fn call_hierarchy_and_instantiations() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "tb.vhd",
+            "\
+package pkg is
+  procedure prc;
+end package;
+
+package body pkg is
+  procedure prc is
+  begin
+  end procedure;
+end package body;
+
+entity leaf is
+end entity;
+
+architecture a of leaf is
+begin
+end architecture;
+
+use work.pkg.all;
+
+entity tb is
+end entity;
+
+architecture a of tb is
+begin
+  inst : entity work.leaf;
+
+  main : process
+  begin
+    prc;
+    wait;
+  end process;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['tb.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        let items = server
+            .prepare_call_hierarchy(&CallHierarchyPrepareParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                    position: Position::new(28, 4),
+                },
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        let item = items[0].clone();
+        assert_eq!(item.name, "prc");
+        assert_eq!(
+            item.selection_range,
+            Range::new(Position::new(1, 12), Position::new(1, 15))
+        );
+
+        let incoming = server
+            .call_hierarchy_incoming_calls(&CallHierarchyIncomingCallsParams {
+                item: item.clone(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            incoming
+                .iter()
+                .map(|call| (call.from.name.as_str(), call.from_ranges.clone()))
+                .collect::<Vec<_>>(),
+            vec![(
+                "main",
+                vec![Range::new(Position::new(28, 4), Position::new(28, 7))]
+            )]
+        );
+
+        let outgoing = server
+            .call_hierarchy_outgoing_calls(&CallHierarchyOutgoingCallsParams {
+                item: incoming[0].from.clone(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            outgoing
+                .iter()
+                .map(|call| (call.to.name.as_str(), call.from_ranges.clone()))
+                .collect::<Vec<_>>(),
+            vec![(
+                "prc",
+                vec![Range::new(Position::new(28, 4), Position::new(28, 7))]
+            )]
+        );
+
+        let instantiated_by = server
+            .instantiated_by(&TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                position: Position::new(10, 7),
+            })
+            .unwrap();
+        assert_eq!(
+            instantiated_by
+                .iter()
+                .map(|call| (call.from.name.as_str(), call.from_ranges.clone()))
+                .collect::<Vec<_>>(),
+            vec![(
+                "tb",
+                vec![Range::new(Position::new(24, 16), Position::new(24, 25))]
+            )]
+        );
+
+        let instantiates = server
+            .instantiates(&TextDocumentPositionParams {
+                text_document: TextDocumentIdentifier::new(file_uri),
+                position: Position::new(22, 18),
+            })
+            .unwrap();
+        assert_eq!(
+            instantiates
+                .iter()
+                .map(|call| (call.to.name.as_str(), call.to.selection_range))
+                .collect::<Vec<_>>(),
+            vec![(
+                "leaf",
+                Range::new(Position::new(10, 7), Position::new(10, 11))
+            )]
+        );
+    }
+
+    #[test]
+    fn type_hierarchy() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+  type bits_t is ('0', '1');
+  subtype sub_t is bits_t;
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['pkg.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_request(
+            "client/registerCapability",
+            RegistrationParams {
+                registrations: vec![Registration {
+                    id: "textDocument/prepareTypeHierarchy".to_owned(),
+                    method: "textDocument/prepareTypeHierarchy".to_owned(),
+                    register_options: serde_json::to_value(
+                        TypeHierarchyRegistrationOptions::default(),
+                    )
+                    .ok(),
+                }],
+            },
+        );
+
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                type_hierarchy: Some(TypeHierarchyClientCapabilities {
+                    dynamic_registration: Some(true),
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+
+        let items = server
+            .prepare_type_hierarchy(&TypeHierarchyPrepareParams {
+                text_document_position_params: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier::new(file_uri),
+                    position: Position::new(2, 10),
+                },
+                work_done_progress_params: Default::default(),
+            })
+            .unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name, "sub_t");
+
+        let supertypes = server
+            .type_hierarchy_supertypes(&TypeHierarchySupertypesParams {
+                item: items[0].clone(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+        assert_eq!(
+            supertypes
+                .iter()
+                .map(|item| (item.name.as_str(), item.selection_range))
+                .collect::<Vec<_>>(),
+            vec![(
+                "bits_t",
+                Range::new(Position::new(1, 7), Position::new(1, 13))
+            )]
+        );
+
+        let subtypes = server
+            .type_hierarchy_subtypes(&TypeHierarchySubtypesParams {
+                item: supertypes[0].clone(),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap();
+        assert_eq!(subtypes, items);
+    }
+
+    #[test]
+    fn analysis_of_changes_is_deferred() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  sig <= not sig;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri);
+
+        // No diagnostics are published until the pending changes are analyzed
+        server.text_document_did_change_notification(&DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: file_uri.clone(),
+                version: 1,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(6, 16), Position::new(6, 16))),
+                range_length: None,
+                text: "2".to_owned(),
+            }],
+        });
+        assert!(server.has_pending_changes());
+
+        // Requests are served from the last analysis
+        let declaration = server.text_document_declaration(&TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier::new(file_uri.clone()),
+            position: Position::new(6, 2),
+        });
+        assert_eq!(
+            declaration,
+            Some(Location {
+                uri: file_uri,
+                range: Range::new(Position::new(4, 9), Position::new(4, 12)),
+            })
+        );
+
+        mock.expect_notification_contains("textDocument/publishDiagnostics", "sig2");
+        server.analyse_pending_changes();
+        assert!(!server.has_pending_changes());
+    }
+
+    /// Initialize a project with a file using a signal `sig` on line 6
+    fn initialize_signal_project(
+        mock: &RpcMock,
+        server: &mut VHDLServer,
+    ) -> (tempfile::TempDir, Url) {
+        let (tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+  signal sig : bit;
+begin
+  sig <= not sig;
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        expect_loaded_config_messages(mock, &config_uri);
+        initialize_server(server, root_uri);
+        (tempdir, file_uri)
+    }
+
+    fn change_signal_name(server: &mut VHDLServer, file_uri: &Url, range: Range, text: &str) {
+        server.text_document_did_change_notification(&DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: file_uri.clone(),
+                version: 1,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(range),
+                range_length: None,
+                text: text.to_owned(),
+            }],
+        });
+    }
+
+    #[test]
+    fn requests_are_served_during_background_analysis() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, file_uri) = initialize_signal_project(&mock, &mut server);
+
+        change_signal_name(
+            &mut server,
+            &file_uri,
+            Range::new(Position::new(6, 16), Position::new(6, 16)),
+            "2",
+        );
+        server.start_background_analysis();
+        assert!(server.background.is_running());
+        assert!(!server.has_pending_changes());
+
+        // The last completed analysis still resolves the reference
+        let reference = TextDocumentPositionParams {
+            text_document: TextDocumentIdentifier::new(file_uri.clone()),
+            position: Position::new(6, 14),
+        };
+        assert!(server.text_document_declaration(&reference).is_some());
+
+        mock.expect_notification_contains("textDocument/publishDiagnostics", "sig2");
+        server.analyse_pending_changes();
+        assert!(!server.background.is_running());
+        assert_eq!(server.text_document_declaration(&reference), None);
+
+        // The previously served project catches up with the change
+        change_signal_name(
+            &mut server,
+            &file_uri,
+            Range::new(Position::new(6, 16), Position::new(6, 17)),
+            "",
+        );
+        server.start_background_analysis();
+        mock.expect_notification(
+            "textDocument/publishDiagnostics",
+            PublishDiagnosticsParams {
+                uri: file_uri.clone(),
+                diagnostics: vec![],
+                version: None,
+            },
+        );
+        server.analyse_pending_changes();
+        assert!(server.text_document_declaration(&reference).is_some());
+    }
+
+    #[test]
+    fn changes_of_cancelled_background_analysis_are_pending_again() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, file_uri) = initialize_signal_project(&mock, &mut server);
+
+        change_signal_name(
+            &mut server,
+            &file_uri,
+            Range::new(Position::new(6, 16), Position::new(6, 16)),
+            "2",
+        );
+        server.start_background_analysis();
+        let result = server.background.wait().unwrap();
+        server.finish_background_analysis(AnalysisResult {
+            project: result.project,
+            diagnostics: None,
+            messages: result.messages,
+        });
+        assert!(!server.background.is_running());
+        assert!(server.has_pending_changes());
+
+        mock.expect_notification_contains("textDocument/publishDiagnostics", "sig2");
+        server.analyse_pending_changes();
+        assert!(!server.has_pending_changes());
+    }
+
+    #[test]
+    fn work_done_progress_during_initial_analysis() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            format!(
+                "
+[libraries]
+std.files = [
+'{}/../vhdl_libraries/std/standard.vhd',
+]
+lib.files = ['ent.vhd']
+",
+                std::env::var("CARGO_MANIFEST_DIR").unwrap()
+            ),
+        );
+        let token = ProgressToken::String("vhdl_ls/analysis".to_owned());
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_request(
+            "window/workDoneProgress/create",
+            WorkDoneProgressCreateParams {
+                token: token.clone(),
+            },
+        );
+        mock.expect_notification(
+            "$/progress",
+            ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::Begin(
+                    WorkDoneProgressBegin {
+                        title: "Analyzing VHDL project".to_owned(),
+                        cancellable: Some(false),
+                        message: Some("2 files".to_owned()),
+                        percentage: None,
+                    },
+                )),
+            },
+        );
+        mock.expect_notification(
+            "$/progress",
+            ProgressParams {
+                token,
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::End(WorkDoneProgressEnd {
+                    message: None,
+                })),
+            },
+        );
+
+        let capabilities = ClientCapabilities {
+            window: Some(WindowClientCapabilities {
+                work_done_progress: Some(true),
+                ..WindowClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+    }
+
+    #[test]
+    fn unchanged_diagnostics_are_not_published_again() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let code = "\
+entity ent is
+end entity ent2;
+";
+        let file_uri = write_file(&root_uri, "ent.vhd", code);
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+",
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "End identifier mismatch",
+        );
+        initialize_server(&mut server, root_uri);
+
+        // The file is analyzed again without publishing the same diagnostics
+        server.text_document_did_open_notification(&DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: file_uri,
+                language_id: "vhdl".to_owned(),
+                version: 0,
+                text: code.to_owned(),
+            },
+        });
+        server.analyse_pending_changes();
+    }
+
+    #[test]
+    fn unlisted_file_is_analyzed_in_configured_library() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(
+            &root_uri,
+            "pkg.vhd",
+            "\
+package pkg is
+end package;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['pkg.vhd']
+
+[unmapped]
+default_library = 'lib'
+",
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        initialize_server(&mut server, root_uri.clone());
+
+        // The package is visible through work without any diagnostics to publish
+        mock.expect_warning_contains("analyzing it in library lib");
+        server.text_document_did_open_notification(&DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: root_uri.join("scratch.vhd").unwrap(),
+                language_id: "vhdl".to_owned(),
+                version: 0,
+                text: "\
+use work.pkg.all;
+
+entity scratch is
+end entity;
+"
+                .to_owned(),
+            },
+        });
+        server.analyse_pending_changes();
+    }
+
+    #[test]
+    fn inactive_regions_are_published_as_unnecessary_code() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity;
+
+architecture a of ent is
+`if VARIANT = \"sim\" then
+  signal sim_only : bit;
+`end if
+begin
+end architecture;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+
+[conditional_analysis]
+variant = 'synth'
+",
+        );
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_notification_contains(
+            "textDocument/publishDiagnostics",
+            "Excluded by conditional analysis",
+        );
+        initialize_server(&mut server, root_uri);
+    }
+
+    #[test]
+    fn pull_diagnostics_with_result_ids() {
+        let (mock, mut server) = setup_server();
+        let (_tempdir, root_uri) = temp_root_uri();
+        let file_uri = write_file(
+            &root_uri,
+            "ent.vhd",
+            "\
+entity ent is
+end entity ent2;
+",
+        );
+        let config_uri = write_config(
+            &root_uri,
+            "
+[libraries]
+lib.files = ['ent.vhd']
+",
+        );
+        // Diagnostics are not pushed to clients pulling them
+        expect_loaded_config_messages(&mock, &config_uri);
+        mock.expect_request("workspace/diagnostic/refresh", ());
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                diagnostic: Some(DiagnosticClientCapabilities::default()),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            workspace: Some(WorkspaceClientCapabilities {
+                diagnostic: Some(DiagnosticWorkspaceClientCapabilities {
+                    refresh_support: Some(true),
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        #[allow(deprecated)]
+        let initialize_params = InitializeParams {
+            root_uri: Some(root_uri),
+            capabilities,
+            ..Default::default()
+        };
+        server.initialize_request(initialize_params);
+        server.initialized_notification();
+
+        let document_diagnostic = |server: &mut VHDLServer, previous_result_id: Option<String>| {
+            let result = server.text_document_diagnostic(&DocumentDiagnosticParams {
+                text_document: TextDocumentIdentifier::new(file_uri.clone()),
+                identifier: None,
+                previous_result_id,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            });
+            match result {
+                DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(full)) => {
+                    let report = full.full_document_diagnostic_report;
+                    (report.result_id, Some(report.items.len()))
+                }
+                DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Unchanged(
+                    unchanged,
+                )) => (
+                    Some(unchanged.unchanged_document_diagnostic_report.result_id),
+                    None,
+                ),
+                DocumentDiagnosticReportResult::Partial(_) => panic!("Unexpected partial result"),
+            }
+        };
+
+        let (result_id, items) = document_diagnostic(&mut server, None);
+        assert_eq!(items, Some(1));
+        assert_eq!(
+            document_diagnostic(&mut server, result_id.clone()),
+            (result_id.clone(), None)
+        );
+
+        let workspace_diagnostic = server.workspace_diagnostic(&WorkspaceDiagnosticParams {
+            identifier: None,
+            previous_result_ids: vec![PreviousResultId {
+                uri: file_uri.clone(),
+                value: result_id.clone().unwrap(),
+            }],
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        });
+        assert!(matches!(
+            workspace_diagnostic,
+            WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { ref items })
+                if matches!(items.as_slice(), [WorkspaceDocumentDiagnosticReport::Unchanged(_)])
+        ));
+
+        server.text_document_did_change_notification(&DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier {
+                uri: file_uri.clone(),
+                version: 1,
+            },
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 14), Position::new(1, 15))),
+                range_length: None,
+                text: "".to_owned(),
+            }],
+        });
+        assert!(server.has_pending_changes());
+
+        // Pulling does not wait for the analysis of pending changes
+        assert_eq!(
+            document_diagnostic(&mut server, result_id.clone()),
+            (result_id.clone(), None)
+        );
+        assert!(server.has_pending_changes());
+
+        // The client is asked to pull the diagnostics again once the analysis has finished
+        mock.expect_request("workspace/diagnostic/refresh", ());
+        server.start_background_analysis();
+        let result = server.background.wait().unwrap();
+        server.finish_background_analysis(result);
+
+        let (new_result_id, items) = document_diagnostic(&mut server, result_id.clone());
+        assert_eq!(items, Some(0));
+        assert_ne!(new_result_id, result_id);
     }
 }